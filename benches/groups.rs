@@ -25,6 +25,14 @@ fn criterion_benchmark(c: &mut Criterion) {
         c.bench_function(&format!("{} from bytes wide", name), move |b| {
             b.iter(|| Scalar::from_bytes_wide(black_box(&bytes)))
         });
+        // Inversion by the extended Euclidean algorithm is faster for small values.
+        let z = Scalar::from_bytes_wide(&[0x5a; 64]);
+        c.bench_function(&format!("{} inversion", name), move |b| {
+            b.iter(|| black_box(&z).invert())
+        });
+        c.bench_function(&format!("{} variable-time inversion", name), move |b| {
+            b.iter(|| black_box(&z).invert_vartime())
+        });
     }
 
     // Pairings
@@ -42,6 +50,13 @@ fn criterion_benchmark(c: &mut Criterion) {
         c.bench_function(&format!("{} scalar multiplication", name), move |b| {
             b.iter(|| black_box(a) * black_box(s))
         });
+        c.bench_function(
+            &format!("{} variable-time scalar multiplication", name),
+            move |b| b.iter(|| black_box(a).mul_vartime(black_box(&[1, 2, 3, 4]))),
+        );
+        c.bench_function(&format!("{} generator multiplication", name), move |b| {
+            b.iter(|| Gt::generator_mul(black_box(&s)))
+        });
         c.bench_function(
             &format!("{} deserialize compressed element", name),
             move |b| b.iter(|| Gt::from_compressed(black_box(&compressed))),
@@ -125,6 +140,10 @@ fn criterion_benchmark(c: &mut Criterion) {
         c.bench_function(&format!("{} scalar multiplication", name), move |b| {
             b.iter(|| black_box(a) * black_box(s))
         });
+        c.bench_function(
+            &format!("{} variable-time scalar multiplication", name),
+            move |b| b.iter(|| black_box(a).mul_vartime(black_box(&s))),
+        );
         c.bench_function(&format!("{} batch to affine n={}", name, N), move |b| {
             b.iter(|| {
                 G1Projective::batch_normalize(black_box(&v), black_box(&mut q));
@@ -194,6 +213,10 @@ fn criterion_benchmark(c: &mut Criterion) {
         c.bench_function(&format!("{} scalar multiplication", name), move |b| {
             b.iter(|| black_box(a) * black_box(s))
         });
+        c.bench_function(
+            &format!("{} variable-time scalar multiplication", name),
+            move |b| b.iter(|| black_box(a).mul_vartime(black_box(&s))),
+        );
         c.bench_function(&format!("{} batch to affine n={}", name, N), move |b| {
             b.iter(|| {
                 G2Projective::batch_normalize(black_box(&v), black_box(&mut q));
@@ -0,0 +1,78 @@
+#[macro_use]
+extern crate criterion;
+
+extern crate bls12_381;
+use bls12_381::msm::{
+    multi_exp_auto_vartime, multi_exp_small_vartime, multi_exp_vartime_with_scratch,
+    recommended_window,
+};
+use bls12_381::*;
+
+use criterion::{black_box, Criterion};
+
+/// Returns `n` distinct points of G1 and `n` scalars of full length.
+fn pairs(n: usize) -> (Vec<G1Affine>, Vec<Scalar>) {
+    let step = G1Projective::generator() * Scalar::from(0x5eed);
+    let mut projective = Vec::with_capacity(n);
+    let mut p = G1Projective::generator();
+    for _ in 0..n {
+        projective.push(p);
+        p += step;
+    }
+    let mut bases = vec![G1Affine::identity(); n];
+    G1Projective::batch_normalize(&projective, &mut bases);
+
+    let mut s = Scalar::from(0x1234_5678);
+    let scalars = (0..n)
+        .map(|_| {
+            s = s.square() + Scalar::one();
+            s
+        })
+        .collect();
+    (bases, scalars)
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    // The windows around those of `recommended_window`.
+    for log_len in [8, 12, 14, 15, 16] {
+        let (bases, scalars) = pairs(1 << log_len);
+        let recommended = recommended_window(bases.len());
+
+        let mut group =
+            c.benchmark_group(format!("G1 multi-scalar multiplication n=2^{}", log_len));
+        group.sample_size(10);
+        for window in (recommended - 1).max(1)..=(recommended + 1).min(16) {
+            let mut scratch = vec![G1Projective::identity(); 1 << (window - 1)];
+            group.bench_function(format!("window {}", window), |b| {
+                b.iter(|| {
+                    multi_exp_vartime_with_scratch(
+                        black_box(&bases),
+                        black_box(&scalars),
+                        &mut scratch,
+                    )
+                })
+            });
+        }
+        group.finish();
+    }
+
+    // Small scalars against the same scalars converted to `Scalar`.
+    {
+        let (bases, _) = pairs(1 << 12);
+        let small: Vec<u8> = (0..bases.len()).map(|i| (i * 37) as u8).collect();
+        let scalars: Vec<Scalar> = small.iter().map(|&s| Scalar::from(s as u64)).collect();
+
+        let mut group = c.benchmark_group("G1 multi-scalar multiplication n=2^12 u8");
+        group.sample_size(10);
+        group.bench_function("small", |b| {
+            b.iter(|| multi_exp_small_vartime(black_box(&bases), black_box(&small)))
+        });
+        group.bench_function("auto", |b| {
+            b.iter(|| multi_exp_auto_vartime(black_box(&bases), black_box(&scalars)))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,384 @@
+//! This module implements the pairing-based accumulator of [Nguyen][nguyen].
+//!
+//! The accumulator manager holds a secret key $s$ and publishes $Q = s \cdot g_2$. A
+//! set $X$ of scalars is accumulated into the single point
+//! $V = \prod_{x \in X} (x + s) \cdot g_1$.
+//!
+//! * A membership witness for $y \in X$ is $W = V / (y + s)$, which is checked with
+//!   $e(W, y \cdot g_2 + Q) = e(V, g_2)$.
+//! * A non-membership witness for $y \notin X$ is a pair $(W, d)$ with $d \neq 0$
+//!   such that $(y + s) \cdot W + d \cdot g_1 = V$, which is checked with
+//!   $e(W, y \cdot g_2 + Q) \cdot e(d \cdot g_1, g_2) = e(V, g_2)$.
+//!
+//! Only the manager can update the accumulator and issue witnesses, but witness
+//! holders can update their witnesses themselves after each addition or removal,
+//! knowing only the element and the accumulator values.
+//!
+//! [nguyen]: https://eprint.iacr.org/2005/123
+
+use ff::Field;
+use group::{Curve, Group};
+use rand_core::RngCore;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use crate::{multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, Scalar};
+
+/// Generates a random secret key for the accumulator manager, and the corresponding
+/// public key in $\mathbb{G}_2$.
+pub fn keygen(mut rng: impl RngCore) -> (Scalar, G2Affine) {
+    let sk = Scalar::random(&mut rng);
+    (sk, G2Affine::from(G2Affine::generator() * sk))
+}
+
+/// The value of an accumulator.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Accumulator(G1Affine);
+
+impl ConditionallySelectable for Accumulator {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Accumulator(G1Affine::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl Accumulator {
+    /// Accumulates `elements` with the secret key `sk`.
+    pub fn new(sk: &Scalar, elements: &[Scalar]) -> Self {
+        let exponent = elements.iter().fold(Scalar::one(), |acc, x| acc * (x + sk));
+        Accumulator(G1Affine::from(G1Affine::generator() * exponent))
+    }
+
+    /// Returns the accumulated point $V$.
+    pub fn value(&self) -> &G1Affine {
+        &self.0
+    }
+
+    /// Returns the accumulator of the set with `element` added.
+    pub fn add(&self, sk: &Scalar, element: &Scalar) -> Self {
+        Accumulator(G1Affine::from(self.0 * (element + sk)))
+    }
+
+    /// Returns the accumulator of the set with `element` removed. The element must be
+    /// in the accumulated set.
+    ///
+    /// Returns `None` in the negligibly unlikely case that `element` is $-s$.
+    pub fn remove(&self, sk: &Scalar, element: &Scalar) -> CtOption<Self> {
+        (element + sk)
+            .invert()
+            .map(|inv| Accumulator(G1Affine::from(self.0 * inv)))
+    }
+
+    /// Issues a membership witness for `element`, which must be in the accumulated
+    /// set.
+    ///
+    /// Returns `None` in the negligibly unlikely case that `element` is $-s$.
+    pub fn membership_witness(&self, sk: &Scalar, element: &Scalar) -> CtOption<MembershipWitness> {
+        self.remove(sk, element).map(|w| MembershipWitness(w.0))
+    }
+
+    /// Issues a non-membership witness for `element`, given the accumulated set
+    /// `elements`.
+    ///
+    /// Returns `None` if `element` is in the set, or in the negligibly unlikely case
+    /// that it is $-s$.
+    pub fn non_membership_witness(
+        &self,
+        sk: &Scalar,
+        elements: &[Scalar],
+        element: &Scalar,
+    ) -> CtOption<NonMembershipWitness> {
+        // d is the remainder of prod (x + X) divided by (y + X).
+        let d = elements
+            .iter()
+            .fold(Scalar::one(), |acc, x| acc * (x - element));
+
+        (element + sk).invert().and_then(|inv| {
+            let w = (G1Projective::from(self.0) - G1Affine::generator() * d) * inv;
+            CtOption::new(
+                NonMembershipWitness {
+                    w: w.to_affine(),
+                    d,
+                },
+                !d.is_zero(),
+            )
+        })
+    }
+}
+
+/// A witness that an element is in the accumulated set.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MembershipWitness(G1Affine);
+
+impl ConditionallySelectable for MembershipWitness {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        MembershipWitness(G1Affine::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl MembershipWitness {
+    /// Returns the witness point $W$.
+    pub fn value(&self) -> &G1Affine {
+        &self.0
+    }
+
+    /// Updates this witness for `element` after `added` was added to the accumulator
+    /// `old`, as $W' = V + (x' - y) \cdot W$.
+    pub fn update_on_addition(&self, element: &Scalar, added: &Scalar, old: &Accumulator) -> Self {
+        MembershipWitness((old.0 + self.0 * (added - element)).to_affine())
+    }
+
+    /// Updates this witness for `element` after `removed` was removed, resulting in
+    /// the accumulator `new`, as $W' = (W - V') / (x' - y)$.
+    ///
+    /// Returns `None` if `removed` is `element` itself, whose witness is then
+    /// invalid.
+    pub fn update_on_removal(
+        &self,
+        element: &Scalar,
+        removed: &Scalar,
+        new: &Accumulator,
+    ) -> CtOption<Self> {
+        (removed - element)
+            .invert()
+            .map(|inv| MembershipWitness(((G1Projective::from(self.0) - new.0) * inv).to_affine()))
+    }
+}
+
+/// A witness that an element is not in the accumulated set.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct NonMembershipWitness {
+    w: G1Affine,
+    d: Scalar,
+}
+
+impl ConditionallySelectable for NonMembershipWitness {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        NonMembershipWitness {
+            w: G1Affine::conditional_select(&a.w, &b.w, choice),
+            d: Scalar::conditional_select(&a.d, &b.d, choice),
+        }
+    }
+}
+
+impl NonMembershipWitness {
+    /// Returns the witness point $W$.
+    pub fn value(&self) -> &G1Affine {
+        &self.w
+    }
+
+    /// Returns the nonzero remainder $d$.
+    pub fn remainder(&self) -> &Scalar {
+        &self.d
+    }
+
+    /// Updates this witness for `element` after `added` was added to the accumulator
+    /// `old`, as $W' = V + (x' - y) \cdot W$ and $d' = (x' - y) d$.
+    ///
+    /// Returns `None` if `added` is `element` itself, which is then a member.
+    pub fn update_on_addition(
+        &self,
+        element: &Scalar,
+        added: &Scalar,
+        old: &Accumulator,
+    ) -> CtOption<Self> {
+        let delta = added - element;
+        CtOption::new(
+            NonMembershipWitness {
+                w: (old.0 + self.w * delta).to_affine(),
+                d: self.d * delta,
+            },
+            !delta.is_zero(),
+        )
+    }
+
+    /// Updates this witness for `element` after `removed` was removed, resulting in
+    /// the accumulator `new`, as $W' = (W - V') / (x' - y)$ and $d' = d / (x' - y)$.
+    ///
+    /// Returns `None` if `removed` is `element` itself, which cannot have been in
+    /// the set.
+    pub fn update_on_removal(
+        &self,
+        element: &Scalar,
+        removed: &Scalar,
+        new: &Accumulator,
+    ) -> CtOption<Self> {
+        (removed - element)
+            .invert()
+            .map(|inv| NonMembershipWitness {
+                w: ((G1Projective::from(self.w) - new.0) * inv).to_affine(),
+                d: self.d * inv,
+            })
+    }
+}
+
+/// Checks that $e(W, y \cdot g_2 + Q) \cdot e(R, g_2) = 1$.
+fn check(pk: &G2Affine, element: &Scalar, w: &G1Affine, rest: G1Projective) -> Choice {
+    let yq = G2Affine::from(G2Affine::generator() * element + pk);
+    let rest = rest.to_affine();
+
+    multi_miller_loop(&[
+        (w, &G2Prepared::from(yq)),
+        (&rest, &G2Prepared::from(G2Affine::generator())),
+    ])
+    .final_exponentiation()
+    .is_identity()
+}
+
+/// Checks that `witness` shows that `element` is in the set accumulated in
+/// `accumulator` by the manager with public key `pk`.
+pub fn verify_membership(
+    pk: &G2Affine,
+    accumulator: &Accumulator,
+    element: &Scalar,
+    witness: &MembershipWitness,
+) -> Choice {
+    check(pk, element, &witness.0, -G1Projective::from(accumulator.0))
+}
+
+/// Checks that `witness` shows that `element` is not in the set accumulated in
+/// `accumulator` by the manager with public key `pk`.
+pub fn verify_non_membership(
+    pk: &G2Affine,
+    accumulator: &Accumulator,
+    element: &Scalar,
+    witness: &NonMembershipWitness,
+) -> Choice {
+    let rest = G1Affine::generator() * witness.d - accumulator.0;
+    check(pk, element, &witness.w, rest) & !witness.d.ct_eq(&Scalar::zero())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn setup() -> (Scalar, G2Affine, [Scalar; 4]) {
+        let rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let (sk, pk) = keygen(rng);
+        let elements = [
+            Scalar::from(11),
+            Scalar::from(22),
+            Scalar::from(33),
+            Scalar::from(44),
+        ];
+        (sk, pk, elements)
+    }
+
+    #[test]
+    fn test_membership() {
+        let (sk, pk, elements) = setup();
+        let acc = Accumulator::new(&sk, &elements);
+
+        for y in &elements {
+            let witness = acc.membership_witness(&sk, y).unwrap();
+            assert!(bool::from(verify_membership(&pk, &acc, y, &witness)));
+            assert!(!bool::from(verify_membership(
+                &pk,
+                &acc,
+                &(y + Scalar::one()),
+                &witness
+            )));
+        }
+
+        let other = Accumulator::new(&sk, &elements[1..]);
+        let witness = acc.membership_witness(&sk, &elements[1]).unwrap();
+        assert!(!bool::from(verify_membership(
+            &pk,
+            &other,
+            &elements[1],
+            &witness
+        )));
+    }
+
+    #[test]
+    fn test_non_membership() {
+        let (sk, pk, elements) = setup();
+        let acc = Accumulator::new(&sk, &elements);
+
+        let y = Scalar::from(55);
+        let witness = acc.non_membership_witness(&sk, &elements, &y).unwrap();
+        assert!(bool::from(verify_non_membership(&pk, &acc, &y, &witness)));
+        assert!(!bool::from(verify_non_membership(
+            &pk,
+            &acc,
+            &Scalar::from(56),
+            &witness
+        )));
+
+        assert!(bool::from(
+            acc.non_membership_witness(&sk, &elements, &elements[2])
+                .is_none()
+        ));
+
+        // A zero remainder would otherwise make any accumulator a "witness".
+        let forged = NonMembershipWitness {
+            w: acc.membership_witness(&sk, &elements[0]).unwrap().0,
+            d: Scalar::zero(),
+        };
+        assert!(!bool::from(verify_non_membership(
+            &pk,
+            &acc,
+            &elements[0],
+            &forged
+        )));
+    }
+
+    #[test]
+    fn test_add_remove() {
+        let (sk, _, elements) = setup();
+        let acc = Accumulator::new(&sk, &elements[..3]);
+        let added = acc.add(&sk, &elements[3]);
+        assert_eq!(added, Accumulator::new(&sk, &elements));
+        assert_eq!(added.remove(&sk, &elements[3]).unwrap(), acc);
+    }
+
+    #[test]
+    fn test_witness_updates() {
+        let (sk, pk, elements) = setup();
+        let y = elements[0];
+        let z = Scalar::from(1000);
+
+        let acc = Accumulator::new(&sk, &elements[..2]);
+        let mut member = acc.membership_witness(&sk, &y).unwrap();
+        let mut non_member = acc.non_membership_witness(&sk, &elements[..2], &z).unwrap();
+
+        // Add the remaining elements one at a time.
+        let mut acc = acc;
+        for x in &elements[2..] {
+            let new = acc.add(&sk, x);
+            member = member.update_on_addition(&y, x, &acc);
+            non_member = non_member.update_on_addition(&z, x, &acc).unwrap();
+            acc = new;
+
+            assert!(bool::from(verify_membership(&pk, &acc, &y, &member)));
+            assert!(bool::from(verify_non_membership(
+                &pk,
+                &acc,
+                &z,
+                &non_member
+            )));
+        }
+        assert_eq!(member, acc.membership_witness(&sk, &y).unwrap());
+
+        // Remove an element other than y.
+        let removed = elements[1];
+        let acc = acc.remove(&sk, &removed).unwrap();
+        let member = member.update_on_removal(&y, &removed, &acc).unwrap();
+        let non_member = non_member.update_on_removal(&z, &removed, &acc).unwrap();
+        assert!(bool::from(verify_membership(&pk, &acc, &y, &member)));
+        assert!(bool::from(verify_non_membership(
+            &pk,
+            &acc,
+            &z,
+            &non_member
+        )));
+
+        assert!(bool::from(member.update_on_removal(&y, &y, &acc).is_none()));
+        assert!(bool::from(
+            non_member.update_on_addition(&z, &z, &acc).is_none()
+        ));
+    }
+}
@@ -0,0 +1,172 @@
+//! [`arbitrary`] support for the scalar, field and group types of this crate, for
+//! fuzzing.
+//!
+//! Scalars and field elements are reductions of wide integers read from the input,
+//! so that every element can be produced. Points are found on the curve from an
+//! arbitrary x-coordinate, incremented until $x^3 + b$ is a square, and mapped into
+//! the prime-order subgroup with `clear_cofactor`, so that they satisfy the same
+//! invariants as deserialized points.
+
+#[cfg(feature = "tower")]
+use arbitrary::size_hint;
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::Scalar;
+
+#[cfg(feature = "groups")]
+use crate::{
+    constants::{G1_B, G2_B},
+    G1Affine, G1Projective, G2Affine, G2Projective,
+};
+#[cfg(feature = "tower")]
+use crate::{fp::Fp, fp12::Fp12, fp2::Fp2, fp6::Fp6};
+#[cfg(feature = "groups")]
+use subtle::{Choice, ConditionallyNegatable};
+
+impl<'a> Arbitrary<'a> for Scalar {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut bytes = [0u8; 64];
+        u.fill_buffer(&mut bytes)?;
+        Ok(Scalar::from_bytes_wide(&bytes))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (64, Some(64))
+    }
+}
+
+#[cfg(feature = "tower")]
+impl<'a> Arbitrary<'a> for Fp {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut limbs = [0u64; 12];
+        for limb in limbs.iter_mut() {
+            *limb = u.arbitrary()?;
+        }
+        Ok(Fp::from_u768(limbs))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (96, Some(96))
+    }
+}
+
+#[cfg(feature = "tower")]
+impl<'a> Arbitrary<'a> for Fp2 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Fp2 {
+            c0: u.arbitrary()?,
+            c1: u.arbitrary()?,
+        })
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and(Fp::size_hint(depth), Fp::size_hint(depth))
+    }
+}
+
+#[cfg(feature = "tower")]
+impl<'a> Arbitrary<'a> for Fp6 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Fp6 {
+            c0: u.arbitrary()?,
+            c1: u.arbitrary()?,
+            c2: u.arbitrary()?,
+        })
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and_all(&[Fp2::size_hint(depth); 3])
+    }
+}
+
+#[cfg(feature = "tower")]
+impl<'a> Arbitrary<'a> for Fp12 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Fp12 {
+            c0: u.arbitrary()?,
+            c1: u.arbitrary()?,
+        })
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and(Fp6::size_hint(depth), Fp6::size_hint(depth))
+    }
+}
+
+macro_rules! impl_arbitrary_point {
+    ($affine:ident, $projective:ident, $field:ident, $b:expr) => {
+        #[cfg(feature = "groups")]
+        impl<'a> Arbitrary<'a> for $projective {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                let mut x = $field::arbitrary(u)?;
+                let y_is_odd = Choice::from(u.arbitrary::<bool>()? as u8);
+                loop {
+                    let y = ((x.square() * x) + $b).sqrt();
+                    if bool::from(y.is_some()) {
+                        let mut y = y.unwrap();
+                        y.conditional_negate(y_is_odd);
+                        let p = $affine {
+                            x,
+                            y,
+                            infinity: Choice::from(0u8),
+                        };
+                        return Ok($projective::from(p).clear_cofactor());
+                    }
+                    x += $field::one();
+                }
+            }
+
+            fn size_hint(depth: usize) -> (usize, Option<usize>) {
+                size_hint::and($field::size_hint(depth), bool::size_hint(depth))
+            }
+        }
+
+        #[cfg(feature = "groups")]
+        impl<'a> Arbitrary<'a> for $affine {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                Ok($affine::from($projective::arbitrary(u)?))
+            }
+
+            fn size_hint(depth: usize) -> (usize, Option<usize>) {
+                $projective::size_hint(depth)
+            }
+        }
+    };
+}
+
+impl_arbitrary_point!(G1Affine, G1Projective, Fp, G1_B);
+impl_arbitrary_point!(G2Affine, G2Projective, Fp2, G2_B);
+
+#[cfg(all(test, feature = "groups"))]
+mod tests {
+    use super::*;
+    use rand_core::{RngCore, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_arbitrary_points() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let mut data = [0u8; 1024];
+
+        for _ in 0..10 {
+            rng.fill_bytes(&mut data);
+            let mut u = Unstructured::new(&data);
+
+            let p = G1Affine::arbitrary(&mut u).unwrap();
+            assert!(bool::from(p.is_on_curve() & p.is_torsion_free()));
+            let q = G2Affine::arbitrary(&mut u).unwrap();
+            assert!(bool::from(q.is_on_curve() & q.is_torsion_free()));
+            assert!(!bool::from(p.is_identity() | q.is_identity()));
+        }
+
+        // An exhausted input still produces points of the subgroup.
+        let mut u = Unstructured::new(&[]);
+        let p = G1Projective::arbitrary(&mut u).unwrap();
+        assert!(bool::from(G1Affine::from(p).is_torsion_free()));
+        let q = G2Projective::arbitrary(&mut u).unwrap();
+        assert!(bool::from(G2Affine::from(q).is_torsion_free()));
+    }
+}
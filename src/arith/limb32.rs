@@ -0,0 +1,220 @@
+//! A backend on 32-bit words, for targets without a 64-bit multiplier. The limbs
+//! of the elements are split into their halves, which leaves the Montgomery form
+//! unchanged since 2^64 = (2^32)^2.
+
+use super::{Backend, FieldParams, Limbs};
+
+pub(crate) struct Limb32;
+
+/// The largest number of 64-bit limbs that the backend supports.
+const MAX_LIMBS: usize = 6;
+
+/// Compute a + b + carry, returning the result and the new carry over.
+#[inline(always)]
+const fn adc(a: u32, b: u32, carry: u32) -> (u32, u32) {
+    let ret = (a as u64) + (b as u64) + (carry as u64);
+    (ret as u32, (ret >> 32) as u32)
+}
+
+/// Compute a - (b + borrow), returning the result and the new borrow.
+#[inline(always)]
+const fn sbb(a: u32, b: u32, borrow: u32) -> (u32, u32) {
+    let ret = (a as u64).wrapping_sub((b as u64) + ((borrow >> 31) as u64));
+    (ret as u32, (ret >> 32) as u32)
+}
+
+/// Compute a + (b * c) + carry, returning the result and the new carry over.
+#[inline(always)]
+const fn mac(a: u32, b: u32, c: u32, carry: u32) -> (u32, u32) {
+    let ret = (a as u64) + ((b as u64) * (c as u64)) + (carry as u64);
+    (ret as u32, (ret >> 32) as u32)
+}
+
+/// The little-endian 32-bit words of `N` limbs, in a buffer for the largest
+/// number of limbs.
+#[inline(always)]
+fn split<const N: usize>(a: &[u64; N]) -> [u32; 2 * MAX_LIMBS] {
+    assert!(N <= MAX_LIMBS);
+    let mut words = [0u32; 2 * MAX_LIMBS];
+    for i in 0..N {
+        words[2 * i] = a[i] as u32;
+        words[2 * i + 1] = (a[i] >> 32) as u32;
+    }
+    words
+}
+
+#[inline(always)]
+fn join<const N: usize>(words: &[u32; 2 * MAX_LIMBS]) -> [u64; N] {
+    let mut a = [0u64; N];
+    for i in 0..N {
+        a[i] = (words[2 * i] as u64) | ((words[2 * i + 1] as u64) << 32);
+    }
+    a
+}
+
+/// Subtracts the modulus `p` from the integer `hi * 2^(64 N) + a` if that is not
+/// smaller than the modulus, on `n` words.
+#[inline(always)]
+fn reduce(
+    a: &[u32; 2 * MAX_LIMBS],
+    hi: u32,
+    p: &[u32; 2 * MAX_LIMBS],
+    n: usize,
+) -> [u32; 2 * MAX_LIMBS] {
+    let mut res = [0u32; 2 * MAX_LIMBS];
+    let mut borrow = 0;
+    for i in 0..n {
+        let (word, b) = sbb(a[i], p[i], borrow);
+        res[i] = word;
+        borrow = b;
+    }
+    let (_, borrow) = sbb(hi, 0, borrow);
+
+    // borrow = 0x000...000 if the subtraction did not underflow, and 0xfff...fff
+    // if it did, in which case a is kept.
+    for i in 0..n {
+        res[i] = (res[i] & !borrow) | (a[i] & borrow);
+    }
+    res
+}
+
+impl<P: FieldParams<N>, const N: usize> Backend<P, N> for Limb32 {
+    fn add(a: &[u64; N], b: &[u64; N]) -> [u64; N] {
+        let (a, b, p) = (split(a), split(b), split(&P::MODULUS));
+        let mut sum = [0u32; 2 * MAX_LIMBS];
+        let mut carry = 0;
+        for i in 0..2 * N {
+            let (word, c) = adc(a[i], b[i], carry);
+            sum[i] = word;
+            carry = c;
+        }
+        join(&reduce(&sum, carry, &p, 2 * N))
+    }
+
+    fn sub(a: &[u64; N], b: &[u64; N]) -> [u64; N] {
+        let (a, b, p) = (split(a), split(b), split(&P::MODULUS));
+        let mut diff = [0u32; 2 * MAX_LIMBS];
+        let mut borrow = 0;
+        for i in 0..2 * N {
+            let (word, b) = sbb(a[i], b[i], borrow);
+            diff[i] = word;
+            borrow = b;
+        }
+
+        // If underflow occurred on the final word, borrow = 0xfff...fff, otherwise
+        // borrow = 0x000...000. Thus, we use it as a mask to conditionally add the
+        // modulus.
+        let mut carry = 0;
+        for i in 0..2 * N {
+            let (word, c) = adc(diff[i], p[i] & borrow, carry);
+            diff[i] = word;
+            carry = c;
+        }
+        join(&diff)
+    }
+
+    fn neg(a: &[u64; N]) -> [u64; N] {
+        let (a, p) = (split(a), split(&P::MODULUS));
+        let mut res = [0u32; 2 * MAX_LIMBS];
+        let mut borrow = 0;
+        let mut nonzero = 0;
+        for i in 0..2 * N {
+            let (word, b) = sbb(p[i], a[i], borrow);
+            res[i] = word;
+            borrow = b;
+            nonzero |= a[i];
+        }
+
+        // Let's use a mask if a was zero, which would mean the result of the
+        // subtraction is p.
+        let mask = ((nonzero == 0) as u32).wrapping_sub(1);
+        for word in res.iter_mut() {
+            *word &= mask;
+        }
+        join(&res)
+    }
+
+    fn mul(a: &[u64; N], b: &[u64; N]) -> [u64; N] {
+        let (a, b, p) = (split(a), split(b), split(&P::MODULUS));
+        // -(p^{-1} mod 2^32) mod 2^32 is the low half of INV.
+        let inv = P::INV as u32;
+        let n = 2 * N;
+
+        // Coarsely integrated operand scanning, as in the portable backend.
+        let mut t = [0u32; 2 * MAX_LIMBS];
+        let mut t_n = 0;
+        for b_i in b.iter().take(n) {
+            let mut carry = 0;
+            for j in 0..n {
+                let (word, c) = mac(t[j], a[j], *b_i, carry);
+                t[j] = word;
+                carry = c;
+            }
+            let (word, t_n1) = adc(t_n, carry, 0);
+            t_n = word;
+
+            let m = t[0].wrapping_mul(inv);
+            let (_, mut carry) = mac(t[0], m, p[0], 0);
+            for j in 1..n {
+                let (word, c) = mac(t[j], m, p[j], carry);
+                t[j - 1] = word;
+                carry = c;
+            }
+            let (word, c) = adc(t_n, carry, 0);
+            t[n - 1] = word;
+            t_n = t_n1 + c;
+        }
+        join(&reduce(&t, t_n, &p, n))
+    }
+
+    fn sum_of_products<E: Limbs<N>, const T: usize>(a: &[E; T], b: &[E; T]) -> [u64; N] {
+        // The interleaved reduction keeps the intermediate sums below (T + 1) p, which
+        // must fit in N limbs. Otherwise the products are reduced one by one.
+        if T as u64 >= 1 << P::MODULUS[N - 1].leading_zeros() {
+            return a.iter().zip(b.iter()).fold([0; N], |acc, (a, b)| {
+                let product = <Self as Backend<P, N>>::mul(a.limbs(), b.limbs());
+                <Self as Backend<P, N>>::add(&acc, &product)
+            });
+        }
+
+        let (mut a_words, mut b_words) = ([[0; 2 * MAX_LIMBS]; T], [[0; 2 * MAX_LIMBS]; T]);
+        for (words, a) in a_words.iter_mut().zip(a.iter()) {
+            *words = split(a.limbs());
+        }
+        for (words, b) in b_words.iter_mut().zip(b.iter()) {
+            *words = split(b.limbs());
+        }
+        let p = split(&P::MODULUS);
+        let inv = P::INV as u32;
+        let n = 2 * N;
+
+        // Algorithm 2 of Longa's ePrint 2022-367, as in the unrolled backend: the
+        // products of each word of every a_i are summed before a single step of the
+        // reduction, so that the reduction is paid once for all of the products.
+        let mut t = [0u32; 2 * MAX_LIMBS];
+        let mut t_n = 0;
+        for j in 0..n {
+            for (a_i, b_i) in a_words.iter().zip(b_words.iter()) {
+                let mut carry = 0;
+                for k in 0..n {
+                    let (word, c) = mac(t[k], a_i[j], b_i[k], carry);
+                    t[k] = word;
+                    carry = c;
+                }
+                t_n = adc(t_n, carry, 0).0;
+            }
+
+            let m = t[0].wrapping_mul(inv);
+            let (_, mut carry) = mac(t[0], m, p[0], 0);
+            for k in 1..n {
+                let (word, c) = mac(t[k], m, p[k], carry);
+                t[k - 1] = word;
+                carry = c;
+            }
+            let (word, c) = adc(t_n, carry, 0);
+            t[n - 1] = word;
+            t_n = c;
+        }
+        join(&reduce(&t, t_n, &p, n))
+    }
+}
@@ -0,0 +1,191 @@
+//! Backends for the arithmetic of the base field and of the scalar field.
+//!
+//! [`Fp`](crate::fp::Fp) and [`Scalar`](crate::Scalar) keep their elements in
+//! Montgomery form as little-endian 64-bit limbs, and implement their operations
+//! as constant functions, unrolled for their number of limbs, so that they can be
+//! used in constants. A [`Backend`] is another implementation of the same
+//! operations on the same representation, so that implementations suited to
+//! other targets, such as assembly, SIMD or 32-bit arithmetic, can coexist with
+//! them and be checked against each other. The operators of both fields use the
+//! [`Selected`] backend of the target.
+//!
+//! Every backend must be constant time, and must return exactly the limbs that
+//! [`Unrolled`] returns: the Montgomery radix is $2^{64 N}$ whatever the width of
+//! the words that a backend computes with.
+
+#[cfg(any(test, target_pointer_width = "32"))]
+mod limb32;
+#[cfg(test)]
+mod portable;
+
+#[cfg(any(test, target_pointer_width = "32"))]
+pub(crate) use limb32::Limb32;
+#[cfg(test)]
+pub(crate) use portable::Portable;
+
+/// The parameters of a prime field whose elements have `N` 64-bit limbs.
+pub(crate) trait FieldParams<const N: usize> {
+    /// The modulus, which must leave the most significant bit of the last limb
+    /// clear.
+    const MODULUS: [u64; N];
+
+    /// -(MODULUS^{-1} mod 2^64) mod 2^64
+    const INV: u64;
+}
+
+/// An implementation of the arithmetic of the field `P`, on elements in
+/// Montgomery form that are smaller than the modulus.
+pub(crate) trait Backend<P: FieldParams<N>, const N: usize> {
+    fn add(a: &[u64; N], b: &[u64; N]) -> [u64; N];
+
+    fn sub(a: &[u64; N], b: &[u64; N]) -> [u64; N];
+
+    fn neg(a: &[u64; N]) -> [u64; N];
+
+    /// Computes the Montgomery product a b R^{-1}.
+    fn mul(a: &[u64; N], b: &[u64; N]) -> [u64; N];
+
+    fn square(a: &[u64; N]) -> [u64; N] {
+        Self::mul(a, a)
+    }
+
+    /// Computes the sum of the Montgomery products a_i b_i R^{-1}.
+    fn sum_of_products<E: Limbs<N>, const T: usize>(a: &[E; T], b: &[E; T]) -> [u64; N] {
+        a.iter().zip(b.iter()).fold([0; N], |acc, (a, b)| {
+            Self::add(&acc, &Self::mul(a.limbs(), b.limbs()))
+        })
+    }
+}
+
+/// Elements whose limbs a [`Backend`] reads in place, so that arrays of elements
+/// need not be copied into arrays of limbs.
+pub(crate) trait Limbs<const N: usize> {
+    fn limbs(&self) -> &[u64; N];
+}
+
+impl<const N: usize> Limbs<N> for [u64; N] {
+    #[inline(always)]
+    fn limbs(&self) -> &[u64; N] {
+        self
+    }
+}
+
+/// The unrolled constant functions of the fields themselves, which are the
+/// fastest on 64-bit targets.
+pub(crate) struct Unrolled;
+
+/// The backend of the operators of the fields on this target.
+#[cfg(not(target_pointer_width = "32"))]
+pub(crate) type Selected = Unrolled;
+
+/// The backend of the operators of the fields on this target.
+#[cfg(target_pointer_width = "32")]
+pub(crate) type Selected = Limb32;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::ScalarParams;
+    use rand_core::{RngCore, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    /// Returns a random integer smaller than the modulus of `P`.
+    fn random<P: FieldParams<N>, const N: usize>(rng: &mut XorShiftRng) -> [u64; N] {
+        loop {
+            let mut limbs = [0u64; N];
+            for limb in limbs.iter_mut() {
+                *limb = rng.next_u64();
+            }
+            limbs[N - 1] &= u64::MAX >> P::MODULUS[N - 1].leading_zeros();
+            let mut borrow = 0;
+            for (limb, modulus) in limbs.iter().zip(P::MODULUS.iter()) {
+                borrow = crate::util::sbb(*limb, *modulus, borrow).1;
+            }
+            if borrow != 0 {
+                return limbs;
+            }
+        }
+    }
+
+    /// Elements on the boundaries of the carries and of the reductions.
+    fn edges<P: FieldParams<N>, const N: usize>() -> [[u64; N]; 4] {
+        let mut one = [0u64; N];
+        one[0] = 1;
+        let mut minus_one = P::MODULUS;
+        minus_one[0] -= 1;
+        let mut half = [0u64; N];
+        for (i, limb) in half.iter_mut().enumerate() {
+            *limb = P::MODULUS[i] >> 1;
+            if i + 1 < N {
+                *limb |= P::MODULUS[i + 1] << 63;
+            }
+        }
+        [[0; N], one, minus_one, half]
+    }
+
+    /// Checks that the backends `A` and `B` agree on every operation.
+    fn cross_check<A, B, P, const N: usize>()
+    where
+        A: Backend<P, N>,
+        B: Backend<P, N>,
+        P: FieldParams<N>,
+    {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        let mut elements = edges::<P, N>().to_vec();
+        for _ in 0..100 {
+            elements.push(random::<P, N>(&mut rng));
+        }
+
+        for a in elements.iter() {
+            assert_eq!(A::neg(a), B::neg(a));
+            assert_eq!(A::square(a), B::square(a));
+            for b in elements.iter() {
+                assert_eq!(A::add(a, b), B::add(a, b));
+                assert_eq!(A::sub(a, b), B::sub(a, b));
+                assert_eq!(A::mul(a, b), B::mul(a, b));
+            }
+        }
+
+        for (a, b) in elements
+            .chunks_exact(6)
+            .zip(elements.chunks_exact(6).skip(1))
+        {
+            let (a, b) = (
+                [a[0], a[1], a[2], a[3], a[4], a[5]],
+                [b[0], b[1], b[2], b[3], b[4], b[5]],
+            );
+            let expected = A::sum_of_products(&a, &b);
+            assert_eq!(B::sum_of_products(&a, &b), expected);
+            assert_eq!(
+                (0..6).fold([0; N], |acc, i| A::add(&acc, &A::mul(&a[i], &b[i]))),
+                expected
+            );
+
+            let (a, b) = ([a[0], a[1]], [b[0], b[1]]);
+            assert_eq!(A::sum_of_products(&a, &b), B::sum_of_products(&a, &b));
+            assert_eq!(A::sum_of_products(&[a[0]], &[b[0]]), A::mul(&a[0], &b[0]));
+            assert_eq!(B::sum_of_products(&[a[0]], &[b[0]]), A::mul(&a[0], &b[0]));
+        }
+    }
+
+    #[test]
+    fn test_scalar_backends() {
+        cross_check::<Unrolled, Portable, ScalarParams, 4>();
+        cross_check::<Unrolled, Limb32, ScalarParams, 4>();
+        cross_check::<Portable, Limb32, ScalarParams, 4>();
+    }
+
+    #[cfg(feature = "tower")]
+    #[test]
+    fn test_fp_backends() {
+        use crate::fp::FpParams;
+
+        cross_check::<Unrolled, Portable, FpParams, 6>();
+        cross_check::<Unrolled, Limb32, FpParams, 6>();
+        cross_check::<Portable, Limb32, FpParams, 6>();
+    }
+}
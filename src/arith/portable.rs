@@ -0,0 +1,113 @@
+//! A backend on 64-bit limbs for any number of them, with loops rather than the
+//! unrolled code of the fields. It is not selected on any target, but written as
+//! plainly as possible to serve as a reference for the other backends.
+
+use super::{Backend, FieldParams};
+use crate::util::{adc, mac, sbb};
+
+pub(crate) struct Portable;
+
+/// Subtracts the modulus from the integer `hi * 2^(64 N) + a` if that is not
+/// smaller than the modulus.
+#[inline(always)]
+fn reduce<P: FieldParams<N>, const N: usize>(a: &[u64; N], hi: u64) -> [u64; N] {
+    let mut res = [0u64; N];
+    let mut borrow = 0;
+    for i in 0..N {
+        let (limb, b) = sbb(a[i], P::MODULUS[i], borrow);
+        res[i] = limb;
+        borrow = b;
+    }
+    let (_, borrow) = sbb(hi, 0, borrow);
+
+    // borrow = 0x000...000 if the subtraction did not underflow, and 0xfff...fff
+    // if it did, in which case a is kept.
+    for i in 0..N {
+        res[i] = (res[i] & !borrow) | (a[i] & borrow);
+    }
+    res
+}
+
+impl<P: FieldParams<N>, const N: usize> Backend<P, N> for Portable {
+    fn add(a: &[u64; N], b: &[u64; N]) -> [u64; N] {
+        let mut sum = [0u64; N];
+        let mut carry = 0;
+        for i in 0..N {
+            let (limb, c) = adc(a[i], b[i], carry);
+            sum[i] = limb;
+            carry = c;
+        }
+        reduce::<P, N>(&sum, carry)
+    }
+
+    fn sub(a: &[u64; N], b: &[u64; N]) -> [u64; N] {
+        let mut diff = [0u64; N];
+        let mut borrow = 0;
+        for i in 0..N {
+            let (limb, b) = sbb(a[i], b[i], borrow);
+            diff[i] = limb;
+            borrow = b;
+        }
+
+        // If underflow occurred on the final limb, borrow = 0xfff...fff, otherwise
+        // borrow = 0x000...000. Thus, we use it as a mask to conditionally add the
+        // modulus.
+        let mut carry = 0;
+        for (limb, modulus) in diff.iter_mut().zip(P::MODULUS.iter()) {
+            let (sum, c) = adc(*limb, modulus & borrow, carry);
+            *limb = sum;
+            carry = c;
+        }
+        diff
+    }
+
+    fn neg(a: &[u64; N]) -> [u64; N] {
+        let mut res = [0u64; N];
+        let mut borrow = 0;
+        let mut nonzero = 0;
+        for i in 0..N {
+            let (limb, b) = sbb(P::MODULUS[i], a[i], borrow);
+            res[i] = limb;
+            borrow = b;
+            nonzero |= a[i];
+        }
+
+        // Let's use a mask if a was zero, which would mean the result of the
+        // subtraction is p.
+        let mask = ((nonzero == 0) as u64).wrapping_sub(1);
+        for limb in res.iter_mut() {
+            *limb &= mask;
+        }
+        res
+    }
+
+    fn mul(a: &[u64; N], b: &[u64; N]) -> [u64; N] {
+        // Coarsely integrated operand scanning: each word of b is multiplied in and
+        // a word of the product reduced away in turn, so that t has N + 2 words,
+        // the last two being t_n and t_n1.
+        let mut t = [0u64; N];
+        let mut t_n = 0;
+        for b_i in b.iter() {
+            let mut carry = 0;
+            for j in 0..N {
+                let (limb, c) = mac(t[j], a[j], *b_i, carry);
+                t[j] = limb;
+                carry = c;
+            }
+            let (limb, t_n1) = adc(t_n, carry, 0);
+            t_n = limb;
+
+            let m = t[0].wrapping_mul(P::INV);
+            let (_, mut carry) = mac(t[0], m, P::MODULUS[0], 0);
+            for j in 1..N {
+                let (limb, c) = mac(t[j], m, P::MODULUS[j], carry);
+                t[j - 1] = limb;
+                carry = c;
+            }
+            let (limb, c) = adc(t_n, carry, 0);
+            t[N - 1] = limb;
+            t_n = t_n1 + c;
+        }
+        reduce::<P, N>(&t, t_n)
+    }
+}
@@ -0,0 +1,239 @@
+//! Conversions between the types of this crate and those of the [`ark-bls12-381`]
+//! crate.
+//!
+//! Both crates store field elements in Montgomery form with the same radix and
+//! limb order, and build the extension fields with the same towers, so field
+//! elements convert without any arithmetic. Projective points are converted between
+//! the homogeneous coordinates used here and the Jacobian coordinates used by
+//! `ark-bls12-381` with a few multiplications, and without inversions.
+//!
+//! Conversions into this crate are fallible for points and for elements of
+//! $\mathbb{G}_T$, since the corresponding `ark-bls12-381` types do not guarantee
+//! that their values are in the prime-order subgroup.
+//!
+//! [`ark-bls12-381`]: https://docs.rs/ark-bls12-381
+
+use ark_bls12_381 as ark;
+use ark_ff::{BigInteger256, BigInteger384};
+use subtle::{Choice, ConditionallySelectable};
+
+use crate::fp::Fp;
+use crate::fp2::Fp2;
+use crate::{Error, G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+
+#[cfg(feature = "pairings")]
+use crate::{fp12::Fp12, fp6::Fp6, Gt};
+
+impl From<Fp> for ark::Fq {
+    fn from(f: Fp) -> Self {
+        ark::Fq::new(BigInteger384(f.0))
+    }
+}
+
+impl From<ark::Fq> for Fp {
+    fn from(f: ark::Fq) -> Self {
+        Fp((f.0).0)
+    }
+}
+
+impl From<Fp2> for ark::Fq2 {
+    fn from(f: Fp2) -> Self {
+        ark::Fq2::new(f.c0.into(), f.c1.into())
+    }
+}
+
+impl From<ark::Fq2> for Fp2 {
+    fn from(f: ark::Fq2) -> Self {
+        Fp2 {
+            c0: f.c0.into(),
+            c1: f.c1.into(),
+        }
+    }
+}
+
+impl From<Scalar> for ark::Fr {
+    fn from(s: Scalar) -> Self {
+        ark::Fr::new(BigInteger256(s.0))
+    }
+}
+
+impl From<ark::Fr> for Scalar {
+    fn from(s: ark::Fr) -> Self {
+        Scalar((s.0).0)
+    }
+}
+
+macro_rules! impl_point_conversions {
+    ($affine:ident, $projective:ident, $field:ident) => {
+        impl From<$affine> for ark::$affine {
+            fn from(p: $affine) -> Self {
+                ark::$affine::new(p.x.into(), p.y.into(), bool::from(p.infinity))
+            }
+        }
+
+        impl TryFrom<ark::$affine> for $affine {
+            type Error = Error;
+
+            fn try_from(p: ark::$affine) -> Result<Self, Error> {
+                let infinity = Choice::from(p.infinity as u8);
+                let p = $affine::conditional_select(
+                    &$affine {
+                        x: p.x.into(),
+                        y: p.y.into(),
+                        infinity,
+                    },
+                    &$affine::identity(),
+                    infinity,
+                );
+
+                if !bool::from(p.is_on_curve()) {
+                    Err(Error::NotOnCurve)
+                } else if !bool::from(p.is_torsion_free()) {
+                    Err(Error::WrongSubgroup)
+                } else {
+                    Ok(p)
+                }
+            }
+        }
+
+        impl From<$projective> for ark::$projective {
+            fn from(p: $projective) -> Self {
+                // (X : Y : Z) in homogeneous coordinates is (XZ : YZ^2 : Z) in
+                // Jacobian coordinates.
+                let z2 = p.z.square();
+                ark::$projective::new((p.x * p.z).into(), (p.y * z2).into(), p.z.into())
+            }
+        }
+
+        impl TryFrom<ark::$projective> for $projective {
+            type Error = Error;
+
+            fn try_from(p: ark::$projective) -> Result<Self, Error> {
+                // (X : Y : Z) in Jacobian coordinates is (XZ : Y : Z^3) in homogeneous
+                // coordinates.
+                let (x, y, z) = ($field::from(p.x), $field::from(p.y), $field::from(p.z));
+                let p = $projective {
+                    x: x * z,
+                    y,
+                    z: z.square() * z,
+                };
+
+                if !bool::from(p.is_on_curve()) {
+                    Err(Error::NotOnCurve)
+                } else if !bool::from($affine::from(p).is_torsion_free()) {
+                    Err(Error::WrongSubgroup)
+                } else {
+                    Ok(p)
+                }
+            }
+        }
+    };
+}
+
+impl_point_conversions!(G1Affine, G1Projective, Fp);
+impl_point_conversions!(G2Affine, G2Projective, Fp2);
+
+#[cfg(feature = "pairings")]
+impl From<Fp6> for ark::Fq6 {
+    fn from(f: Fp6) -> Self {
+        ark::Fq6::new(f.c0.into(), f.c1.into(), f.c2.into())
+    }
+}
+
+#[cfg(feature = "pairings")]
+impl From<ark::Fq6> for Fp6 {
+    fn from(f: ark::Fq6) -> Self {
+        Fp6 {
+            c0: f.c0.into(),
+            c1: f.c1.into(),
+            c2: f.c2.into(),
+        }
+    }
+}
+
+#[cfg(feature = "pairings")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pairings")))]
+impl From<Gt> for ark::Fq12 {
+    fn from(g: Gt) -> Self {
+        ark::Fq12::new(g.0.c0.into(), g.0.c1.into())
+    }
+}
+
+#[cfg(feature = "pairings")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pairings")))]
+impl TryFrom<ark::Fq12> for Gt {
+    type Error = Error;
+
+    fn try_from(f: ark::Fq12) -> Result<Self, Error> {
+        let f = Fp12 {
+            c0: f.c0.into(),
+            c1: f.c1.into(),
+        };
+
+        if bool::from(f.is_element()) {
+            Ok(Gt(f))
+        } else {
+            Err(Error::WrongSubgroup)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_conversions() {
+        // ark-bls12-381 stores the same Montgomery representation.
+        assert_eq!(Fp::from(ark::g1::G1_GENERATOR_X), G1Affine::generator().x);
+        let mut seven = [0u8; 48];
+        seven[47] = 7;
+        assert_eq!(
+            Fp::from(ark::Fq::from(7u64)),
+            Fp::from_bytes(&seven).unwrap()
+        );
+        assert_eq!(Scalar::from(ark::Fr::from(7u64)), Scalar::from(7u64));
+
+        let s = Scalar::from_raw([1, 2, 3, 4]).invert().unwrap();
+        assert_eq!(Scalar::from(ark::Fr::from(s)), s);
+        let f = G2Affine::generator().y;
+        assert_eq!(Fp2::from(ark::Fq2::from(f)), f);
+    }
+
+    #[test]
+    fn test_point_conversions() {
+        let s = Scalar::from_raw([5, 6, 7, 8]);
+        let p = G1Affine::generator() * s;
+        let q = G2Affine::generator() * s;
+
+        for p in [p, G1Projective::identity()] {
+            assert_eq!(G1Projective::try_from(ark::G1Projective::from(p)), Ok(p));
+            let p = G1Affine::from(p);
+            assert_eq!(G1Affine::try_from(ark::G1Affine::from(p)), Ok(p));
+        }
+        for q in [q, G2Projective::identity()] {
+            assert_eq!(G2Projective::try_from(ark::G2Projective::from(q)), Ok(q));
+            let q = G2Affine::from(q);
+            assert_eq!(G2Affine::try_from(ark::G2Affine::from(q)), Ok(q));
+        }
+
+        let mut invalid = ark::G1Affine::from(G1Affine::generator());
+        invalid.y = ark::Fq::from(1u64);
+        assert_eq!(G1Affine::try_from(invalid), Err(Error::NotOnCurve));
+
+        // A point of order 3 on the curve, outside of the subgroup.
+        let torsion = ark::G1Affine::new(ark::Fq::from(0u64), ark::Fq::from(2u64), false);
+        assert_eq!(G1Affine::try_from(torsion), Err(Error::WrongSubgroup));
+    }
+
+    #[cfg(feature = "pairings")]
+    #[test]
+    fn test_gt_conversions() {
+        let g = Gt::generator() * Scalar::from(42u64);
+        assert_eq!(Gt::try_from(ark::Fq12::from(g)), Ok(g));
+
+        let mut invalid = ark::Fq12::from(g);
+        invalid.c1 = ark::Fq12::from(Gt::identity()).c1;
+        assert_eq!(Gt::try_from(invalid), Err(Error::WrongSubgroup));
+    }
+}
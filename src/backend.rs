@@ -0,0 +1,384 @@
+//! A boundary for offloading the multi-scalar multiplications and number-theoretic
+//! transforms of provers to other hardware, such as a GPU.
+//!
+//! A [`Backend`] provides these operations, with the CPU implementations of this
+//! crate as default methods. An accelerated backend, implemented outside of this
+//! crate, overrides the operations it supports and calls the defaults for the
+//! others, or for inputs too small to be worth offloading. [`Cpu`] is the backend
+//! that overrides nothing, and `Threaded`, with the `parallel` feature, the one that
+//! runs large transforms on several threads.
+//!
+//! This module is only the boundary: the crate does not provide a GPU backend, and
+//! every backend that it does provide runs on the CPU.
+
+use ff::PrimeField;
+
+use crate::msm::multi_exp_auto_vartime;
+use crate::{G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+
+/// A provider of the operations that dominate the cost of proving.
+///
+/// Every method has a CPU implementation, so implementations only override the
+/// methods that they accelerate.
+pub trait Backend {
+    /// Computes $\sum_i s_i P_i$ in $\mathbb{G}_1$. Like the functions of
+    /// [`crate::msm`], this is variable time in the scalars.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bases` and `scalars` do not have the same length.
+    fn msm_g1(&self, bases: &[G1Affine], scalars: &[Scalar]) -> G1Projective {
+        multi_exp_auto_vartime(bases, scalars)
+    }
+
+    /// Computes $\sum_i s_i P_i$ in $\mathbb{G}_2$. Like the functions of
+    /// [`crate::msm`], this is variable time in the scalars.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bases` and `scalars` do not have the same length.
+    fn msm_g2(&self, bases: &[G2Affine], scalars: &[Scalar]) -> G2Projective {
+        multi_exp_auto_vartime(bases, scalars)
+    }
+
+    /// Replaces the coefficients of a polynomial of degree less than $n$ with its
+    /// evaluations at $\omega^0, \ldots, \omega^{n - 1}$, in natural order, where $n$
+    /// is the length of `values` and $\omega$ is the primitive $n$th root of unity
+    /// obtained by squaring [`PrimeField::root_of_unity`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `values` is not a power of two dividing $2^{32}$.
+    fn ntt(&self, values: &mut [Scalar]) {
+        ntt(values, false)
+    }
+
+    /// Inverts [`Backend::ntt`], replacing evaluations with coefficients.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `values` is not a power of two dividing $2^{32}$.
+    fn intt(&self, values: &mut [Scalar]) {
+        ntt(values, true)
+    }
+}
+
+/// The backend that runs every operation on the CPU.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Cpu;
+
+impl Backend for Cpu {}
+
+/// The backend that runs the transforms of at least $2^{12}$ values on several
+/// threads of the CPU, with the four-step algorithm: a transform of size
+/// $n = n_1 n_2$ is computed as $n_1$ transforms of size $n_2$, each on a row of
+/// the inputs, then $n_2$ transforms of size $n_1$, each on a column of the
+/// results, and the transforms of each step are split between the threads.
+///
+/// The threads work on copies of their rows, so the transforms take twice the
+/// memory of the values, and the twiddles and the copies make the four-step
+/// algorithm about a third slower than the transform of [`Cpu`] on a single
+/// core. Smaller transforms, and the multi-scalar multiplications, run on the
+/// calling thread as with [`Cpu`].
+#[cfg(feature = "parallel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+#[derive(Clone, Copy, Debug)]
+pub struct Threaded {
+    threads: usize,
+}
+
+#[cfg(feature = "parallel")]
+impl Threaded {
+    /// The smallest transform that is split between threads.
+    const MIN_PARALLEL_SIZE: usize = 1 << 12;
+
+    /// Returns the backend that uses up to `threads` threads, or one if `threads`
+    /// is zero.
+    pub fn new(threads: usize) -> Self {
+        Threaded {
+            threads: threads.max(1),
+        }
+    }
+
+    fn transform(&self, values: &mut [Scalar], inverse: bool) {
+        if self.threads > 1 && values.len() >= Self::MIN_PARALLEL_SIZE {
+            four_step_ntt(values, inverse, self.threads)
+        } else {
+            ntt(values, inverse)
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl Backend for Threaded {
+    fn ntt(&self, values: &mut [Scalar]) {
+        self.transform(values, false)
+    }
+
+    fn intt(&self, values: &mut [Scalar]) {
+        self.transform(values, true)
+    }
+}
+
+/// Returns the primitive `n`th root of unity, or its inverse if `inverse` is set,
+/// for the power of two `n`.
+fn root_of_unity(n: usize, inverse: bool) -> Scalar {
+    let mut omega = Scalar::root_of_unity();
+    for _ in n.trailing_zeros()..Scalar::S {
+        omega = omega.square();
+    }
+    if inverse {
+        omega = omega.invert().unwrap();
+    }
+    omega
+}
+
+/// An in-place radix-2 number-theoretic transform over the multiplicative subgroup
+/// of order `values.len()`.
+fn ntt(values: &mut [Scalar], inverse: bool) {
+    let n = values.len();
+    assert!(n.is_power_of_two() && n.trailing_zeros() <= Scalar::S);
+    if n == 1 {
+        return;
+    }
+
+    radix2(values, &root_of_unity(n, inverse));
+
+    if inverse {
+        let n_inv = Scalar::from(n as u64).invert().unwrap();
+        for value in values.iter_mut() {
+            *value *= n_inv;
+        }
+    }
+}
+
+/// The transform of [`ntt`] with the root of unity `omega` of order `values.len()`,
+/// without the division by that order of the inverse transform.
+fn radix2(values: &mut [Scalar], omega: &Scalar) {
+    let n = values.len();
+    if n == 1 {
+        return;
+    }
+
+    let shift = usize::BITS - n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> shift;
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    // The root of unity of order 2^i is omega^(n / 2^i), the square of that of order
+    // 2^(i + 1).
+    let log_n = n.trailing_zeros() as usize;
+    let mut roots = [*omega; Scalar::S as usize + 1];
+    for i in (1..log_n).rev() {
+        roots[i] = roots[i + 1].square();
+    }
+
+    for (len, omega_len) in (1..=log_n).map(|i| (1 << i, roots[i])) {
+        for chunk in values.chunks_exact_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            let mut w = Scalar::one();
+            for (a, b) in lo.iter_mut().zip(hi.iter_mut()) {
+                let t = *b * w;
+                *b = *a - t;
+                *a += t;
+                w *= omega_len;
+            }
+        }
+    }
+}
+
+/// Computes [`ntt`] with the four-step algorithm, on up to `threads` threads.
+///
+/// With $n = n_1 n_2$, the input $j = j_1 + n_1 j_2$ and the output
+/// $k = k_2 + n_2 k_1$, $\omega^{jk} = \omega^{n_1 j_2 k_2} \omega^{j_1 k_2}
+/// \omega^{n_2 j_1 k_1}$, where $\omega^{n_1}$ and $\omega^{n_2}$ are the roots of
+/// unity of the transforms of sizes $n_2$ and $n_1$. The inverse transform is the
+/// same with the inverse roots, followed by the division by $n$.
+#[cfg(feature = "parallel")]
+fn four_step_ntt(values: &mut [Scalar], inverse: bool, threads: usize) {
+    use crate::threads::{chunk_size, map_chunks};
+    use alloc::vec::Vec;
+
+    let n = values.len();
+    assert!(n.is_power_of_two() && n.trailing_zeros() <= Scalar::S);
+    let n1 = 1 << (n.trailing_zeros() / 2);
+    let n2 = n / n1;
+    let omega = root_of_unity(n, inverse);
+    let (omega_n1, omega_n2) = (omega.pow_u64(n1 as u64), omega.pow_u64(n2 as u64));
+    let n_inv = Scalar::from(n as u64).invert().unwrap();
+
+    // Row j1 holds the inputs j1 + n1 j2, whose transform is twiddled by
+    // omega^(j1 k2).
+    let mut rows = Vec::with_capacity(n);
+    for j1 in 0..n1 {
+        rows.extend((0..n2).map(|j2| values[j1 + n1 * j2]));
+    }
+    let rows = map_chunks(
+        &rows,
+        chunk_size(n1, threads) * n2,
+        move |offset, mut chunk| {
+            for (j1, row) in (offset / n2..).zip(chunk.chunks_exact_mut(n2)) {
+                radix2(row, &omega_n1);
+                let w = omega.pow_u64(j1 as u64);
+                let mut twiddle = Scalar::one();
+                for value in row.iter_mut() {
+                    *value *= twiddle;
+                    twiddle *= w;
+                }
+            }
+            chunk
+        },
+    )
+    .concat();
+
+    // Column k2 holds the twiddled transforms at k2, whose transform is the outputs
+    // k2 + n2 k1.
+    let mut columns = Vec::with_capacity(n);
+    for k2 in 0..n2 {
+        columns.extend((0..n1).map(|j1| rows[j1 * n2 + k2]));
+    }
+    let columns = map_chunks(
+        &columns,
+        chunk_size(n2, threads) * n1,
+        move |_, mut chunk| {
+            for column in chunk.chunks_exact_mut(n1) {
+                radix2(column, &omega_n2);
+            }
+            if inverse {
+                for value in chunk.iter_mut() {
+                    *value *= n_inv;
+                }
+            }
+            chunk
+        },
+    )
+    .concat();
+
+    for k2 in 0..n2 {
+        for k1 in 0..n1 {
+            values[k2 + n2 * k1] = columns[k2 * n1 + k1];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_cpu_msm() {
+        let bases = (1..=40)
+            .map(|i| G1Affine::from(G1Affine::generator() * Scalar::from(i)))
+            .collect::<Vec<_>>();
+        let scalars = (1..=40)
+            .map(|i| Scalar::from(i).invert().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            Cpu.msm_g1(&bases, &scalars),
+            G1Projective::generator() * Scalar::from(40)
+        );
+        assert_eq!(
+            Cpu.msm_g2(&[G2Affine::generator()], &[Scalar::from(3)]),
+            G2Projective::generator() * Scalar::from(3)
+        );
+        assert_eq!(Cpu.msm_g1(&[], &[]), G1Projective::identity());
+    }
+
+    #[test]
+    fn test_ntt() {
+        let coefficients = (0..8).map(|i| Scalar::from(i * i + 1)).collect::<Vec<_>>();
+
+        let mut omega = Scalar::root_of_unity();
+        for _ in 3..Scalar::S {
+            omega = omega.square();
+        }
+        let expected = (0..8)
+            .map(|i| {
+                let x = omega.pow_vartime(&[i, 0, 0, 0]);
+                coefficients
+                    .iter()
+                    .rev()
+                    .fold(Scalar::zero(), |acc, c| acc * x + c)
+            })
+            .collect::<Vec<_>>();
+
+        let mut values = coefficients.clone();
+        Cpu.ntt(&mut values);
+        assert_eq!(values, expected);
+        Cpu.intt(&mut values);
+        assert_eq!(values, coefficients);
+
+        let mut single = [Scalar::from(5)];
+        Cpu.ntt(&mut single);
+        assert_eq!(single, [Scalar::from(5)]);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_four_step_ntt() {
+        for log_n in 0..=9 {
+            let values = (0..1u64 << log_n)
+                .map(|i| Scalar::from(i * i + 7).invert().unwrap())
+                .collect::<Vec<_>>();
+            let mut expected = values.clone();
+            ntt(&mut expected, false);
+
+            for threads in [1, 2, 3, 64] {
+                let mut transformed = values.clone();
+                four_step_ntt(&mut transformed, false, threads);
+                assert_eq!(transformed, expected);
+                four_step_ntt(&mut transformed, true, threads);
+                assert_eq!(transformed, values);
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_threaded_ntt() {
+        let values = (0..Threaded::MIN_PARALLEL_SIZE as u64)
+            .map(Scalar::from)
+            .collect::<Vec<_>>();
+        let mut expected = values.clone();
+        Cpu.ntt(&mut expected);
+
+        for threads in [0, 1, 4] {
+            let mut transformed = values.clone();
+            Threaded::new(threads).ntt(&mut transformed);
+            assert_eq!(transformed, expected);
+            Threaded::new(threads).intt(&mut transformed);
+            assert_eq!(transformed, values);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ntt_invalid_length() {
+        Cpu.ntt(&mut [Scalar::one(); 6]);
+    }
+
+    #[test]
+    fn test_override() {
+        // A backend that counts the transforms it is asked for, and otherwise falls
+        // back to the CPU.
+        struct Counting(core::cell::Cell<usize>);
+
+        impl Backend for Counting {
+            fn ntt(&self, values: &mut [Scalar]) {
+                self.0.set(self.0.get() + 1);
+                Cpu.ntt(values)
+            }
+        }
+
+        let backend = Counting(core::cell::Cell::new(0));
+        let mut values = [Scalar::one(), Scalar::zero()];
+        backend.ntt(&mut values);
+        backend.intt(&mut values);
+        assert_eq!(values, [Scalar::one(), Scalar::zero()]);
+        assert_eq!(backend.0.get(), 1);
+    }
+}
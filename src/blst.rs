@@ -0,0 +1,233 @@
+//! Conversions between the types of this crate and those of the [`blst`] crate.
+//!
+//! Both crates store field elements and `blst_fr` scalars in Montgomery form with
+//! the same radix and limb order, so these convert by copying limbs, without any
+//! arithmetic. Projective points are converted between the homogeneous coordinates
+//! used here and the Jacobian coordinates used by `blst` with a few
+//! multiplications, and without inversions.
+//!
+//! Conversions into this crate are fallible for points, since `blst` does not
+//! guarantee that its point types hold elements of the prime-order subgroup, and for
+//! `blst_scalar`, whose bytes need not be a canonical encoding.
+//!
+//! [`blst`]: https://docs.rs/blst
+
+use ::blst::{
+    blst_fp, blst_fp2, blst_fr, blst_p1, blst_p1_affine, blst_p2, blst_p2_affine, blst_scalar,
+};
+use subtle::{Choice, ConditionallySelectable};
+
+use crate::fp::Fp;
+use crate::fp2::Fp2;
+use crate::{Error, G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+
+impl From<Fp> for blst_fp {
+    fn from(f: Fp) -> Self {
+        blst_fp { l: f.0 }
+    }
+}
+
+impl From<blst_fp> for Fp {
+    fn from(f: blst_fp) -> Self {
+        Fp(f.l)
+    }
+}
+
+impl From<Fp2> for blst_fp2 {
+    fn from(f: Fp2) -> Self {
+        blst_fp2 {
+            fp: [f.c0.into(), f.c1.into()],
+        }
+    }
+}
+
+impl From<blst_fp2> for Fp2 {
+    fn from(f: blst_fp2) -> Self {
+        Fp2 {
+            c0: f.fp[0].into(),
+            c1: f.fp[1].into(),
+        }
+    }
+}
+
+impl From<Scalar> for blst_fr {
+    fn from(s: Scalar) -> Self {
+        blst_fr { l: s.0 }
+    }
+}
+
+impl From<blst_fr> for Scalar {
+    fn from(s: blst_fr) -> Self {
+        Scalar(s.l)
+    }
+}
+
+impl From<Scalar> for blst_scalar {
+    fn from(s: Scalar) -> Self {
+        blst_scalar { b: s.to_bytes() }
+    }
+}
+
+impl TryFrom<blst_scalar> for Scalar {
+    type Error = Error;
+
+    fn try_from(s: blst_scalar) -> Result<Self, Error> {
+        Option::from(Scalar::from_bytes(&s.b)).ok_or(Error::NonCanonicalField)
+    }
+}
+
+macro_rules! impl_point_conversions {
+    (
+        $affine:ident,
+        $projective:ident,
+        $field:ident,
+        $blst_affine:ident,
+        $blst_projective:ident
+    ) => {
+        impl From<$affine> for $blst_affine {
+            fn from(p: $affine) -> Self {
+                // blst represents the point at infinity with zero coordinates.
+                let p = $affine::conditional_select(
+                    &p,
+                    &$affine {
+                        x: $field::zero(),
+                        y: $field::zero(),
+                        infinity: Choice::from(1u8),
+                    },
+                    p.infinity,
+                );
+                $blst_affine {
+                    x: p.x.into(),
+                    y: p.y.into(),
+                }
+            }
+        }
+
+        impl TryFrom<$blst_affine> for $affine {
+            type Error = Error;
+
+            fn try_from(p: $blst_affine) -> Result<Self, Error> {
+                let (x, y) = ($field::from(p.x), $field::from(p.y));
+                let infinity = x.is_zero() & y.is_zero();
+                let p = $affine::conditional_select(
+                    &$affine {
+                        x,
+                        y,
+                        infinity: Choice::from(0u8),
+                    },
+                    &$affine::identity(),
+                    infinity,
+                );
+
+                if !bool::from(p.is_on_curve()) {
+                    Err(Error::NotOnCurve)
+                } else if !bool::from(p.is_torsion_free()) {
+                    Err(Error::WrongSubgroup)
+                } else {
+                    Ok(p)
+                }
+            }
+        }
+
+        impl From<$projective> for $blst_projective {
+            fn from(p: $projective) -> Self {
+                // (X : Y : Z) in homogeneous coordinates is (XZ : YZ^2 : Z) in
+                // Jacobian coordinates.
+                let z2 = p.z.square();
+                $blst_projective {
+                    x: (p.x * p.z).into(),
+                    y: (p.y * z2).into(),
+                    z: p.z.into(),
+                }
+            }
+        }
+
+        impl TryFrom<$blst_projective> for $projective {
+            type Error = Error;
+
+            fn try_from(p: $blst_projective) -> Result<Self, Error> {
+                // (X : Y : Z) in Jacobian coordinates is (XZ : Y : Z^3) in homogeneous
+                // coordinates, and blst represents the point at infinity with Z = 0.
+                let (x, y, z) = ($field::from(p.x), $field::from(p.y), $field::from(p.z));
+                let p = $projective::conditional_select(
+                    &$projective {
+                        x: x * z,
+                        y,
+                        z: z.square() * z,
+                    },
+                    &$projective::identity(),
+                    z.is_zero(),
+                );
+
+                if !bool::from(p.is_on_curve()) {
+                    Err(Error::NotOnCurve)
+                } else if !bool::from($affine::from(p).is_torsion_free()) {
+                    Err(Error::WrongSubgroup)
+                } else {
+                    Ok(p)
+                }
+            }
+        }
+    };
+}
+
+impl_point_conversions!(G1Affine, G1Projective, Fp, blst_p1_affine, blst_p1);
+impl_point_conversions!(G2Affine, G2Projective, Fp2, blst_p2_affine, blst_p2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scalar_conversions() {
+        let s = Scalar::from_raw([1, 2, 3, 4]).invert().unwrap();
+        assert_eq!(Scalar::from(blst_fr::from(s)), s);
+        assert_eq!(Scalar::try_from(blst_scalar::from(s)), Ok(s));
+        assert_eq!(blst_scalar::from(Scalar::from(7)).b[0], 7);
+
+        let invalid = blst_scalar { b: [0xff; 32] };
+        assert_eq!(Scalar::try_from(invalid), Err(Error::NonCanonicalField));
+    }
+
+    #[test]
+    fn test_point_conversions() {
+        let s = Scalar::from_raw([5, 6, 7, 8]);
+        let p = G1Affine::generator() * s;
+        let q = G2Affine::generator() * s;
+
+        for p in [p, G1Projective::identity()] {
+            assert_eq!(G1Projective::try_from(blst_p1::from(p)), Ok(p));
+            let p = G1Affine::from(p);
+            assert_eq!(G1Affine::try_from(blst_p1_affine::from(p)), Ok(p));
+        }
+        for q in [q, G2Projective::identity()] {
+            assert_eq!(G2Projective::try_from(blst_p2::from(q)), Ok(q));
+            let q = G2Affine::from(q);
+            assert_eq!(G2Affine::try_from(blst_p2_affine::from(q)), Ok(q));
+        }
+
+        assert_eq!(
+            blst_p1_affine::from(G1Affine::identity()),
+            blst_p1_affine::default()
+        );
+        assert_eq!(
+            G2Affine::try_from(blst_p2_affine::default()),
+            Ok(G2Affine::identity())
+        );
+        assert_eq!(
+            G1Projective::try_from(blst_p1::default()),
+            Ok(G1Projective::identity())
+        );
+
+        let mut invalid = blst_p1_affine::from(G1Affine::generator());
+        invalid.y = Fp::one().into();
+        assert_eq!(G1Affine::try_from(invalid), Err(Error::NotOnCurve));
+
+        // A point of order 3 on the curve, outside of the subgroup.
+        let torsion = blst_p1_affine {
+            x: Fp::zero().into(),
+            y: (Fp::one() + Fp::one()).into(),
+        };
+        assert_eq!(G1Affine::try_from(torsion), Err(Error::WrongSubgroup));
+    }
+}
@@ -0,0 +1,283 @@
+//! Decompression of points in constant contexts, so that the square roots that
+//! decompression requires are computed by the compiler rather than at run time.
+//!
+//! `Choice` has no constant constructor, so neither `G1Affine` nor `G2Affine` can
+//! be built in a constant. Decompression thus produces the uncompressed encoding
+//! of the point, whose decoding is cheap. Constant functions cannot call trait
+//! methods either, so the few field operations that are not already constant
+//! functions are reimplemented here, in variable time, since the points are
+//! constants.
+
+use crate::constants::{G1_B, G2_B};
+use crate::fp::{Fp, MODULUS, R2};
+use crate::fp2::{Fp2, TWO_INV};
+use crate::util::sbb;
+
+/// (p + 1) / 4, the exponent of the square roots in `Fp`.
+const P_PLUS_1_DIV_4: [u64; 6] = [
+    0xee7f_bfff_ffff_eaab,
+    0x07aa_ffff_ac54_ffff,
+    0xd9cc_34a8_3dac_3d89,
+    0xd91d_d2e1_3ce1_44af,
+    0x92c6_e9ed_90d2_eb35,
+    0x0680_447a_8e5f_f9a6,
+];
+
+/// (p - 3) / 4.
+const P_MINUS_3_DIV_4: [u64; 6] = [
+    0xee7f_bfff_ffff_eaaa,
+    0x07aa_ffff_ac54_ffff,
+    0xd9cc_34a8_3dac_3d89,
+    0xd91d_d2e1_3ce1_44af,
+    0x92c6_e9ed_90d2_eb35,
+    0x0680_447a_8e5f_f9a6,
+];
+
+/// (p - 1) / 2 + 1, the smallest integer that is lexicographically largest.
+const P_MINUS_1_DIV_2_PLUS_1: [u64; 6] = [
+    0xdcff_7fff_ffff_d556,
+    0x0f55_ffff_58a9_ffff,
+    0xb398_6950_7b58_7b12,
+    0xb23b_a5c2_79c2_895f,
+    0x258d_d3db_21a5_d66b,
+    0x0d00_88f5_1cbf_f34d,
+];
+
+/// Fails the evaluation of the constant, or panics at run time, unless `valid`.
+///
+/// `panic!` cannot be used in constant functions before Rust 1.57, but indexing
+/// out of bounds has the same effect.
+#[allow(clippy::no_effect)]
+const fn require(valid: bool) {
+    [()][!valid as usize]
+}
+
+/// Returns whether `a` is smaller than `b`, as little-endian integers.
+const fn less_than(a: &[u64; 6], b: &[u64; 6]) -> bool {
+    let mut borrow = 0;
+    let mut i = 0;
+    while i < 6 {
+        borrow = sbb(a[i], b[i], borrow).1;
+        i += 1;
+    }
+    borrow != 0
+}
+
+const fn fp_eq(a: &Fp, b: &Fp) -> bool {
+    let mut i = 0;
+    while i < 6 {
+        if a.0[i] != b.0[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn fp_is_zero(a: &Fp) -> bool {
+    fp_eq(a, &Fp::zero())
+}
+
+const fn fp_pow(a: &Fp, by: &[u64; 6]) -> Fp {
+    let mut res = Fp::one();
+    let mut i = 6;
+    while i > 0 {
+        i -= 1;
+        let mut j = 64;
+        while j > 0 {
+            j -= 1;
+            res = res.square();
+            if (by[i] >> j) & 1 == 1 {
+                res = res.mul(a);
+            }
+        }
+    }
+    res
+}
+
+const fn fp_sqrt(a: &Fp) -> Option<Fp> {
+    let sqrt = fp_pow(a, &P_PLUS_1_DIV_4);
+    if fp_eq(&sqrt.square(), a) {
+        Some(sqrt)
+    } else {
+        None
+    }
+}
+
+/// The canonical integer representation of `a`.
+const fn fp_canonical(a: &Fp) -> [u64; 6] {
+    Fp::montgomery_reduce(
+        a.0[0], a.0[1], a.0[2], a.0[3], a.0[4], a.0[5], 0, 0, 0, 0, 0, 0,
+    )
+    .0
+}
+
+const fn fp_lexicographically_largest(a: &Fp) -> bool {
+    !less_than(&fp_canonical(a), &P_MINUS_1_DIV_2_PLUS_1)
+}
+
+/// Decodes the big-endian element of `Fp` at `bytes[offset..offset + 48]`, with
+/// the flag bits masked away if `flags` is set, failing if it is not canonical.
+const fn fp_from_bytes(bytes: &[u8], offset: usize, flags: bool) -> Option<Fp> {
+    let mut limbs = [0u64; 6];
+    let mut i = 0;
+    while i < 48 {
+        let mut byte = bytes[offset + i];
+        if flags && i == 0 {
+            byte &= 0b0001_1111;
+        }
+        limbs[5 - i / 8] = (limbs[5 - i / 8] << 8) | byte as u64;
+        i += 1;
+    }
+
+    if less_than(&limbs, &MODULUS) {
+        Some(Fp(limbs).mul(&R2))
+    } else {
+        None
+    }
+}
+
+/// Writes the big-endian encoding of `a` to `bytes[offset..offset + 48]`.
+const fn fp_to_bytes<const N: usize>(a: &Fp, mut bytes: [u8; N], offset: usize) -> [u8; N] {
+    let limbs = fp_canonical(a);
+    let mut i = 0;
+    while i < 48 {
+        bytes[offset + i] = (limbs[5 - i / 8] >> (8 * (7 - i % 8))) as u8;
+        i += 1;
+    }
+    bytes
+}
+
+const fn fp2_eq(a: &Fp2, b: &Fp2) -> bool {
+    fp_eq(&a.c0, &b.c0) && fp_eq(&a.c1, &b.c1)
+}
+
+const fn fp2_mul(a: &Fp2, b: &Fp2) -> Fp2 {
+    Fp2 {
+        c0: a.c0.mul(&b.c0).sub(&a.c1.mul(&b.c1)),
+        c1: a.c0.mul(&b.c1).add(&a.c1.mul(&b.c0)),
+    }
+}
+
+/// Computes a square root of `a` with the complex method, as [`Fp2::sqrt`] does.
+const fn fp2_sqrt(a: &Fp2) -> Option<Fp2> {
+    let s = match fp_sqrt(&a.c0.square().add(&a.c1.square())) {
+        Some(s) => s,
+        None => return None,
+    };
+
+    // t = (a0 + s) / 2, or a0 if that is zero.
+    let mut t = a.c0.add(&s).mul(&TWO_INV);
+    if fp_is_zero(&t) {
+        t = a.c0;
+    }
+
+    // With u = t^((p - 3) / 4), x = u t is the root of t or of -t, and x u is 1 or
+    // -1 accordingly, so that y = a1 / (2 x) is computed without an inversion.
+    let u = fp_pow(&t, &P_MINUS_3_DIV_4);
+    let x = u.mul(&t);
+    let t_is_square = fp_eq(&x.square(), &t);
+    let x_inv = if t_is_square { u } else { u.neg() };
+    let y = a.c1.mul(&x_inv).mul(&TWO_INV);
+    let sqrt = if t_is_square {
+        Fp2 { c0: x, c1: y }
+    } else {
+        Fp2 { c0: y, c1: x }
+    };
+
+    if fp2_eq(&fp2_mul(&sqrt, &sqrt), a) {
+        Some(sqrt)
+    } else {
+        None
+    }
+}
+
+const fn fp2_lexicographically_largest(a: &Fp2) -> bool {
+    fp_lexicographically_largest(&a.c1)
+        || (fp_is_zero(&a.c1) && fp_lexicographically_largest(&a.c0))
+}
+
+/// Checks the flags of a compressed encoding, returning whether the point is the
+/// identity and whether the sort flag is set.
+const fn flags(first_byte: u8) -> (bool, bool) {
+    let compression_flag_set = (first_byte >> 7) & 1 == 1;
+    let infinity_flag_set = (first_byte >> 6) & 1 == 1;
+    let sort_flag_set = (first_byte >> 5) & 1 == 1;
+    require(compression_flag_set && !(infinity_flag_set && sort_flag_set));
+    (infinity_flag_set, sort_flag_set)
+}
+
+/// Decompresses the compressed encoding of an element of $\mathbb{G}_1$ into its
+/// uncompressed encoding.
+pub(crate) const fn decompress_g1(bytes: &[u8; 48]) -> [u8; 96] {
+    let (infinity, sort) = flags(bytes[0]);
+    let (x, valid) = match fp_from_bytes(bytes, 0, true) {
+        Some(x) => (x, true),
+        None => (Fp::zero(), false),
+    };
+    require(valid);
+
+    let mut res = [0; 96];
+    if infinity {
+        require(fp_is_zero(&x));
+        res[0] = 1 << 6;
+        return res;
+    }
+
+    let y = match fp_sqrt(&x.square().mul(&x).add(&G1_B)) {
+        Some(y) => y,
+        None => {
+            // x^3 + b is not a square: the point is not on the curve.
+            require(false);
+            Fp::zero()
+        }
+    };
+    let y = if fp_lexicographically_largest(&y) == sort {
+        y
+    } else {
+        y.neg()
+    };
+
+    let res = fp_to_bytes(&x, res, 0);
+    fp_to_bytes(&y, res, 48)
+}
+
+/// Decompresses the compressed encoding of an element of $\mathbb{G}_2$ into its
+/// uncompressed encoding.
+pub(crate) const fn decompress_g2(bytes: &[u8; 96]) -> [u8; 192] {
+    let (infinity, sort) = flags(bytes[0]);
+    let (x, valid) = match (
+        fp_from_bytes(bytes, 0, true),
+        fp_from_bytes(bytes, 48, false),
+    ) {
+        (Some(c1), Some(c0)) => (Fp2 { c0, c1 }, true),
+        _ => (Fp2::zero(), false),
+    };
+    require(valid);
+
+    let mut res = [0; 192];
+    if infinity {
+        require(fp2_eq(&x, &Fp2::zero()));
+        res[0] = 1 << 6;
+        return res;
+    }
+
+    let y = match fp2_sqrt(&fp2_mul(&x.square(), &x).add(&G2_B)) {
+        Some(y) => y,
+        None => {
+            // x^3 + b' is not a square: the point is not on the curve.
+            require(false);
+            Fp2::zero()
+        }
+    };
+    let y = if fp2_lexicographically_largest(&y) == sort {
+        y
+    } else {
+        y.neg()
+    };
+
+    let res = fp_to_bytes(&x.c1, res, 0);
+    let res = fp_to_bytes(&x.c0, res, 48);
+    let res = fp_to_bytes(&y.c1, res, 96);
+    fp_to_bytes(&y.c0, res, 144)
+}
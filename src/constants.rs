@@ -0,0 +1,645 @@
+//! Parameters of BLS12-381, and constants of the curves on which $\mathbb{G}_1$ and
+//! $\mathbb{G}_2$ are defined and of the isogenous curves through which messages are
+//! hashed to them.
+//!
+//! The integer parameters are given as little-endian limbs of 64 bits, and the moduli
+//! also as bytes in the endianness of the encodings of their field elements. Each
+//! coordinate is given as a field element, and as the limbs of its canonical and
+//! Montgomery representations for use outside of this crate.
+//! [`Fp::from_raw_unchecked`] accepts the latter.
+
+use crate::fp::Fp;
+use crate::fp2::Fp2;
+
+/// The characteristic $p$ of the base field $\mathbb{F}_p$, as little-endian limbs.
+pub const FP_MODULUS: [u64; 6] = [
+    0xb9fe_ffff_ffff_aaab,
+    0x1eab_fffe_b153_ffff,
+    0x6730_d2a0_f6b0_f624,
+    0x6477_4b84_f385_12bf,
+    0x4b1b_a7b6_434b_acd7,
+    0x1a01_11ea_397f_e69a,
+];
+
+/// [`FP_MODULUS`] as 48 big-endian bytes, the byte order of [`Fp::to_bytes`].
+pub const FP_MODULUS_BYTES: [u8; 48] = limbs_to_be_bytes(FP_MODULUS);
+
+/// The order $q$ of $\mathbb{G}_1$, $\mathbb{G}_2$ and $\mathbb{G}_T$, which is the
+/// characteristic of the scalar field, as little-endian limbs.
+pub const SCALAR_MODULUS: [u64; 4] = [
+    0xffff_ffff_0000_0001,
+    0x53bd_a402_fffe_5bfe,
+    0x3339_d808_09a1_d805,
+    0x73ed_a753_299d_7d48,
+];
+
+/// [`SCALAR_MODULUS`] as 32 little-endian bytes, the byte order of
+/// [`Scalar::to_bytes`](crate::Scalar::to_bytes).
+pub const SCALAR_MODULUS_BYTES: [u8; 32] = limbs_to_le_bytes(SCALAR_MODULUS);
+
+/// The absolute value of the BLS parameter $x = -\texttt{0xd201000000010000}$ from
+/// which the curve is derived, with $p = (x - 1)^2 (x^4 - x^2 + 1) / 3 + x$ and
+/// $q = x^4 - x^2 + 1$.
+pub const BLS_X: u64 = 0xd201_0000_0001_0000;
+
+/// Whether the BLS parameter $x$ is negative, which it is.
+pub const BLS_X_IS_NEGATIVE: bool = true;
+
+/// The cofactor $h_1 = (x - 1)^2 / 3$ of $\mathbb{G}_1$ in the group of points of its
+/// curve over $\mathbb{F}_p$, as little-endian limbs.
+pub const G1_COFACTOR: [u64; 2] = [0x8c00_aaab_0000_aaab, 0x396c_8c00_5555_e156];
+
+/// The cofactor $h_2$ of $\mathbb{G}_2$ in the group of points of its curve over
+/// $\mathbb{F}_{p^2}$, which is $(x^8 - 4 x^7 + 5 x^6 - 4 x^4 + 6 x^3 - 4 x^2 - 4 x +
+/// 13) / 9$, as little-endian limbs.
+pub const G2_COFACTOR: [u64; 8] = [
+    0xcf1c_38e3_1c72_38e5,
+    0x1616_ec6e_786f_0c70,
+    0x2153_7e29_3a66_91ae,
+    0xa628_f1cb_4d9e_82ef,
+    0xa68a_205b_2e5a_7ddf,
+    0xcd91_de45_4708_5aba,
+    0x091d_5079_2876_a202,
+    0x05d5_43a9_5414_e7f1,
+];
+
+/// The effective cofactor $h_\textrm{eff} = 1 - x$ of $\mathbb{G}_1$, a multiple of
+/// [`G1_COFACTOR`] modulo $q$ by which a point of the curve can be multiplied instead
+/// to map it to $\mathbb{G}_1$, as computed by `G1Projective::clear_cofactor`.
+pub const G1_H_EFF: u64 = 0xd201_0000_0001_0001;
+
+/// The effective cofactor $h_\textrm{eff} = 3 (x^2 - 1) h_2$ of $\mathbb{G}_2$ from
+/// the `BLS12381G2` hash-to-curve suites, as little-endian limbs. Multiplying by it is
+/// what `G2Projective::clear_cofactor` computes.
+pub const G2_H_EFF: [u64; 10] = [
+    0xe802_0005_aaa9_5551,
+    0x5989_4c0a_debb_f6b4,
+    0xe954_cbc0_6689_f6a3,
+    0x2ec0_ec69_d747_7c1a,
+    0x6d82_bf01_5d12_12b0,
+    0x329c_2f17_8731_db95,
+    0x9986_ff03_1508_ffe1,
+    0x88e2_a8e9_145a_d768,
+    0x584c_6a0e_a91b_3528,
+    0x0bc6_9f08_f2ee_75b3,
+];
+
+/// The coefficient $b = 4$ of the curve $E: y^2 = x^3 + b$ on which $\mathbb{G}_1$ is
+/// defined.
+pub const G1_B: Fp = Fp::from_raw_unchecked(G1_B_MONTGOMERY);
+
+/// [`G1_B`] as the little-endian limbs of its canonical integer representation.
+pub const G1_B_CANONICAL: [u64; 6] = [
+    0x0000_0000_0000_0004,
+    0x0000_0000_0000_0000,
+    0x0000_0000_0000_0000,
+    0x0000_0000_0000_0000,
+    0x0000_0000_0000_0000,
+    0x0000_0000_0000_0000,
+];
+
+/// [`G1_B`] as the little-endian limbs of its Montgomery form.
+pub const G1_B_MONTGOMERY: [u64; 6] = [
+    0xaa27_0000_000c_fff3,
+    0x53cc_0032_fc34_000a,
+    0x478f_e97a_6b0a_807f,
+    0xb1d3_7ebe_e6ba_24d7,
+    0x8ec9_733b_bf78_ab2f,
+    0x09d6_4551_3d83_de7e,
+];
+
+/// The $x$-coordinate of [`G1Affine::generator`](crate::G1Affine::generator).
+pub const G1_GENERATOR_X: Fp = Fp::from_raw_unchecked(G1_GENERATOR_X_MONTGOMERY);
+
+/// [`G1_GENERATOR_X`] as the little-endian limbs of its canonical integer representation.
+pub const G1_GENERATOR_X_CANONICAL: [u64; 6] = [
+    0xfb3a_f00a_db22_c6bb,
+    0x6c55_e83f_f97a_1aef,
+    0xa14e_3a3f_171b_ac58,
+    0xc368_8c4f_9774_b905,
+    0x2695_638c_4fa9_ac0f,
+    0x17f1_d3a7_3197_d794,
+];
+
+/// [`G1_GENERATOR_X`] as the little-endian limbs of its Montgomery form.
+pub const G1_GENERATOR_X_MONTGOMERY: [u64; 6] = [
+    0x5cb3_8790_fd53_0c16,
+    0x7817_fc67_9976_fff5,
+    0x154f_95c7_143b_a1c1,
+    0xf0ae_6acd_f3d0_e747,
+    0xedce_6ecc_21db_f440,
+    0x1201_7741_9e0b_fb75,
+];
+
+/// The $y$-coordinate of [`G1Affine::generator`](crate::G1Affine::generator).
+pub const G1_GENERATOR_Y: Fp = Fp::from_raw_unchecked(G1_GENERATOR_Y_MONTGOMERY);
+
+/// [`G1_GENERATOR_Y`] as the little-endian limbs of its canonical integer representation.
+pub const G1_GENERATOR_Y_CANONICAL: [u64; 6] = [
+    0x0caa_2329_46c5_e7e1,
+    0xd03c_c744_a288_8ae4,
+    0x00db_18cb_2c04_b3ed,
+    0xfcf5_e095_d5d0_0af6,
+    0xa09e_30ed_741d_8ae4,
+    0x08b3_f481_e3aa_a0f1,
+];
+
+/// [`G1_GENERATOR_Y`] as the little-endian limbs of its Montgomery form.
+pub const G1_GENERATOR_Y_MONTGOMERY: [u64; 6] = [
+    0xbaac_93d5_0ce7_2271,
+    0x8c22_631a_7918_fd8e,
+    0xdd59_5f13_5707_25ce,
+    0x51ac_5829_5040_5194,
+    0x0e1c_8c3f_ad00_59c0,
+    0x0bbc_3efc_5008_a26a,
+];
+
+/// The coefficient $b' = 4(u + 1)$ of the twist $E': y^2 = x^3 + b'$ on which
+/// $\mathbb{G}_2$ is defined.
+pub const G2_B: Fp2 = Fp2 {
+    c0: Fp::from_raw_unchecked(G2_B_MONTGOMERY[0]),
+    c1: Fp::from_raw_unchecked(G2_B_MONTGOMERY[1]),
+};
+
+/// [`G2_B`] as the little-endian limbs of the canonical integer representations
+/// of $c_0$ and $c_1$.
+pub const G2_B_CANONICAL: [[u64; 6]; 2] = [
+    [
+        0x0000_0000_0000_0004,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+    ],
+    [
+        0x0000_0000_0000_0004,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+    ],
+];
+
+/// [`G2_B`] as the little-endian limbs of the Montgomery forms of $c_0$ and $c_1$.
+pub const G2_B_MONTGOMERY: [[u64; 6]; 2] = [
+    [
+        0xaa27_0000_000c_fff3,
+        0x53cc_0032_fc34_000a,
+        0x478f_e97a_6b0a_807f,
+        0xb1d3_7ebe_e6ba_24d7,
+        0x8ec9_733b_bf78_ab2f,
+        0x09d6_4551_3d83_de7e,
+    ],
+    [
+        0xaa27_0000_000c_fff3,
+        0x53cc_0032_fc34_000a,
+        0x478f_e97a_6b0a_807f,
+        0xb1d3_7ebe_e6ba_24d7,
+        0x8ec9_733b_bf78_ab2f,
+        0x09d6_4551_3d83_de7e,
+    ],
+];
+
+/// The $x$-coordinate of [`G2Affine::generator`](crate::G2Affine::generator).
+pub const G2_GENERATOR_X: Fp2 = Fp2 {
+    c0: Fp::from_raw_unchecked(G2_GENERATOR_X_MONTGOMERY[0]),
+    c1: Fp::from_raw_unchecked(G2_GENERATOR_X_MONTGOMERY[1]),
+};
+
+/// [`G2_GENERATOR_X`] as the little-endian limbs of the canonical integer representations
+/// of $c_0$ and $c_1$.
+pub const G2_GENERATOR_X_CANONICAL: [[u64; 6]; 2] = [
+    [
+        0xd480_56c8_c121_bdb8,
+        0x0bac_0326_a805_bbef,
+        0xb451_0b64_7ae3_d177,
+        0xc6e4_7ad4_fa40_3b02,
+        0x2608_0527_2dc5_1051,
+        0x024a_a2b2_f08f_0a91,
+    ],
+    [
+        0xe5ac_7d05_5d04_2b7e,
+        0x334c_f112_1394_5d57,
+        0xb5da_61bb_dc7f_5049,
+        0x596b_d0d0_9920_b61a,
+        0x7dac_d3a0_8827_4f65,
+        0x13e0_2b60_5271_9f60,
+    ],
+];
+
+/// [`G2_GENERATOR_X`] as the little-endian limbs of the Montgomery forms of $c_0$ and $c_1$.
+pub const G2_GENERATOR_X_MONTGOMERY: [[u64; 6]; 2] = [
+    [
+        0xf5f2_8fa2_0294_0a10,
+        0xb3f5_fb26_87b4_961a,
+        0xa1a8_93b5_3e2a_e580,
+        0x9894_999d_1a3c_aee9,
+        0x6f67_b763_1863_366b,
+        0x0581_9192_4350_bcd7,
+    ],
+    [
+        0xa5a9_c075_9e23_f606,
+        0xaaa0_c59d_bccd_60c3,
+        0x3bb1_7e18_e286_7806,
+        0x1b1a_b6cc_8541_b367,
+        0xc2b6_ed0e_f215_8547,
+        0x1192_2a09_7360_edf3,
+    ],
+];
+
+/// The $y$-coordinate of [`G2Affine::generator`](crate::G2Affine::generator).
+pub const G2_GENERATOR_Y: Fp2 = Fp2 {
+    c0: Fp::from_raw_unchecked(G2_GENERATOR_Y_MONTGOMERY[0]),
+    c1: Fp::from_raw_unchecked(G2_GENERATOR_Y_MONTGOMERY[1]),
+};
+
+/// [`G2_GENERATOR_Y`] as the little-endian limbs of the canonical integer representations
+/// of $c_0$ and $c_1$.
+pub const G2_GENERATOR_Y_CANONICAL: [[u64; 6]; 2] = [
+    [
+        0xe193_5486_08b8_2801,
+        0x923a_c9cc_3bac_a289,
+        0x6d42_9a69_5160_d12c,
+        0xadfd_9baa_8cbd_d3a7,
+        0x8cc9_cdc6_da2e_351a,
+        0x0ce5_d527_727d_6e11,
+    ],
+    [
+        0xaaa9_075f_f05f_79be,
+        0x3f37_0d27_5cec_1da1,
+        0x2674_92ab_572e_99ab,
+        0xcb3e_287e_85a7_63af,
+        0x32ac_d2b0_2bc2_8b99,
+        0x0606_c4a0_2ea7_34cc,
+    ],
+];
+
+/// [`G2_GENERATOR_Y`] as the little-endian limbs of the Montgomery forms of $c_0$ and $c_1$.
+pub const G2_GENERATOR_Y_MONTGOMERY: [[u64; 6]; 2] = [
+    [
+        0x4c73_0af8_6049_4c4a,
+        0x597c_fa1f_5e36_9c5a,
+        0xe7e6_856c_aa0a_635a,
+        0xbbef_b5e9_6e0d_495f,
+        0x07d3_a975_f0ef_25a2,
+        0x0083_fd8e_7e80_dae5,
+    ],
+    [
+        0xadc0_fc92_df64_b05d,
+        0x18aa_270a_2b14_61dc,
+        0x86ad_ac6a_3be4_eba0,
+        0x7949_5c4e_c93d_a33a,
+        0xe717_5850_a43c_caed,
+        0x0b2b_c2a1_63de_1bf2,
+    ],
+];
+
+/// The coefficient $A'$ of the curve $E_1': y^2 = x^3 + A' x + B'$, which is
+/// 11-isogenous to the curve of $\mathbb{G}_1$, and onto which hashing to
+/// $\mathbb{G}_1$ applies the simplified SWU map.
+pub const G1_ISO_A: Fp = Fp::from_raw_unchecked(G1_ISO_A_MONTGOMERY);
+
+/// [`G1_ISO_A`] as the little-endian limbs of its canonical integer representation.
+pub const G1_ISO_A_CANONICAL: [u64; 6] = [
+    0x5cf4_2808_2d58_4c1d,
+    0x9893_6f8d_a0e0_f97f,
+    0xd8e8_981a_efd8_81ac,
+    0xb0ea_9853_83ee_66a8,
+    0x3d69_3a02_c96d_4982,
+    0x0014_4698_a3b8_e943,
+];
+
+/// [`G1_ISO_A`] as the little-endian limbs of its Montgomery form.
+pub const G1_ISO_A_MONTGOMERY: [u64; 6] = [
+    0x2f65_aa0e_9af5_aa51,
+    0x8646_4c2d_1e84_16c3,
+    0xb85c_e591_b7bd_31e2,
+    0x27e1_1c91_b5f2_4e7c,
+    0x2837_6eda_6bfc_1835,
+    0x1554_55c3_e507_1d85,
+];
+
+/// The coefficient $B'$ of the curve $E_1'$ of [`G1_ISO_A`].
+pub const G1_ISO_B: Fp = Fp::from_raw_unchecked(G1_ISO_B_MONTGOMERY);
+
+/// [`G1_ISO_B`] as the little-endian limbs of its canonical integer representation.
+pub const G1_ISO_B_CANONICAL: [u64; 6] = [
+    0xd1cc_48e9_8e17_2be0,
+    0x5a23_215a_316c_eaa5,
+    0xa0b9_c14f_cef3_5ef5,
+    0x2016_c1f0_f24f_4070,
+    0x018b_12e8_753e_ee3b,
+    0x12e2_908d_1168_8030,
+];
+
+/// [`G1_ISO_B`] as the little-endian limbs of its Montgomery form.
+pub const G1_ISO_B_MONTGOMERY: [u64; 6] = [
+    0xfb99_6971_fe22_a1e0,
+    0x9aa9_3eb3_5b74_2d6f,
+    0x8c47_6013_de99_c5c4,
+    0x873e_27c3_a221_e571,
+    0xca72_b5e4_5a52_d888,
+    0x0682_4061_418a_386b,
+];
+
+/// The coefficient $A' = 240 u$ of the curve $E_2': y^2 = x^3 + A' x + B'$, which is
+/// 3-isogenous to the twist of $\mathbb{G}_2$, and onto which hashing to
+/// $\mathbb{G}_2$ applies the simplified SWU map.
+pub const G2_ISO_A: Fp2 = Fp2 {
+    c0: Fp::from_raw_unchecked(G2_ISO_A_MONTGOMERY[0]),
+    c1: Fp::from_raw_unchecked(G2_ISO_A_MONTGOMERY[1]),
+};
+
+/// [`G2_ISO_A`] as the little-endian limbs of the canonical integer representations
+/// of $c_0$ and $c_1$.
+pub const G2_ISO_A_CANONICAL: [[u64; 6]; 2] = [
+    [
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+    ],
+    [
+        0x0000_0000_0000_00f0,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+    ],
+];
+
+/// [`G2_ISO_A`] as the little-endian limbs of the Montgomery forms of $c_0$ and $c_1$.
+pub const G2_ISO_A_MONTGOMERY: [[u64; 6]; 2] = [
+    [
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+    ],
+    [
+        0xe53a_0000_0313_5242,
+        0x0108_0c0f_def8_0285,
+        0xe788_9edb_e340_f6bd,
+        0x0b51_3751_2631_0601,
+        0x02d6_9857_17c7_44ab,
+        0x1220_b4e9_79ea_5467,
+    ],
+];
+
+/// The coefficient $B' = 1012(u + 1)$ of the curve $E_2'$ of [`G2_ISO_A`].
+pub const G2_ISO_B: Fp2 = Fp2 {
+    c0: Fp::from_raw_unchecked(G2_ISO_B_MONTGOMERY[0]),
+    c1: Fp::from_raw_unchecked(G2_ISO_B_MONTGOMERY[1]),
+};
+
+/// [`G2_ISO_B`] as the little-endian limbs of the canonical integer representations
+/// of $c_0$ and $c_1$.
+pub const G2_ISO_B_CANONICAL: [[u64; 6]; 2] = [
+    [
+        0x0000_0000_0000_03f4,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+    ],
+    [
+        0x0000_0000_0000_03f4,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+        0x0000_0000_0000_0000,
+    ],
+];
+
+/// [`G2_ISO_B`] as the little-endian limbs of the Montgomery forms of $c_0$ and $c_1$.
+pub const G2_ISO_B_MONTGOMERY: [[u64; 6]; 2] = [
+    [
+        0x22ea_0000_0cf8_9db2,
+        0x6ec8_32df_7138_0aa4,
+        0x6e1b_9440_3db5_a66e,
+        0x75bf_3c53_a794_73ba,
+        0x3dd3_a569_412c_0a34,
+        0x125c_db5e_74dc_4fd1,
+    ],
+    [
+        0x22ea_0000_0cf8_9db2,
+        0x6ec8_32df_7138_0aa4,
+        0x6e1b_9440_3db5_a66e,
+        0x75bf_3c53_a794_73ba,
+        0x3dd3_a569_412c_0a34,
+        0x125c_db5e_74dc_4fd1,
+    ],
+];
+
+const fn limbs_to_be_bytes<const L: usize, const B: usize>(limbs: [u64; L]) -> [u8; B] {
+    let mut bytes = [0u8; B];
+    let mut i = 0;
+    while i < B {
+        bytes[B - 1 - i] = (limbs[i / 8] >> (8 * (i % 8))) as u8;
+        i += 1;
+    }
+    bytes
+}
+
+const fn limbs_to_le_bytes<const L: usize, const B: usize>(limbs: [u64; L]) -> [u8; B] {
+    let mut bytes = [0u8; B];
+    let mut i = 0;
+    while i < B {
+        bytes[i] = (limbs[i / 8] >> (8 * (i % 8))) as u8;
+        i += 1;
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Scalar;
+    #[cfg(feature = "groups")]
+    use crate::{G1Affine, G1Projective, G2Affine, G2Projective};
+    #[cfg(feature = "groups")]
+    use group::Group;
+    #[cfg(feature = "groups")]
+    use subtle::Choice;
+
+    #[test]
+    fn test_representations() {
+        for (fp, canonical, montgomery) in [
+            (G1_B, G1_B_CANONICAL, G1_B_MONTGOMERY),
+            (
+                G1_GENERATOR_X,
+                G1_GENERATOR_X_CANONICAL,
+                G1_GENERATOR_X_MONTGOMERY,
+            ),
+            (
+                G1_GENERATOR_Y,
+                G1_GENERATOR_Y_CANONICAL,
+                G1_GENERATOR_Y_MONTGOMERY,
+            ),
+            (G1_ISO_A, G1_ISO_A_CANONICAL, G1_ISO_A_MONTGOMERY),
+            (G1_ISO_B, G1_ISO_B_CANONICAL, G1_ISO_B_MONTGOMERY),
+        ] {
+            assert_eq!(Fp::from_raw(canonical).unwrap(), fp);
+            assert_eq!(Fp::from_raw_unchecked(montgomery), fp);
+        }
+        for (fp2, canonical, montgomery) in [
+            (G2_B, G2_B_CANONICAL, G2_B_MONTGOMERY),
+            (
+                G2_GENERATOR_X,
+                G2_GENERATOR_X_CANONICAL,
+                G2_GENERATOR_X_MONTGOMERY,
+            ),
+            (
+                G2_GENERATOR_Y,
+                G2_GENERATOR_Y_CANONICAL,
+                G2_GENERATOR_Y_MONTGOMERY,
+            ),
+            (G2_ISO_A, G2_ISO_A_CANONICAL, G2_ISO_A_MONTGOMERY),
+            (G2_ISO_B, G2_ISO_B_CANONICAL, G2_ISO_B_MONTGOMERY),
+        ] {
+            assert_eq!(Fp2::from_raw(canonical).unwrap(), fp2);
+            assert_eq!(Fp::from_raw_unchecked(montgomery[0]), fp2.c0);
+            assert_eq!(Fp::from_raw_unchecked(montgomery[1]), fp2.c1);
+        }
+    }
+
+    #[cfg(feature = "groups")]
+    #[test]
+    fn test_curve_coefficients() {
+        let four = Fp::one() + Fp::one() + Fp::one() + Fp::one();
+        assert_eq!(G1_B, four);
+        assert_eq!(G2_B, Fp2 { c0: four, c1: four });
+
+        let g = G1Affine::generator();
+        assert_eq!(g.y.square(), g.x.square() * g.x + G1_B);
+        let g = G2Affine::generator();
+        assert_eq!(g.y.square(), g.x.square() * g.x + G2_B);
+
+        let small = |n: u64| Fp::from_raw([n, 0, 0, 0, 0, 0]).unwrap();
+        assert_eq!(
+            G2_ISO_A,
+            Fp2 {
+                c0: Fp::zero(),
+                c1: small(240)
+            }
+        );
+        assert_eq!(
+            G2_ISO_B,
+            Fp2 {
+                c0: small(1012),
+                c1: small(1012)
+            }
+        );
+    }
+
+    #[test]
+    fn test_moduli() {
+        let mut p_minus_1 = FP_MODULUS;
+        p_minus_1[0] -= 1;
+        assert_eq!(Fp::from_raw(p_minus_1).unwrap(), -Fp::one());
+        assert!(bool::from(Fp::from_raw(FP_MODULUS).is_none()));
+        assert!(bool::from(Fp::from_bytes(&FP_MODULUS_BYTES).is_none()));
+        assert_eq!(FP_MODULUS_BYTES[0], 0x1a);
+        assert_eq!(FP_MODULUS_BYTES[47], 0xab);
+
+        let mut q_minus_1 = SCALAR_MODULUS;
+        q_minus_1[0] -= 1;
+        assert_eq!(Scalar::from_raw(q_minus_1), -Scalar::one());
+        assert!(bool::from(
+            Scalar::from_raw_checked(SCALAR_MODULUS).is_none()
+        ));
+        assert!(bool::from(
+            Scalar::from_bytes(&SCALAR_MODULUS_BYTES).is_none()
+        ));
+        assert_eq!(SCALAR_MODULUS_BYTES[0], 0x01);
+        assert_eq!(SCALAR_MODULUS_BYTES[31], 0x73);
+
+        // q = x^4 - x^2 + 1, whatever the sign of x.
+        let x = Scalar::from(BLS_X);
+        assert_eq!(
+            x.square().square() - x.square() + Scalar::one(),
+            Scalar::zero()
+        );
+    }
+
+    #[cfg(feature = "groups")]
+    /// Multiplies `p` by the integer of the little-endian `limbs`.
+    fn mul_by_limbs<G: Group>(p: G, limbs: &[u64]) -> G {
+        limbs
+            .iter()
+            .rev()
+            .flat_map(|limb| (0..64).rev().map(move |i| (limb >> i) & 1 == 1))
+            .fold(G::identity(), |acc, bit| {
+                let acc = acc.double();
+                if bit {
+                    acc + p
+                } else {
+                    acc
+                }
+            })
+    }
+
+    #[cfg(feature = "groups")]
+    #[test]
+    fn test_cofactors() {
+        let x = u128::from(BLS_X) + 1;
+        assert_eq!(
+            u128::from(G1_COFACTOR[0]) | (u128::from(G1_COFACTOR[1]) << 64),
+            x * x / 3
+        );
+
+        // Points of the curves that are not in the subgroups land in them once
+        // multiplied by the cofactors.
+        let mut found = 0;
+        for n in 1..20 {
+            let x = Fp::from_raw([n, 0, 0, 0, 0, 0]).unwrap();
+            if let Some(y) = Option::<Fp>::from((x.square() * x + G1_B).sqrt()) {
+                let p = G1Affine {
+                    x,
+                    y,
+                    infinity: Choice::from(0u8),
+                };
+                assert!(!bool::from(p.is_torsion_free()));
+                let cleared = G1Affine::from(mul_by_limbs(G1Projective::from(p), &G1_COFACTOR));
+                assert!(bool::from(cleared.is_torsion_free()));
+                assert!(!bool::from(cleared.is_identity()));
+                assert_eq!(
+                    mul_by_limbs(G1Projective::from(p), &[G1_H_EFF]),
+                    G1Projective::from(p).clear_cofactor()
+                );
+                found += 1;
+            }
+
+            let x = Fp2 {
+                c0: Fp::one(),
+                c1: x,
+            };
+            if let Some(y) = Option::<Fp2>::from((x.square() * x + G2_B).sqrt()) {
+                let p = G2Affine {
+                    x,
+                    y,
+                    infinity: Choice::from(0u8),
+                };
+                assert!(!bool::from(p.is_torsion_free()));
+                let cleared = G2Affine::from(mul_by_limbs(G2Projective::from(p), &G2_COFACTOR));
+                assert!(bool::from(cleared.is_torsion_free()));
+                assert!(!bool::from(cleared.is_identity()));
+                assert_eq!(
+                    mul_by_limbs(G2Projective::from(p), &G2_H_EFF),
+                    G2Projective::from(p).clear_cofactor()
+                );
+                found += 1;
+            }
+        }
+        assert!(found >= 4);
+    }
+}
@@ -0,0 +1,81 @@
+//! Hashed ElGamal key encapsulation.
+//!
+//! The sender picks a random $r$ and sends the encapsulation $U = r \cdot g_1$. Both
+//! parties derive the shared key by hashing $U$ and the Diffie-Hellman value
+//! $r \cdot P = s \cdot U$ with the [`ExpandMessage`] variant `X` chosen by the caller.
+//! The key can then be used with a symmetric authenticated encryption scheme.
+
+use ff::Field;
+use rand_core::RngCore;
+
+use crate::hash_to_curve::{ExpandMessage, ExpandMessageState};
+use crate::{G1Affine, Scalar};
+
+/// The domain separation tag used to derive shared keys. It assumes that `X` is
+/// [`ExpandMsgXmd`](crate::hash_to_curve::ExpandMsgXmd) instantiated with SHA-256.
+pub const DST: &[u8] = b"ELGAMAL-KEM_BLS12381G1_XMD:SHA-256_";
+
+/// The length in bytes of shared keys.
+pub const KEY_LEN: usize = 32;
+
+/// Derives the shared key from the encapsulation and the Diffie-Hellman value.
+fn derive_key<X: ExpandMessage>(encapsulation: &G1Affine, shared: &G1Affine) -> [u8; KEY_LEN] {
+    let mut input = [0u8; 96];
+    input[..48].copy_from_slice(&encapsulation.to_compressed());
+    input[48..].copy_from_slice(&shared.to_compressed());
+
+    let mut key = [0u8; KEY_LEN];
+    X::init_expand(&input, DST, KEY_LEN).read_into(&mut key);
+    key
+}
+
+/// Generates a fresh shared key for the holder of the secret key of `pk`, returning
+/// the encapsulation to send them and the key.
+pub fn encapsulate<X: ExpandMessage, R: RngCore>(
+    pk: &G1Affine,
+    mut rng: R,
+) -> (G1Affine, [u8; KEY_LEN]) {
+    let r = Scalar::random(&mut rng);
+    let encapsulation = G1Affine::from(G1Affine::generator() * r);
+    let shared = G1Affine::from(pk * r);
+    (encapsulation, derive_key::<X>(&encapsulation, &shared))
+}
+
+/// Recovers the shared key from `encapsulation` with the secret key `sk`.
+///
+/// An encapsulation that was not produced for the public key of `sk` yields an
+/// unrelated key, so the key must be used with authenticated encryption.
+pub fn decapsulate<X: ExpandMessage>(sk: &Scalar, encapsulation: &G1Affine) -> [u8; KEY_LEN] {
+    let shared = G1Affine::from(encapsulation * sk);
+    derive_key::<X>(encapsulation, &shared)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elgamal::keygen;
+    use crate::hash_to_curve::ExpandMsgXmd;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    type Xmd = ExpandMsgXmd<sha2::Sha256>;
+
+    #[test]
+    fn test_kem() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let (sk, pk) = keygen(&mut rng);
+
+        let (encapsulation, key) = encapsulate::<Xmd, _>(&pk, &mut rng);
+        assert_eq!(decapsulate::<Xmd>(&sk, &encapsulation), key);
+
+        let (other, key2) = encapsulate::<Xmd, _>(&pk, &mut rng);
+        assert_ne!(key, key2);
+        assert_ne!(decapsulate::<Xmd>(&sk, &other), key);
+
+        let (wrong, _) = keygen(&mut rng);
+        assert_ne!(decapsulate::<Xmd>(&wrong, &encapsulation), key);
+    }
+}
@@ -0,0 +1,228 @@
+//! This module implements ElGamal encryption over $\mathbb{G}_1$.
+//!
+//! Messages are encrypted "in the exponent": a message $m \in \mathbb{F}_q$ is encoded
+//! as $m \cdot g_1$, so that ciphertexts are additively homomorphic. Decryption
+//! recovers $m \cdot g_1$, from which [`decrypt`] finds small messages by solving a
+//! discrete logarithm. The [`kem`] submodule provides hashed ElGamal key
+//! encapsulation for encrypting arbitrary data.
+
+use core::ops::Add;
+
+use alloc::collections::BTreeMap;
+use ff::Field;
+use group::Curve;
+use rand_core::RngCore;
+
+use crate::{G1Affine, G1Projective, Scalar};
+
+pub mod kem;
+
+/// Generates a random secret key and the corresponding public key.
+pub fn keygen(mut rng: impl RngCore) -> (Scalar, G1Affine) {
+    let sk = Scalar::random(&mut rng);
+    (sk, G1Affine::from(G1Affine::generator() * sk))
+}
+
+/// An ElGamal ciphertext $(r \cdot g_1, m \cdot g_1 + r \cdot P)$ of a message $m$
+/// under the public key $P$.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Ciphertext {
+    c1: G1Affine,
+    c2: G1Affine,
+}
+
+impl Ciphertext {
+    /// Serializes this ciphertext as the compressed encodings of its two points.
+    pub fn to_bytes(&self) -> [u8; 96] {
+        let mut bytes = [0u8; 96];
+        bytes[..48].copy_from_slice(&self.c1.to_compressed());
+        bytes[48..].copy_from_slice(&self.c2.to_compressed());
+        bytes
+    }
+
+    /// Attempts to deserialize a ciphertext produced by [`Ciphertext::to_bytes`].
+    pub fn from_bytes(bytes: &[u8; 96]) -> Option<Self> {
+        let mut c1 = [0u8; 48];
+        let mut c2 = [0u8; 48];
+        c1.copy_from_slice(&bytes[..48]);
+        c2.copy_from_slice(&bytes[48..]);
+
+        let c1 = Option::from(G1Affine::from_compressed(&c1))?;
+        let c2 = Option::from(G1Affine::from_compressed(&c2))?;
+        Some(Ciphertext { c1, c2 })
+    }
+
+    /// Constructs a ciphertext from its components in projective form.
+    fn from_projective(c1: G1Projective, c2: G1Projective) -> Self {
+        let mut affine = [G1Affine::identity(); 2];
+        G1Projective::batch_normalize(&[c1, c2], &mut affine);
+        Ciphertext {
+            c1: affine[0],
+            c2: affine[1],
+        }
+    }
+}
+
+impl Add<Ciphertext> for Ciphertext {
+    type Output = Ciphertext;
+
+    /// Returns a ciphertext of the sum of the messages of both ciphertexts, which must
+    /// be under the same public key.
+    fn add(self, rhs: Ciphertext) -> Ciphertext {
+        Ciphertext::from_projective(
+            self.c1 + G1Projective::from(rhs.c1),
+            self.c2 + G1Projective::from(rhs.c2),
+        )
+    }
+}
+
+impl<'a, 'b> Add<&'b Ciphertext> for &'a Ciphertext {
+    type Output = Ciphertext;
+
+    fn add(self, rhs: &'b Ciphertext) -> Ciphertext {
+        *self + *rhs
+    }
+}
+
+/// Encrypts the message `m` under the public key `pk`.
+pub fn encrypt(pk: &G1Affine, m: &Scalar, mut rng: impl RngCore) -> Ciphertext {
+    let r = Scalar::random(&mut rng);
+    Ciphertext::from_projective(
+        G1Affine::generator() * r,
+        G1Affine::generator() * m + pk * r,
+    )
+}
+
+/// Returns a fresh encryption of the same message as `ciphertext`, which must be
+/// under the public key `pk`, that cannot be linked to it.
+pub fn rerandomize(pk: &G1Affine, ciphertext: &Ciphertext, mut rng: impl RngCore) -> Ciphertext {
+    let r = Scalar::random(&mut rng);
+    Ciphertext::from_projective(
+        ciphertext.c1 + G1Affine::generator() * r,
+        ciphertext.c2 + pk * r,
+    )
+}
+
+/// Decrypts `ciphertext` with the secret key `sk`, returning the encoding
+/// $m \cdot g_1$ of its message.
+pub fn decrypt_to_point(sk: &Scalar, ciphertext: &Ciphertext) -> G1Affine {
+    (G1Projective::from(ciphertext.c2) - ciphertext.c1 * sk).to_affine()
+}
+
+/// Decrypts `ciphertext` with the secret key `sk`, returning its message if it is
+/// at most `max`.
+///
+/// The message is found with the baby-step giant-step algorithm, in time and memory
+/// proportional to the square root of `max`. This function is variable time in the
+/// message, and returns `None` if it is larger than `max`.
+pub fn decrypt(sk: &Scalar, ciphertext: &Ciphertext, max: u64) -> Option<u64> {
+    let target = G1Projective::from(decrypt_to_point(sk, ciphertext));
+
+    // The smallest step such that step * step > max.
+    let mut step = 1u64;
+    while step.checked_mul(step).map_or(false, |s| s <= max) {
+        step += 1;
+    }
+
+    let mut baby_steps = BTreeMap::new();
+    let mut point = G1Projective::identity();
+    for j in 0..step {
+        baby_steps
+            .entry(point.to_affine().to_compressed())
+            .or_insert(j);
+        point += G1Affine::generator();
+    }
+
+    // Here `point` is step * g_1.
+    let giant_step = -point;
+    let mut point = target;
+    for i in 0..step {
+        if let Some(j) = baby_steps.get(&point.to_affine().to_compressed()) {
+            let m = i * step + j;
+            return if m <= max { Some(m) } else { None };
+        }
+        point += giant_step;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ])
+    }
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let mut rng = rng();
+        let (sk, pk) = keygen(&mut rng);
+
+        for m in [0u64, 1, 99, 100] {
+            let ciphertext = encrypt(&pk, &Scalar::from(m), &mut rng);
+            assert_eq!(
+                decrypt_to_point(&sk, &ciphertext),
+                G1Affine::from(G1Affine::generator() * Scalar::from(m))
+            );
+            assert_eq!(decrypt(&sk, &ciphertext, 100), Some(m));
+        }
+
+        let ciphertext = encrypt(&pk, &Scalar::from(101), &mut rng);
+        assert_eq!(decrypt(&sk, &ciphertext, 100), None);
+        assert_eq!(decrypt(&sk, &ciphertext, 101), Some(101));
+        let ciphertext = encrypt(&pk, &-Scalar::one(), &mut rng);
+        assert_eq!(decrypt(&sk, &ciphertext, 1000), None);
+
+        let (other, _) = keygen(&mut rng);
+        let ciphertext = encrypt(&pk, &Scalar::from(7), &mut rng);
+        assert_ne!(decrypt(&other, &ciphertext, 100), Some(7));
+    }
+
+    #[test]
+    fn test_homomorphic_addition() {
+        let mut rng = rng();
+        let (sk, pk) = keygen(&mut rng);
+
+        // A tally of yes votes.
+        let votes = [1u64, 0, 1, 1, 0, 1];
+        let tally = votes
+            .iter()
+            .map(|&v| encrypt(&pk, &Scalar::from(v), &mut rng))
+            .reduce(|acc, c| acc + c)
+            .unwrap();
+        assert_eq!(decrypt(&sk, &tally, votes.len() as u64), Some(4));
+    }
+
+    #[test]
+    fn test_rerandomize() {
+        let mut rng = rng();
+        let (sk, pk) = keygen(&mut rng);
+
+        let ciphertext = encrypt(&pk, &Scalar::from(42), &mut rng);
+        let rerandomized = rerandomize(&pk, &ciphertext, &mut rng);
+        assert_ne!(ciphertext, rerandomized);
+        assert_ne!(ciphertext.c1, rerandomized.c1);
+        assert_eq!(decrypt(&sk, &rerandomized, 100), Some(42));
+    }
+
+    #[test]
+    fn test_serialization() {
+        let mut rng = rng();
+        let (_, pk) = keygen(&mut rng);
+
+        let ciphertext = encrypt(&pk, &Scalar::from(5), &mut rng);
+        let bytes = ciphertext.to_bytes();
+        assert_eq!(Ciphertext::from_bytes(&bytes), Some(ciphertext));
+
+        let mut invalid = bytes;
+        invalid[48] &= 0x7f;
+        assert_eq!(Ciphertext::from_bytes(&invalid), None);
+    }
+}
@@ -0,0 +1,250 @@
+//! This module implements an encoding of $\mathbb{G}_1$ elements as byte strings that
+//! are indistinguishable from uniformly random ones, for protocols whose transcripts
+//! must not reveal that they carry curve points.
+//!
+//! The encoding is [Elligator Squared] instantiated with the map of
+//! [Fouque and Tibouchi] to the curve $E: y^2 = x^3 + 4$. A point $P$ is encoded as
+//! a pair $(u_1, u_2)$ of field elements such that
+//! $P = [1 - z](f(u_1) + f(u_2))$, chosen uniformly among all such pairs for $P$,
+//! where $f$ is the map and $[1 - z]$ clears the cofactor. Each field element is
+//! written as a 64-byte big-endian integer chosen uniformly among those congruent to it
+//! modulo $p$. Conversely, every 128-byte string decodes to an element of
+//! $\mathbb{G}_1$, and uniformly random strings decode to nearly uniformly random
+//! elements.
+//!
+//! Encoding repeatedly samples candidates until one is accepted, so neither direction
+//! is constant time. The randomness of the encoding is essential: the same point
+//! should be encoded afresh every time it is sent.
+//!
+//! [Elligator Squared]: https://ia.cr/2014/043
+//! [Fouque and Tibouchi]: https://www.di.ens.fr/~fouque/pub/latincrypt12.pdf
+
+use rand_core::RngCore;
+use subtle::Choice;
+
+use crate::fp::Fp;
+use crate::generic_array::{typenum::U64, GenericArray};
+use crate::hash_to_curve::HashToField;
+use crate::{G1Affine, G1Projective, Scalar};
+
+/// The length of an encoded point in bytes.
+pub const ENCODED_LENGTH: usize = 128;
+
+/// Encodes `point` as a byte string that is indistinguishable from a uniformly random
+/// one, using `rng` for the randomness of the encoding.
+pub fn encode(point: &G1Affine, mut rng: impl RngCore) -> [u8; ENCODED_LENGTH] {
+    let map = Map::new();
+
+    // A preimage of the point under cofactor clearing, chosen uniformly by adding a
+    // random point of the cofactor subgroup. For any point R on the curve,
+    // [1 - z](R - [1 - z]^(-1)[1 - z]R) is the identity.
+    let r = map.map(&Fp::random(&mut rng)) + map.map(&Fp::random(&mut rng));
+    let h_eff_inv = Scalar::from(0xd201_0000_0001_0001).invert().unwrap();
+    let q = r + (G1Projective::from(point) - r.clear_cofactor()) * h_eff_inv;
+
+    loop {
+        let u1 = Fp::random(&mut rng);
+        let target = G1Affine::from(q - map.map(&u1));
+        if bool::from(target.is_identity()) {
+            continue;
+        }
+
+        // Every point has at most four preimages under the map, so accepting the
+        // j-th one for a uniformly random j < 4 selects each pair (u1, u2) with the
+        // same probability.
+        let (preimages, n) = map.preimages(&target);
+        let j = (rng.next_u32() % 4) as usize;
+        if j < n {
+            let mut bytes = [0u8; ENCODED_LENGTH];
+            bytes[..64].copy_from_slice(&widen(&u1, &mut rng));
+            bytes[64..].copy_from_slice(&widen(&preimages[j], &mut rng));
+            return bytes;
+        }
+    }
+}
+
+/// Decodes a byte string produced by [`encode`]. Every byte string is the encoding of
+/// some element of $\mathbb{G}_1$.
+pub fn decode(bytes: &[u8; ENCODED_LENGTH]) -> G1Affine {
+    let map = Map::new();
+    let u1 = Fp::from_okm(GenericArray::<u8, U64>::from_slice(&bytes[..64]));
+    let u2 = Fp::from_okm(GenericArray::<u8, U64>::from_slice(&bytes[64..]));
+    G1Affine::from((map.map(&u1) + map.map(&u2)).clear_cofactor())
+}
+
+/// Returns a 64-byte big-endian integer that is congruent to `value` modulo $p$,
+/// chosen uniformly among all of them but for a negligible bias.
+fn widen(value: &Fp, mut rng: impl RngCore) -> [u8; 64] {
+    let value = value.to_bytes();
+    loop {
+        // Replace the residue of a random integer with `value`, retrying in the rare
+        // case that the result does not fit in 64 bytes.
+        let mut bytes = [0u8; 64];
+        rng.fill_bytes(&mut bytes);
+        let residue = Fp::from_okm(GenericArray::<u8, U64>::from_slice(&bytes)).to_bytes();
+
+        let mut carry = 0i32;
+        for i in (0..64).rev() {
+            let mut digit = i32::from(bytes[i]) + carry;
+            if i >= 16 {
+                digit += i32::from(value[i - 16]) - i32::from(residue[i - 16]);
+            }
+            bytes[i] = digit as u8;
+            carry = digit >> 8;
+        }
+        if carry == 0 {
+            return bytes;
+        }
+    }
+}
+
+/// The constants of the map of Fouque and Tibouchi to $y^2 = x^3 + b$.
+struct Map {
+    b: Fp,
+    /// $\sqrt{-3}$.
+    s: Fp,
+    /// $(-1 + \sqrt{-3}) / 2$, a primitive cube root of unity.
+    c: Fp,
+}
+
+impl Map {
+    fn new() -> Self {
+        let two = Fp::one() + Fp::one();
+        let three = two + Fp::one();
+        let b = two + two;
+        let s = (-three).sqrt().unwrap();
+        let c = (s - Fp::one()) * two.invert().unwrap();
+        Map { b, s, c }
+    }
+
+    /// Maps `t` to a point on the curve, which is not necessarily in $\mathbb{G}_1$.
+    ///
+    /// With $w = \sqrt{-3} t / (1 + b + t^2)$, the image has the first of
+    /// $x_1 = c - t w$, $x_2 = -1 - x_1$ and $x_3 = 1 + 1 / w^2$ that is the
+    /// $x$-coordinate of a point, and the $y$-coordinate with the same sign as $t$. The
+    /// exceptional inputs, where $w$ is zero or undefined, are mapped to the identity.
+    fn map(&self, t: &Fp) -> G1Projective {
+        let den = Fp::one() + self.b + t.square();
+        if bool::from(t.is_zero() | den.is_zero()) {
+            return G1Projective::identity();
+        }
+
+        let w = self.s * t * den.invert().unwrap();
+        let x1 = self.c - t * w;
+        let x2 = -Fp::one() - x1;
+        let x3 = Fp::one() + w.square().invert().unwrap();
+        for x in [x1, x2, x3] {
+            if let Some(y) = Option::<Fp>::from((x.square() * x + self.b).sqrt()) {
+                let y = if bool::from(y.sgn0() ^ t.sgn0()) {
+                    -y
+                } else {
+                    y
+                };
+                return G1Projective::from(G1Affine {
+                    x,
+                    y,
+                    infinity: Choice::from(0u8),
+                });
+            }
+        }
+        unreachable!("one of the candidates is the x-coordinate of a point")
+    }
+
+    /// Returns the preimages of a point under [`Map::map`], and how many there are.
+    fn preimages(&self, point: &G1Affine) -> ([Fp; 4], usize) {
+        let one = Fp::one();
+        let b1 = one + self.b;
+
+        // Candidates for t^2: the equation x = x_i(t) is linear in t^2 for x_1 and x_2,
+        // and quadratic for x_3.
+        let mut squares = [None; 4];
+        for (square, x) in squares.iter_mut().zip([point.x, -one - point.x]) {
+            *square = Option::<Fp>::from((x - self.c + self.s).invert())
+                .map(|inv| b1 * (self.c - x) * inv);
+        }
+        let two = one + one;
+        let half_b = b1 + (point.x - one) * (two + one) * two.invert().unwrap();
+        if let Some(root) = Option::<Fp>::from((half_b.square() - b1.square()).sqrt()) {
+            squares[2] = Some(-half_b + root);
+            squares[3] = Some(-half_b - root);
+        }
+
+        let mut preimages = [Fp::zero(); 4];
+        let mut n = 0;
+        for square in squares.iter().flatten() {
+            let t = match Option::<Fp>::from(square.sqrt()) {
+                Some(t) if bool::from(t.sgn0() ^ point.y.sgn0()) => -t,
+                Some(t) => t,
+                None => continue,
+            };
+            if G1Affine::from(self.map(&t)) == *point && !preimages[..n].contains(&t) {
+                preimages[n] = t;
+                n += 1;
+            }
+        }
+        (preimages, n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use group::Group;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ])
+    }
+
+    #[test]
+    fn test_map_preimages() {
+        let mut rng = rng();
+        let map = Map::new();
+        for _ in 0..20 {
+            let t = Fp::random(&mut rng);
+            let point = G1Affine::from(map.map(&t));
+            assert!(bool::from(point.is_on_curve()));
+
+            let (preimages, n) = map.preimages(&point);
+            assert!(preimages[..n].contains(&t));
+            for t in &preimages[..n] {
+                assert_eq!(G1Affine::from(map.map(t)), point);
+            }
+        }
+        assert!(bool::from(map.map(&Fp::zero()).is_identity()));
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let mut rng = rng();
+        let points = [
+            G1Affine::generator(),
+            G1Affine::identity(),
+            G1Affine::from(G1Projective::random(&mut rng)),
+        ];
+        for point in points.iter() {
+            let first = encode(point, &mut rng);
+            let second = encode(point, &mut rng);
+            assert_ne!(first[..], second[..]);
+            assert_eq!(decode(&first), *point);
+            assert_eq!(decode(&second), *point);
+        }
+    }
+
+    #[test]
+    fn test_decode_random() {
+        let mut rng = rng();
+        for _ in 0..10 {
+            let mut bytes = [0u8; ENCODED_LENGTH];
+            rng.fill_bytes(&mut bytes);
+            assert!(bool::from(decode(&bytes).is_torsion_free()));
+        }
+        assert!(bool::from(
+            decode(&[0xff; ENCODED_LENGTH]).is_torsion_free()
+        ));
+    }
+}
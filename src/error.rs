@@ -0,0 +1,79 @@
+use core::fmt;
+
+#[cfg(feature = "groups")]
+use crate::fp::Fp;
+
+/// The reasons for which a scalar, a field element or a group element may fail to
+/// deserialize, to be parsed or to be constructed, reported by the `TryFrom<&[u8]>`
+/// and `FromStr` implementations and the variable-time `try_from_*` functions, the
+/// counterparts of the constructors that return a `CtOption`, and by the
+/// conversions from the types of other crates behind the `arkworks`, `blst` and
+/// `rkyv` features.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The input does not have the length of the encoding.
+    InvalidLength,
+    /// An integer or a coordinate is not the canonical representation of a scalar
+    /// or of a field element.
+    NonCanonicalField,
+    /// The coordinates are not those of a point on the curve, or a compressed
+    /// element of `Gt` has no decompression.
+    NotOnCurve,
+    /// The point is on the curve but not in the prime-order subgroup, or an element
+    /// of $\mathbb{F}_{p^{12}}$ is not in $\mathbb{G}_T$.
+    WrongSubgroup,
+    /// The flag bits are inconsistent with the encoding or with each other.
+    BadFlags,
+    /// The point is the identity, where the identity is not allowed, as for BLS
+    /// public keys.
+    Identity,
+    /// A string to parse is empty, or has a character that is not a digit of the
+    /// radix.
+    InvalidDigit,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::InvalidLength => "invalid encoding length",
+            Error::NonCanonicalField => "non-canonical field element",
+            Error::NotOnCurve => "point is not on the curve",
+            Error::WrongSubgroup => "point is not in the prime-order subgroup",
+            Error::BadFlags => "invalid flag bits",
+            Error::Identity => "point is the identity",
+            Error::InvalidDigit => "invalid digit",
+        })
+    }
+}
+
+/// Checks the flags and the field elements of an encoded group element, which is a
+/// sequence of 48-byte field elements with the flags in the three most significant
+/// bits.
+#[cfg(feature = "groups")]
+pub(crate) fn check_encoding(bytes: &[u8], compressed: bool) -> Result<(), Error> {
+    let compression_flag_set = bytes[0] >> 7 == 1;
+    let infinity_flag_set = (bytes[0] >> 6) & 1 == 1;
+    let sort_flag_set = (bytes[0] >> 5) & 1 == 1;
+    if compression_flag_set != compressed || (sort_flag_set && (infinity_flag_set || !compressed)) {
+        return Err(Error::BadFlags);
+    }
+
+    let mut is_zero = true;
+    for (i, chunk) in bytes.chunks(48).enumerate() {
+        let mut tmp = [0; 48];
+        tmp.copy_from_slice(chunk);
+        if i == 0 {
+            // Mask away the flag bits
+            tmp[0] &= 0b0001_1111;
+        }
+        if bool::from(Fp::from_bytes(&tmp).is_none()) {
+            return Err(Error::NonCanonicalField);
+        }
+        is_zero &= tmp.iter().all(|&b| b == 0);
+    }
+
+    if infinity_flag_set && !is_zero {
+        return Err(Error::BadFlags);
+    }
+    Ok(())
+}
@@ -0,0 +1,646 @@
+//! A C interface to the core operations of this crate.
+//!
+//! Every function takes its inputs and outputs as pointers to byte buffers together
+//! with their lengths, and returns a [`Status`]. Outputs are only written on
+//! success. The encodings are those of the rest of the crate:
+//!
+//! - points of $\mathbb{G}_1$ and $\mathbb{G}_2$ are compressed, in 48 and 96
+//!   bytes, except where a function is documented to take or produce uncompressed
+//!   points, in 96 and 192 bytes;
+//! - scalars are 32 bytes, little-endian, and must be canonical;
+//! - elements of $\mathbb{G}_T$ are compressed, in 288 bytes.
+//!
+//! Inputs are validated, including checking that points are in the prime-order
+//! subgroup. A panic inside a function is caught and reported as
+//! [`Status::Panic`] rather than unwinding into the caller, unless the crate is
+//! built with `panic = "abort"`. Hashing to curves uses the `BLS12381G1_XMD:SHA-256_SSWU_RO_` and
+//! `BLS12381G2_XMD:SHA-256_SSWU_RO_` suites.
+//!
+//! To use this interface from another language, build the crate as a `cdylib` or
+//! `staticlib` with the `ffi` feature, for example with
+//! `cargo rustc --release --features ffi --crate-type cdylib`.
+//!
+//! # Safety
+//!
+//! Each pointer must either be valid for reads (or, for outputs, writes) of the
+//! length passed with it, or be null with a length of zero. Output buffers must not
+//! overlap inputs.
+
+#![allow(unsafe_code)]
+
+// Linking the standard library provides the panic handler and the global allocator
+// that a `cdylib` or `staticlib` requires.
+extern crate std;
+
+use core::convert::TryFrom;
+use core::slice;
+use std::panic::{self, AssertUnwindSafe};
+
+use alloc::vec::Vec;
+use group::Curve;
+
+use crate::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use crate::msm::multi_exp_auto_vartime;
+use crate::{
+    multi_miller_loop, pairing, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, Gt,
+    Scalar,
+};
+
+/// The result of a call through the C interface.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The operation succeeded.
+    Success = 0,
+    /// A pointer was null while its length was nonzero.
+    NullPointer = 1,
+    /// An input or output buffer did not have the expected length.
+    InvalidLength = 2,
+    /// An input was not a valid encoding of a point or scalar.
+    InvalidEncoding = 3,
+    /// The operation panicked. This indicates a bug in this crate.
+    Panic = 4,
+}
+
+type Xmd = ExpandMsgXmd<sha2::Sha256>;
+
+/// Runs the body of an `extern "C"` function, converting its result and any panic
+/// into a [`Status`], as unwinding across the C boundary is undefined behavior.
+fn status(f: impl FnOnce() -> Result<(), Status>) -> Status {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result.err().unwrap_or(Status::Success),
+        Err(_) => Status::Panic,
+    }
+}
+
+unsafe fn input<'a>(ptr: *const u8, len: usize) -> Result<&'a [u8], Status> {
+    if len == 0 {
+        Ok(&[])
+    } else if ptr.is_null() {
+        Err(Status::NullPointer)
+    } else {
+        Ok(slice::from_raw_parts(ptr, len))
+    }
+}
+
+unsafe fn input_array<'a, const N: usize>(
+    ptr: *const u8,
+    len: usize,
+) -> Result<&'a [u8; N], Status> {
+    <&[u8; N]>::try_from(input(ptr, len)?).map_err(|_| Status::InvalidLength)
+}
+
+unsafe fn output<'a, const N: usize>(ptr: *mut u8, len: usize) -> Result<&'a mut [u8; N], Status> {
+    if len != N {
+        Err(Status::InvalidLength)
+    } else if ptr.is_null() {
+        Err(Status::NullPointer)
+    } else {
+        Ok(&mut *(ptr as *mut [u8; N]))
+    }
+}
+
+/// Splits `bytes` into chunks of `N` bytes, failing if its length is not a multiple
+/// of `N`.
+fn chunks<const N: usize>(bytes: &[u8]) -> Result<impl Iterator<Item = &[u8; N]>, Status> {
+    if bytes.len() % N != 0 {
+        return Err(Status::InvalidLength);
+    }
+    Ok(bytes
+        .chunks_exact(N)
+        .map(|chunk| <&[u8; N]>::try_from(chunk).unwrap()))
+}
+
+fn read_g1(bytes: &[u8; 48]) -> Result<G1Affine, Status> {
+    Option::from(G1Affine::from_compressed(bytes)).ok_or(Status::InvalidEncoding)
+}
+
+fn read_g2(bytes: &[u8; 96]) -> Result<G2Affine, Status> {
+    Option::from(G2Affine::from_compressed(bytes)).ok_or(Status::InvalidEncoding)
+}
+
+fn read_scalar(bytes: &[u8; 32]) -> Result<Scalar, Status> {
+    Option::from(Scalar::from_bytes(bytes)).ok_or(Status::InvalidEncoding)
+}
+
+/// Compresses the uncompressed $\mathbb{G}_1$ point `point` (96 bytes) into `out`
+/// (48 bytes).
+///
+/// # Safety
+///
+/// See the [module documentation](self#safety).
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g1_compress(
+    point: *const u8,
+    point_len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> Status {
+    status(|| {
+        let p =
+            Option::<G1Affine>::from(G1Affine::from_uncompressed(input_array(point, point_len)?))
+                .ok_or(Status::InvalidEncoding)?;
+        *output(out, out_len)? = p.to_compressed();
+        Ok(())
+    })
+}
+
+/// Decompresses the $\mathbb{G}_1$ point `point` (48 bytes) into `out` (96 bytes).
+///
+/// # Safety
+///
+/// See the [module documentation](self#safety).
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g1_decompress(
+    point: *const u8,
+    point_len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> Status {
+    status(|| {
+        let p = read_g1(input_array(point, point_len)?)?;
+        *output(out, out_len)? = p.to_uncompressed();
+        Ok(())
+    })
+}
+
+/// Compresses the uncompressed $\mathbb{G}_2$ point `point` (192 bytes) into `out`
+/// (96 bytes).
+///
+/// # Safety
+///
+/// See the [module documentation](self#safety).
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g2_compress(
+    point: *const u8,
+    point_len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> Status {
+    status(|| {
+        let p =
+            Option::<G2Affine>::from(G2Affine::from_uncompressed(input_array(point, point_len)?))
+                .ok_or(Status::InvalidEncoding)?;
+        *output(out, out_len)? = p.to_compressed();
+        Ok(())
+    })
+}
+
+/// Decompresses the $\mathbb{G}_2$ point `point` (96 bytes) into `out` (192 bytes).
+///
+/// # Safety
+///
+/// See the [module documentation](self#safety).
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g2_decompress(
+    point: *const u8,
+    point_len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> Status {
+    status(|| {
+        let p = read_g2(input_array(point, point_len)?)?;
+        *output(out, out_len)? = p.to_uncompressed();
+        Ok(())
+    })
+}
+
+/// Multiplies the $\mathbb{G}_1$ point `point` by `scalar`, writing the result to
+/// `out` (48 bytes).
+///
+/// # Safety
+///
+/// See the [module documentation](self#safety).
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g1_mul(
+    point: *const u8,
+    point_len: usize,
+    scalar: *const u8,
+    scalar_len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> Status {
+    status(|| {
+        let p = read_g1(input_array(point, point_len)?)?;
+        let s = read_scalar(input_array(scalar, scalar_len)?)?;
+        *output(out, out_len)? = (p * s).to_affine().to_compressed();
+        Ok(())
+    })
+}
+
+/// Multiplies the $\mathbb{G}_2$ point `point` by `scalar`, writing the result to
+/// `out` (96 bytes).
+///
+/// # Safety
+///
+/// See the [module documentation](self#safety).
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g2_mul(
+    point: *const u8,
+    point_len: usize,
+    scalar: *const u8,
+    scalar_len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> Status {
+    status(|| {
+        let p = read_g2(input_array(point, point_len)?)?;
+        let s = read_scalar(input_array(scalar, scalar_len)?)?;
+        *output(out, out_len)? = (p * s).to_affine().to_compressed();
+        Ok(())
+    })
+}
+
+/// Computes $\sum_i s_i P_i$ for the concatenated $\mathbb{G}_1$ points `points`
+/// and the concatenated scalars `scalars`, writing the result to `out` (48 bytes).
+///
+/// `points` and `scalars` must hold the same number of elements. This runs in
+/// variable time, so the scalars must not be secret.
+///
+/// # Safety
+///
+/// See the [module documentation](self#safety).
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g1_msm(
+    points: *const u8,
+    points_len: usize,
+    scalars: *const u8,
+    scalars_len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> Status {
+    status(|| {
+        if points_len / 48 != scalars_len / 32 {
+            return Err(Status::InvalidLength);
+        }
+        let points = chunks(input(points, points_len)?)?
+            .map(read_g1)
+            .collect::<Result<Vec<_>, Status>>()?;
+        let scalars = chunks(input(scalars, scalars_len)?)?
+            .map(read_scalar)
+            .collect::<Result<Vec<_>, Status>>()?;
+        let out = output(out, out_len)?;
+        *out = multi_exp_auto_vartime(&points, &scalars)
+            .to_affine()
+            .to_compressed();
+        Ok(())
+    })
+}
+
+/// Computes $\sum_i s_i P_i$ for the concatenated $\mathbb{G}_2$ points `points`
+/// and the concatenated scalars `scalars`, writing the result to `out` (96 bytes).
+///
+/// `points` and `scalars` must hold the same number of elements. This runs in
+/// variable time, so the scalars must not be secret.
+///
+/// # Safety
+///
+/// See the [module documentation](self#safety).
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_g2_msm(
+    points: *const u8,
+    points_len: usize,
+    scalars: *const u8,
+    scalars_len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> Status {
+    status(|| {
+        if points_len / 96 != scalars_len / 32 {
+            return Err(Status::InvalidLength);
+        }
+        let points = chunks(input(points, points_len)?)?
+            .map(read_g2)
+            .collect::<Result<Vec<_>, Status>>()?;
+        let scalars = chunks(input(scalars, scalars_len)?)?
+            .map(read_scalar)
+            .collect::<Result<Vec<_>, Status>>()?;
+        let out = output(out, out_len)?;
+        *out = multi_exp_auto_vartime(&points, &scalars)
+            .to_affine()
+            .to_compressed();
+        Ok(())
+    })
+}
+
+/// Computes the pairing $e(P, Q)$ of the $\mathbb{G}_1$ point `p` and the
+/// $\mathbb{G}_2$ point `q`, writing the result to `out` (288 bytes).
+///
+/// # Safety
+///
+/// See the [module documentation](self#safety).
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_pairing(
+    p: *const u8,
+    p_len: usize,
+    q: *const u8,
+    q_len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> Status {
+    status(|| {
+        let p = read_g1(input_array(p, p_len)?)?;
+        let q = read_g2(input_array(q, q_len)?)?;
+        *output(out, out_len)? = pairing(&p, &q).to_compressed();
+        Ok(())
+    })
+}
+
+/// Checks whether $\prod_i e(P_i, Q_i)$ is the identity, for the concatenated
+/// $\mathbb{G}_1$ points `ps` and the concatenated $\mathbb{G}_2$ points `qs`,
+/// writing the answer to `result`.
+///
+/// `ps` and `qs` must hold the same number of points. This is how most
+/// pairing-based signatures and proofs are verified.
+///
+/// # Safety
+///
+/// See the [module documentation](self#safety). `result` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_pairing_check(
+    ps: *const u8,
+    ps_len: usize,
+    qs: *const u8,
+    qs_len: usize,
+    result: *mut bool,
+) -> Status {
+    status(|| {
+        if ps_len / 48 != qs_len / 96 {
+            return Err(Status::InvalidLength);
+        }
+        let terms = chunks(input(ps, ps_len)?)?
+            .zip(chunks(input(qs, qs_len)?)?)
+            .map(|(p, q)| Ok((read_g1(p)?, G2Prepared::from(read_g2(q)?))))
+            .collect::<Result<Vec<_>, Status>>()?;
+        if result.is_null() {
+            return Err(Status::NullPointer);
+        }
+
+        let terms = terms.iter().map(|(p, q)| (p, q)).collect::<Vec<_>>();
+        *result = multi_miller_loop(&terms).final_exponentiation() == Gt::identity();
+        Ok(())
+    })
+}
+
+/// Hashes `msg` to a $\mathbb{G}_1$ point with the domain separation tag `dst`,
+/// writing the result to `out` (48 bytes).
+///
+/// # Safety
+///
+/// See the [module documentation](self#safety).
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_hash_to_g1(
+    msg: *const u8,
+    msg_len: usize,
+    dst: *const u8,
+    dst_len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> Status {
+    status(|| {
+        let (msg, dst) = (input(msg, msg_len)?, input(dst, dst_len)?);
+        let out = output(out, out_len)?;
+        *out = <G1Projective as HashToCurve<Xmd>>::hash_to_curve(msg, dst)
+            .to_affine()
+            .to_compressed();
+        Ok(())
+    })
+}
+
+/// Hashes `msg` to a $\mathbb{G}_2$ point with the domain separation tag `dst`,
+/// writing the result to `out` (96 bytes).
+///
+/// # Safety
+///
+/// See the [module documentation](self#safety).
+#[no_mangle]
+pub unsafe extern "C" fn bls12_381_hash_to_g2(
+    msg: *const u8,
+    msg_len: usize,
+    dst: *const u8,
+    dst_len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> Status {
+    status(|| {
+        let (msg, dst) = (input(msg, msg_len)?, input(dst, dst_len)?);
+        let out = output(out, out_len)?;
+        *out = <G2Projective as HashToCurve<Xmd>>::hash_to_curve(msg, dst)
+            .to_affine()
+            .to_compressed();
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ptr;
+
+    #[test]
+    fn test_serialization() {
+        let p = G1Affine::generator();
+        let q = G2Affine::generator();
+        let (mut p_out, mut q_out) = ([0u8; 96], [0u8; 192]);
+        let (mut p_compressed, mut q_compressed) = ([0u8; 48], [0u8; 96]);
+
+        unsafe {
+            let status =
+                bls12_381_g1_decompress(p.to_compressed().as_ptr(), 48, p_out.as_mut_ptr(), 96);
+            assert_eq!(status, Status::Success);
+            assert_eq!(p_out, p.to_uncompressed());
+            let status = bls12_381_g1_compress(p_out.as_ptr(), 96, p_compressed.as_mut_ptr(), 48);
+            assert_eq!(status, Status::Success);
+            assert_eq!(p_compressed, p.to_compressed());
+
+            let status =
+                bls12_381_g2_decompress(q.to_compressed().as_ptr(), 96, q_out.as_mut_ptr(), 192);
+            assert_eq!(status, Status::Success);
+            assert_eq!(q_out, q.to_uncompressed());
+            let status = bls12_381_g2_compress(q_out.as_ptr(), 192, q_compressed.as_mut_ptr(), 96);
+            assert_eq!(status, Status::Success);
+            assert_eq!(q_compressed, q.to_compressed());
+
+            let mut invalid = p.to_compressed();
+            invalid[47] ^= 1;
+            let status = bls12_381_g1_decompress(invalid.as_ptr(), 48, p_out.as_mut_ptr(), 96);
+            assert_eq!(status, Status::InvalidEncoding);
+            let status = bls12_381_g1_decompress(invalid.as_ptr(), 47, p_out.as_mut_ptr(), 96);
+            assert_eq!(status, Status::InvalidLength);
+            let status = bls12_381_g1_decompress(ptr::null(), 48, p_out.as_mut_ptr(), 96);
+            assert_eq!(status, Status::NullPointer);
+            let status =
+                bls12_381_g2_decompress(q.to_compressed().as_ptr(), 96, q_out.as_mut_ptr(), 96);
+            assert_eq!(status, Status::InvalidLength);
+        }
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let s = Scalar::from_raw([1, 2, 3, 4]);
+        let t = Scalar::from_raw([5, 6, 7, 8]);
+        let p = G1Affine::generator();
+        let q = G2Affine::from(G2Affine::generator() * t);
+        let scalars = [s.to_bytes(), t.to_bytes()].concat();
+        let (mut p_out, mut q_out) = ([0u8; 48], [0u8; 96]);
+
+        unsafe {
+            let status = bls12_381_g1_mul(
+                p.to_compressed().as_ptr(),
+                48,
+                s.to_bytes().as_ptr(),
+                32,
+                p_out.as_mut_ptr(),
+                48,
+            );
+            assert_eq!(status, Status::Success);
+            assert_eq!(p_out, (p * s).to_affine().to_compressed());
+
+            let status = bls12_381_g2_mul(
+                q.to_compressed().as_ptr(),
+                96,
+                s.to_bytes().as_ptr(),
+                32,
+                q_out.as_mut_ptr(),
+                96,
+            );
+            assert_eq!(status, Status::Success);
+            assert_eq!(q_out, (q * s).to_affine().to_compressed());
+
+            let points = [p.to_compressed(), G1Affine::from(p * t).to_compressed()].concat();
+            let status = bls12_381_g1_msm(
+                points.as_ptr(),
+                96,
+                scalars.as_ptr(),
+                64,
+                p_out.as_mut_ptr(),
+                48,
+            );
+            assert_eq!(status, Status::Success);
+            assert_eq!(p_out, (p * (s + t * t)).to_affine().to_compressed());
+
+            let points = [q.to_compressed(), G2Affine::generator().to_compressed()].concat();
+            let status = bls12_381_g2_msm(
+                points.as_ptr(),
+                192,
+                scalars.as_ptr(),
+                64,
+                q_out.as_mut_ptr(),
+                96,
+            );
+            assert_eq!(status, Status::Success);
+            assert_eq!(
+                q_out,
+                (q * s + G2Affine::generator() * t)
+                    .to_affine()
+                    .to_compressed()
+            );
+
+            // An empty sum is the identity.
+            let status = bls12_381_g1_msm(ptr::null(), 0, ptr::null(), 0, p_out.as_mut_ptr(), 48);
+            assert_eq!(status, Status::Success);
+            assert_eq!(p_out, G1Affine::identity().to_compressed());
+
+            let status = bls12_381_g1_msm(
+                points.as_ptr(),
+                96,
+                scalars.as_ptr(),
+                32,
+                p_out.as_mut_ptr(),
+                48,
+            );
+            assert_eq!(status, Status::InvalidLength);
+            let invalid = [0xffu8; 32];
+            let status = bls12_381_g1_mul(
+                p.to_compressed().as_ptr(),
+                48,
+                invalid.as_ptr(),
+                32,
+                p_out.as_mut_ptr(),
+                48,
+            );
+            assert_eq!(status, Status::InvalidEncoding);
+        }
+    }
+
+    #[test]
+    fn test_pairing() {
+        let s = Scalar::from_raw([1, 2, 3, 4]);
+        let p = G1Affine::from(G1Affine::generator() * s);
+        let q = G2Affine::generator();
+        let mut out = [0u8; 288];
+
+        unsafe {
+            let status = bls12_381_pairing(
+                p.to_compressed().as_ptr(),
+                48,
+                q.to_compressed().as_ptr(),
+                96,
+                out.as_mut_ptr(),
+                288,
+            );
+            assert_eq!(status, Status::Success);
+            assert_eq!(out, (Gt::generator() * s).to_compressed());
+
+            // e(sG, H) e(-G, sH) = 1
+            let ps = [p.to_compressed(), (-G1Affine::generator()).to_compressed()].concat();
+            let qs = [q.to_compressed(), G2Affine::from(q * s).to_compressed()].concat();
+            let mut result = false;
+            let status = bls12_381_pairing_check(ps.as_ptr(), 96, qs.as_ptr(), 192, &mut result);
+            assert_eq!(status, Status::Success);
+            assert!(result);
+
+            let status = bls12_381_pairing_check(ps.as_ptr(), 48, qs.as_ptr(), 96, &mut result);
+            assert_eq!(status, Status::Success);
+            assert!(!result);
+
+            let status = bls12_381_pairing_check(ps.as_ptr(), 96, qs.as_ptr(), 96, &mut result);
+            assert_eq!(status, Status::InvalidLength);
+        }
+    }
+
+    #[test]
+    fn test_panic() {
+        assert_eq!(status(|| Ok(())), Status::Success);
+        assert_eq!(status(|| Err(Status::InvalidLength)), Status::InvalidLength);
+        assert_eq!(status(|| panic!("bug")), Status::Panic);
+    }
+
+    #[test]
+    fn test_hash_to_curve() {
+        let (msg, dst) = (
+            b"abc",
+            b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        );
+        let mut out = [0u8; 48];
+        unsafe {
+            let status = bls12_381_hash_to_g1(
+                msg.as_ptr(),
+                msg.len(),
+                dst.as_ptr(),
+                dst.len(),
+                out.as_mut_ptr(),
+                48,
+            );
+            assert_eq!(status, Status::Success);
+        }
+        let expected = <G1Projective as HashToCurve<Xmd>>::hash_to_curve(msg, dst);
+        assert_eq!(out, expected.to_affine().to_compressed());
+
+        let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+        let mut out = [0u8; 96];
+        unsafe {
+            let status = bls12_381_hash_to_g2(
+                ptr::null(),
+                0,
+                dst.as_ptr(),
+                dst.len(),
+                out.as_mut_ptr(),
+                96,
+            );
+            assert_eq!(status, Status::Success);
+        }
+        let expected = <G2Projective as HashToCurve<Xmd>>::hash_to_curve(b"", dst);
+        assert_eq!(out, expected.to_affine().to_compressed());
+    }
+}
@@ -3,11 +3,16 @@
 
 use core::fmt;
 use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+#[cfg(any(test, feature = "groups"))]
 use rand_core::RngCore;
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
+use crate::arith::{Backend, FieldParams, Limbs, Selected, Unrolled};
 use crate::util::{adc, mac, sbb};
+use crate::{Error, Scalar};
 
+/// Represents an element of the base field $\mathbb{F}_p$ of the BLS12-381 elliptic
+/// curve construction.
 // The internal representation of this type is six 64-bit unsigned
 // integers in little-endian order. `Fp` values are always in
 // Montgomery form; i.e., Scalar(a) = aR mod p, with R = 2^384.
@@ -66,8 +71,25 @@ impl ConditionallySelectable for Fp {
     }
 }
 
+/// Decodes the canonical big-endian encoding of [`Fp::to_bytes`], which must be
+/// exactly 48 bytes long.
+impl<'a> TryFrom<&'a [u8]> for Fp {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Fp, Error> {
+        let bytes = <&[u8; 48]>::try_from(bytes).map_err(|_| Error::InvalidLength)?;
+        Option::from(Fp::from_bytes(bytes)).ok_or(Error::NonCanonicalField)
+    }
+}
+
+impl From<u64> for Fp {
+    fn from(val: u64) -> Fp {
+        Fp([val, 0, 0, 0, 0, 0]) * R2
+    }
+}
+
 /// p = 4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559787
-const MODULUS: [u64; 6] = [
+pub(crate) const MODULUS: [u64; 6] = [
     0xb9fe_ffff_ffff_aaab,
     0x1eab_fffe_b153_ffff,
     0x6730_d2a0_f6b0_f624,
@@ -79,6 +101,53 @@ const MODULUS: [u64; 6] = [
 /// INV = -(p^{-1} mod 2^64) mod 2^64
 const INV: u64 = 0x89f3_fffc_fffc_fffd;
 
+/// The parameters of `Fp` for the arithmetic backends.
+pub(crate) struct FpParams;
+
+impl FieldParams<6> for FpParams {
+    const MODULUS: [u64; 6] = MODULUS;
+    const INV: u64 = INV;
+}
+
+impl Backend<FpParams, 6> for Unrolled {
+    #[inline(always)]
+    fn add(a: &[u64; 6], b: &[u64; 6]) -> [u64; 6] {
+        Fp::add(&Fp(*a), &Fp(*b)).0
+    }
+
+    #[inline(always)]
+    fn sub(a: &[u64; 6], b: &[u64; 6]) -> [u64; 6] {
+        Fp::sub(&Fp(*a), &Fp(*b)).0
+    }
+
+    #[inline(always)]
+    fn neg(a: &[u64; 6]) -> [u64; 6] {
+        Fp::neg(&Fp(*a)).0
+    }
+
+    #[inline(always)]
+    fn mul(a: &[u64; 6], b: &[u64; 6]) -> [u64; 6] {
+        Fp::mul(&Fp(*a), &Fp(*b)).0
+    }
+
+    #[inline(always)]
+    fn square(a: &[u64; 6]) -> [u64; 6] {
+        Fp::square(&Fp(*a)).0
+    }
+
+    #[inline]
+    fn sum_of_products<E: Limbs<6>, const T: usize>(a: &[E; T], b: &[E; T]) -> [u64; 6] {
+        Fp::sum_of_products_unrolled(a, b).0
+    }
+}
+
+impl Limbs<6> for Fp {
+    #[inline(always)]
+    fn limbs(&self) -> &[u64; 6] {
+        &self.0
+    }
+}
+
 /// R = 2^384 mod p
 const R: Fp = Fp([
     0x7609_0000_0002_fffd,
@@ -90,7 +159,7 @@ const R: Fp = Fp([
 ]);
 
 /// R2 = 2^(384*2) mod p
-const R2: Fp = Fp([
+pub(crate) const R2: Fp = Fp([
     0xf4df_1f34_1c34_1746,
     0x0a76_e6a6_09d1_04f1,
     0x8de5_476c_4c95_b6d5,
@@ -114,7 +183,7 @@ impl<'a> Neg for &'a Fp {
 
     #[inline]
     fn neg(self) -> Fp {
-        self.neg()
+        Fp(<Selected as Backend<FpParams, 6>>::neg(&self.0))
     }
 }
 
@@ -132,7 +201,7 @@ impl<'a, 'b> Sub<&'b Fp> for &'a Fp {
 
     #[inline]
     fn sub(self, rhs: &'b Fp) -> Fp {
-        self.sub(rhs)
+        Fp(<Selected as Backend<FpParams, 6>>::sub(&self.0, &rhs.0))
     }
 }
 
@@ -141,7 +210,7 @@ impl<'a, 'b> Add<&'b Fp> for &'a Fp {
 
     #[inline]
     fn add(self, rhs: &'b Fp) -> Fp {
-        self.add(rhs)
+        Fp(<Selected as Backend<FpParams, 6>>::add(&self.0, &rhs.0))
     }
 }
 
@@ -150,7 +219,7 @@ impl<'a, 'b> Mul<&'b Fp> for &'a Fp {
 
     #[inline]
     fn mul(self, rhs: &'b Fp) -> Fp {
-        self.mul(rhs)
+        Fp(<Selected as Backend<FpParams, 6>>::mul(&self.0, &rhs.0))
     }
 }
 
@@ -158,6 +227,22 @@ impl_binops_additive!(Fp, Fp);
 impl_binops_multiplicative!(Fp, Fp);
 
 impl Fp {
+    /// A primitive cube root of unity, the counterpart of
+    /// [`WithSmallOrderMulGroup<3>::ZETA`][zeta] in later versions of `ff`.
+    ///
+    /// The endomorphism $(x, y) \mapsto (\zeta x, y)$ of $\mathbb{G}_1$ multiplies its
+    /// points by `Scalar::ZETA`.
+    ///
+    /// [zeta]: https://docs.rs/ff/0.13/ff/trait.WithSmallOrderMulGroup.html#associatedconstant.ZETA
+    pub const ZETA: Fp = Fp::from_raw_unchecked([
+        0x30f1_361b_798a_64e8,
+        0xf3b8_ddab_7ece_5a2a,
+        0x16a8_ca3a_c615_77f7,
+        0xc26a_2ff8_74fd_029b,
+        0x3636_b766_6070_1c6e,
+        0x051b_a4ab_241b_6160,
+    ]);
+
     /// Returns zero, the additive identity.
     #[inline]
     pub const fn zero() -> Fp {
@@ -170,6 +255,7 @@ impl Fp {
         R
     }
 
+    /// Returns whether or not this element is zero.
     pub fn is_zero(&self) -> Choice {
         self.ct_eq(&Fp::zero())
     }
@@ -177,14 +263,22 @@ impl Fp {
     /// Attempts to convert a big-endian byte representation of
     /// a scalar into an `Fp`, failing if the input is not canonical.
     pub fn from_bytes(bytes: &[u8; 48]) -> CtOption<Fp> {
-        let mut tmp = Fp([0, 0, 0, 0, 0, 0]);
+        Fp::from_raw([
+            u64::from_be_bytes(<[u8; 8]>::try_from(&bytes[40..48]).unwrap()),
+            u64::from_be_bytes(<[u8; 8]>::try_from(&bytes[32..40]).unwrap()),
+            u64::from_be_bytes(<[u8; 8]>::try_from(&bytes[24..32]).unwrap()),
+            u64::from_be_bytes(<[u8; 8]>::try_from(&bytes[16..24]).unwrap()),
+            u64::from_be_bytes(<[u8; 8]>::try_from(&bytes[8..16]).unwrap()),
+            u64::from_be_bytes(<[u8; 8]>::try_from(&bytes[0..8]).unwrap()),
+        ])
+    }
 
-        tmp.0[5] = u64::from_be_bytes(<[u8; 8]>::try_from(&bytes[0..8]).unwrap());
-        tmp.0[4] = u64::from_be_bytes(<[u8; 8]>::try_from(&bytes[8..16]).unwrap());
-        tmp.0[3] = u64::from_be_bytes(<[u8; 8]>::try_from(&bytes[16..24]).unwrap());
-        tmp.0[2] = u64::from_be_bytes(<[u8; 8]>::try_from(&bytes[24..32]).unwrap());
-        tmp.0[1] = u64::from_be_bytes(<[u8; 8]>::try_from(&bytes[32..40]).unwrap());
-        tmp.0[0] = u64::from_be_bytes(<[u8; 8]>::try_from(&bytes[40..48]).unwrap());
+    /// Attempts to convert an integer represented in little endian
+    /// into an `Fp`, failing if the input is not canonical. Unlike
+    /// [`Fp::from_raw_unchecked`], this expects the integer itself
+    /// rather than its Montgomery form.
+    pub fn from_raw(limbs: [u64; 6]) -> CtOption<Fp> {
+        let mut tmp = Fp(limbs);
 
         // Try to subtract the modulus
         let (_, borrow) = sbb(tmp.0[0], MODULUS[0], 0);
@@ -206,6 +300,25 @@ impl Fp {
         CtOption::new(tmp, Choice::from(is_some))
     }
 
+    /// Attempts to convert an integer represented in little endian into an `Fp`, as
+    /// [`Fp::from_raw`] does, failing with [`Error::NonCanonicalField`] if it is not
+    /// canonical.
+    ///
+    /// **This is not constant time**; use `from_raw()` for secret inputs.
+    pub fn try_from_raw(limbs: [u64; 6]) -> Result<Fp, Error> {
+        Option::from(Fp::from_raw(limbs)).ok_or(Error::NonCanonicalField)
+    }
+
+    /// Lifts `scalar` into `Fp`, as the element with the same canonical
+    /// representation. This is always possible, since $q < p$.
+    pub fn from_scalar(scalar: &Scalar) -> Fp {
+        let limbs = <[u64; 4]>::from(scalar);
+
+        // Convert to Montgomery form by computing
+        // (a.R^0 * R^2) / R = a.R
+        Fp([limbs[0], limbs[1], limbs[2], limbs[3], 0, 0]) * R2
+    }
+
     /// Converts an element of `Fp` into a byte representation in
     /// big-endian byte order.
     pub fn to_bytes(self) -> [u8; 48] {
@@ -226,6 +339,7 @@ impl Fp {
         res
     }
 
+    #[cfg(any(test, feature = "groups"))]
     pub(crate) fn random(mut rng: impl RngCore) -> Fp {
         let mut bytes = [0u8; 96];
         rng.fill_bytes(&mut bytes);
@@ -248,7 +362,8 @@ impl Fp {
     }
 
     /// Reduces a big-endian 64-bit limb representation of a 768-bit number.
-    fn from_u768(limbs: [u64; 12]) -> Fp {
+    #[cfg(any(test, feature = "groups", feature = "arbitrary"))]
+    pub(crate) fn from_u768(limbs: [u64; 12]) -> Fp {
         // We reduce an arbitrary 768-bit number by decomposing it into two 384-bit digits
         // with the higher bits multiplied by 2^384. Thus, we perform two reductions
         //
@@ -268,6 +383,18 @@ impl Fp {
         d0 * R2 + d1 * R3
     }
 
+    /// Returns the "sign" of this element as defined in
+    /// [section 4.1 of RFC 9380](https://www.rfc-editor.org/rfc/rfc9380#section-4.1),
+    /// which is its parity, so that zero is considered positive.
+    pub fn sgn0(&self) -> Choice {
+        // Turn into canonical form by computing
+        // (a.R) / R = a
+        let tmp = Fp::montgomery_reduce(
+            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5], 0, 0, 0, 0, 0, 0,
+        );
+        Choice::from((tmp.0[0] & 1) as u8)
+    }
+
     /// Returns whether or not this element is strictly lexicographically
     /// larger than its negation.
     pub fn lexicographically_largest(&self) -> Choice {
@@ -320,6 +447,7 @@ impl Fp {
         res
     }
 
+    /// Computes the square root of this element, if it exists.
     #[inline]
     pub fn sqrt(&self) -> CtOption<Self> {
         // We use Shank's method, as p = 3 (mod 4). This means
@@ -357,6 +485,57 @@ impl Fp {
         CtOption::new(t, !self.is_zero())
     }
 
+    /// Computes the multiplicative inverse of this element with the binary extended
+    /// Euclidean algorithm, failing if the element is zero. This is about three
+    /// times faster than [`Fp::invert`], for use on public values.
+    ///
+    /// **This operation is variable time with respect to the element.**
+    pub fn invert_vartime(&self) -> CtOption<Self> {
+        // The inverse of aR is a^-1 R^-1, which is brought back to the Montgomery
+        // form of a^-1 by a multiplication with R^3.
+        match crate::util::invert_vartime(&self.0, &MODULUS) {
+            Some(inverse) => CtOption::new(Fp(inverse) * R3, Choice::from(1)),
+            None => CtOption::new(Fp::zero(), Choice::from(0)),
+        }
+    }
+
+    /// Inverts every nonzero element of `values` with a single inversion, leaving
+    /// the zero elements unchanged, in constant time.
+    ///
+    /// The partial products are kept in an array of `N` elements on the stack, so
+    /// this needs no allocator.
+    pub fn batch_invert<const N: usize>(values: &mut [Fp; N]) {
+        let mut products = [Fp::one(); N];
+        let mut acc = Fp::one();
+        for (value, product) in values.iter().zip(products.iter_mut()) {
+            *product = acc;
+            // Zero elements are skipped.
+            acc = Fp::conditional_select(&(acc * value), &acc, value.is_zero());
+        }
+
+        // The product is nonzero, since only nonzero elements were multiplied.
+        let mut inv = acc.invert().unwrap();
+        for (value, product) in values.iter_mut().zip(products.iter()).rev() {
+            let skip = value.is_zero();
+            let next = Fp::conditional_select(&(inv * *value), &inv, skip);
+            *value = Fp::conditional_select(&(inv * product), value, skip);
+            inv = next;
+        }
+    }
+
+    /// Inverts every nonzero element of `values`, leaving the zero elements
+    /// unchanged, in constant time and on up to `threads` threads.
+    ///
+    /// Each thread multiplies the elements of a contiguous chunk of `values`, the
+    /// products of the chunks are inverted with a single inversion, and each thread
+    /// then recovers the inverses of the elements of its own chunk.
+    #[cfg(feature = "parallel")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+    pub fn batch_invert_parallel(values: &mut [Fp], threads: usize) {
+        let inverses = crate::threads::map_inverses(values, threads, |v| *v, |_, inv| inv);
+        values.copy_from_slice(&inverses);
+    }
+
     #[inline]
     const fn subtract_p(&self) -> Fp {
         let (r0, borrow) = sbb(self.0[0], MODULUS[0], 0);
@@ -378,6 +557,7 @@ impl Fp {
         Fp([r0, r1, r2, r3, r4, r5])
     }
 
+    /// Adds `rhs` to `self`, returning the result.
     #[inline]
     pub const fn add(&self, rhs: &Fp) -> Fp {
         let (d0, carry) = adc(self.0[0], rhs.0[0], 0);
@@ -392,6 +572,7 @@ impl Fp {
         (&Fp([d0, d1, d2, d3, d4, d5])).subtract_p()
     }
 
+    /// Negates `self`.
     #[inline]
     pub const fn neg(&self) -> Fp {
         let (d0, borrow) = sbb(MODULUS[0], self.0[0], 0);
@@ -417,17 +598,25 @@ impl Fp {
         ])
     }
 
+    /// Subtracts `rhs` from `self`, returning the result.
     #[inline]
     pub const fn sub(&self, rhs: &Fp) -> Fp {
         (&rhs.neg()).add(self)
     }
 
+    /// Returns `c = a.zip(b).fold(0, |acc, (a_i, b_i)| acc + a_i * b_i)`, with the
+    /// backend of the target.
+    #[inline]
+    pub(crate) fn sum_of_products<const T: usize>(a: [Fp; T], b: [Fp; T]) -> Fp {
+        Fp(<Selected as Backend<FpParams, 6>>::sum_of_products(&a, &b))
+    }
+
     /// Returns `c = a.zip(b).fold(0, |acc, (a_i, b_i)| acc + a_i * b_i)`.
     ///
     /// Implements Algorithm 2 from Patrick Longa's
     /// [ePrint 2022-367](https://eprint.iacr.org/2022/367) §3.
     #[inline]
-    pub(crate) fn sum_of_products<const T: usize>(a: [Fp; T], b: [Fp; T]) -> Fp {
+    fn sum_of_products_unrolled<E: Limbs<6>, const T: usize>(a: &[E; T], b: &[E; T]) -> Fp {
         // For a single `a x b` multiplication, operand scanning (schoolbook) takes each
         // limb of `a` in turn, and multiplies it by all of the limbs of `b` to compute
         // the result as a double-width intermediate representation, which is then fully
@@ -452,12 +641,12 @@ impl Fp {
                     (u0, u1, u2, u3, u4, u5, 0),
                     |(t0, t1, t2, t3, t4, t5, t6), i| {
                         // Compute digit_j x row and accumulate into `u`.
-                        let (t0, carry) = mac(t0, a[i].0[j], b[i].0[0], 0);
-                        let (t1, carry) = mac(t1, a[i].0[j], b[i].0[1], carry);
-                        let (t2, carry) = mac(t2, a[i].0[j], b[i].0[2], carry);
-                        let (t3, carry) = mac(t3, a[i].0[j], b[i].0[3], carry);
-                        let (t4, carry) = mac(t4, a[i].0[j], b[i].0[4], carry);
-                        let (t5, carry) = mac(t5, a[i].0[j], b[i].0[5], carry);
+                        let (t0, carry) = mac(t0, a[i].limbs()[j], b[i].limbs()[0], 0);
+                        let (t1, carry) = mac(t1, a[i].limbs()[j], b[i].limbs()[1], carry);
+                        let (t2, carry) = mac(t2, a[i].limbs()[j], b[i].limbs()[2], carry);
+                        let (t3, carry) = mac(t3, a[i].limbs()[j], b[i].limbs()[3], carry);
+                        let (t4, carry) = mac(t4, a[i].limbs()[j], b[i].limbs()[4], carry);
+                        let (t5, carry) = mac(t5, a[i].limbs()[j], b[i].limbs()[5], carry);
                         let (t6, _) = adc(t6, 0, carry);
 
                         (t0, t1, t2, t3, t4, t5, t6)
@@ -561,6 +750,7 @@ impl Fp {
         (&Fp([r6, r7, r8, r9, r10, r11])).subtract_p()
     }
 
+    /// Multiplies `rhs` by `self`, returning the result.
     #[inline]
     pub const fn mul(&self, rhs: &Fp) -> Fp {
         let (t0, carry) = mac(0, self.0[0], rhs.0[0], 0);
@@ -658,6 +848,69 @@ impl Fp {
 
         Self::montgomery_reduce(t0, t1, t2, t3, t4, t5, t6, t7, t8, t9, t10, t11)
     }
+
+    /// Multiplies each element of `a` by the corresponding element of `b`, in place.
+    /// This function will panic if `a.len() != b.len()`.
+    pub fn mul_assign_batch(a: &mut [Fp], b: &[Fp]) {
+        zip_batch(a, b, Fp::mul)
+    }
+
+    /// Adds to each element of `a` the corresponding element of `b`, in place. This
+    /// function will panic if `a.len() != b.len()`.
+    pub fn add_assign_batch(a: &mut [Fp], b: &[Fp]) {
+        zip_batch(a, b, Fp::add)
+    }
+
+    /// Subtracts from each element of `a` the corresponding element of `b`, in
+    /// place. This function will panic if `a.len() != b.len()`.
+    pub fn sub_assign_batch(a: &mut [Fp], b: &[Fp]) {
+        zip_batch(a, b, Fp::sub)
+    }
+
+    /// Squares each element of `a`, in place.
+    pub fn square_batch(a: &mut [Fp]) {
+        let mut chunks = a.chunks_exact_mut(BATCH_LANES);
+        for a in &mut chunks {
+            let a: &mut [Fp; BATCH_LANES] = a.try_into().unwrap();
+            *a = [a[0].square(), a[1].square(), a[2].square(), a[3].square()];
+        }
+        for a in chunks.into_remainder() {
+            *a = a.square();
+        }
+    }
+}
+
+/// The number of elements processed together by the batch operations on [`Fp`].
+///
+/// The elements of a batch are independent, so splitting it into fixed-size groups
+/// of lanes lets the compiler interleave their instruction chains, or vectorize
+/// them on targets with wide enough integer multipliers.
+const BATCH_LANES: usize = 4;
+
+/// Applies `f` to each element of `a` and the corresponding element of `b`,
+/// `BATCH_LANES` elements at a time.
+#[inline(always)]
+fn zip_batch(a: &mut [Fp], b: &[Fp], f: impl Fn(&Fp, &Fp) -> Fp) {
+    assert_eq!(a.len(), b.len());
+
+    let mut a_chunks = a.chunks_exact_mut(BATCH_LANES);
+    let mut b_chunks = b.chunks_exact(BATCH_LANES);
+    for (a, b) in (&mut a_chunks).zip(&mut b_chunks) {
+        let a: &mut [Fp; BATCH_LANES] = a.try_into().unwrap();
+        *a = [
+            f(&a[0], &b[0]),
+            f(&a[1], &b[1]),
+            f(&a[2], &b[2]),
+            f(&a[3], &b[3]),
+        ];
+    }
+    for (a, b) in a_chunks
+        .into_remainder()
+        .iter_mut()
+        .zip(b_chunks.remainder())
+    {
+        *a = f(a, b);
+    }
 }
 
 #[test]
@@ -676,6 +929,7 @@ fn test_conditional_selection() {
 }
 
 #[test]
+#[allow(clippy::needless_borrow)]
 fn test_equality() {
     fn is_equal(a: &Fp, b: &Fp) -> bool {
         let eq = a == b;
@@ -848,6 +1102,43 @@ fn test_debug() {
     );
 }
 
+#[test]
+fn test_from_u64() {
+    assert_eq!(Fp::from(0), Fp::zero());
+    assert_eq!(Fp::from(1), Fp::one());
+    assert_eq!(
+        Fp::from(u64::MAX),
+        Fp::from_raw([u64::MAX, 0, 0, 0, 0, 0]).unwrap()
+    );
+    assert_eq!(Fp::from(6) * Fp::from(7), Fp::from(42));
+}
+
+#[test]
+fn test_from_raw() {
+    assert_eq!(Fp::from_raw([0; 6]).unwrap(), Fp::zero());
+    assert_eq!(Fp::from_raw([1, 0, 0, 0, 0, 0]).unwrap(), Fp::one());
+
+    let mut limbs = MODULUS;
+    limbs[0] -= 1;
+    assert_eq!(Fp::from_raw(limbs).unwrap(), -Fp::one());
+    assert!(bool::from(Fp::from_raw(MODULUS).is_none()));
+    assert!(bool::from(Fp::from_raw([u64::max_value(); 6]).is_none()));
+}
+
+#[test]
+fn test_from_scalar() {
+    assert_eq!(Fp::from_scalar(&Scalar::zero()), Fp::zero());
+    assert_eq!(Fp::from_scalar(&Scalar::one()), Fp::one());
+
+    let scalar = -Scalar::one();
+    let mut bytes = [0u8; 48];
+    for (b, s) in bytes[16..].iter_mut().zip(scalar.to_bytes().iter().rev()) {
+        *b = *s;
+    }
+    assert_eq!(Fp::from_scalar(&scalar), Fp::from_bytes(&bytes).unwrap());
+    assert_eq!(Scalar::from_fp_reduced(&Fp::from_scalar(&scalar)), scalar);
+}
+
 #[test]
 fn test_from_bytes() {
     let mut a = Fp([
@@ -939,6 +1230,76 @@ fn test_inversion() {
     assert!(bool::from(Fp::zero().invert().is_none()));
 }
 
+#[test]
+fn test_invert_vartime() {
+    use rand_core::SeedableRng;
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+
+    assert!(bool::from(Fp::zero().invert_vartime().is_none()));
+    assert_eq!(Fp::one().invert_vartime().unwrap(), Fp::one());
+    assert_eq!((-Fp::one()).invert_vartime().unwrap(), -Fp::one());
+    for _ in 0..100 {
+        let a = Fp::random(&mut rng);
+        assert_eq!(a.invert_vartime().unwrap(), a.invert().unwrap());
+    }
+}
+
+#[test]
+fn test_batch_invert() {
+    let mut values = [Fp::zero(); 6];
+    for (i, value) in values.iter_mut().enumerate() {
+        *value = Fp::from_raw_unchecked([i as u64 + 2, 1, 2, 3, 4, 5]);
+    }
+    values[2] = Fp::zero();
+    let mut inverses = values;
+    Fp::batch_invert(&mut inverses);
+    for (value, inverse) in values.iter().zip(inverses.iter()) {
+        assert_eq!(*inverse, value.invert().unwrap_or(Fp::zero()));
+    }
+
+    Fp::batch_invert::<0>(&mut []);
+    let mut zeros = [Fp::zero(); 2];
+    Fp::batch_invert(&mut zeros);
+    assert_eq!(zeros, [Fp::zero(); 2]);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_batch_invert_parallel() {
+    let mut values = [Fp::zero(); 13];
+    for (i, value) in values.iter_mut().enumerate() {
+        *value = Fp::from_raw_unchecked([i as u64 + 2, 1, 2, 3, 4, 5]);
+    }
+    values[2] = Fp::zero();
+    values[12] = Fp::zero();
+    let mut expected = values;
+    for value in expected.iter_mut() {
+        *value = value.invert().unwrap_or(Fp::zero());
+    }
+
+    for threads in [0, 1, 2, 5, 13, 20] {
+        let mut inverses = values;
+        Fp::batch_invert_parallel(&mut inverses, threads);
+        assert_eq!(inverses, expected);
+    }
+    Fp::batch_invert_parallel(&mut [], 3);
+    let mut zeros = [Fp::zero(); 3];
+    Fp::batch_invert_parallel(&mut zeros, 2);
+    assert_eq!(zeros, [Fp::zero(); 3]);
+}
+
+#[test]
+fn test_sgn0() {
+    assert!(!bool::from(Fp::zero().sgn0()));
+    assert!(bool::from(Fp::one().sgn0()));
+    assert!(!bool::from((-Fp::one()).sgn0()));
+    assert!(!bool::from((Fp::one() + Fp::one()).sgn0()));
+}
+
 #[test]
 fn test_lexicographic_largest() {
     assert!(!bool::from(Fp::zero().lexicographically_largest()));
@@ -987,3 +1348,40 @@ fn test_zeroize() {
     a.zeroize();
     assert!(bool::from(a.is_zero()));
 }
+
+#[test]
+fn test_batch_operations() {
+    use rand_core::SeedableRng;
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    let a = (0..9)
+        .map(|_| Fp::random(&mut rng))
+        .collect::<std::vec::Vec<_>>();
+    let b = (0..9)
+        .map(|_| Fp::random(&mut rng))
+        .collect::<std::vec::Vec<_>>();
+
+    // Cover batches that are shorter, as long, and longer than the lanes.
+    for len in [0, 3, 4, 9] {
+        let (a, b) = (&a[..len], &b[..len]);
+
+        let mut c = a.to_vec();
+        Fp::mul_assign_batch(&mut c, b);
+        assert!(c.iter().zip(a.iter().zip(b)).all(|(c, (a, b))| *c == a * b));
+
+        let mut c = a.to_vec();
+        Fp::add_assign_batch(&mut c, b);
+        assert!(c.iter().zip(a.iter().zip(b)).all(|(c, (a, b))| *c == a + b));
+
+        let mut c = a.to_vec();
+        Fp::sub_assign_batch(&mut c, b);
+        assert!(c.iter().zip(a.iter().zip(b)).all(|(c, (a, b))| *c == a - b));
+
+        let mut c = a.to_vec();
+        Fp::square_batch(&mut c);
+        assert!(c.iter().zip(a).all(|(c, a)| *c == a.square()));
+    }
+}
@@ -1,7 +1,10 @@
+//! This module implements arithmetic over the degree-12 extension field Fp12.
+
 use crate::fp::*;
 use crate::fp2::*;
 use crate::fp6::*;
 use crate::scalar::MODULUS;
+use crate::{BLS_X, BLS_X_IS_NEGATIVE};
 
 use core::fmt;
 use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
@@ -12,7 +15,9 @@ use rand_core::RngCore;
 
 /// This represents an element $c_0 + c_1 w$ of $\mathbb{F}_{p^12} = \mathbb{F}_{p^6} / w^2 - v$.
 pub struct Fp12 {
+    /// The coefficient $c_0$.
     pub c0: Fp6,
+    /// The coefficient $c_1$ of $w$.
     pub c1: Fp6,
 }
 
@@ -43,6 +48,12 @@ impl From<Fp6> for Fp12 {
     }
 }
 
+impl From<u64> for Fp12 {
+    fn from(val: u64) -> Fp12 {
+        Fp12::from(Fp::from(val))
+    }
+}
+
 impl PartialEq for Fp12 {
     fn eq(&self, other: &Fp12) -> bool {
         self.ct_eq(other).into()
@@ -90,6 +101,7 @@ impl ConstantTimeEq for Fp12 {
 }
 
 impl Fp12 {
+    /// Returns zero, the additive identity.
     #[inline]
     pub fn zero() -> Self {
         Fp12 {
@@ -98,6 +110,7 @@ impl Fp12 {
         }
     }
 
+    /// Returns one, the multiplicative identity.
     #[inline]
     pub fn one() -> Self {
         Fp12 {
@@ -114,6 +127,8 @@ impl Fp12 {
         }
     }
 
+    /// Multiplies this element by the sparse element $c_0 + c_1 v + c_4 v w$, in
+    /// the form of a line evaluation in the Miller loop.
     pub fn mul_by_014(&self, c0: &Fp2, c1: &Fp2, c4: &Fp2) -> Fp12 {
         let aa = self.c0.mul_by_01(c0, c1);
         let bb = self.c1.mul_by_1(c4);
@@ -128,11 +143,13 @@ impl Fp12 {
         Fp12 { c0, c1 }
     }
 
+    /// Returns whether or not this element is zero.
     #[inline(always)]
     pub fn is_zero(&self) -> Choice {
         self.c0.is_zero() & self.c1.is_zero()
     }
 
+    /// Returns the conjugate $c_0 - c_1 w$ of this element.
     #[inline(always)]
     pub fn conjugate(&self) -> Self {
         Fp12 {
@@ -171,6 +188,7 @@ impl Fp12 {
         Fp12 { c0, c1 }
     }
 
+    /// Squares this element.
     #[inline]
     pub fn square(&self) -> Self {
         let ab = self.c0 * self.c1;
@@ -185,6 +203,122 @@ impl Fp12 {
         Fp12 { c0, c1 }
     }
 
+    /// Squares this element, which must be in the cyclotomic subgroup of order
+    /// $p^4 - p^2 + 1$, such as the elements of $\mathbb{G}_T$. The result is
+    /// unspecified for other elements.
+    #[must_use]
+    pub fn cyclotomic_square(&self) -> Self {
+        // Adaptation of Algorithm 5.5.4, Guide to Pairing-Based Cryptography
+        // Faster Squaring in the Cyclotomic Subgroup of Sixth Degree Extensions
+        // https://eprint.iacr.org/2009/565.pdf
+        let mut z0 = self.c0.c0;
+        let mut z4 = self.c0.c1;
+        let mut z3 = self.c0.c2;
+        let mut z2 = self.c1.c0;
+        let mut z1 = self.c1.c1;
+        let mut z5 = self.c1.c2;
+
+        let (t0, t1) = fp4_square(z0, z1);
+
+        // For A
+        z0 = t0 - z0;
+        z0 = z0 + z0 + t0;
+
+        z1 = t1 + z1;
+        z1 = z1 + z1 + t1;
+
+        let (mut t0, t1) = fp4_square(z2, z3);
+        let (t2, t3) = fp4_square(z4, z5);
+
+        // For C
+        z4 = t0 - z4;
+        z4 = z4 + z4 + t0;
+
+        z5 = t1 + z5;
+        z5 = z5 + z5 + t1;
+
+        // For B
+        t0 = t3.mul_by_nonresidue();
+        z2 = t0 + z2;
+        z2 = z2 + z2 + t0;
+
+        z3 = t2 - z3;
+        z3 = z3 + z3 + t2;
+
+        Fp12 {
+            c0: Fp6 {
+                c0: z0,
+                c1: z4,
+                c2: z3,
+            },
+            c1: Fp6 {
+                c0: z2,
+                c1: z1,
+                c2: z5,
+            },
+        }
+    }
+
+    /// Squares this element `n` times, computing $f^{2^n}$, for an element of the
+    /// cyclotomic subgroup as in [`Fp12::cyclotomic_square`].
+    ///
+    /// Long chains use the compressed squaring of [Karabina], which keeps four of
+    /// the six coefficients and is cheaper than [`Fp12::cyclotomic_square`], at the
+    /// cost of an inversion in $\mathbb{F}_{p^2}$ to recover the others at the end.
+    /// Shorter chains, for which the inversion would dominate, use
+    /// [`Fp12::cyclotomic_square`]. The running time depends only on `n`.
+    ///
+    /// [Karabina]: https://eprint.iacr.org/2010/542.pdf
+    #[must_use]
+    pub fn cyclotomic_square_n(&self, n: usize) -> Self {
+        // The number of squarings from which the compressed ones are faster.
+        const COMPRESSED_THRESHOLD: usize = 64;
+
+        if n < COMPRESSED_THRESHOLD {
+            let mut f = *self;
+            for _ in 0..n {
+                f = f.cyclotomic_square();
+            }
+            return f;
+        }
+
+        let mut g = CompressedCyclotomic::from(self);
+        for _ in 0..n {
+            g = g.square();
+        }
+        g.decompress()
+    }
+
+    /// Raises this element to the power of the BLS parameter
+    /// $x = -\texttt{0xd201000000010000}$, using [`Fp12::cyclotomic_square`]. As for
+    /// that function, this element must be in the cyclotomic subgroup.
+    #[must_use]
+    pub fn cyclotomic_exp_by_x(&self) -> Self {
+        let x = BLS_X;
+        let mut tmp = Fp12::one();
+        let mut found_one = false;
+        for i in (0..64).rev().map(|b| ((x >> b) & 1) == 1) {
+            if found_one {
+                tmp = tmp.cyclotomic_square()
+            } else {
+                found_one = i;
+            }
+
+            if i {
+                tmp *= self;
+            }
+        }
+
+        // The inverse of an element of the cyclotomic subgroup is its conjugate.
+        if BLS_X_IS_NEGATIVE {
+            tmp.conjugate()
+        } else {
+            tmp
+        }
+    }
+
+    /// Computes the multiplicative inverse of this element, failing if the element
+    /// is zero.
     pub fn invert(&self) -> CtOption<Self> {
         (self.c0.square() - self.c1.square().mul_by_nonresidue())
             .invert()
@@ -237,6 +371,7 @@ impl Fp12 {
 
     /// Converts an element of `Fp12` into a byte representation in
     /// big-endian byte order.
+    #[allow(clippy::wrong_self_convention)]
     pub fn to_bytes(&self) -> [u8; 576] {
         let mut res = [0; 576];
 
@@ -250,7 +385,7 @@ impl Fp12 {
     pub fn is_element(&self) -> Choice {
         // The exponent is a constant,
         // thus this operation is constant time as well.
-        let modulus_pow = self.pow_vartime(&<[u64; 4]>::from(&MODULUS));
+        let modulus_pow = self.pow_vartime(&MODULUS.0);
 
         // Any field of characteristic p has at most one subgroup
         // of order q so it suffices to check that raising the
@@ -260,6 +395,118 @@ impl Fp12 {
     }
 }
 
+/// An element $g_0 + g_1 v + g_2 v^2 + (g_3 + g_4 v + g_5 v^2) w$ of the cyclotomic
+/// subgroup, compressed to $(g_1, g_2, g_3, g_5)$, from which the other coefficients
+/// can be recovered.
+#[derive(Clone, Copy)]
+struct CompressedCyclotomic {
+    g1: Fp2,
+    g2: Fp2,
+    g3: Fp2,
+    g5: Fp2,
+}
+
+impl<'a> From<&'a Fp12> for CompressedCyclotomic {
+    fn from(f: &'a Fp12) -> Self {
+        CompressedCyclotomic {
+            g1: f.c0.c1,
+            g2: f.c0.c2,
+            g3: f.c1.c0,
+            g5: f.c1.c2,
+        }
+    }
+}
+
+impl CompressedCyclotomic {
+    /// Squares this element, with the formulas of section 3.2 of Karabina's paper
+    /// adapted to this tower.
+    #[must_use]
+    fn square(&self) -> Self {
+        let CompressedCyclotomic { g1, g2, g3, g5 } = *self;
+
+        let g1_squared = g1.square();
+        let g2_squared = g2.square();
+        let g3_squared = g3.square();
+        let g5_squared = g5.square();
+        let g1_g5 = (g1 + g5).square() - g1_squared - g5_squared;
+        let g2_g3 = (g2 + g3).square() - g2_squared - g3_squared;
+
+        // h1 = 3 (g3^2 + nr g2^2) - 2 g1
+        let t = g3_squared + g2_squared.mul_by_nonresidue();
+        let h1 = t + t + t - g1 - g1;
+        // h2 = 3 (g1^2 + nr g5^2) - 2 g2
+        let t = g1_squared + g5_squared.mul_by_nonresidue();
+        let h2 = t + t + t - g2 - g2;
+        // h3 = 6 nr g1 g5 + 2 g3
+        let t = g1_g5.mul_by_nonresidue();
+        let h3 = t + t + t + g3 + g3;
+        // h5 = 6 g2 g3 + 2 g5
+        let h5 = g2_g3 + g2_g3 + g2_g3 + g5 + g5;
+
+        CompressedCyclotomic {
+            g1: h1,
+            g2: h2,
+            g3: h3,
+            g5: h5,
+        }
+    }
+
+    /// Recovers the element of the cyclotomic subgroup.
+    fn decompress(&self) -> Fp12 {
+        let CompressedCyclotomic { g1, g2, g3, g5 } = *self;
+
+        // If g3 is nonzero, g4 = (nr g5^2 + 3 g1^2 - 2 g2) / (4 g3), and otherwise
+        // g4 = 2 g1 g5 / g2.
+        let g3_is_zero = g3.is_zero();
+        let g1_squared = g1.square();
+        let num = Fp2::conditional_select(
+            &(g5.square().mul_by_nonresidue() + g1_squared + g1_squared + g1_squared - g2 - g2),
+            &((g1 + g1) * g5),
+            g3_is_zero,
+        );
+        let den = Fp2::conditional_select(&(g3 + g3 + g3 + g3), &g2, g3_is_zero);
+        let g4 = num * den.invert().unwrap_or(Fp2::zero());
+
+        // g0 = nr (2 g4^2 + g3 g5 - 3 g1 g2) + 1
+        let g1_g2 = g1 * g2;
+        let g4_squared = g4.square();
+        let t = g4_squared + g4_squared + g3 * g5 - g1_g2 - g1_g2 - g1_g2;
+        let g0 = t.mul_by_nonresidue() + Fp2::one();
+
+        // Only the identity has g2 = g3 = 0.
+        Fp12::conditional_select(
+            &Fp12 {
+                c0: Fp6 {
+                    c0: g0,
+                    c1: g1,
+                    c2: g2,
+                },
+                c1: Fp6 {
+                    c0: g3,
+                    c1: g4,
+                    c2: g5,
+                },
+            },
+            &Fp12::one(),
+            g3_is_zero & g2.is_zero(),
+        )
+    }
+}
+
+#[must_use]
+fn fp4_square(a: Fp2, b: Fp2) -> (Fp2, Fp2) {
+    let t0 = a.square();
+    let t1 = b.square();
+    let mut t2 = t1.mul_by_nonresidue();
+    let c0 = t2 + t0;
+    t2 = a + b;
+    t2 = t2.square();
+    t2 -= t0;
+    let c1 = t2 - t1;
+
+    (c0, c1)
+}
+
 impl<'a, 'b> Mul<&'b Fp12> for &'a Fp12 {
     type Output = Fp12;
 
@@ -324,8 +571,47 @@ impl<'a, 'b> Sub<&'b Fp12> for &'a Fp12 {
     }
 }
 
+impl<'a, 'b> Mul<&'b Fp> for &'a Fp12 {
+    type Output = Fp12;
+
+    #[inline]
+    fn mul(self, rhs: &'b Fp) -> Self::Output {
+        Fp12 {
+            c0: self.c0 * rhs,
+            c1: self.c1 * rhs,
+        }
+    }
+}
+
+impl<'a, 'b> Mul<&'b Fp2> for &'a Fp12 {
+    type Output = Fp12;
+
+    #[inline]
+    fn mul(self, rhs: &'b Fp2) -> Self::Output {
+        Fp12 {
+            c0: self.c0 * rhs,
+            c1: self.c1 * rhs,
+        }
+    }
+}
+
+impl<'a, 'b> Mul<&'b Fp6> for &'a Fp12 {
+    type Output = Fp12;
+
+    #[inline]
+    fn mul(self, rhs: &'b Fp6) -> Self::Output {
+        Fp12 {
+            c0: self.c0 * rhs,
+            c1: self.c1 * rhs,
+        }
+    }
+}
+
 impl_binops_additive!(Fp12, Fp12);
 impl_binops_multiplicative!(Fp12, Fp12);
+impl_binops_multiplicative!(Fp12, Fp);
+impl_binops_multiplicative!(Fp12, Fp2);
+impl_binops_multiplicative!(Fp12, Fp6);
 
 #[test]
 fn test_arithmetic() {
@@ -714,6 +1000,31 @@ fn test_arithmetic() {
     );
 }
 
+#[test]
+fn test_mul_by_subfield() {
+    use rand_core::SeedableRng;
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    for _ in 0..10 {
+        let a = Fp12::random(&mut rng);
+        let b = Fp::random(&mut rng);
+        let c = Fp2::random(&mut rng);
+        let d = Fp6::random(&mut rng);
+        assert_eq!(a * b, a * Fp12::from(b));
+        assert_eq!(a * c, a * Fp12::from(c));
+        assert_eq!(a * d, a * Fp12::from(d));
+        let mut e = a;
+        e *= b;
+        e *= c;
+        e *= d;
+        assert_eq!(e, a * b * c * d);
+    }
+    assert_eq!(Fp12::from(7) * Fp6::from(6), Fp12::from(42));
+}
+
 #[cfg(feature = "zeroize")]
 #[test]
 fn test_zeroize() {
@@ -2,14 +2,30 @@
 
 use core::fmt;
 use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+#[cfg(any(test, feature = "groups"))]
 use rand_core::RngCore;
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
 use crate::fp::Fp;
-
+use crate::Error;
+
+/// 1 / 2 in Montgomery form.
+pub(crate) const TWO_INV: Fp = Fp::from_raw_unchecked([
+    0x1804_0000_0001_5554,
+    0x8550_0005_3ab0_0001,
+    0x633c_b57c_253c_276f,
+    0x6e22_d1ec_31eb_b502,
+    0xd391_6126_f2d1_4ca2,
+    0x17fb_b857_1a00_6596,
+]);
+
+/// Represents an element of the quadratic extension field $\mathbb{F}_{p^2} = \mathbb{F}_p(u) / (u^2 + 1)$
+/// as $c_0 + c_1 u$.
 #[derive(Copy, Clone)]
 pub struct Fp2 {
+    /// The coefficient $c_0$.
     pub c0: Fp,
+    /// The coefficient $c_1$ of $u$.
     pub c1: Fp,
 }
 
@@ -37,6 +53,12 @@ impl From<Fp> for Fp2 {
     }
 }
 
+impl From<u64> for Fp2 {
+    fn from(val: u64) -> Fp2 {
+        Fp2::from(Fp::from(val))
+    }
+}
+
 impl ConstantTimeEq for Fp2 {
     fn ct_eq(&self, other: &Self) -> Choice {
         self.c0.ct_eq(&other.c0) & self.c1.ct_eq(&other.c1)
@@ -105,10 +127,24 @@ impl<'a, 'b> Mul<&'b Fp2> for &'a Fp2 {
     }
 }
 
+impl<'a, 'b> Mul<&'b Fp> for &'a Fp2 {
+    type Output = Fp2;
+
+    #[inline]
+    fn mul(self, rhs: &'b Fp) -> Fp2 {
+        Fp2 {
+            c0: self.c0 * rhs,
+            c1: self.c1 * rhs,
+        }
+    }
+}
+
 impl_binops_additive!(Fp2, Fp2);
 impl_binops_multiplicative!(Fp2, Fp2);
+impl_binops_multiplicative!(Fp2, Fp);
 
 impl Fp2 {
+    /// Returns zero, the additive identity.
     #[inline]
     pub const fn zero() -> Fp2 {
         Fp2 {
@@ -117,6 +153,7 @@ impl Fp2 {
         }
     }
 
+    /// Returns one, the multiplicative identity.
     #[inline]
     pub const fn one() -> Fp2 {
         Fp2 {
@@ -125,10 +162,12 @@ impl Fp2 {
         }
     }
 
+    /// Returns whether or not this element is zero.
     pub fn is_zero(&self) -> Choice {
         self.c0.is_zero() & self.c1.is_zero()
     }
 
+    #[cfg(any(test, feature = "groups"))]
     pub(crate) fn random(mut rng: impl RngCore) -> Fp2 {
         Fp2 {
             c0: Fp::random(&mut rng),
@@ -144,6 +183,7 @@ impl Fp2 {
         self.conjugate()
     }
 
+    /// Returns the conjugate $c_0 - c_1 u$ of this element.
     #[inline(always)]
     pub fn conjugate(&self) -> Self {
         Fp2 {
@@ -152,6 +192,7 @@ impl Fp2 {
         }
     }
 
+    /// Multiplies this element by the quadratic non-residue $u + 1$.
     #[inline(always)]
     pub fn mul_by_nonresidue(&self) -> Fp2 {
         // Multiply a + bu by u + 1, getting
@@ -165,6 +206,16 @@ impl Fp2 {
         }
     }
 
+    /// Returns the "sign" of this element as defined in
+    /// [section 4.1 of RFC 9380](https://www.rfc-editor.org/rfc/rfc9380#section-4.1):
+    /// the sign of $c_0$, or of $c_1$ if $c_0$ is zero.
+    pub fn sgn0(&self) -> Choice {
+        let sign_0 = self.c0.sgn0();
+        let zero_0 = self.c0.is_zero();
+        let sign_1 = self.c1.sgn0();
+        sign_0 | (zero_0 & sign_1)
+    }
+
     /// Returns whether or not this element is strictly lexicographically
     /// larger than its negation.
     #[inline]
@@ -179,6 +230,7 @@ impl Fp2 {
             | (self.c1.is_zero() & self.c0.lexicographically_largest())
     }
 
+    /// Squares this element.
     pub const fn square(&self) -> Fp2 {
         // Complex squaring:
         //
@@ -202,6 +254,7 @@ impl Fp2 {
         }
     }
 
+    /// Multiplies `rhs` by `self`, returning the result.
     pub fn mul(&self, rhs: &Fp2) -> Fp2 {
         // F_{p^2} x F_{p^2} multiplication implemented with operand scanning (schoolbook)
         // computes the result as:
@@ -221,6 +274,7 @@ impl Fp2 {
         }
     }
 
+    /// Adds `rhs` to `self`, returning the result.
     pub const fn add(&self, rhs: &Fp2) -> Fp2 {
         Fp2 {
             c0: (&self.c0).add(&rhs.c0),
@@ -228,6 +282,7 @@ impl Fp2 {
         }
     }
 
+    /// Subtracts `rhs` from `self`, returning the result.
     pub const fn sub(&self, rhs: &Fp2) -> Fp2 {
         Fp2 {
             c0: (&self.c0).sub(&rhs.c0),
@@ -235,6 +290,7 @@ impl Fp2 {
         }
     }
 
+    /// Negates `self`.
     pub const fn neg(&self) -> Fp2 {
         Fp2 {
             c0: (&self.c0).neg(),
@@ -242,55 +298,45 @@ impl Fp2 {
         }
     }
 
+    /// Computes the square root of this element, if it exists.
     pub fn sqrt(&self) -> CtOption<Self> {
-        // Algorithm 9, https://eprint.iacr.org/2012/685.pdf
-        // with constant time modifications.
-
-        CtOption::new(Fp2::zero(), self.is_zero()).or_else(|| {
-            // a1 = self^((p - 3) / 4)
-            let a1 = self.pow_vartime(&[
-                0xee7f_bfff_ffff_eaaa,
+        // We use the "complex method", as p = 3 (mod 4), which reduces the
+        // square root in Fp2 to two square roots and an inversion in Fp.
+        // A square root x0 + x1 u of a0 + a1 u satisfies x0^2 - x1^2 = a0 and
+        // 2 x0 x1 = a1, so that x0^2 + x1^2 = s, where s^2 = a0^2 + a1^2, and
+        // x0^2 = (a0 + s) / 2.
+
+        // The norm of a square in Fp2 is a square in Fp.
+        (self.c0.square() + self.c1.square()).sqrt().and_then(|s| {
+            // t = (a0 + s) / 2. This is zero only if a1 = 0 and s = -a0,
+            // in which case we use the other root, for t = a0.
+            let t = (self.c0 + s) * TWO_INV;
+            let t = Fp::conditional_select(&t, &self.c0, t.is_zero());
+
+            // x = t^((p + 1) / 4) is the square root of t if t is a
+            // square, and of -t otherwise, since -1 is nonsquare in Fp.
+            let x = t.pow_vartime(&[
+                0xee7f_bfff_ffff_eaab,
                 0x07aa_ffff_ac54_ffff,
                 0xd9cc_34a8_3dac_3d89,
                 0xd91d_d2e1_3ce1_44af,
                 0x92c6_e9ed_90d2_eb35,
                 0x0680_447a_8e5f_f9a6,
             ]);
+            let t_is_square = x.square().ct_eq(&t);
 
-            // alpha = a1^2 * self = self^((p - 3) / 2 + 1) = self^((p - 1) / 2)
-            let alpha = a1.square() * self;
-
-            // x0 = self^((p + 1) / 4)
-            let x0 = a1 * self;
-
-            // In the event that alpha = -1, the element is order p - 1 and so
-            // we're just trying to get the square of an element of the subfield
-            // Fp. This is given by x0 * u, since u = sqrt(-1). Since the element
-            // x0 = a + bu has b = 0, the solution is therefore au.
-            CtOption::new(
-                Fp2 {
-                    c0: -x0.c1,
-                    c1: x0.c0,
-                },
-                alpha.ct_eq(&(&Fp2::one()).neg()),
-            )
-            // Otherwise, the correct solution is (1 + alpha)^((q - 1) // 2) * x0
-            .or_else(|| {
-                CtOption::new(
-                    (alpha + Fp2::one()).pow_vartime(&[
-                        0xdcff_7fff_ffff_d555,
-                        0x0f55_ffff_58a9_ffff,
-                        0xb398_6950_7b58_7b12,
-                        0xb23b_a5c2_79c2_895f,
-                        0x258d_d3db_21a5_d66b,
-                        0x0d00_88f5_1cbf_f34d,
-                    ]) * x0,
-                    Choice::from(1),
-                )
-            })
-            // Only return the result if it's really the square root (and so
-            // self is actually quadratic nonresidue)
-            .and_then(|sqrt| CtOption::new(sqrt, sqrt.square().ct_eq(self)))
+            // y = a1 / (2 x), which is zero when self is zero.
+            let y = self.c1 * (x + x).invert().unwrap_or(Fp::zero());
+
+            // If t is a square, the root is x + y u. Otherwise,
+            // (a0 - s) / 2 = -a1^2 / (4 t) = y^2 is, and the root is y + x u.
+            let (mut x, mut y) = (x, y);
+            Fp::conditional_swap(&mut x, &mut y, !t_is_square);
+            let sqrt = Fp2 { c0: x, c1: y };
+
+            // Only return the result if it's really the square root (and
+            // so self is actually quadratic residue)
+            CtOption::new(sqrt, sqrt.square().ct_eq(self))
         })
     }
 
@@ -367,6 +413,25 @@ impl Fp2 {
         c0.and_then(|c0| c1.map(|c1| Fp2 { c0, c1 }))
     }
 
+    /// Attempts to convert the integers represented in little endian
+    /// by `limbs` into the coefficients $c_0$ and $c_1$ of an `Fp2`,
+    /// failing if either is not canonical. See [`Fp::from_raw`].
+    pub fn from_raw(limbs: [[u64; 6]; 2]) -> CtOption<Fp2> {
+        let c0 = Fp::from_raw(limbs[0]);
+        let c1 = Fp::from_raw(limbs[1]);
+
+        c0.and_then(|c0| c1.map(|c1| Fp2 { c0, c1 }))
+    }
+
+    /// Attempts to convert the integers represented in little endian by `limbs` into
+    /// an `Fp2`, as [`Fp2::from_raw`] does, failing with [`Error::NonCanonicalField`]
+    /// if either is not canonical.
+    ///
+    /// **This is not constant time**; use `from_raw()` for secret inputs.
+    pub fn try_from_raw(limbs: [[u64; 6]; 2]) -> Result<Fp2, Error> {
+        Option::from(Fp2::from_raw(limbs)).ok_or(Error::NonCanonicalField)
+    }
+
     /// Converts an element of `Fp2` into a byte representation in
     /// big-endian byte order.
     pub fn to_bytes(&self) -> [u8; 96] {
@@ -401,6 +466,7 @@ fn test_conditional_selection() {
 }
 
 #[test]
+#[allow(clippy::needless_borrow)]
 fn test_equality() {
     fn is_equal(a: &Fp2, b: &Fp2) -> bool {
         let eq = a == b;
@@ -791,6 +857,75 @@ fn test_sqrt() {
     ));
 }
 
+#[test]
+fn test_sqrt_random() {
+    use rand_core::SeedableRng;
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    let mut nonsquares = 0;
+    for _ in 0..100 {
+        let a = Fp2::random(&mut rng);
+        let b = a.square();
+        let root = b.sqrt().unwrap();
+        assert!(root == a || root == -a);
+
+        // Squares and nonsquares of the subfield, and pure imaginary squares
+        for b in [
+            Fp2::from(a.c0.square()),
+            Fp2::from(-a.c0.square()),
+            Fp2 {
+                c0: Fp::zero(),
+                c1: a.c0.square() + a.c0.square(),
+            },
+        ] {
+            assert_eq!(b.sqrt().unwrap().square(), b);
+        }
+
+        if bool::from(a.sqrt().is_none()) {
+            nonsquares += 1;
+        }
+    }
+    assert!(nonsquares > 25 && nonsquares < 75);
+    assert_eq!(Fp2::zero().sqrt().unwrap(), Fp2::zero());
+}
+
+#[test]
+fn test_mul_by_subfield() {
+    use rand_core::SeedableRng;
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    for _ in 0..10 {
+        let a = Fp2::random(&mut rng);
+        let b = Fp::random(&mut rng);
+        assert_eq!(a * b, a * Fp2::from(b));
+        let mut c = a;
+        c *= b;
+        assert_eq!(c, a * b);
+    }
+    assert_eq!(Fp2::from(7) * Fp::from(6), Fp2::from(42));
+}
+
+#[test]
+fn test_from_raw() {
+    let one = [1, 0, 0, 0, 0, 0];
+    assert_eq!(
+        Fp2::from_raw([one, [2, 0, 0, 0, 0, 0]]).unwrap(),
+        Fp2 {
+            c0: Fp::one(),
+            c1: Fp::one() + Fp::one(),
+        }
+    );
+    assert!(bool::from(
+        Fp2::from_raw([one, [u64::max_value(); 6]]).is_none()
+    ));
+}
+
 #[test]
 fn test_inversion() {
     let a = Fp2 {
@@ -836,6 +971,16 @@ fn test_inversion() {
     assert!(bool::from(Fp2::zero().invert().is_none()));
 }
 
+#[test]
+fn test_sgn0() {
+    let sgn0 = |c0: Fp, c1: Fp| bool::from(Fp2 { c0, c1 }.sgn0());
+    assert!(!sgn0(Fp::zero(), Fp::zero()));
+    assert!(sgn0(Fp::zero(), Fp::one()));
+    assert!(!sgn0(Fp::zero(), -Fp::one()));
+    assert!(sgn0(Fp::one(), -Fp::one()));
+    assert!(!sgn0(-Fp::one(), Fp::one()));
+}
+
 #[test]
 fn test_lexicographic_largest() {
     assert!(!bool::from(Fp2::zero().lexicographically_largest()));
@@ -1,3 +1,5 @@
+//! This module implements arithmetic over the sextic extension field Fp6.
+
 use crate::fp::*;
 use crate::fp2::*;
 
@@ -10,8 +12,11 @@ use rand_core::RngCore;
 
 /// This represents an element $c_0 + c_1 v + c_2 v^2$ of $\mathbb{F}_{p^6} = \mathbb{F}_{p^2} / v^3 - u - 1$.
 pub struct Fp6 {
+    /// The coefficient $c_0$.
     pub c0: Fp2,
+    /// The coefficient $c_1$ of $v$.
     pub c1: Fp2,
+    /// The coefficient $c_2$ of $v^2$.
     pub c2: Fp2,
 }
 
@@ -35,6 +40,12 @@ impl From<Fp2> for Fp6 {
     }
 }
 
+impl From<u64> for Fp6 {
+    fn from(val: u64) -> Fp6 {
+        Fp6::from(Fp::from(val))
+    }
+}
+
 impl PartialEq for Fp6 {
     fn eq(&self, other: &Fp6) -> bool {
         self.ct_eq(other).into()
@@ -83,6 +94,7 @@ impl ConstantTimeEq for Fp6 {
 }
 
 impl Fp6 {
+    /// Returns zero, the additive identity.
     #[inline]
     pub fn zero() -> Self {
         Fp6 {
@@ -92,6 +104,7 @@ impl Fp6 {
         }
     }
 
+    /// Returns one, the multiplicative identity.
     #[inline]
     pub fn one() -> Self {
         Fp6 {
@@ -110,6 +123,7 @@ impl Fp6 {
         }
     }
 
+    /// Multiplies this element by $c_1 v$.
     pub fn mul_by_1(&self, c1: &Fp2) -> Fp6 {
         let b_b = self.c1 * c1;
 
@@ -125,6 +139,7 @@ impl Fp6 {
         }
     }
 
+    /// Multiplies this element by $c_0 + c_1 v$.
     pub fn mul_by_01(&self, c0: &Fp2, c1: &Fp2) -> Fp6 {
         let a_a = self.c0 * c0;
         let b_b = self.c1 * c1;
@@ -204,6 +219,7 @@ impl Fp6 {
         Fp6 { c0, c1, c2 }
     }
 
+    /// Returns whether or not this element is zero.
     #[inline(always)]
     pub fn is_zero(&self) -> Choice {
         self.c0.is_zero() & self.c1.is_zero() & self.c2.is_zero()
@@ -290,6 +306,7 @@ impl Fp6 {
         }
     }
 
+    /// Squares this element.
     #[inline]
     pub fn square(&self) -> Self {
         let s0 = self.c0.square();
@@ -435,6 +452,8 @@ impl Fp6 {
         CtOption::new(a, c1 | c2)
     }
 
+    /// Computes the multiplicative inverse of this element, failing if the element
+    /// is zero.
     #[inline]
     pub fn invert(&self) -> CtOption<Self> {
         let c0 = (self.c1 * self.c2).mul_by_nonresidue();
@@ -498,7 +517,7 @@ impl Fp6 {
             for _ in 0..8 { res = res.square(); }
             res *= lut[((e >> (2 * 8)) & 255u64) as usize];
             for _ in 0..8 { res = res.square(); }
-            res *= lut[((e >> (1 * 8)) & 255u64) as usize];
+            res *= lut[((e >> 8) & 255u64) as usize];
             for _ in 0..8 { res = res.square(); }
             res *= lut[(e  & 255u64) as usize];
         }
@@ -522,8 +541,21 @@ impl Fp6 {
         c0.and_then(|c0| c1.and_then(|c1| c2.map(|c2| Fp6 { c0, c1, c2 })))
     }
 
+    /// Attempts to convert the integers represented in little endian
+    /// by `limbs` into the coefficients of an `Fp6`, ordered as
+    /// $c_0$, $c_1$ and $c_2$ each given by [`Fp2::from_raw`], failing
+    /// if any is not canonical.
+    pub fn from_raw(limbs: [[u64; 6]; 6]) -> CtOption<Fp6> {
+        let c0 = Fp2::from_raw([limbs[0], limbs[1]]);
+        let c1 = Fp2::from_raw([limbs[2], limbs[3]]);
+        let c2 = Fp2::from_raw([limbs[4], limbs[5]]);
+
+        c0.and_then(|c0| c1.and_then(|c1| c2.map(|c2| Fp6 { c0, c1, c2 })))
+    }
+
     /// Converts an element of `Fp6` into a byte representation in
     /// big-endian byte order.
+    #[allow(clippy::wrong_self_convention)]
     pub fn to_bytes(&self) -> [u8; 288] {
         let mut res = [0; 288];
 
@@ -592,8 +624,36 @@ impl<'a, 'b> Sub<&'b Fp6> for &'a Fp6 {
     }
 }
 
+impl<'a, 'b> Mul<&'b Fp> for &'a Fp6 {
+    type Output = Fp6;
+
+    #[inline]
+    fn mul(self, rhs: &'b Fp) -> Self::Output {
+        Fp6 {
+            c0: self.c0 * rhs,
+            c1: self.c1 * rhs,
+            c2: self.c2 * rhs,
+        }
+    }
+}
+
+impl<'a, 'b> Mul<&'b Fp2> for &'a Fp6 {
+    type Output = Fp6;
+
+    #[inline]
+    fn mul(self, rhs: &'b Fp2) -> Self::Output {
+        Fp6 {
+            c0: self.c0 * rhs,
+            c1: self.c1 * rhs,
+            c2: self.c2 * rhs,
+        }
+    }
+}
+
 impl_binops_additive!(Fp6, Fp6);
 impl_binops_multiplicative!(Fp6, Fp6);
+impl_binops_multiplicative!(Fp6, Fp);
+impl_binops_multiplicative!(Fp6, Fp2);
 
 #[test]
 fn test_arithmetic() {
@@ -793,6 +853,41 @@ fn test_zeroize() {
     assert!(bool::from(a.is_zero()));
 }
 
+#[test]
+fn test_from_raw() {
+    let one = [1, 0, 0, 0, 0, 0];
+    let zero = [0; 6];
+    assert_eq!(
+        Fp6::from_raw([one, zero, zero, zero, zero, zero]).unwrap(),
+        Fp6::one()
+    );
+    assert!(bool::from(
+        Fp6::from_raw([one, zero, zero, zero, zero, [u64::max_value(); 6]]).is_none()
+    ));
+}
+
+#[test]
+fn test_mul_by_subfield() {
+    use rand_core::SeedableRng;
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    for _ in 0..10 {
+        let a = Fp6::random(&mut rng);
+        let b = Fp::random(&mut rng);
+        let c = Fp2::random(&mut rng);
+        assert_eq!(a * b, a * Fp6::from(b));
+        assert_eq!(a * c, a * Fp6::from(c));
+        let mut d = a;
+        d *= b;
+        d *= c;
+        assert_eq!(d, a * b * c);
+    }
+    assert_eq!(Fp6::from(7) * Fp2::from(6), Fp6::from(42));
+}
+
 #[test]
 fn test_sqrt() {
     let a = Fp6 {
@@ -1,6 +1,7 @@
 //! This module provides an implementation of the $\mathbb{G}_1$ group of BLS12-381.
 
 use core::borrow::Borrow;
+use core::cmp::Ordering;
 use core::fmt;
 use core::iter::Sum;
 use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
@@ -9,11 +10,13 @@ use group::{
     Curve, Group, GroupEncoding, UncompressedEncoding,
 };
 use rand_core::RngCore;
-use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+use subtle::{Choice, ConditionallyNegatable, ConditionallySelectable, ConstantTimeEq, CtOption};
 
 #[cfg(feature = "alloc")]
 use group::WnafGroup;
 
+use crate::constants::{G1_B as B, G1_GENERATOR_X, G1_GENERATOR_Y};
+use crate::error::{check_encoding, Error};
 use crate::fp::Fp;
 use crate::Scalar;
 
@@ -28,7 +31,7 @@ use crate::Scalar;
 pub struct G1Affine {
     pub(crate) x: Fp,
     pub(crate) y: Fp,
-    infinity: Choice,
+    pub(crate) infinity: Choice,
 }
 
 impl Default for G1Affine {
@@ -40,9 +43,24 @@ impl Default for G1Affine {
 #[cfg(feature = "zeroize")]
 impl zeroize::DefaultIsZeroes for G1Affine {}
 
+/// Formats the compressed encoding in hexadecimal with a `0x` prefix, or the
+/// uncompressed encoding with the alternate flag (`{:#}`).
 impl fmt::Display for G1Affine {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+        f.write_str("0x")?;
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+/// Formats the compressed encoding in hexadecimal, or the uncompressed encoding
+/// with the alternate flag (`{:#x}`).
+impl fmt::LowerHex for G1Affine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            crate::util::write_hex(f, &self.to_uncompressed())
+        } else {
+            crate::util::write_hex(f, &self.to_compressed())
+        }
     }
 }
 
@@ -170,17 +188,59 @@ where
     }
 }
 
-impl_binops_additive!(G1Projective, G1Affine);
+impl_binops_additive_specify_output!(G1Projective, G1Affine, G1Projective);
+
+impl AddAssign<G1Affine> for G1Projective {
+    #[inline]
+    fn add_assign(&mut self, rhs: G1Affine) {
+        self.add_assign_mixed(&rhs);
+    }
+}
+
+impl<'b> AddAssign<&'b G1Affine> for G1Projective {
+    #[inline]
+    fn add_assign(&mut self, rhs: &'b G1Affine) {
+        self.add_assign_mixed(rhs);
+    }
+}
+
+impl SubAssign<G1Affine> for G1Projective {
+    #[inline]
+    fn sub_assign(&mut self, rhs: G1Affine) {
+        self.sub_assign_mixed(&rhs);
+    }
+}
+
+impl<'b> SubAssign<&'b G1Affine> for G1Projective {
+    #[inline]
+    fn sub_assign(&mut self, rhs: &'b G1Affine) {
+        self.sub_assign_mixed(rhs);
+    }
+}
+
 impl_binops_additive_specify_output!(G1Affine, G1Projective, G1Projective);
 
-const B: Fp = Fp::from_raw_unchecked([
-    0xaa27_0000_000c_fff3,
-    0x53cc_0032_fc34_000a,
-    0x478f_e97a_6b0a_807f,
-    0xb1d3_7ebe_e6ba_24d7,
-    0x8ec9_733b_bf78_ab2f,
-    0x09d6_4551_3d83_de7e,
-]);
+/// Decodes the compressed encoding of [`G1Affine::to_compressed`], which must be
+/// exactly 48 bytes long, as [`G1Affine::try_from_compressed`] does.
+///
+/// **This is not constant time**; use `from_compressed()` for secret inputs.
+impl<'a> TryFrom<&'a [u8]> for G1Affine {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<G1Affine, Error> {
+        G1Affine::try_from_compressed(bytes)
+    }
+}
+
+/// The encodings of group elements described in
+/// [`notes::serialization`](crate::notes::serialization).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PointEncoding {
+    /// The compressed encoding, holding the $x$-coordinate and the sign of $y$.
+    Compressed,
+    /// The uncompressed encoding, holding both coordinates.
+    Uncompressed,
+}
 
 impl G1Affine {
     /// Returns the identity of the group: the point at infinity.
@@ -196,22 +256,8 @@ impl G1Affine {
     /// for how this generator is chosen.
     pub fn generator() -> G1Affine {
         G1Affine {
-            x: Fp::from_raw_unchecked([
-                0x5cb3_8790_fd53_0c16,
-                0x7817_fc67_9976_fff5,
-                0x154f_95c7_143b_a1c1,
-                0xf0ae_6acd_f3d0_e747,
-                0xedce_6ecc_21db_f440,
-                0x1201_7741_9e0b_fb75,
-            ]),
-            y: Fp::from_raw_unchecked([
-                0xbaac_93d5_0ce7_2271,
-                0x8c22_631a_7918_fd8e,
-                0xdd59_5f13_5707_25ce,
-                0x51ac_5829_5040_5194,
-                0x0e1c_8c3f_ad00_59c0,
-                0x0bbc_3efc_5008_a26a,
-            ]),
+            x: G1_GENERATOR_X,
+            y: G1_GENERATOR_Y,
             infinity: Choice::from(0u8),
         }
     }
@@ -369,11 +415,8 @@ impl G1Affine {
                 // Recover a y-coordinate given x by y = sqrt(x^3 + 4)
                 ((x.square() * x) + B).sqrt().and_then(|y| {
                     // Switch to the correct y-coordinate if necessary.
-                    let y = Fp::conditional_select(
-                        &y,
-                        &-y,
-                        y.lexicographically_largest() ^ sort_flag_set,
-                    );
+                    let mut y = y;
+                    y.conditional_negate(y.lexicographically_largest() ^ sort_flag_set);
 
                     CtOption::new(
                         G1Affine {
@@ -389,12 +432,136 @@ impl G1Affine {
         })
     }
 
+    /// Recovers the point of $\mathbb{G}_1$ with x-coordinate `x` whose
+    /// y-coordinate, a square root of $x^3 + b$, has the sign `y_is_odd` as defined
+    /// by [`Fp::sgn0`], its parity. This is for protocols that transmit coordinates
+    /// outside of the flagged encodings of [`G1Affine::from_compressed`], whose sort
+    /// flag instead selects the lexicographically largest square root.
+    ///
+    /// Fails if $x^3 + b$ is not a square or if the point is not in the prime-order
+    /// subgroup. The identity has no x-coordinate, and is never returned.
+    pub fn from_x(x: Fp, y_is_odd: Choice) -> CtOption<Self> {
+        ((x.square() * x) + B).sqrt().and_then(|y| {
+            let mut y = y;
+            y.conditional_negate(y.sgn0() ^ y_is_odd);
+
+            let p = G1Affine {
+                x,
+                y,
+                infinity: Choice::from(0u8),
+            };
+            CtOption::new(p, p.is_torsion_free())
+        })
+    }
+
+    /// Decompresses the compressed encoding `bytes` of a point when evaluated in a
+    /// constant, returning its uncompressed encoding, so that points such as the
+    /// generators of protocols or verification keys can be embedded in binaries
+    /// without computing the square root of their decompression at run time.
+    /// `G1Affine` cannot itself be built in a constant, but decoding the result
+    /// with [`G1Affine::from_uncompressed`] is cheap.
+    ///
+    /// ```
+    /// use bls12_381::G1Affine;
+    ///
+    /// const GENERATOR: [u8; 96] = G1Affine::decompress_const(&[
+    ///     0x97, 0xf1, 0xd3, 0xa7, 0x31, 0x97, 0xd7, 0x94, 0x26, 0x95, 0x63, 0x8c,
+    ///     0x4f, 0xa9, 0xac, 0x0f, 0xc3, 0x68, 0x8c, 0x4f, 0x97, 0x74, 0xb9, 0x05,
+    ///     0xa1, 0x4e, 0x3a, 0x3f, 0x17, 0x1b, 0xac, 0x58, 0x6c, 0x55, 0xe8, 0x3f,
+    ///     0xf9, 0x7a, 0x1a, 0xef, 0xfb, 0x3a, 0xf0, 0x0a, 0xdb, 0x22, 0xc6, 0xbb,
+    /// ]);
+    ///
+    /// let generator = G1Affine::from_uncompressed(&GENERATOR).unwrap();
+    /// assert_eq!(generator, G1Affine::generator());
+    /// ```
+    ///
+    /// This checks the encoding and that the point is on the curve, but not that it
+    /// is in the prime-order subgroup, which [`G1Affine::from_uncompressed`] checks.
+    /// It is not constant time.
+    ///
+    /// # Panics
+    ///
+    /// Panics, or fails to compile when evaluated in a constant, if `bytes` is not
+    /// the compressed encoding of a point on the curve.
+    pub const fn decompress_const(bytes: &[u8; 48]) -> [u8; 96] {
+        crate::const_decompress::decompress_g1(bytes)
+    }
+
+    /// Attempts to deserialize a compressed element, reporting why it is invalid if
+    /// it is. See [`notes::serialization`](crate::notes::serialization) for details
+    /// about how group elements are serialized.
+    ///
+    /// **This is not constant time**; use `from_compressed()` for secret inputs.
+    pub fn try_from_compressed(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes = <&[u8; 48]>::try_from(bytes).map_err(|_| Error::InvalidLength)?;
+        check_encoding(bytes, true)?;
+
+        // With valid flags and coordinates, only the y-coordinate recovery can fail.
+        let p = Option::<Self>::from(Self::from_compressed_unchecked(bytes))
+            .ok_or(Error::NotOnCurve)?;
+        if bool::from(p.is_torsion_free()) {
+            Ok(p)
+        } else {
+            Err(Error::WrongSubgroup)
+        }
+    }
+
+    /// Attempts to deserialize an uncompressed element, reporting why it is invalid
+    /// if it is. See [`notes::serialization`](crate::notes::serialization) for
+    /// details about how group elements are serialized.
+    ///
+    /// **This is not constant time**; use `from_uncompressed()` for secret inputs.
+    pub fn try_from_uncompressed(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes = <&[u8; 96]>::try_from(bytes).map_err(|_| Error::InvalidLength)?;
+        check_encoding(bytes, false)?;
+
+        let p = Option::<Self>::from(Self::from_uncompressed_unchecked(bytes))
+            .ok_or(Error::BadFlags)?;
+        if !bool::from(p.is_on_curve()) {
+            Err(Error::NotOnCurve)
+        } else if !bool::from(p.is_torsion_free()) {
+            Err(Error::WrongSubgroup)
+        } else {
+            Ok(p)
+        }
+    }
+
+    /// Attempts to deserialize an element in either the compressed or the
+    /// uncompressed encoding, which is detected from the length of `bytes` and its
+    /// compression flag. Returns the element together with the encoding it was read
+    /// from. See [`notes::serialization`](crate::notes::serialization) for details
+    /// about how group elements are serialized.
+    ///
+    /// **This is not constant time**; use `from_compressed()` or
+    /// `from_uncompressed()` for secret inputs.
+    pub fn from_bytes_any(bytes: &[u8]) -> Result<(Self, PointEncoding), Error> {
+        match bytes.len() {
+            48 => Self::try_from_compressed(bytes).map(|p| (p, PointEncoding::Compressed)),
+            96 => Self::try_from_uncompressed(bytes).map(|p| (p, PointEncoding::Uncompressed)),
+            _ => Err(Error::InvalidLength),
+        }
+    }
+
     /// Returns true if this element is the identity (the point at infinity).
     #[inline]
     pub fn is_identity(&self) -> Choice {
         self.infinity
     }
 
+    /// Negates this element in place.
+    #[inline]
+    pub fn neg_assign(&mut self) {
+        *self = -&*self;
+    }
+
+    /// Negates this element in place if `choice` is true, and leaves it unchanged
+    /// otherwise, in constant time.
+    #[inline]
+    pub fn conditional_negate(&mut self, choice: Choice) {
+        let neg = -&*self;
+        self.conditional_assign(&neg, choice);
+    }
+
     /// Returns true if this point is free of an $h$-torsion component, and so it
     /// exists within the $q$-order subgroup $\mathbb{G}_1$. This should always return true
     /// unless an "unchecked" API was used.
@@ -418,14 +585,7 @@ impl G1Affine {
 }
 
 /// A nontrivial third root of unity in Fp
-pub const BETA: Fp = Fp::from_raw_unchecked([
-    0x30f1_361b_798a_64e8,
-    0xf3b8_ddab_7ece_5a2a,
-    0x16a8_ca3a_c615_77f7,
-    0xc26a_2ff8_74fd_029b,
-    0x3636_b766_6070_1c6e,
-    0x051b_a4ab_241b_6160,
-]);
+pub const BETA: Fp = Fp::ZETA;
 
 fn endomorphism(p: &G1Affine) -> G1Affine {
     // Endomorphism of the points on the curve.
@@ -580,6 +740,31 @@ fn mul_by_3b(a: Fp) -> Fp {
     a + a + a // 12
 }
 
+/// Splits `scalar` into the digits $a, b < x^2$ of its canonical representation in
+/// base $x^2$, least significant first. There are two of them, since the modulus is
+/// $x^4 - x^2 + 1 < x^4$.
+fn split_by_x_squared(scalar: &Scalar) -> [u128; 2] {
+    // Dividing twice by -x gives the digits of base x^2, two at a time.
+    let mut limbs = <[u64; 4]>::from(scalar);
+    let mut digits = [0u64; 4];
+    for digit in digits.iter_mut() {
+        let mut rem = 0u128;
+        for limb in limbs.iter_mut().rev() {
+            let cur = (rem << 64) | u128::from(*limb);
+            *limb = (cur / u128::from(crate::BLS_X)) as u64;
+            rem = cur % u128::from(crate::BLS_X);
+        }
+        *digit = rem as u64;
+    }
+    debug_assert_eq!(limbs, [0; 4]);
+
+    let x = u128::from(crate::BLS_X);
+    [
+        u128::from(digits[0]) + u128::from(digits[1]) * x,
+        u128::from(digits[2]) + u128::from(digits[3]) * x,
+    ]
+}
+
 impl G1Projective {
     /// Returns the identity of the group: the point at infinity.
     pub fn identity() -> G1Projective {
@@ -616,6 +801,13 @@ impl G1Projective {
 
     /// Computes the doubling of this point.
     pub fn double(&self) -> G1Projective {
+        let mut p = *self;
+        p.double_assign();
+        p
+    }
+
+    /// Doubles this point in place.
+    pub fn double_assign(&mut self) {
         // Algorithm 9, https://eprint.iacr.org/2015/1060.pdf
 
         let t0 = self.y.square();
@@ -637,13 +829,11 @@ impl G1Projective {
         let x3 = t0 * t1;
         let x3 = x3 + x3;
 
-        let tmp = G1Projective {
-            x: x3,
-            y: y3,
-            z: z3,
-        };
-
-        G1Projective::conditional_select(&tmp, &G1Projective::identity(), self.is_identity())
+        let is_identity = self.is_identity();
+        self.x = x3;
+        self.y = y3;
+        self.z = z3;
+        self.conditional_assign(&G1Projective::identity(), is_identity);
     }
 
     /// Adds this point to another point.
@@ -693,6 +883,13 @@ impl G1Projective {
 
     /// Adds this point to another point in the affine model.
     pub fn add_mixed(&self, rhs: &G1Affine) -> G1Projective {
+        let mut p = *self;
+        p.add_assign_mixed(rhs);
+        p
+    }
+
+    /// Adds a point in the affine model to this point in place.
+    pub fn add_assign_mixed(&mut self, rhs: &G1Affine) {
         // Algorithm 8, https://eprint.iacr.org/2015/1060.pdf
 
         let t0 = self.x * rhs.x;
@@ -728,39 +925,134 @@ impl G1Projective {
             z: z3,
         };
 
-        G1Projective::conditional_select(&tmp, self, rhs.is_identity())
+        self.conditional_assign(&tmp, !rhs.is_identity());
+    }
+
+    /// Subtracts a point in the affine model from this point in place.
+    pub fn sub_assign_mixed(&mut self, rhs: &G1Affine) {
+        self.add_assign_mixed(&-rhs);
+    }
+
+    /// Multiplies `self` by `scalar` in variable time, which is almost twice as fast
+    /// as multiplying with `*`.
+    ///
+    /// Since the endomorphism $\phi(x, y) = (\beta x, y)$ satisfies $\phi(P) = -x^2 P$
+    /// for every $P \in \mathbb{G}\_1$, the scalar is split into two 128-bit digits
+    /// with $k = a + b x^2$, and $a P + b (-\phi(P))$ is computed with half of the
+    /// doublings. Each digit is recoded in width-5 non-adjacent form, with a table of
+    /// the odd multiples $Q, 3Q, \ldots, 15Q$ of each point $Q \in \{P, -\phi(P)\}$.
+    ///
+    /// The result is only correct if `self` is in $\mathbb{G}\_1$, which holds for
+    /// every point that is not decoded with an `_unchecked` function. The running
+    /// time and memory access pattern depend on `scalar`, so this must only be used
+    /// when both `self` and `scalar` are public, as when verifying signatures or
+    /// proofs.
+    pub fn mul_vartime(&self, scalar: &Scalar) -> G1Projective {
+        const WINDOW: usize = 5;
+        const TABLE_SIZE: usize = 1 << (WINDOW - 2);
+
+        let mut multiples = [*self; TABLE_SIZE];
+        let double = self.double();
+        for i in 1..TABLE_SIZE {
+            multiples[i] = multiples[i - 1] + double;
+        }
+        // The endomorphism maps (X : Y : Z) to (BETA X : Y : Z).
+        let mut tables = [multiples; 2];
+        for q in tables[1].iter_mut() {
+            q.x *= BETA;
+            q.neg_assign();
+        }
+
+        let digits = split_by_x_squared(scalar)
+            .map(|k| Scalar::from_raw([k as u64, (k >> 64) as u64, 0, 0]).wnaf(WINDOW));
+        let mut acc = G1Projective::identity();
+        // The digits have at most 129 signed digits each.
+        for pos in (0..=128).rev() {
+            acc.double_assign();
+            for (digits, table) in digits.iter().zip(tables.iter()) {
+                let digit = digits[pos];
+                match digit.cmp(&0) {
+                    Ordering::Greater => acc += table[(digit / 2) as usize],
+                    Ordering::Less => acc -= table[(-digit / 2) as usize],
+                    Ordering::Equal => {}
+                }
+            }
+        }
+
+        acc
     }
 
     fn multiply(&self, by: &[u8; 32]) -> G1Projective {
+        // This is a fixed-window implementation of point multiplication
+        // with 4-bit windows, moving from most significant to least
+        // significant window of the scalar. Each window's multiple of
+        // `self` is selected by scanning the whole table, so that the
+        // memory access pattern does not depend on the scalar.
+        let mut table = [G1Projective::identity(); 16];
+        let mut multiple = G1Projective::identity();
+        for entry in table.iter_mut().skip(1) {
+            multiple += self;
+            *entry = multiple;
+        }
+
         let mut acc = G1Projective::identity();
+        for byte in by.iter().rev() {
+            for window in [byte >> 4, byte & 0xf] {
+                for _ in 0..4 {
+                    acc.double_assign();
+                }
 
-        // This is a simple double-and-add implementation of point
-        // multiplication, moving from most significant to least
-        // significant bit of the scalar.
-        //
-        // We skip the leading bit because it's always unset for Fq
-        // elements.
-        for bit in by
-            .iter()
-            .rev()
-            .flat_map(|byte| (0..8).rev().map(move |i| Choice::from((byte >> i) & 1u8)))
-            .skip(1)
-        {
-            acc = acc.double();
-            acc = G1Projective::conditional_select(&acc, &(acc + self), bit);
+                let mut addend = G1Projective::identity();
+                for (i, entry) in table.iter().enumerate() {
+                    addend.conditional_assign(entry, (i as u8).ct_eq(&window));
+                }
+                acc += addend;
+            }
         }
 
         acc
     }
 
-    /// Multiply `self` by `crate::BLS_X`, using double and add.
-    fn mul_by_x(&self) -> G1Projective {
+    /// Multiplies `self` by `scalar` with blinding against power and electromagnetic
+    /// side channels, using the randomness of `rng`.
+    ///
+    /// The scalar is split into a random share $r$ and $k - r$, and each share
+    /// multiplies a copy of `self` whose projective coordinates are scaled by a fresh
+    /// random factor, so that neither the scalar nor the intermediate coordinates
+    /// are the same from one call to the next. This is constant time, like the
+    /// multiplication it wraps, and about twice as slow; it is meant for secret
+    /// scalars on devices whose power draw or emissions can be measured.
+    pub fn mul_blinded(&self, scalar: &Scalar, mut rng: impl RngCore) -> G1Projective {
+        let r = <Scalar as ff::Field>::random(&mut rng);
+        self.randomize_coordinates(&mut rng) * r
+            + self.randomize_coordinates(&mut rng) * (scalar - r)
+    }
+
+    /// Scales the projective coordinates of `self` by a random nonzero factor, which
+    /// does not change the point.
+    fn randomize_coordinates(&self, rng: impl RngCore) -> G1Projective {
+        let lambda = Fp::random(rng);
+        let lambda = Fp::conditional_select(&lambda, &Fp::one(), lambda.is_zero());
+        G1Projective {
+            x: self.x * lambda,
+            y: self.y * lambda,
+            z: self.z * lambda,
+        }
+    }
+
+    /// Multiplies `self` by the parameter $x = -\texttt{0xd201000000010000}$ of
+    /// BLS12-381, by doubling and adding along the bits of $|x|$, then negating.
+    ///
+    /// The operations only depend on $x$, so this is constant time. Together with the
+    /// cofactors in [`constants`](crate::constants), this is the building block of
+    /// subgroup checks and cofactor clearing.
+    pub fn mul_by_x(&self) -> G1Projective {
         let mut xself = G1Projective::identity();
         // NOTE: in BLS12-381 we can just skip the first bit.
         let mut x = crate::BLS_X >> 1;
         let mut tmp = *self;
         while x != 0 {
-            tmp = tmp.double();
+            tmp.double_assign();
 
             if x % 2 == 1 {
                 xself += tmp;
@@ -774,6 +1066,29 @@ impl G1Projective {
         xself
     }
 
+    /// Multiplies `self` by the parameter $x$ of BLS12-381, as [`G1Projective::mul_by_x`]
+    /// does, along the bits of $|x|$ from the most significant one, which saves an
+    /// addition, and returning early for the identity.
+    ///
+    /// **This is not constant time**: it is faster for the identity.
+    pub fn mul_by_x_vartime(&self) -> G1Projective {
+        if bool::from(self.is_identity()) {
+            return *self;
+        }
+
+        let mut xself = *self;
+        for i in (0..63).rev() {
+            xself.double_assign();
+            if (crate::BLS_X >> i) & 1 == 1 {
+                xself += self;
+            }
+        }
+        if crate::BLS_X_IS_NEGATIVE {
+            xself = -xself;
+        }
+        xself
+    }
+
     /// Multiplies by $(1 - z)$, where $z$ is the parameter of BLS12-381, which
     /// [suffices to clear](https://ia.cr/2019/403) the cofactor and map
     /// elliptic curve points to elements of $\mathbb{G}\_1$.
@@ -818,12 +1133,64 @@ impl G1Projective {
         }
     }
 
+    /// Converts an array of `N` `G1Projective` elements into `G1Affine` elements, as
+    /// [`G1Projective::batch_normalize`] does, with the length checked at compile
+    /// time.
+    pub fn batch_normalize_array<const N: usize>(p: &[Self; N]) -> [G1Affine; N] {
+        let mut q = [G1Affine::identity(); N];
+        G1Projective::batch_normalize(p, &mut q);
+        q
+    }
+
+    /// Converts a batch of `G1Projective` elements into `G1Affine` elements, as
+    /// [`G1Projective::batch_normalize`] does, on up to `threads` threads. This
+    /// function will panic if `p.len() != q.len()`.
+    ///
+    /// Each thread multiplies the z-coordinates of a contiguous chunk of `p`, the
+    /// products of the chunks are inverted with a single inversion, and each thread
+    /// then recovers the inverses of the z-coordinates of its own chunk. The chunks
+    /// are copied to and from the threads.
+    #[cfg(feature = "parallel")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+    pub fn batch_normalize_parallel(p: &[Self], q: &mut [G1Affine], threads: usize) {
+        assert_eq!(p.len(), q.len());
+
+        let affine = crate::threads::map_inverses(
+            p,
+            threads,
+            |p| p.z,
+            |p, zinv| {
+                let q = G1Affine {
+                    x: p.x * zinv,
+                    y: p.y * zinv,
+                    infinity: Choice::from(0u8),
+                };
+                G1Affine::conditional_select(&q, &G1Affine::identity(), p.is_identity())
+            },
+        );
+        q.copy_from_slice(&affine);
+    }
+
     /// Returns true if this element is the identity (the point at infinity).
     #[inline]
     pub fn is_identity(&self) -> Choice {
         self.z.is_zero()
     }
 
+    /// Negates this element in place.
+    #[inline]
+    pub fn neg_assign(&mut self) {
+        *self = -&*self;
+    }
+
+    /// Negates this element in place if `choice` is true, and leaves it unchanged
+    /// otherwise, in constant time.
+    #[inline]
+    pub fn conditional_negate(&mut self, choice: Choice) {
+        let neg = -&*self;
+        self.conditional_assign(&neg, choice);
+    }
+
     /// Returns true if this point is on the curve. This should always return
     /// true unless an "unchecked" API was used.
     pub fn is_on_curve(&self) -> Choice {
@@ -1071,6 +1438,22 @@ impl UncompressedEncoding for G1Affine {
     }
 }
 
+impl UncompressedEncoding for G1Projective {
+    type Uncompressed = G1Uncompressed;
+
+    fn from_uncompressed(bytes: &Self::Uncompressed) -> CtOption<Self> {
+        G1Affine::from_uncompressed(&bytes.0).map(Self::from)
+    }
+
+    fn from_uncompressed_unchecked(bytes: &Self::Uncompressed) -> CtOption<Self> {
+        G1Affine::from_uncompressed_unchecked(&bytes.0).map(Self::from)
+    }
+
+    fn to_uncompressed(&self) -> Self::Uncompressed {
+        G1Uncompressed(G1Affine::from(self).to_uncompressed())
+    }
+}
+
 #[test]
 fn test_beta() {
     assert_eq!(
@@ -1087,6 +1470,94 @@ fn test_beta() {
     assert_ne!(BETA * BETA, Fp::one());
     assert_eq!(BETA * BETA * BETA, Fp::one());
 }
+
+#[test]
+fn test_zeta_is_eigenvalue() {
+    let p = G1Affine::from(G1Affine::generator() * Scalar::from(0x1234_5678u64));
+    for p in [G1Affine::generator(), p, G1Affine::identity()] {
+        assert_eq!(endomorphism(&p), G1Affine::from(p * Scalar::ZETA));
+    }
+}
+
+#[test]
+fn test_from_x() {
+    let p = G1Affine::from(G1Affine::generator() * Scalar::from(0x1234_5678u64));
+    for p in [G1Affine::generator(), p] {
+        assert_eq!(G1Affine::from_x(p.x, p.y.sgn0()).unwrap(), p);
+        assert_eq!(G1Affine::from_x(p.x, !p.y.sgn0()).unwrap(), -p);
+    }
+
+    // Points of the curve outside of the subgroup, and x-coordinates of no point.
+    let mut on_curve = 0;
+    let mut off_curve = 0;
+    let mut x = Fp::zero();
+    while on_curve < 3 || off_curve < 3 {
+        if bool::from(((x.square() * x) + B).sqrt().is_some()) {
+            on_curve += 1;
+        } else {
+            off_curve += 1;
+        }
+        assert!(bool::from(G1Affine::from_x(x, Choice::from(0u8)).is_none()));
+        assert!(bool::from(G1Affine::from_x(x, Choice::from(1u8)).is_none()));
+        x += Fp::one();
+    }
+}
+
+#[test]
+fn test_decompress_const() {
+    const GENERATOR: [u8; 96] = G1Affine::decompress_const(&[
+        0x97, 0xf1, 0xd3, 0xa7, 0x31, 0x97, 0xd7, 0x94, 0x26, 0x95, 0x63, 0x8c, 0x4f, 0xa9, 0xac,
+        0x0f, 0xc3, 0x68, 0x8c, 0x4f, 0x97, 0x74, 0xb9, 0x05, 0xa1, 0x4e, 0x3a, 0x3f, 0x17, 0x1b,
+        0xac, 0x58, 0x6c, 0x55, 0xe8, 0x3f, 0xf9, 0x7a, 0x1a, 0xef, 0xfb, 0x3a, 0xf0, 0x0a, 0xdb,
+        0x22, 0xc6, 0xbb,
+    ]);
+    const IDENTITY: [u8; 96] = G1Affine::decompress_const(&{
+        let mut bytes = [0; 48];
+        bytes[0] = 0xc0;
+        bytes
+    });
+    assert_eq!(GENERATOR, G1Affine::generator().to_uncompressed());
+    assert_eq!(IDENTITY, G1Affine::identity().to_uncompressed());
+
+    let mut p = G1Projective::generator();
+    for _ in 0..10 {
+        let q = G1Affine::from(p);
+        assert_eq!(
+            G1Affine::decompress_const(&q.to_compressed()),
+            q.to_uncompressed()
+        );
+        assert_eq!(
+            G1Affine::decompress_const(&(-q).to_compressed()),
+            (-q).to_uncompressed()
+        );
+        p = p.double() + G1Projective::generator();
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_decompress_const_not_on_curve() {
+    // An x-coordinate for which x^3 + b is not a square.
+    let mut x = Fp::one();
+    while bool::from(((x.square() * x) + B).sqrt().is_some()) {
+        x += Fp::one();
+    }
+    let p = G1Affine {
+        x,
+        y: Fp::zero(),
+        infinity: Choice::from(0u8),
+    };
+    G1Affine::decompress_const(&p.to_compressed());
+}
+
+#[test]
+#[should_panic]
+fn test_decompress_const_bad_flags() {
+    let mut bytes = G1Affine::generator().to_compressed();
+    bytes[0] &= 0x7f;
+    G1Affine::decompress_const(&bytes);
+}
+
 #[test]
 fn test_is_on_curve() {
     assert!(bool::from(G1Affine::identity().is_on_curve()));
@@ -1519,6 +1990,36 @@ fn test_mixed_addition() {
     }
 }
 
+#[test]
+fn test_in_place_operations() {
+    let p = G1Projective::generator().double();
+    let q = G1Affine::from(G1Projective::generator() * Scalar::from(5));
+
+    let mut a = p;
+    a.double_assign();
+    assert_eq!(a, p.double());
+
+    let mut a = p;
+    a.add_assign_mixed(&q);
+    assert_eq!(a, p.add_mixed(&q));
+    a.sub_assign_mixed(&q);
+    assert_eq!(a, p);
+    a += q;
+    a -= &q;
+    assert_eq!(a, p);
+
+    // The identity is handled on either side.
+    let mut a = G1Projective::identity();
+    a.double_assign();
+    assert!(bool::from(a.is_identity()));
+    a.add_assign_mixed(&q);
+    assert_eq!(a, G1Projective::from(q));
+    a.add_assign_mixed(&G1Affine::identity());
+    assert_eq!(a, G1Projective::from(q));
+    a.sub_assign_mixed(&q);
+    assert!(bool::from(a.is_identity()));
+}
+
 #[test]
 #[allow(clippy::eq_op)]
 fn test_projective_negation_and_subtraction() {
@@ -1534,6 +2035,28 @@ fn test_affine_negation_and_subtraction() {
     assert_eq!(G1Projective::from(a) + (-a), G1Projective::from(a) - a);
 }
 
+#[test]
+fn test_windowed_scalar_multiplication() {
+    let g = G1Projective::generator();
+
+    // Scalars whose windows exercise every table entry and the edges of the
+    // scalar field.
+    let mut naive = G1Projective::identity();
+    for i in 0..=32u64 {
+        assert_eq!(g * Scalar::from(i), naive);
+        naive += g;
+    }
+    assert_eq!(g * -Scalar::one(), -g);
+    assert_eq!(g * Scalar::from_raw([u64::MAX, u64::MAX, 0, 0]), {
+        let lo = g * Scalar::from_raw([u64::MAX, 0, 0, 0]);
+        (lo + g) * Scalar::from_raw([0, 1, 0, 0]) - g
+    });
+    assert_eq!(
+        G1Projective::identity() * Scalar::from(7),
+        G1Projective::identity()
+    );
+}
+
 #[test]
 fn test_projective_scalar_multiplication() {
     let g = G1Projective::generator();
@@ -1574,6 +2097,40 @@ fn test_affine_scalar_multiplication() {
     assert_eq!(G1Affine::from(g * a) * b, g * c);
 }
 
+#[test]
+fn test_mul_vartime() {
+    let g = G1Projective::generator();
+    let a = Scalar::from_raw([
+        0x2b56_8297_a56d_a71c,
+        0xd8c3_9ecb_0ef3_75d1,
+        0x435c_38da_67bf_bf96,
+        0x8088_a050_26b6_59b2,
+    ]);
+    let p = g * a;
+
+    for s in [
+        a,
+        -a,
+        Scalar::zero(),
+        Scalar::one(),
+        -Scalar::one(),
+        Scalar::from(0x0f),
+        Scalar::from(0x10),
+        Scalar::from_raw([u64::MAX, u64::MAX, 0, 0]),
+    ] {
+        assert_eq!(p.mul_vartime(&s), p * s);
+    }
+    let mut s = a;
+    for _ in 0..8 {
+        s = s.square() + a;
+        assert_eq!(p.mul_vartime(&s), p * s);
+    }
+    assert_eq!(
+        G1Projective::identity().mul_vartime(&a),
+        G1Projective::identity()
+    );
+}
+
 #[test]
 fn test_is_torsion_free() {
     let a = G1Affine {
@@ -1615,6 +2172,38 @@ fn test_mul_by_x() {
 
     let point = G1Projective::generator() * Scalar::from(42);
     assert_eq!(point.mul_by_x(), point * x);
+
+    for p in [generator, point, G1Projective::identity()] {
+        assert_eq!(p.mul_by_x_vartime(), p.mul_by_x());
+    }
+}
+
+#[test]
+fn test_mul_blinded() {
+    use rand_core::SeedableRng;
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    let point = G1Projective::generator() * Scalar::from(42);
+    let scalar = <Scalar as ff::Field>::random(&mut rng);
+
+    let blinded = point.mul_blinded(&scalar, &mut rng);
+    assert_eq!(blinded, point * scalar);
+    // The coordinates differ from one call to the next, but not the point.
+    let again = point.mul_blinded(&scalar, &mut rng);
+    assert_eq!(again, blinded);
+    assert_ne!(again.z, blinded.z);
+
+    assert_eq!(
+        G1Projective::identity().mul_blinded(&scalar, &mut rng),
+        G1Projective::identity()
+    );
+    assert_eq!(
+        point.mul_blinded(&Scalar::zero(), &mut rng),
+        G1Projective::identity()
+    );
 }
 
 #[test]
@@ -1701,11 +2290,31 @@ fn test_batch_normalize() {
                 G1Projective::batch_normalize(&v[..], &mut t[..]);
 
                 assert_eq!(&t[..], &expected[..]);
+                assert_eq!(G1Projective::batch_normalize_array(&v), expected);
             }
         }
     }
 }
 
+#[cfg(feature = "parallel")]
+#[test]
+fn test_batch_normalize_parallel() {
+    let mut p = [G1Projective::identity(); 11];
+    for i in 1..p.len() {
+        p[i] = p[i - 1] + G1Projective::generator() * Scalar::from(i as u64);
+    }
+    p[4] = G1Projective::identity();
+    let mut expected = [G1Affine::identity(); 11];
+    G1Projective::batch_normalize(&p, &mut expected);
+
+    for threads in [0, 1, 2, 3, 11, 16] {
+        let mut q = [G1Affine::generator(); 11];
+        G1Projective::batch_normalize_parallel(&p, &mut q, threads);
+        assert_eq!(q, expected);
+    }
+    G1Projective::batch_normalize_parallel(&[], &mut [], 4);
+}
+
 #[cfg(feature = "zeroize")]
 #[test]
 fn test_zeroize() {
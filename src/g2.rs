@@ -1,6 +1,7 @@
 //! This module provides an implementation of the $\mathbb{G}_2$ group of BLS12-381.
 
 use core::borrow::Borrow;
+use core::cmp::Ordering;
 use core::fmt;
 use core::iter::Sum;
 use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
@@ -9,13 +10,16 @@ use group::{
     Curve, Group, GroupEncoding, UncompressedEncoding,
 };
 use rand_core::RngCore;
-use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+use subtle::{Choice, ConditionallyNegatable, ConditionallySelectable, ConstantTimeEq, CtOption};
 
 #[cfg(feature = "alloc")]
 use group::WnafGroup;
 
+use crate::constants::{G2_B as B, G2_GENERATOR_X, G2_GENERATOR_Y};
+use crate::error::{check_encoding, Error};
 use crate::fp::Fp;
 use crate::fp2::Fp2;
+use crate::g1::PointEncoding;
 use crate::Scalar;
 
 /// This is an element of $\mathbb{G}_2$ represented in the affine coordinate space.
@@ -29,7 +33,7 @@ use crate::Scalar;
 pub struct G2Affine {
     pub(crate) x: Fp2,
     pub(crate) y: Fp2,
-    infinity: Choice,
+    pub(crate) infinity: Choice,
 }
 
 impl Default for G2Affine {
@@ -41,9 +45,24 @@ impl Default for G2Affine {
 #[cfg(feature = "zeroize")]
 impl zeroize::DefaultIsZeroes for G2Affine {}
 
+/// Formats the compressed encoding in hexadecimal with a `0x` prefix, or the
+/// uncompressed encoding with the alternate flag (`{:#}`).
 impl fmt::Display for G2Affine {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+        f.write_str("0x")?;
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+/// Formats the compressed encoding in hexadecimal, or the uncompressed encoding
+/// with the alternate flag (`{:#x}`).
+impl fmt::LowerHex for G2Affine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            crate::util::write_hex(f, &self.to_uncompressed())
+        } else {
+            crate::util::write_hex(f, &self.to_compressed())
+        }
     }
 }
 
@@ -171,27 +190,49 @@ where
     }
 }
 
-impl_binops_additive!(G2Projective, G2Affine);
+impl_binops_additive_specify_output!(G2Projective, G2Affine, G2Projective);
+
+impl AddAssign<G2Affine> for G2Projective {
+    #[inline]
+    fn add_assign(&mut self, rhs: G2Affine) {
+        self.add_assign_mixed(&rhs);
+    }
+}
+
+impl<'b> AddAssign<&'b G2Affine> for G2Projective {
+    #[inline]
+    fn add_assign(&mut self, rhs: &'b G2Affine) {
+        self.add_assign_mixed(rhs);
+    }
+}
+
+impl SubAssign<G2Affine> for G2Projective {
+    #[inline]
+    fn sub_assign(&mut self, rhs: G2Affine) {
+        self.sub_assign_mixed(&rhs);
+    }
+}
+
+impl<'b> SubAssign<&'b G2Affine> for G2Projective {
+    #[inline]
+    fn sub_assign(&mut self, rhs: &'b G2Affine) {
+        self.sub_assign_mixed(rhs);
+    }
+}
+
 impl_binops_additive_specify_output!(G2Affine, G2Projective, G2Projective);
 
-const B: Fp2 = Fp2 {
-    c0: Fp::from_raw_unchecked([
-        0xaa27_0000_000c_fff3,
-        0x53cc_0032_fc34_000a,
-        0x478f_e97a_6b0a_807f,
-        0xb1d3_7ebe_e6ba_24d7,
-        0x8ec9_733b_bf78_ab2f,
-        0x09d6_4551_3d83_de7e,
-    ]),
-    c1: Fp::from_raw_unchecked([
-        0xaa27_0000_000c_fff3,
-        0x53cc_0032_fc34_000a,
-        0x478f_e97a_6b0a_807f,
-        0xb1d3_7ebe_e6ba_24d7,
-        0x8ec9_733b_bf78_ab2f,
-        0x09d6_4551_3d83_de7e,
-    ]),
-};
+/// Decodes the compressed encoding of [`G2Affine::to_compressed`], which must be
+/// exactly 96 bytes long, as [`G2Affine::try_from_compressed`] does.
+///
+/// **This is not constant time**; use `from_compressed()` for secret inputs.
+impl<'a> TryFrom<&'a [u8]> for G2Affine {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<G2Affine, Error> {
+        G2Affine::try_from_compressed(bytes)
+    }
+}
 
 const B3: Fp2 = Fp2::add(&Fp2::add(&B, &B), &B);
 
@@ -209,42 +250,8 @@ impl G2Affine {
     /// for how this generator is chosen.
     pub fn generator() -> G2Affine {
         G2Affine {
-            x: Fp2 {
-                c0: Fp::from_raw_unchecked([
-                    0xf5f2_8fa2_0294_0a10,
-                    0xb3f5_fb26_87b4_961a,
-                    0xa1a8_93b5_3e2a_e580,
-                    0x9894_999d_1a3c_aee9,
-                    0x6f67_b763_1863_366b,
-                    0x0581_9192_4350_bcd7,
-                ]),
-                c1: Fp::from_raw_unchecked([
-                    0xa5a9_c075_9e23_f606,
-                    0xaaa0_c59d_bccd_60c3,
-                    0x3bb1_7e18_e286_7806,
-                    0x1b1a_b6cc_8541_b367,
-                    0xc2b6_ed0e_f215_8547,
-                    0x1192_2a09_7360_edf3,
-                ]),
-            },
-            y: Fp2 {
-                c0: Fp::from_raw_unchecked([
-                    0x4c73_0af8_6049_4c4a,
-                    0x597c_fa1f_5e36_9c5a,
-                    0xe7e6_856c_aa0a_635a,
-                    0xbbef_b5e9_6e0d_495f,
-                    0x07d3_a975_f0ef_25a2,
-                    0x0083_fd8e_7e80_dae5,
-                ]),
-                c1: Fp::from_raw_unchecked([
-                    0xadc0_fc92_df64_b05d,
-                    0x18aa_270a_2b14_61dc,
-                    0x86ad_ac6a_3be4_eba0,
-                    0x7949_5c4e_c93d_a33a,
-                    0xe717_5850_a43c_caed,
-                    0x0b2b_c2a1_63de_1bf2,
-                ]),
-            },
+            x: G2_GENERATOR_X,
+            y: G2_GENERATOR_Y,
             infinity: Choice::from(0u8),
         }
     }
@@ -442,11 +449,8 @@ impl G2Affine {
                     // Recover a y-coordinate given x by y = sqrt(x^3 + 4)
                     ((x.square() * x) + B).sqrt().and_then(|y| {
                         // Switch to the correct y-coordinate if necessary.
-                        let y = Fp2::conditional_select(
-                            &y,
-                            &-y,
-                            y.lexicographically_largest() ^ sort_flag_set,
-                        );
+                        let mut y = y;
+                        y.conditional_negate(y.lexicographically_largest() ^ sort_flag_set);
 
                         CtOption::new(
                             G2Affine {
@@ -463,12 +467,122 @@ impl G2Affine {
         })
     }
 
+    /// Recovers the point of $\mathbb{G}_2$ with x-coordinate `x` whose
+    /// y-coordinate, a square root of $x^3 + b$, has the sign `y_is_odd` as defined
+    /// by [`Fp2::sgn0`]. This is for protocols that transmit coordinates
+    /// outside of the flagged encodings of [`G2Affine::from_compressed`], whose sort
+    /// flag instead selects the lexicographically largest square root.
+    ///
+    /// Fails if $x^3 + b$ is not a square or if the point is not in the prime-order
+    /// subgroup. The identity has no x-coordinate, and is never returned.
+    pub fn from_x(x: Fp2, y_is_odd: Choice) -> CtOption<Self> {
+        ((x.square() * x) + B).sqrt().and_then(|y| {
+            let mut y = y;
+            y.conditional_negate(y.sgn0() ^ y_is_odd);
+
+            let p = G2Affine {
+                x,
+                y,
+                infinity: Choice::from(0u8),
+            };
+            CtOption::new(p, p.is_torsion_free())
+        })
+    }
+
+    /// Decompresses the compressed encoding `bytes` of a point when evaluated in a
+    /// constant, returning its uncompressed encoding, so that points such as the
+    /// generators of protocols or verification keys can be embedded in binaries
+    /// without computing the square root of their decompression at run time.
+    /// `G2Affine` cannot itself be built in a constant, but decoding the result
+    /// with [`G2Affine::from_uncompressed`] is cheap.
+    ///
+    /// This checks the encoding and that the point is on the curve, but not that it
+    /// is in the prime-order subgroup, which [`G2Affine::from_uncompressed`] checks.
+    /// It is not constant time.
+    ///
+    /// # Panics
+    ///
+    /// Panics, or fails to compile when evaluated in a constant, if `bytes` is not
+    /// the compressed encoding of a point on the curve.
+    pub const fn decompress_const(bytes: &[u8; 96]) -> [u8; 192] {
+        crate::const_decompress::decompress_g2(bytes)
+    }
+
+    /// Attempts to deserialize a compressed element, reporting why it is invalid if
+    /// it is. See [`notes::serialization`](crate::notes::serialization) for details
+    /// about how group elements are serialized.
+    ///
+    /// **This is not constant time**; use `from_compressed()` for secret inputs.
+    pub fn try_from_compressed(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes = <&[u8; 96]>::try_from(bytes).map_err(|_| Error::InvalidLength)?;
+        check_encoding(bytes, true)?;
+
+        // With valid flags and coordinates, only the y-coordinate recovery can fail.
+        let p = Option::<Self>::from(Self::from_compressed_unchecked(bytes))
+            .ok_or(Error::NotOnCurve)?;
+        if bool::from(p.is_torsion_free()) {
+            Ok(p)
+        } else {
+            Err(Error::WrongSubgroup)
+        }
+    }
+
+    /// Attempts to deserialize an uncompressed element, reporting why it is invalid
+    /// if it is. See [`notes::serialization`](crate::notes::serialization) for
+    /// details about how group elements are serialized.
+    ///
+    /// **This is not constant time**; use `from_uncompressed()` for secret inputs.
+    pub fn try_from_uncompressed(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes = <&[u8; 192]>::try_from(bytes).map_err(|_| Error::InvalidLength)?;
+        check_encoding(bytes, false)?;
+
+        let p = Option::<Self>::from(Self::from_uncompressed_unchecked(bytes))
+            .ok_or(Error::BadFlags)?;
+        if !bool::from(p.is_on_curve()) {
+            Err(Error::NotOnCurve)
+        } else if !bool::from(p.is_torsion_free()) {
+            Err(Error::WrongSubgroup)
+        } else {
+            Ok(p)
+        }
+    }
+
+    /// Attempts to deserialize an element in either the compressed or the
+    /// uncompressed encoding, which is detected from the length of `bytes` and its
+    /// compression flag. Returns the element together with the encoding it was read
+    /// from. See [`notes::serialization`](crate::notes::serialization) for details
+    /// about how group elements are serialized.
+    ///
+    /// **This is not constant time**; use `from_compressed()` or
+    /// `from_uncompressed()` for secret inputs.
+    pub fn from_bytes_any(bytes: &[u8]) -> Result<(Self, PointEncoding), Error> {
+        match bytes.len() {
+            96 => Self::try_from_compressed(bytes).map(|p| (p, PointEncoding::Compressed)),
+            192 => Self::try_from_uncompressed(bytes).map(|p| (p, PointEncoding::Uncompressed)),
+            _ => Err(Error::InvalidLength),
+        }
+    }
+
     /// Returns true if this element is the identity (the point at infinity).
     #[inline]
     pub fn is_identity(&self) -> Choice {
         self.infinity
     }
 
+    /// Negates this element in place.
+    #[inline]
+    pub fn neg_assign(&mut self) {
+        *self = -&*self;
+    }
+
+    /// Negates this element in place if `choice` is true, and leaves it unchanged
+    /// otherwise, in constant time.
+    #[inline]
+    pub fn conditional_negate(&mut self, choice: Choice) {
+        let neg = -&*self;
+        self.conditional_assign(&neg, choice);
+    }
+
     /// Returns true if this point is free of an $h$-torsion component, and so it
     /// exists within the $q$-order subgroup $\mathbb{G}_2$. This should always return true
     /// unless an "unchecked" API was used.
@@ -631,6 +745,26 @@ fn mul_by_3b(x: Fp2) -> Fp2 {
     x * B3
 }
 
+/// Splits `scalar` into the digits $k_i < -x$ of its canonical representation in
+/// base $-x$, least significant first. There are four of them, since the modulus is
+/// $x^4 - x^2 + 1 < x^4$.
+fn split_by_x(scalar: &Scalar) -> [u64; 4] {
+    let mut limbs = <[u64; 4]>::from(scalar);
+    let mut digits = [0u64; 4];
+    for digit in digits.iter_mut() {
+        let mut rem = 0u128;
+        for limb in limbs.iter_mut().rev() {
+            let cur = (rem << 64) | u128::from(*limb);
+            *limb = (cur / u128::from(crate::BLS_X)) as u64;
+            rem = cur % u128::from(crate::BLS_X);
+        }
+        *digit = rem as u64;
+    }
+    debug_assert_eq!(limbs, [0; 4]);
+
+    digits
+}
+
 impl G2Projective {
     /// Returns the identity of the group: the point at infinity.
     pub fn identity() -> G2Projective {
@@ -687,6 +821,13 @@ impl G2Projective {
 
     /// Computes the doubling of this point.
     pub fn double(&self) -> G2Projective {
+        let mut p = *self;
+        p.double_assign();
+        p
+    }
+
+    /// Doubles this point in place.
+    pub fn double_assign(&mut self) {
         // Algorithm 9, https://eprint.iacr.org/2015/1060.pdf
 
         let t0 = self.y.square();
@@ -708,13 +849,11 @@ impl G2Projective {
         let x3 = t0 * t1;
         let x3 = x3 + x3;
 
-        let tmp = G2Projective {
-            x: x3,
-            y: y3,
-            z: z3,
-        };
-
-        G2Projective::conditional_select(&tmp, &G2Projective::identity(), self.is_identity())
+        let is_identity = self.is_identity();
+        self.x = x3;
+        self.y = y3;
+        self.z = z3;
+        self.conditional_assign(&G2Projective::identity(), is_identity);
     }
 
     /// Adds this point to another point.
@@ -764,6 +903,13 @@ impl G2Projective {
 
     /// Adds this point to another point in the affine model.
     pub fn add_mixed(&self, rhs: &G2Affine) -> G2Projective {
+        let mut p = *self;
+        p.add_assign_mixed(rhs);
+        p
+    }
+
+    /// Adds a point in the affine model to this point in place.
+    pub fn add_assign_mixed(&mut self, rhs: &G2Affine) {
         // Algorithm 8, https://eprint.iacr.org/2015/1060.pdf
 
         let t0 = self.x * rhs.x;
@@ -799,26 +945,88 @@ impl G2Projective {
             z: z3,
         };
 
-        G2Projective::conditional_select(&tmp, self, rhs.is_identity())
+        self.conditional_assign(&tmp, !rhs.is_identity());
+    }
+
+    /// Subtracts a point in the affine model from this point in place.
+    pub fn sub_assign_mixed(&mut self, rhs: &G2Affine) {
+        self.add_assign_mixed(&-rhs);
+    }
+
+    /// Multiplies `self` by `scalar` in variable time, which is more than twice as
+    /// fast as multiplying with `*`.
+    ///
+    /// Since $\psi(P) = x P$ for every $P \in \mathbb{G}\_2$, the scalar is split
+    /// into four 64-bit digits $k_i$ with $k = \sum_i k_i (-x)^i$, and
+    /// $\sum_i k_i (-\psi)^i(P)$ is computed with a quarter of the doublings. Each
+    /// digit is recoded in width-5 non-adjacent form, with a table of the odd
+    /// multiples $Q, 3Q, \ldots, 15Q$ of each point $Q = (-\psi)^i(P)$.
+    ///
+    /// The result is only correct if `self` is in $\mathbb{G}\_2$, which holds for
+    /// every point that is not decoded with an `_unchecked` function. The running
+    /// time and memory access pattern depend on `scalar`, so this must only be used
+    /// when both `self` and `scalar` are public, as when verifying signatures or
+    /// proofs.
+    pub fn mul_vartime(&self, scalar: &Scalar) -> G2Projective {
+        const WINDOW: usize = 5;
+        const TABLE_SIZE: usize = 1 << (WINDOW - 2);
+
+        let mut multiples = [*self; TABLE_SIZE];
+        let double = self.double();
+        for i in 1..TABLE_SIZE {
+            multiples[i] = multiples[i - 1] + double;
+        }
+        let mut tables = [multiples; 4];
+        for i in 1..4 {
+            for j in 0..TABLE_SIZE {
+                tables[i][j] = -tables[i - 1][j].psi();
+            }
+        }
+
+        let digits = split_by_x(scalar).map(|k| Scalar::from(k).wnaf(WINDOW));
+        let mut acc = G2Projective::identity();
+        // The digits have at most 65 signed digits each.
+        for pos in (0..=64).rev() {
+            acc.double_assign();
+            for (digits, table) in digits.iter().zip(tables.iter()) {
+                let digit = digits[pos];
+                match digit.cmp(&0) {
+                    Ordering::Greater => acc += table[(digit / 2) as usize],
+                    Ordering::Less => acc -= table[(-digit / 2) as usize],
+                    Ordering::Equal => {}
+                }
+            }
+        }
+
+        acc
     }
 
     fn multiply(&self, by: &[u8]) -> G2Projective {
+        // This is a fixed-window implementation of point multiplication
+        // with 4-bit windows, moving from most significant to least
+        // significant window of the scalar. Each window's multiple of
+        // `self` is selected by scanning the whole table, so that the
+        // memory access pattern does not depend on the scalar.
+        let mut table = [G2Projective::identity(); 16];
+        let mut multiple = G2Projective::identity();
+        for entry in table.iter_mut().skip(1) {
+            multiple += self;
+            *entry = multiple;
+        }
+
         let mut acc = G2Projective::identity();
+        for byte in by.iter().rev() {
+            for window in [byte >> 4, byte & 0xf] {
+                for _ in 0..4 {
+                    acc.double_assign();
+                }
 
-        // This is a simple double-and-add implementation of point
-        // multiplication, moving from most significant to least
-        // significant bit of the scalar.
-        //
-        // We skip the leading bit because it's always unset for Fq
-        // elements.
-        for bit in by
-            .iter()
-            .rev()
-            .flat_map(|byte| (0..8).rev().map(move |i| Choice::from((byte >> i) & 1u8)))
-            .skip(1)
-        {
-            acc = acc.double();
-            acc = G2Projective::conditional_select(&acc, &(acc + self), bit);
+                let mut addend = G2Projective::identity();
+                for (i, entry) in table.iter().enumerate() {
+                    addend.conditional_assign(entry, (i as u8).ct_eq(&window));
+                }
+                acc += addend;
+            }
         }
 
         acc
@@ -891,14 +1099,46 @@ impl G2Projective {
         }
     }
 
-    /// Multiply `self` by `crate::BLS_X`, using double and add.
-    fn mul_by_x(&self) -> G2Projective {
+    /// Multiplies `self` by `scalar` with blinding against power and electromagnetic
+    /// side channels, using the randomness of `rng`.
+    ///
+    /// The scalar is split into a random share $r$ and $k - r$, and each share
+    /// multiplies a copy of `self` whose projective coordinates are scaled by a fresh
+    /// random factor, so that neither the scalar nor the intermediate coordinates
+    /// are the same from one call to the next. This is constant time, like the
+    /// multiplication it wraps, and about twice as slow; it is meant for secret
+    /// scalars on devices whose power draw or emissions can be measured.
+    pub fn mul_blinded(&self, scalar: &Scalar, mut rng: impl RngCore) -> G2Projective {
+        let r = <Scalar as ff::Field>::random(&mut rng);
+        self.randomize_coordinates(&mut rng) * r
+            + self.randomize_coordinates(&mut rng) * (scalar - r)
+    }
+
+    /// Scales the projective coordinates of `self` by a random nonzero factor, which
+    /// does not change the point.
+    fn randomize_coordinates(&self, rng: impl RngCore) -> G2Projective {
+        let lambda = Fp2::random(rng);
+        let lambda = Fp2::conditional_select(&lambda, &Fp2::one(), lambda.is_zero());
+        G2Projective {
+            x: self.x * lambda,
+            y: self.y * lambda,
+            z: self.z * lambda,
+        }
+    }
+
+    /// Multiplies `self` by the parameter $x = -\texttt{0xd201000000010000}$ of
+    /// BLS12-381, by doubling and adding along the bits of $|x|$, then negating.
+    ///
+    /// The operations only depend on $x$, so this is constant time. Together with the
+    /// cofactors in [`constants`](crate::constants), this is the building block of
+    /// subgroup checks and cofactor clearing.
+    pub fn mul_by_x(&self) -> G2Projective {
         let mut xself = G2Projective::identity();
         // NOTE: in BLS12-381 we can just skip the first bit.
         let mut x = crate::BLS_X >> 1;
         let mut acc = *self;
         while x != 0 {
-            acc = acc.double();
+            acc.double_assign();
             if x % 2 == 1 {
                 xself += acc;
             }
@@ -911,6 +1151,29 @@ impl G2Projective {
         xself
     }
 
+    /// Multiplies `self` by the parameter $x$ of BLS12-381, as [`G2Projective::mul_by_x`]
+    /// does, along the bits of $|x|$ from the most significant one, which saves an
+    /// addition, and returning early for the identity.
+    ///
+    /// **This is not constant time**: it is faster for the identity.
+    pub fn mul_by_x_vartime(&self) -> G2Projective {
+        if bool::from(self.is_identity()) {
+            return *self;
+        }
+
+        let mut xself = *self;
+        for i in (0..63).rev() {
+            xself.double_assign();
+            if (crate::BLS_X >> i) & 1 == 1 {
+                xself += self;
+            }
+        }
+        if crate::BLS_X_IS_NEGATIVE {
+            xself = -xself;
+        }
+        xself
+    }
+
     /// Clears the cofactor, using [Budroni-Pintore](https://ia.cr/2017/419).
     /// This is equivalent to multiplying by $h\_\textrm{eff} = 3(z^2 - 1) \cdot
     /// h_2$, where $h_2$ is the cofactor of $\mathbb{G}\_2$ and $z$ is the
@@ -963,12 +1226,64 @@ impl G2Projective {
         }
     }
 
+    /// Converts an array of `N` `G2Projective` elements into `G2Affine` elements, as
+    /// [`G2Projective::batch_normalize`] does, with the length checked at compile
+    /// time.
+    pub fn batch_normalize_array<const N: usize>(p: &[Self; N]) -> [G2Affine; N] {
+        let mut q = [G2Affine::identity(); N];
+        G2Projective::batch_normalize(p, &mut q);
+        q
+    }
+
+    /// Converts a batch of `G2Projective` elements into `G2Affine` elements, as
+    /// [`G2Projective::batch_normalize`] does, on up to `threads` threads. This
+    /// function will panic if `p.len() != q.len()`.
+    ///
+    /// Each thread multiplies the z-coordinates of a contiguous chunk of `p`, the
+    /// products of the chunks are inverted with a single inversion, and each thread
+    /// then recovers the inverses of the z-coordinates of its own chunk. The chunks
+    /// are copied to and from the threads.
+    #[cfg(feature = "parallel")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+    pub fn batch_normalize_parallel(p: &[Self], q: &mut [G2Affine], threads: usize) {
+        assert_eq!(p.len(), q.len());
+
+        let affine = crate::threads::map_inverses(
+            p,
+            threads,
+            |p| p.z,
+            |p, zinv| {
+                let q = G2Affine {
+                    x: p.x * zinv,
+                    y: p.y * zinv,
+                    infinity: Choice::from(0u8),
+                };
+                G2Affine::conditional_select(&q, &G2Affine::identity(), p.is_identity())
+            },
+        );
+        q.copy_from_slice(&affine);
+    }
+
     /// Returns true if this element is the identity (the point at infinity).
     #[inline]
     pub fn is_identity(&self) -> Choice {
         self.z.is_zero()
     }
 
+    /// Negates this element in place.
+    #[inline]
+    pub fn neg_assign(&mut self) {
+        *self = -&*self;
+    }
+
+    /// Negates this element in place if `choice` is true, and leaves it unchanged
+    /// otherwise, in constant time.
+    #[inline]
+    pub fn conditional_negate(&mut self, choice: Choice) {
+        let neg = -&*self;
+        self.conditional_assign(&neg, choice);
+    }
+
     /// Returns true if this point is on the curve. This should always return
     /// true unless an "unchecked" API was used.
     pub fn is_on_curve(&self) -> Choice {
@@ -1215,6 +1530,104 @@ impl UncompressedEncoding for G2Affine {
     }
 }
 
+impl UncompressedEncoding for G2Projective {
+    type Uncompressed = G2Uncompressed;
+
+    fn from_uncompressed(bytes: &Self::Uncompressed) -> CtOption<Self> {
+        G2Affine::from_uncompressed(&bytes.0).map(Self::from)
+    }
+
+    fn from_uncompressed_unchecked(bytes: &Self::Uncompressed) -> CtOption<Self> {
+        G2Affine::from_uncompressed_unchecked(&bytes.0).map(Self::from)
+    }
+
+    fn to_uncompressed(&self) -> Self::Uncompressed {
+        G2Uncompressed(G2Affine::from(self).to_uncompressed())
+    }
+}
+
+#[test]
+fn test_from_x() {
+    let p = G2Affine::from(G2Affine::generator() * Scalar::from(0x1234_5678u64));
+    for p in [G2Affine::generator(), p] {
+        assert_eq!(G2Affine::from_x(p.x, p.y.sgn0()).unwrap(), p);
+        assert_eq!(G2Affine::from_x(p.x, !p.y.sgn0()).unwrap(), -p);
+    }
+
+    // Points of the curve outside of the subgroup, and x-coordinates of no point.
+    let mut on_curve = 0;
+    let mut off_curve = 0;
+    let mut x = Fp2::zero();
+    while on_curve < 3 || off_curve < 3 {
+        if bool::from(((x.square() * x) + B).sqrt().is_some()) {
+            on_curve += 1;
+        } else {
+            off_curve += 1;
+        }
+        assert!(bool::from(G2Affine::from_x(x, Choice::from(0u8)).is_none()));
+        assert!(bool::from(G2Affine::from_x(x, Choice::from(1u8)).is_none()));
+        x += Fp2::one();
+    }
+}
+
+#[test]
+fn test_decompress_const() {
+    const GENERATOR: [u8; 192] = G2Affine::decompress_const(&[
+        0x93, 0xe0, 0x2b, 0x60, 0x52, 0x71, 0x9f, 0x60, 0x7d, 0xac, 0xd3, 0xa0, 0x88, 0x27, 0x4f,
+        0x65, 0x59, 0x6b, 0xd0, 0xd0, 0x99, 0x20, 0xb6, 0x1a, 0xb5, 0xda, 0x61, 0xbb, 0xdc, 0x7f,
+        0x50, 0x49, 0x33, 0x4c, 0xf1, 0x12, 0x13, 0x94, 0x5d, 0x57, 0xe5, 0xac, 0x7d, 0x05, 0x5d,
+        0x04, 0x2b, 0x7e, 0x02, 0x4a, 0xa2, 0xb2, 0xf0, 0x8f, 0x0a, 0x91, 0x26, 0x08, 0x05, 0x27,
+        0x2d, 0xc5, 0x10, 0x51, 0xc6, 0xe4, 0x7a, 0xd4, 0xfa, 0x40, 0x3b, 0x02, 0xb4, 0x51, 0x0b,
+        0x64, 0x7a, 0xe3, 0xd1, 0x77, 0x0b, 0xac, 0x03, 0x26, 0xa8, 0x05, 0xbb, 0xef, 0xd4, 0x80,
+        0x56, 0xc8, 0xc1, 0x21, 0xbd, 0xb8,
+    ]);
+    const IDENTITY: [u8; 192] = G2Affine::decompress_const(&{
+        let mut bytes = [0; 96];
+        bytes[0] = 0xc0;
+        bytes
+    });
+    assert_eq!(GENERATOR, G2Affine::generator().to_uncompressed());
+    assert_eq!(IDENTITY, G2Affine::identity().to_uncompressed());
+
+    let mut p = G2Projective::generator();
+    for _ in 0..10 {
+        let q = G2Affine::from(p);
+        assert_eq!(
+            G2Affine::decompress_const(&q.to_compressed()),
+            q.to_uncompressed()
+        );
+        assert_eq!(
+            G2Affine::decompress_const(&(-q).to_compressed()),
+            (-q).to_uncompressed()
+        );
+        p = p.double() + G2Projective::generator();
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_decompress_const_not_on_curve() {
+    // An x-coordinate for which x^3 + b is not a square.
+    let mut x = Fp2::one();
+    while bool::from(((x.square() * x) + B).sqrt().is_some()) {
+        x += Fp2::one();
+    }
+    let p = G2Affine {
+        x,
+        y: Fp2::zero(),
+        infinity: Choice::from(0u8),
+    };
+    G2Affine::decompress_const(&p.to_compressed());
+}
+
+#[test]
+#[should_panic]
+fn test_decompress_const_bad_flags() {
+    let mut bytes = G2Affine::generator().to_compressed();
+    bytes[0] &= 0x7f;
+    G2Affine::decompress_const(&bytes);
+}
+
 #[test]
 fn test_is_on_curve() {
     assert!(bool::from(G2Affine::identity().is_on_curve()));
@@ -1783,6 +2196,36 @@ fn test_mixed_addition() {
     }
 }
 
+#[test]
+fn test_in_place_operations() {
+    let p = G2Projective::generator().double();
+    let q = G2Affine::from(G2Projective::generator() * Scalar::from(5));
+
+    let mut a = p;
+    a.double_assign();
+    assert_eq!(a, p.double());
+
+    let mut a = p;
+    a.add_assign_mixed(&q);
+    assert_eq!(a, p.add_mixed(&q));
+    a.sub_assign_mixed(&q);
+    assert_eq!(a, p);
+    a += q;
+    a -= &q;
+    assert_eq!(a, p);
+
+    // The identity is handled on either side.
+    let mut a = G2Projective::identity();
+    a.double_assign();
+    assert!(bool::from(a.is_identity()));
+    a.add_assign_mixed(&q);
+    assert_eq!(a, G2Projective::from(q));
+    a.add_assign_mixed(&G2Affine::identity());
+    assert_eq!(a, G2Projective::from(q));
+    a.sub_assign_mixed(&q);
+    assert!(bool::from(a.is_identity()));
+}
+
 #[test]
 #[allow(clippy::eq_op)]
 fn test_projective_negation_and_subtraction() {
@@ -1798,6 +2241,28 @@ fn test_affine_negation_and_subtraction() {
     assert_eq!(G2Projective::from(a) + (-a), G2Projective::from(a) - a);
 }
 
+#[test]
+fn test_windowed_scalar_multiplication() {
+    let g = G2Projective::generator();
+
+    // Scalars whose windows exercise every table entry and the edges of the
+    // scalar field.
+    let mut naive = G2Projective::identity();
+    for i in 0..=32u64 {
+        assert_eq!(g * Scalar::from(i), naive);
+        naive += g;
+    }
+    assert_eq!(g * -Scalar::one(), -g);
+    assert_eq!(g * Scalar::from_raw([u64::MAX, u64::MAX, 0, 0]), {
+        let lo = g * Scalar::from_raw([u64::MAX, 0, 0, 0]);
+        (lo + g) * Scalar::from_raw([0, 1, 0, 0]) - g
+    });
+    assert_eq!(
+        G2Projective::identity() * Scalar::from(7),
+        G2Projective::identity()
+    );
+}
+
 #[test]
 fn test_projective_scalar_multiplication() {
     let g = G2Projective::generator();
@@ -1838,6 +2303,40 @@ fn test_affine_scalar_multiplication() {
     assert_eq!(G2Affine::from(g * a) * b, g * c);
 }
 
+#[test]
+fn test_mul_vartime() {
+    let g = G2Projective::generator();
+    let a = Scalar::from_raw([
+        0x2b56_8297_a56d_a71c,
+        0xd8c3_9ecb_0ef3_75d1,
+        0x435c_38da_67bf_bf96,
+        0x8088_a050_26b6_59b2,
+    ]);
+    let p = g * a;
+
+    for s in [
+        a,
+        -a,
+        Scalar::zero(),
+        Scalar::one(),
+        -Scalar::one(),
+        Scalar::from(0x0f),
+        Scalar::from(0x10),
+        Scalar::from_raw([u64::MAX, u64::MAX, 0, 0]),
+    ] {
+        assert_eq!(p.mul_vartime(&s), p * s);
+    }
+    let mut s = a;
+    for _ in 0..8 {
+        s = s.square() + a;
+        assert_eq!(p.mul_vartime(&s), p * s);
+    }
+    assert_eq!(
+        G2Projective::identity().mul_vartime(&a),
+        G2Projective::identity()
+    );
+}
+
 #[test]
 fn test_is_torsion_free() {
     let a = G2Affine {
@@ -1899,6 +2398,38 @@ fn test_mul_by_x() {
 
     let point = G2Projective::generator() * Scalar::from(42);
     assert_eq!(point.mul_by_x(), point * x);
+
+    for p in [generator, point, G2Projective::identity()] {
+        assert_eq!(p.mul_by_x_vartime(), p.mul_by_x());
+    }
+}
+
+#[test]
+fn test_mul_blinded() {
+    use rand_core::SeedableRng;
+
+    let mut rng = rand_xorshift::XorShiftRng::from_seed([
+        0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06, 0xbc,
+        0xe5,
+    ]);
+    let point = G2Projective::generator() * Scalar::from(42);
+    let scalar = <Scalar as ff::Field>::random(&mut rng);
+
+    let blinded = point.mul_blinded(&scalar, &mut rng);
+    assert_eq!(blinded, point * scalar);
+    // The coordinates differ from one call to the next, but not the point.
+    let again = point.mul_blinded(&scalar, &mut rng);
+    assert_eq!(again, blinded);
+    assert_ne!(again.z, blinded.z);
+
+    assert_eq!(
+        G2Projective::identity().mul_blinded(&scalar, &mut rng),
+        G2Projective::identity()
+    );
+    assert_eq!(
+        point.mul_blinded(&Scalar::zero(), &mut rng),
+        G2Projective::identity()
+    );
 }
 
 #[test]
@@ -2104,11 +2635,31 @@ fn test_batch_normalize() {
                 G2Projective::batch_normalize(&v[..], &mut t[..]);
 
                 assert_eq!(&t[..], &expected[..]);
+                assert_eq!(G2Projective::batch_normalize_array(&v), expected);
             }
         }
     }
 }
 
+#[cfg(feature = "parallel")]
+#[test]
+fn test_batch_normalize_parallel() {
+    let mut p = [G2Projective::identity(); 11];
+    for i in 1..p.len() {
+        p[i] = p[i - 1] + G2Projective::generator() * Scalar::from(i as u64);
+    }
+    p[4] = G2Projective::identity();
+    let mut expected = [G2Affine::identity(); 11];
+    G2Projective::batch_normalize(&p, &mut expected);
+
+    for threads in [0, 1, 2, 3, 11, 16] {
+        let mut q = [G2Affine::generator(); 11];
+        G2Projective::batch_normalize_parallel(&p, &mut q, threads);
+        assert_eq!(q, expected);
+    }
+    G2Projective::batch_normalize_parallel(&[], &mut [], 4);
+}
+
 #[cfg(feature = "zeroize")]
 #[test]
 fn test_zeroize() {
@@ -0,0 +1,263 @@
+//! This module implements verification of [Groth16] proofs over BLS12-381.
+//!
+//! A proof $(A, B, C)$ for the public inputs $x_1, \ldots, x_\ell$ is accepted when
+//!
+//! $$e(A, B) = e(\alpha, \beta) \cdot e(\textstyle\sum_i x_i \cdot IC_i, \gamma) \cdot e(C, \delta),$$
+//!
+//! with $x_0 = 1$. The [`PreparedVerifyingKey`] caches $e(\alpha, \beta)$ and the
+//! line functions of $-\gamma$ and $-\delta$, so that [`verify_proof`] computes a
+//! single multi-Miller loop and final exponentiation per proof.
+//!
+//! [`Proof::to_bytes`] uses the same layout as the proofs of the `bellman` crate,
+//! the compressed encodings of $A$, $B$ and $C$, so proofs that it produces can be
+//! checked with nothing but this crate. A [`VerifyingKey`] holds only the elements
+//! that verification uses, without the $\beta$ and $\delta$ in $\mathbb{G}_1$ of
+//! `bellman`'s keys, and has no serialization: keys produced elsewhere are built
+//! from their decoded points.
+//!
+//! [Groth16]: https://eprint.iacr.org/2016/260
+
+use core::fmt;
+
+use crate::msm::multi_exp_vartime;
+use crate::{multi_miller_loop, G1Affine, G2Affine, G2Prepared, Gt, Scalar};
+
+use alloc::vec::Vec;
+
+/// An error produced when verifying a proof.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The number of public inputs does not match the verifying key.
+    InvalidInputLength,
+    /// The proof is not valid for the public inputs.
+    InvalidProof,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::InvalidInputLength => "wrong number of public inputs",
+            Error::InvalidProof => "invalid proof",
+        })
+    }
+}
+
+/// A Groth16 proof $(A, B, C)$.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Proof {
+    /// The element $A$.
+    pub a: G1Affine,
+    /// The element $B$.
+    pub b: G2Affine,
+    /// The element $C$.
+    pub c: G1Affine,
+}
+
+impl Proof {
+    /// Serializes this proof as the compressed encodings of $A$, $B$ and $C$.
+    pub fn to_bytes(&self) -> [u8; 192] {
+        let mut bytes = [0u8; 192];
+        bytes[..48].copy_from_slice(&self.a.to_compressed());
+        bytes[48..144].copy_from_slice(&self.b.to_compressed());
+        bytes[144..].copy_from_slice(&self.c.to_compressed());
+        bytes
+    }
+
+    /// Attempts to deserialize a proof produced by [`Proof::to_bytes`].
+    pub fn from_bytes(bytes: &[u8; 192]) -> Option<Self> {
+        let mut a = [0u8; 48];
+        let mut b = [0u8; 96];
+        let mut c = [0u8; 48];
+        a.copy_from_slice(&bytes[..48]);
+        b.copy_from_slice(&bytes[48..144]);
+        c.copy_from_slice(&bytes[144..]);
+
+        let a = Option::from(G1Affine::from_compressed(&a))?;
+        let b = Option::from(G2Affine::from_compressed(&b))?;
+        let c = Option::from(G1Affine::from_compressed(&c))?;
+        Some(Proof { a, b, c })
+    }
+}
+
+/// A Groth16 verifying key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifyingKey {
+    /// The element $\alpha$ in $\mathbb{G}_1$.
+    pub alpha_g1: G1Affine,
+    /// The element $\beta$ in $\mathbb{G}_2$.
+    pub beta_g2: G2Affine,
+    /// The element $\gamma$ in $\mathbb{G}_2$.
+    pub gamma_g2: G2Affine,
+    /// The element $\delta$ in $\mathbb{G}_2$.
+    pub delta_g2: G2Affine,
+    /// The elements $IC_0, \ldots, IC_\ell$ in $\mathbb{G}_1$, one more than the
+    /// number of public inputs.
+    pub ic: Vec<G1Affine>,
+}
+
+/// A [`VerifyingKey`] with the precomputations used by [`verify_proof`].
+#[derive(Clone, Debug)]
+pub struct PreparedVerifyingKey {
+    alpha_g1_beta_g2: Gt,
+    neg_gamma_g2: G2Prepared,
+    neg_delta_g2: G2Prepared,
+    ic: Vec<G1Affine>,
+}
+
+impl PreparedVerifyingKey {
+    /// Prepares `vk` for verifying proofs.
+    pub fn new(vk: &VerifyingKey) -> Self {
+        PreparedVerifyingKey {
+            alpha_g1_beta_g2: crate::pairing(&vk.alpha_g1, &vk.beta_g2),
+            neg_gamma_g2: G2Prepared::from(-vk.gamma_g2),
+            neg_delta_g2: G2Prepared::from(-vk.delta_g2),
+            ic: vk.ic.clone(),
+        }
+    }
+
+    /// Returns the number of public inputs expected by this key.
+    pub fn num_inputs(&self) -> usize {
+        self.ic.len().saturating_sub(1)
+    }
+}
+
+impl From<&VerifyingKey> for PreparedVerifyingKey {
+    fn from(vk: &VerifyingKey) -> Self {
+        PreparedVerifyingKey::new(vk)
+    }
+}
+
+/// Computes $IC_0 + \sum_i x_i \cdot IC_i$ for the public inputs $x_i$.
+///
+/// This function is variable time in the public inputs.
+fn prepare_inputs(pvk: &PreparedVerifyingKey, public_inputs: &[Scalar]) -> Result<G1Affine, Error> {
+    if pvk.ic.is_empty() || public_inputs.len() != pvk.num_inputs() {
+        return Err(Error::InvalidInputLength);
+    }
+
+    let acc = multi_exp_vartime(&pvk.ic[1..], public_inputs) + pvk.ic[0];
+    Ok(G1Affine::from(acc))
+}
+
+/// Verifies `proof` for `public_inputs` under the prepared verifying key `pvk`.
+pub fn verify_proof(
+    pvk: &PreparedVerifyingKey,
+    proof: &Proof,
+    public_inputs: &[Scalar],
+) -> Result<(), Error> {
+    let acc = prepare_inputs(pvk, public_inputs)?;
+
+    let result = multi_miller_loop(&[
+        (&proof.a, &G2Prepared::from(proof.b)),
+        (&acc, &pvk.neg_gamma_g2),
+        (&proof.c, &pvk.neg_delta_g2),
+    ])
+    .final_exponentiation();
+
+    if result == pvk.alpha_g1_beta_g2 {
+        Ok(())
+    } else {
+        Err(Error::InvalidProof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::G1Projective;
+
+    /// Simulates a proof for `inputs` with the trapdoor of a verifying key whose
+    /// elements are the given multiples of the generators.
+    fn simulate(
+        trapdoor: &[Scalar; 4],
+        ic: &[Scalar],
+        inputs: &[Scalar],
+        a: Scalar,
+        b: Scalar,
+    ) -> (VerifyingKey, Proof) {
+        let [alpha, beta, gamma, delta] = *trapdoor;
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+
+        let vk = VerifyingKey {
+            alpha_g1: G1Affine::from(g1 * alpha),
+            beta_g2: G2Affine::from(g2 * beta),
+            gamma_g2: G2Affine::from(g2 * gamma),
+            delta_g2: G2Affine::from(g2 * delta),
+            ic: ic.iter().map(|k| G1Affine::from(g1 * k)).collect(),
+        };
+
+        let sum = inputs
+            .iter()
+            .zip(ic[1..].iter())
+            .fold(ic[0], |acc, (x, k)| acc + x * k);
+        let c = (a * b - alpha * beta - gamma * sum) * delta.invert().unwrap();
+        let proof = Proof {
+            a: G1Affine::from(g1 * a),
+            b: G2Affine::from(g2 * b),
+            c: G1Affine::from(g1 * c),
+        };
+        (vk, proof)
+    }
+
+    #[test]
+    fn test_verify_proof() {
+        let trapdoor = [
+            Scalar::from(11),
+            Scalar::from(13),
+            Scalar::from(17),
+            Scalar::from(19),
+        ];
+        let ic = [Scalar::from(3), Scalar::from(5), Scalar::from(7)];
+        let inputs = [Scalar::from(100), -Scalar::from(42)];
+        let (vk, proof) = simulate(&trapdoor, &ic, &inputs, Scalar::from(23), Scalar::from(29));
+
+        let pvk = PreparedVerifyingKey::from(&vk);
+        assert_eq!(pvk.num_inputs(), 2);
+        assert_eq!(verify_proof(&pvk, &proof, &inputs), Ok(()));
+
+        assert_eq!(
+            verify_proof(&pvk, &proof, &[inputs[0], inputs[0]]),
+            Err(Error::InvalidProof)
+        );
+        assert_eq!(
+            verify_proof(&pvk, &proof, &inputs[..1]),
+            Err(Error::InvalidInputLength)
+        );
+
+        let tampered = Proof {
+            c: G1Affine::from(proof.c + G1Projective::from(G1Affine::generator())),
+            ..proof
+        };
+        assert_eq!(
+            verify_proof(&pvk, &tampered, &inputs),
+            Err(Error::InvalidProof)
+        );
+
+        let empty = VerifyingKey {
+            ic: Vec::new(),
+            ..vk
+        };
+        assert_eq!(
+            verify_proof(&PreparedVerifyingKey::new(&empty), &proof, &[]),
+            Err(Error::InvalidInputLength)
+        );
+    }
+
+    #[test]
+    fn test_proof_serialization() {
+        let (_, proof) = simulate(
+            &[Scalar::one(); 4],
+            &[Scalar::one()],
+            &[],
+            Scalar::from(2),
+            Scalar::from(3),
+        );
+        let bytes = proof.to_bytes();
+        assert_eq!(Proof::from_bytes(&bytes), Some(proof));
+
+        let mut invalid = bytes;
+        invalid[48] &= 0x7f;
+        assert_eq!(Proof::from_bytes(&invalid), None);
+    }
+}
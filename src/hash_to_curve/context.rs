@@ -0,0 +1,153 @@
+//! A hash-to-curve suite with a fixed domain separation tag.
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use super::{ExpandMessage, HashToCurve, HashToCurveAffine, ProcessDst, ProcessedDst};
+
+/// A hash-to-curve suite for the group `G` and message expansion `X`, with a
+/// domain separation tag that is processed once, when the context is created.
+///
+/// ```
+/// use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve, HashToCurveContext};
+/// use bls12_381::G2Projective;
+///
+/// const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+///
+/// let context = HashToCurveContext::<G2Projective, ExpandMsgXmd<sha2::Sha256>>::new(DST);
+/// assert_eq!(
+///     context.hash(b"message"),
+///     <G2Projective as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve(b"message", DST),
+/// );
+/// ```
+pub struct HashToCurveContext<G, X> {
+    dst: ProcessedDst,
+    _marker: PhantomData<fn() -> (G, X)>,
+}
+
+impl<G, X> HashToCurveContext<G, X>
+where
+    G: HashToCurve<X>,
+    X: ExpandMessage + ProcessDst,
+{
+    /// Creates a context for the domain separation tag `dst`.
+    pub fn new(dst: &[u8]) -> Self {
+        HashToCurveContext {
+            dst: X::process_dst(dst),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the processed domain separation tag.
+    pub fn dst(&self) -> &[u8] {
+        self.dst.as_bytes()
+    }
+
+    /// Hashes `message` to an element of `G`, as [`HashToCurve::hash_to_curve`]
+    /// does.
+    pub fn hash(&self, message: impl AsRef<[u8]>) -> G {
+        G::hash_to_curve(message, self.dst())
+    }
+
+    /// Encodes `message` as an element of `G`, as [`HashToCurve::encode_to_curve`]
+    /// does.
+    pub fn encode(&self, message: impl AsRef<[u8]>) -> G {
+        G::encode_to_curve(message, self.dst())
+    }
+}
+
+impl<G, X> HashToCurveContext<G, X>
+where
+    G: HashToCurveAffine<X>,
+    X: ExpandMessage + ProcessDst,
+{
+    /// Hashes `message` to an element of `G` in affine coordinates, as
+    /// [`HashToCurveAffine::hash_to_curve_affine`] does.
+    pub fn hash_affine(&self, message: impl AsRef<[u8]>) -> G::Affine {
+        G::hash_to_curve_affine(message, self.dst())
+    }
+
+    /// Hashes each of `messages` to an element of `G` in affine coordinates, as
+    /// [`HashToCurveAffine::hash_to_curve_affine_batch`] does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `messages` and `output` have different lengths.
+    pub fn hash_affine_batch<M: AsRef<[u8]>>(&self, messages: &[M], output: &mut [G::Affine]) {
+        G::hash_to_curve_affine_batch(messages, self.dst(), output)
+    }
+}
+
+impl<G, X> Clone for HashToCurveContext<G, X> {
+    fn clone(&self) -> Self {
+        HashToCurveContext {
+            dst: self.dst.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<G, X> fmt::Debug for HashToCurveContext<G, X> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HashToCurveContext")
+            .field("dst", &self.dst)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_to_curve::{ExpandMsgXmd, ExpandMsgXof};
+    use crate::{G1Projective, G2Affine, G2Projective};
+    use sha2::Sha256;
+    use sha3::Shake128;
+
+    #[test]
+    fn test_context_matches_free_functions() {
+        let long_dst = [0x42; 300];
+        for dst in [
+            &b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_"[..],
+            &long_dst,
+        ] {
+            let context = HashToCurveContext::<G1Projective, ExpandMsgXmd<Sha256>>::new(dst);
+            assert_eq!(
+                context.hash(b"abc"),
+                <G1Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(b"abc", dst)
+            );
+            assert_eq!(
+                context.encode(b"abc"),
+                <G1Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::encode_to_curve(b"abc", dst)
+            );
+
+            let context = HashToCurveContext::<G2Projective, ExpandMsgXof<Shake128>>::new(dst);
+            assert_eq!(
+                context.hash(b""),
+                <G2Projective as HashToCurve<ExpandMsgXof<Shake128>>>::hash_to_curve(b"", dst)
+            );
+            assert_eq!(context.hash_affine(b""), G2Affine::from(context.hash(b"")));
+            let mut output = [G2Affine::identity(); 2];
+            context.hash_affine_batch(&[&b"abc"[..], b""], &mut output);
+            assert_eq!(
+                output,
+                [
+                    G2Affine::from(context.hash(b"abc")),
+                    context.hash_affine(b"")
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_context_dst() {
+        let dst = b"short";
+        let context = HashToCurveContext::<G1Projective, ExpandMsgXmd<Sha256>>::new(dst);
+        assert_eq!(context.dst(), dst);
+
+        let context = HashToCurveContext::<G1Projective, ExpandMsgXmd<Sha256>>::new(&[0; 256]);
+        assert_eq!(context.dst().len(), 32);
+        let context = HashToCurveContext::<G1Projective, ExpandMsgXof<Shake128>>::new(&[0; 256]);
+        assert_eq!(context.dst().len(), 32);
+        assert_eq!(context.clone().dst(), context.dst());
+    }
+}
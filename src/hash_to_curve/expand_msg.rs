@@ -79,6 +79,45 @@ impl<'x, L: ArrayLength<u8>> ExpandMsgDst<'x, L> {
     }
 }
 
+/// A domain separation tag processed for a message expansion: the tag itself when
+/// it is at most 255 bytes long, and its hash otherwise.
+///
+/// Processing a processed tag again leaves it unchanged, so it can be passed to
+/// [`InitExpandMessage::init_expand`] in place of the original tag.
+#[derive(Clone)]
+pub struct ProcessedDst {
+    data: [u8; 255],
+    len: usize,
+}
+
+impl ProcessedDst {
+    fn new<L: ArrayLength<u8>>(dst: &ExpandMsgDst<'_, L>) -> Self {
+        let mut data = [0; 255];
+        data[..dst.len()].copy_from_slice(dst.data());
+        ProcessedDst {
+            data,
+            len: dst.len(),
+        }
+    }
+
+    /// Returns the processed tag.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+}
+
+impl Debug for ProcessedDst {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ProcessedDst").field(&self.as_bytes()).finish()
+    }
+}
+
+/// Message expansions that can process a domain separation tag ahead of time.
+pub trait ProcessDst {
+    /// Processes `dst` as the message expansion would.
+    fn process_dst(dst: &[u8]) -> ProcessedDst;
+}
+
 /// A trait for message expansion methods supported by hash-to-curve.
 pub trait ExpandMessage: for<'x> InitExpandMessage<'x> {
     // This intermediate is likely only necessary until GATs allow
@@ -105,6 +144,22 @@ pub trait ExpandMessageState<'x> {
     /// Retrieves the number of bytes remaining in the generator.
     fn remain(&self) -> usize;
 
+    /// Constructs an array containing the remaining bytes of the output, without
+    /// allocating.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is not the number of remaining bytes.
+    fn into_array<const N: usize>(mut self) -> [u8; N]
+    where
+        Self: Sized,
+    {
+        assert_eq!(self.remain(), N);
+        let mut result = [0u8; N];
+        self.read_into(&mut result[..]);
+        result
+    }
+
     #[cfg(feature = "alloc")]
     /// Constructs a `Vec` containing the remaining bytes of the output.
     fn into_vec(mut self) -> Vec<u8>
@@ -175,6 +230,15 @@ where
     }
 }
 
+impl<H> ProcessDst for ExpandMsgXof<H>
+where
+    H: Default + Update + ExtendableOutputDirty,
+{
+    fn process_dst(dst: &[u8]) -> ProcessedDst {
+        ProcessedDst::new(&ExpandMsgDst::<U32>::process_xof::<H>(dst))
+    }
+}
+
 /// Constructor for `expand_message_xmd` for a given digest hash function, message, DST,
 /// and output length.
 ///
@@ -246,6 +310,15 @@ where
     }
 }
 
+impl<H> ProcessDst for ExpandMsgXmd<H>
+where
+    H: Digest + BlockInput,
+{
+    fn process_dst(dst: &[u8]) -> ProcessedDst {
+        ProcessedDst::new(&ExpandMsgDst::process_xmd::<H>(dst))
+    }
+}
+
 impl<'x, H> ExpandMessageState<'x> for ExpandMsgXmdState<'x, H>
 where
     H: Digest + BlockInput,
@@ -287,7 +360,6 @@ where
     }
 }
 
-#[cfg(feature = "alloc")]
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -295,6 +367,7 @@ mod tests {
     use sha3::{Shake128, Shake256};
 
     /// From <https://tools.ietf.org/html/draft-irtf-cfrg-hash-to-curve-12#appendix-K.1>
+    #[cfg(feature = "alloc")]
     #[test]
     fn expand_message_xmd_works_for_draft12_testvectors_sha256() {
         let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
@@ -458,6 +531,7 @@ mod tests {
     }
 
     /// From <https://tools.ietf.org/html/draft-irtf-cfrg-hash-to-curve-12#appendix-K.2>
+    #[cfg(feature = "alloc")]
     #[test]
     fn expand_message_xmd_works_for_draft12_testvectors_sha256_long_dst() {
         let dst = b"QUUX-V01-CS02-with-expander-SHA256-128-long-DST-111111\
@@ -625,6 +699,7 @@ mod tests {
     }
 
     /// From <https://tools.ietf.org/html/draft-irtf-cfrg-hash-to-curve-12#appendix-K.3>
+    #[cfg(feature = "alloc")]
     #[test]
     fn expand_message_xmd_works_for_draft12_testvectors_sha512() {
         let dst = b"QUUX-V01-CS02-with-expander-SHA512-256";
@@ -788,6 +863,7 @@ mod tests {
     }
 
     /// From <https://tools.ietf.org/html/draft-irtf-cfrg-hash-to-curve-12#appendix-K.4>
+    #[cfg(feature = "alloc")]
     #[test]
     fn expand_message_xof_works_for_draft12_testvectors_shake128() {
         let dst = b"QUUX-V01-CS02-with-expander-SHAKE128";
@@ -951,6 +1027,7 @@ mod tests {
     }
 
     /// From <https://tools.ietf.org/html/draft-irtf-cfrg-hash-to-curve-12#appendix-K.5>
+    #[cfg(feature = "alloc")]
     #[test]
     fn expand_message_xof_works_for_draft12_testvectors_shake128_long_dst() {
         let dst = b"QUUX-V01-CS02-with-expander-SHAKE128-long-DST-11111111\
@@ -1118,6 +1195,7 @@ mod tests {
     }
 
     /// From <https://tools.ietf.org/html/draft-irtf-cfrg-hash-to-curve-12#appendix-K.6>
+    #[cfg(feature = "alloc")]
     #[test]
     fn expand_message_xof_works_for_draft12_testvectors_shake256() {
         let dst = b"QUUX-V01-CS02-with-expander-SHAKE256";
@@ -1279,4 +1357,38 @@ mod tests {
             uniform_bytes
         );
     }
+
+    #[test]
+    fn expand_message_into_array() {
+        fn check<X: ExpandMessage>(dst: &[u8], uniform_bytes: &str) {
+            let expected = hex::decode(uniform_bytes).unwrap();
+            assert_eq!(
+                X::init_expand(b"abc", dst, 0x20).into_array::<0x20>()[..],
+                expected[..]
+            );
+        }
+
+        check::<ExpandMsgXmd<Sha256>>(
+            b"QUUX-V01-CS02-with-expander-SHA256-128",
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+        );
+        check::<ExpandMsgXmd<Sha512>>(
+            b"QUUX-V01-CS02-with-expander-SHA512-256",
+            "0da749f12fbe5483eb066a5f595055679b976e93abe9be6f0f6318bce7aca8dc",
+        );
+        check::<ExpandMsgXof<Shake128>>(
+            b"QUUX-V01-CS02-with-expander-SHAKE128",
+            "8696af52a4d862417c0763556073f47bc9b9ba43c99b505305cb1ec04a9ab468",
+        );
+        check::<ExpandMsgXof<Shake256>>(
+            b"QUUX-V01-CS02-with-expander-SHAKE256",
+            "b39e493867e2767216792abce1f2676c197c0692aed061560ead251821808e07",
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn expand_message_into_array_wrong_length() {
+        ExpandMsgXmd::<Sha256>::init_expand(b"abc", b"QUUX", 0x20).into_array::<0x10>();
+    }
 }
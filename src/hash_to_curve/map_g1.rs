@@ -1,11 +1,12 @@
 //! Implementation of hash-to-curve for the G1 group.
 
-use subtle::{Choice, ConditionallyNegatable, ConditionallySelectable, ConstantTimeEq};
+use subtle::{ConditionallyNegatable, ConditionallySelectable, ConstantTimeEq};
 
 use super::chain::chain_pm3div4;
-use super::{HashToField, MapToCurve, Sgn0};
+use super::{ExpandMessage, HashToCurveAffine, HashToField, MapToCurve};
+use crate::constants::{G1_ISO_A as SSWU_ELLP_A, G1_ISO_B as SSWU_ELLP_B};
 use crate::fp::Fp;
-use crate::g1::G1Projective;
+use crate::g1::{G1Affine, G1Projective};
 use crate::generic_array::{typenum::U64, GenericArray};
 
 /// Coefficients of the 11-isogeny x map's numerator
@@ -464,24 +465,6 @@ const ISO11_YDEN: [Fp; 16] = [
     ]),
 ];
 
-const SSWU_ELLP_A: Fp = Fp::from_raw_unchecked([
-    0x2f65_aa0e_9af5_aa51,
-    0x8646_4c2d_1e84_16c3,
-    0xb85c_e591_b7bd_31e2,
-    0x27e1_1c91_b5f2_4e7c,
-    0x2837_6eda_6bfc_1835,
-    0x1554_55c3_e507_1d85,
-]);
-
-const SSWU_ELLP_B: Fp = Fp::from_raw_unchecked([
-    0xfb99_6971_fe22_a1e0,
-    0x9aa9_3eb3_5b74_2d6f,
-    0x8c47_6013_de99_c5c4,
-    0x873e_27c3_a221_e571,
-    0xca72_b5e4_5a52_d888,
-    0x0682_4061_418a_386b,
-]);
-
 const SSWU_XI: Fp = Fp::from_raw_unchecked([
     0x886c_0000_0023_ffdc,
     0x0f70_008d_3090_001d,
@@ -525,17 +508,6 @@ impl HashToField for Fp {
     }
 }
 
-impl Sgn0 for Fp {
-    fn sgn0(&self) -> Choice {
-        // Turn into canonical form by computing
-        // (a.R) / R = a
-        let tmp = Fp::montgomery_reduce(
-            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5], 0, 0, 0, 0, 0, 0,
-        );
-        Choice::from((tmp.0[0] & 1) as u8)
-    }
-}
-
 /// Maps an element of [`Fp`] to a point on iso-G1.
 ///
 /// Implements [section 6.6.2 of `draft-irtf-cfrg-hash-to-curve-12`][sswu].
@@ -636,6 +608,14 @@ impl MapToCurve for G1Projective {
     }
 }
 
+impl<X: ExpandMessage> HashToCurveAffine<X> for G1Projective {
+    type Affine = G1Affine;
+
+    fn batch_normalize(points: &[Self], output: &mut [G1Affine]) {
+        G1Projective::batch_normalize(points, output)
+    }
+}
+
 #[cfg(test)]
 fn check_g1_prime(pt: &G1Projective) -> bool {
     // (X : Y : Z)==(X/Z, Y/Z) is on E': y^2 = x^3 + A * x + B.
@@ -930,6 +910,41 @@ fn test_hash_to_curve_10() {
     }
 }
 
+#[test]
+fn test_hash_to_curve_affine() {
+    use crate::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    type Xmd = ExpandMsgXmd<sha2::Sha256>;
+    const DOMAIN: &[u8] = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+
+    // More messages than are converted at once, so that the last batch is partial.
+    let messages = (0..20).map(|i| i.to_string()).collect::<Vec<_>>();
+    let mut output = [G1Affine::identity(); 20];
+    <G1Projective as HashToCurveAffine<Xmd>>::hash_to_curve_affine_batch(
+        &messages,
+        DOMAIN,
+        &mut output,
+    );
+    for (message, point) in messages.iter().zip(output.iter()) {
+        let expected = G1Affine::from(<G1Projective as HashToCurve<Xmd>>::hash_to_curve(
+            message, DOMAIN,
+        ));
+        assert_eq!(*point, expected);
+        assert_eq!(
+            <G1Projective as HashToCurveAffine<Xmd>>::hash_to_curve_affine(message, DOMAIN),
+            expected
+        );
+    }
+
+    <G1Projective as HashToCurveAffine<Xmd>>::hash_to_curve_affine_batch::<&[u8]>(
+        &[],
+        DOMAIN,
+        &mut [],
+    );
+}
+
 #[cfg(test)]
 // p-1 / 2
 pub const P_M1_OVER2: Fp = Fp::from_raw_unchecked([
@@ -942,6 +957,7 @@ pub const P_M1_OVER2: Fp = Fp::from_raw_unchecked([
 ]);
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_sgn0() {
     assert_eq!(bool::from(Fp::zero().sgn0()), false);
     assert_eq!(bool::from(Fp::one().sgn0()), true);
@@ -954,7 +970,7 @@ fn test_sgn0() {
 
     let neg_p_p1_over2 = {
         let mut tmp = p_p1_over2;
-        tmp.conditional_negate(Choice::from(1u8));
+        tmp.conditional_negate(subtle::Choice::from(1u8));
         tmp
     };
     assert_eq!(neg_p_p1_over2, P_M1_OVER2);
@@ -3,12 +3,17 @@
 use subtle::{Choice, ConditionallyNegatable, ConditionallySelectable, ConstantTimeEq};
 
 use super::chain::chain_p2m9div16;
-use super::{HashToField, MapToCurve, Sgn0};
+use super::{ExpandMessage, HashToCurveAffine, HashToField, MapToCurve};
+use crate::constants::{G2_ISO_A as SSWU_ELLP_A, G2_ISO_B as SSWU_ELLP_B};
 use crate::generic_array::{
     typenum::{U128, U64},
     GenericArray,
 };
-use crate::{fp::Fp, fp2::Fp2, g2::G2Projective};
+use crate::{
+    fp::Fp,
+    fp2::Fp2,
+    g2::{G2Affine, G2Projective},
+};
 
 /// Coefficients of the 3-isogeny x map's numerator
 const ISO3_XNUM: [Fp2; 4] = [
@@ -220,37 +225,6 @@ const ISO3_YDEN: [Fp2; 4] = [
     Fp2::one(),
 ];
 
-const SSWU_ELLP_A: Fp2 = Fp2 {
-    c0: Fp::zero(),
-    c1: Fp::from_raw_unchecked([
-        0xe53a_0000_0313_5242,
-        0x0108_0c0f_def8_0285,
-        0xe788_9edb_e340_f6bd,
-        0x0b51_3751_2631_0601,
-        0x02d6_9857_17c7_44ab,
-        0x1220_b4e9_79ea_5467,
-    ]),
-};
-
-const SSWU_ELLP_B: Fp2 = Fp2 {
-    c0: Fp::from_raw_unchecked([
-        0x22ea_0000_0cf8_9db2,
-        0x6ec8_32df_7138_0aa4,
-        0x6e1b_9440_3db5_a66e,
-        0x75bf_3c53_a794_73ba,
-        0x3dd3_a569_412c_0a34,
-        0x125c_db5e_74dc_4fd1,
-    ]),
-    c1: Fp::from_raw_unchecked([
-        0x22ea_0000_0cf8_9db2,
-        0x6ec8_32df_7138_0aa4,
-        0x6e1b_9440_3db5_a66e,
-        0x75bf_3c53_a794_73ba,
-        0x3dd3_a569_412c_0a34,
-        0x125c_db5e_74dc_4fd1,
-    ]),
-};
-
 const SSWU_XI: Fp2 = Fp2 {
     c0: Fp::from_raw_unchecked([
         0x87eb_ffff_fff9_555c,
@@ -375,15 +349,6 @@ impl HashToField for Fp2 {
     }
 }
 
-impl Sgn0 for Fp2 {
-    fn sgn0(&self) -> Choice {
-        let sign_0 = self.c0.sgn0();
-        let zero_0 = self.c0.is_zero();
-        let sign_1 = self.c1.sgn0();
-        sign_0 | (zero_0 & sign_1)
-    }
-}
-
 /// Maps from an [`Fp2]` element to a point on iso-G2.
 fn map_to_curve_simple_swu(u: &Fp2) -> G2Projective {
     let usq = u.square();
@@ -501,6 +466,14 @@ impl MapToCurve for G2Projective {
     }
 }
 
+impl<X: ExpandMessage> HashToCurveAffine<X> for G2Projective {
+    type Affine = G2Affine;
+
+    fn batch_normalize(points: &[Self], output: &mut [G2Affine]) {
+        G2Projective::batch_normalize(points, output)
+    }
+}
+
 #[cfg(test)]
 fn check_g2_prime(pt: &G2Projective) -> bool {
     // (X : Y : Z)==(X/Z, Y/Z) is on E': y^2 = x^3 + A * x + B.
@@ -708,6 +681,42 @@ fn test_hash_to_curve_10() {
 }
 
 #[test]
+fn test_hash_to_curve_affine() {
+    use crate::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    type Xmd = ExpandMsgXmd<sha2::Sha256>;
+    const DOMAIN: &[u8] = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+
+    // More messages than are converted at once, so that the last batch is partial.
+    let messages = (0..20).map(|i| i.to_string()).collect::<Vec<_>>();
+    let mut output = [G2Affine::identity(); 20];
+    <G2Projective as HashToCurveAffine<Xmd>>::hash_to_curve_affine_batch(
+        &messages,
+        DOMAIN,
+        &mut output,
+    );
+    for (message, point) in messages.iter().zip(output.iter()) {
+        let expected = G2Affine::from(<G2Projective as HashToCurve<Xmd>>::hash_to_curve(
+            message, DOMAIN,
+        ));
+        assert_eq!(*point, expected);
+        assert_eq!(
+            <G2Projective as HashToCurveAffine<Xmd>>::hash_to_curve_affine(message, DOMAIN),
+            expected
+        );
+    }
+
+    <G2Projective as HashToCurveAffine<Xmd>>::hash_to_curve_affine_batch::<&[u8]>(
+        &[],
+        DOMAIN,
+        &mut [],
+    );
+}
+
+#[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_sgn0() {
     use super::map_g1::P_M1_OVER2;
 
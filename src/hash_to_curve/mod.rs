@@ -3,19 +3,33 @@
 
 use core::ops::Add;
 
-use subtle::Choice;
-
 pub(crate) mod chain;
 
+mod context;
+pub use self::context::HashToCurveContext;
+
 mod expand_msg;
 pub use self::expand_msg::{
     ExpandMessage, ExpandMessageState, ExpandMsgXmd, ExpandMsgXof, InitExpandMessage,
+    ProcessDst, ProcessedDst,
 };
 
 mod map_g1;
 mod map_g2;
 mod map_scalar;
 
+mod suite;
+pub use self::suite::{
+    CipherSuiteId, Ciphersuite, SuiteExpander, SuiteGroup, SuiteHash, BLS12381G1_XMD_SHA256_SSWU_NU,
+    BLS12381G1_XMD_SHA256_SSWU_RO, BLS12381G2_XMD_SHA256_SSWU_NU, BLS12381G2_XMD_SHA256_SSWU_RO,
+    BLS_POP_PROOF_DST, BLS_SIG_AUG_DST, BLS_SIG_BASIC_DST, BLS_SIG_POP_DST,
+};
+#[cfg(feature = "sha2")]
+pub use self::suite::{
+    Bls12381G1XmdSha256SswuNu, Bls12381G1XmdSha256SswuRo, Bls12381G2XmdSha256SswuNu,
+    Bls12381G2XmdSha256SswuRo, BlsPopProof, BlsSigAug, BlsSigBasic, BlsSigPop,
+};
+
 use crate::generic_array::{typenum::Unsigned, ArrayLength, GenericArray};
 
 /// Enables a byte string to be hashed into one or more field elements for a given curve.
@@ -104,10 +118,56 @@ where
 {
 }
 
-pub(crate) trait Sgn0 {
-    /// Returns either 0 or 1 indicating the "sign" of x, where sgn0(x) == 1
-    /// just when x is "negative". (In other words, this function always considers 0 to be positive.)
-    /// <https://tools.ietf.org/html/draft-irtf-cfrg-hash-to-curve-10#section-4.1>
-    /// The equivalent for draft 6 would be `lexicographically_largest`.
-    fn sgn0(&self) -> Choice;
+/// The number of points that [`HashToCurveAffine::hash_to_curve_affine_batch`]
+/// converts to affine coordinates at once.
+const AFFINE_BATCH_SIZE: usize = 16;
+
+/// Implementation of random oracle maps to the curve that return points in affine
+/// coordinates, for the groups whose points are hashed in projective coordinates.
+///
+/// Hashed points are almost always converted to affine coordinates before they are
+/// used, for instance to prepare them for a pairing. Hashing many messages with
+/// [`HashToCurveAffine::hash_to_curve_affine_batch`] shares the field inversion of
+/// this conversion between them.
+pub trait HashToCurveAffine<X: ExpandMessage>: HashToCurve<X> + Copy + Default {
+    /// The affine representation of the points of `Self`.
+    type Affine: Copy + Default;
+
+    /// Converts `points` to affine coordinates, writing them to `output`.
+    ///
+    /// `points` and `output` have the same length.
+    fn batch_normalize(points: &[Self], output: &mut [Self::Affine]);
+
+    /// Hashes `message` as [`HashToCurve::hash_to_curve`] does, returning the point in
+    /// affine coordinates.
+    fn hash_to_curve_affine(message: impl AsRef<[u8]>, dst: &[u8]) -> Self::Affine {
+        let mut output = [Self::Affine::default()];
+        Self::batch_normalize(&[Self::hash_to_curve(message, dst)], &mut output);
+        output[0]
+    }
+
+    /// Hashes each of `messages` as [`HashToCurve::hash_to_curve`] does, writing the
+    /// points to `output` in affine coordinates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `messages` and `output` have different lengths.
+    fn hash_to_curve_affine_batch<M: AsRef<[u8]>>(
+        messages: &[M],
+        dst: &[u8],
+        output: &mut [Self::Affine],
+    ) {
+        assert_eq!(messages.len(), output.len());
+
+        let mut points = [Self::default(); AFFINE_BATCH_SIZE];
+        for (messages, output) in messages
+            .chunks(AFFINE_BATCH_SIZE)
+            .zip(output.chunks_mut(AFFINE_BATCH_SIZE))
+        {
+            for (point, message) in points.iter_mut().zip(messages) {
+                *point = Self::hash_to_curve(message, dst);
+            }
+            Self::batch_normalize(&points[..messages.len()], output);
+        }
+    }
 }
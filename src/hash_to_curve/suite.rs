@@ -0,0 +1,399 @@
+//! Hash-to-curve ciphersuites, which fix the target group, the message expansion and
+//! the encoding used to hash to a curve.
+
+use super::{ExpandMessage, HashToCurve};
+
+/// A hash-to-curve ciphersuite, as defined in
+/// [section 8 of `draft-irtf-cfrg-hash-to-curve-12`][suites], or a signature scheme
+/// built on one.
+///
+/// Protocols generic over `S: Ciphersuite` hash with [`Ciphersuite::hash`], under
+/// the suite's own domain separation tag where it defines one. The implementations
+/// of the standard suites require the `sha2` feature.
+///
+/// [suites]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-hash-to-curve-12#section-8
+pub trait Ciphersuite {
+    /// The group that messages are hashed to.
+    type Group: HashToCurve<Self::Expander>;
+
+    /// The message expansion.
+    type Expander: ExpandMessage;
+
+    /// The identifier of the hash-to-curve suite, such as
+    /// `BLS12381G2_XMD:SHA-256_SSWU_RO_`, which domain separation tags conventionally
+    /// include.
+    const ID: &'static [u8];
+
+    /// Whether the suite is a random oracle encoding (`_RO_`), computed by
+    /// [`HashToCurve::hash_to_curve`], rather than a nonuniform one (`_NU_`),
+    /// computed by [`HashToCurve::encode_to_curve`].
+    const RANDOM_ORACLE: bool;
+
+    /// The domain separation tag fixed by the suite, if any. The hash-to-curve
+    /// suites leave the tag to the application, while signature schemes fix it.
+    const DST: Option<&'static [u8]>;
+
+    /// Hashes `message` to an element of [`Ciphersuite::Group`] under `dst`, with
+    /// the encoding of the suite.
+    fn hash(message: impl AsRef<[u8]>, dst: &[u8]) -> Self::Group {
+        if Self::RANDOM_ORACLE {
+            Self::Group::hash_to_curve(message, dst)
+        } else {
+            Self::Group::encode_to_curve(message, dst)
+        }
+    }
+}
+
+/// The identifier of the suite `BLS12381G1_XMD:SHA-256_SSWU_RO_`.
+pub const BLS12381G1_XMD_SHA256_SSWU_RO: &[u8] = b"BLS12381G1_XMD:SHA-256_SSWU_RO_";
+
+/// The identifier of the suite `BLS12381G1_XMD:SHA-256_SSWU_NU_`.
+pub const BLS12381G1_XMD_SHA256_SSWU_NU: &[u8] = b"BLS12381G1_XMD:SHA-256_SSWU_NU_";
+
+/// The identifier of the suite `BLS12381G2_XMD:SHA-256_SSWU_RO_`.
+pub const BLS12381G2_XMD_SHA256_SSWU_RO: &[u8] = b"BLS12381G2_XMD:SHA-256_SSWU_RO_";
+
+/// The identifier of the suite `BLS12381G2_XMD:SHA-256_SSWU_NU_`.
+pub const BLS12381G2_XMD_SHA256_SSWU_NU: &[u8] = b"BLS12381G2_XMD:SHA-256_SSWU_NU_";
+
+/// The domain separation tag of the basic BLS signature scheme with signatures in
+/// $\mathbb{G}_2$.
+pub const BLS_SIG_BASIC_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// The domain separation tag of the BLS signature scheme with message augmentation
+/// and signatures in $\mathbb{G}_2$.
+pub const BLS_SIG_AUG_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG_";
+
+/// The domain separation tag of the BLS signature scheme with proofs of possession
+/// and signatures in $\mathbb{G}_2$.
+pub const BLS_SIG_POP_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// The domain separation tag of the proofs of possession of the scheme with
+/// [`BLS_SIG_POP_DST`].
+pub const BLS_POP_PROOF_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// The group that a ciphersuite hashes to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SuiteGroup {
+    /// $\mathbb{G}_1$, with [`G1Projective`](crate::G1Projective).
+    G1,
+    /// $\mathbb{G}_2$, with [`G2Projective`](crate::G2Projective).
+    G2,
+}
+
+/// The message expansion of a ciphersuite.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SuiteExpander {
+    /// `expand_message_xmd`, with [`ExpandMsgXmd`](super::ExpandMsgXmd).
+    Xmd,
+    /// `expand_message_xof`, with [`ExpandMsgXof`](super::ExpandMsgXof).
+    Xof,
+}
+
+/// The hash function of a ciphersuite's message expansion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SuiteHash {
+    /// SHA-256.
+    Sha256,
+}
+
+/// Names one of the standard ciphersuites at runtime, for instance when it is read
+/// from a configuration, and describes what it is made of.
+///
+/// Each variant corresponds to the [`Ciphersuite`] of the same name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CipherSuiteId {
+    /// `BLS12381G1_XMD:SHA-256_SSWU_RO_`.
+    Bls12381G1XmdSha256SswuRo,
+    /// `BLS12381G1_XMD:SHA-256_SSWU_NU_`.
+    Bls12381G1XmdSha256SswuNu,
+    /// `BLS12381G2_XMD:SHA-256_SSWU_RO_`.
+    Bls12381G2XmdSha256SswuRo,
+    /// `BLS12381G2_XMD:SHA-256_SSWU_NU_`.
+    Bls12381G2XmdSha256SswuNu,
+    /// The basic BLS signature scheme, `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_`.
+    BlsSigBasic,
+    /// The BLS signature scheme with message augmentation,
+    /// `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG_`.
+    BlsSigAug,
+    /// The BLS signature scheme with proofs of possession,
+    /// `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_`.
+    BlsSigPop,
+    /// The proofs of possession of [`CipherSuiteId::BlsSigPop`],
+    /// `BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_`.
+    BlsPopProof,
+}
+
+impl CipherSuiteId {
+    /// Every ciphersuite.
+    pub const ALL: [CipherSuiteId; 8] = [
+        CipherSuiteId::Bls12381G1XmdSha256SswuRo,
+        CipherSuiteId::Bls12381G1XmdSha256SswuNu,
+        CipherSuiteId::Bls12381G2XmdSha256SswuRo,
+        CipherSuiteId::Bls12381G2XmdSha256SswuNu,
+        CipherSuiteId::BlsSigBasic,
+        CipherSuiteId::BlsSigAug,
+        CipherSuiteId::BlsSigPop,
+        CipherSuiteId::BlsPopProof,
+    ];
+
+    /// Returns the identifier of the hash-to-curve suite, as [`Ciphersuite::ID`].
+    pub const fn id(self) -> &'static [u8] {
+        match self {
+            CipherSuiteId::Bls12381G1XmdSha256SswuRo => BLS12381G1_XMD_SHA256_SSWU_RO,
+            CipherSuiteId::Bls12381G1XmdSha256SswuNu => BLS12381G1_XMD_SHA256_SSWU_NU,
+            CipherSuiteId::Bls12381G2XmdSha256SswuNu => BLS12381G2_XMD_SHA256_SSWU_NU,
+            CipherSuiteId::Bls12381G2XmdSha256SswuRo
+            | CipherSuiteId::BlsSigBasic
+            | CipherSuiteId::BlsSigAug
+            | CipherSuiteId::BlsSigPop
+            | CipherSuiteId::BlsPopProof => BLS12381G2_XMD_SHA256_SSWU_RO,
+        }
+    }
+
+    /// Returns the group that the suite hashes to.
+    pub const fn group(self) -> SuiteGroup {
+        match self {
+            CipherSuiteId::Bls12381G1XmdSha256SswuRo | CipherSuiteId::Bls12381G1XmdSha256SswuNu => {
+                SuiteGroup::G1
+            }
+            _ => SuiteGroup::G2,
+        }
+    }
+
+    /// Returns the message expansion of the suite.
+    pub const fn expander(self) -> SuiteExpander {
+        SuiteExpander::Xmd
+    }
+
+    /// Returns the hash function of the message expansion of the suite.
+    pub const fn hash(self) -> SuiteHash {
+        SuiteHash::Sha256
+    }
+
+    /// Returns whether the suite is a random oracle encoding, as
+    /// [`Ciphersuite::RANDOM_ORACLE`].
+    pub const fn random_oracle(self) -> bool {
+        !matches!(
+            self,
+            CipherSuiteId::Bls12381G1XmdSha256SswuNu | CipherSuiteId::Bls12381G2XmdSha256SswuNu
+        )
+    }
+
+    /// Returns the domain separation tag fixed by the suite, as [`Ciphersuite::DST`].
+    pub const fn dst(self) -> Option<&'static [u8]> {
+        match self {
+            CipherSuiteId::BlsSigBasic => Some(BLS_SIG_BASIC_DST),
+            CipherSuiteId::BlsSigAug => Some(BLS_SIG_AUG_DST),
+            CipherSuiteId::BlsSigPop => Some(BLS_SIG_POP_DST),
+            CipherSuiteId::BlsPopProof => Some(BLS_POP_PROOF_DST),
+            _ => None,
+        }
+    }
+
+    /// Returns the signature ciphersuite whose domain separation tag is `dst`, if
+    /// any.
+    pub fn from_dst(dst: &[u8]) -> Option<CipherSuiteId> {
+        CipherSuiteId::ALL
+            .iter()
+            .copied()
+            .find(|suite| suite.dst() == Some(dst))
+    }
+}
+
+macro_rules! ciphersuite {
+    ($(#[$doc:meta])* $name:ident, $group:ty, $id:expr, $ro:expr, $dst:expr) => {
+        $(#[$doc])*
+        #[cfg(feature = "sha2")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "sha2")))]
+        #[derive(Clone, Copy, Debug)]
+        pub struct $name;
+
+        #[cfg(feature = "sha2")]
+        impl Ciphersuite for $name {
+            type Group = $group;
+            type Expander = super::ExpandMsgXmd<sha2::Sha256>;
+            const ID: &'static [u8] = $id;
+            const RANDOM_ORACLE: bool = $ro;
+            const DST: Option<&'static [u8]> = $dst;
+        }
+    };
+}
+
+ciphersuite!(
+    /// The suite `BLS12381G1_XMD:SHA-256_SSWU_RO_`.
+    Bls12381G1XmdSha256SswuRo,
+    crate::G1Projective,
+    BLS12381G1_XMD_SHA256_SSWU_RO,
+    true,
+    None
+);
+
+ciphersuite!(
+    /// The suite `BLS12381G1_XMD:SHA-256_SSWU_NU_`.
+    Bls12381G1XmdSha256SswuNu,
+    crate::G1Projective,
+    BLS12381G1_XMD_SHA256_SSWU_NU,
+    false,
+    None
+);
+
+ciphersuite!(
+    /// The suite `BLS12381G2_XMD:SHA-256_SSWU_RO_`.
+    Bls12381G2XmdSha256SswuRo,
+    crate::G2Projective,
+    BLS12381G2_XMD_SHA256_SSWU_RO,
+    true,
+    None
+);
+
+ciphersuite!(
+    /// The suite `BLS12381G2_XMD:SHA-256_SSWU_NU_`.
+    Bls12381G2XmdSha256SswuNu,
+    crate::G2Projective,
+    BLS12381G2_XMD_SHA256_SSWU_NU,
+    false,
+    None
+);
+
+ciphersuite!(
+    /// The ciphersuite of the basic BLS signature scheme with signatures in
+    /// $\mathbb{G}_2$, `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_`.
+    BlsSigBasic,
+    crate::G2Projective,
+    BLS12381G2_XMD_SHA256_SSWU_RO,
+    true,
+    Some(BLS_SIG_BASIC_DST)
+);
+
+ciphersuite!(
+    /// The ciphersuite of the BLS signature scheme with message augmentation and
+    /// signatures in $\mathbb{G}_2$, `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG_`.
+    BlsSigAug,
+    crate::G2Projective,
+    BLS12381G2_XMD_SHA256_SSWU_RO,
+    true,
+    Some(BLS_SIG_AUG_DST)
+);
+
+ciphersuite!(
+    /// The ciphersuite of the BLS signature scheme with proofs of possession and
+    /// signatures in $\mathbb{G}_2$, `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_`.
+    BlsSigPop,
+    crate::G2Projective,
+    BLS12381G2_XMD_SHA256_SSWU_RO,
+    true,
+    Some(BLS_SIG_POP_DST)
+);
+
+ciphersuite!(
+    /// The ciphersuite of the proofs of possession in [`BlsSigPop`],
+    /// `BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_`.
+    BlsPopProof,
+    crate::G2Projective,
+    BLS12381G2_XMD_SHA256_SSWU_RO,
+    true,
+    Some(BLS_POP_PROOF_DST)
+);
+
+#[cfg(all(test, feature = "sha2"))]
+mod tests {
+    use super::*;
+    use crate::hash_to_curve::ExpandMsgXmd;
+    use crate::{G1Projective, G2Projective};
+    use sha2::Sha256;
+
+    type Xmd = ExpandMsgXmd<Sha256>;
+
+    #[test]
+    fn test_hash_to_curve_suites() {
+        let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+        assert_eq!(
+            Bls12381G1XmdSha256SswuRo::hash(b"abc", dst),
+            <G1Projective as HashToCurve<Xmd>>::hash_to_curve(b"abc", dst)
+        );
+        assert_eq!(
+            Bls12381G1XmdSha256SswuNu::hash(b"abc", dst),
+            <G1Projective as HashToCurve<Xmd>>::encode_to_curve(b"abc", dst)
+        );
+        assert_eq!(
+            Bls12381G2XmdSha256SswuRo::hash(b"abc", dst),
+            <G2Projective as HashToCurve<Xmd>>::hash_to_curve(b"abc", dst)
+        );
+        assert_eq!(
+            Bls12381G2XmdSha256SswuNu::hash(b"abc", dst),
+            <G2Projective as HashToCurve<Xmd>>::encode_to_curve(b"abc", dst)
+        );
+    }
+
+    #[test]
+    fn test_signature_suites() {
+        assert_eq!(Bls12381G2XmdSha256SswuRo::DST, None);
+        for (id, dst) in [
+            (BlsSigBasic::ID, BlsSigBasic::DST.unwrap()),
+            (BlsSigAug::ID, BlsSigAug::DST.unwrap()),
+            (BlsSigPop::ID, BlsSigPop::DST.unwrap()),
+            (BlsPopProof::ID, BlsPopProof::DST.unwrap()),
+        ] {
+            // Each tag contains the identifier of the suite it uses.
+            assert!(dst.windows(id.len()).any(|w| w == id));
+        }
+        assert_eq!(
+            BlsSigBasic::hash(b"abc", BlsSigBasic::DST.unwrap()),
+            <G2Projective as HashToCurve<Xmd>>::hash_to_curve(
+                b"abc",
+                b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_"
+            )
+        );
+
+        #[cfg(all(feature = "pairings", feature = "alloc"))]
+        {
+            assert_eq!(BlsSigPop::DST, Some(crate::signature::pop::DST));
+            assert_eq!(BlsPopProof::DST, Some(crate::signature::pop::POP_DST));
+            assert_eq!(BlsSigAug::DST, Some(crate::signature::aug::DST));
+        }
+    }
+
+    #[test]
+    fn test_ciphersuite_ids() {
+        fn check<S: Ciphersuite>(suite: CipherSuiteId, group: SuiteGroup) {
+            assert_eq!(suite.id(), S::ID);
+            assert_eq!(suite.random_oracle(), S::RANDOM_ORACLE);
+            assert_eq!(suite.dst(), S::DST);
+            assert_eq!(suite.group(), group);
+            assert_eq!(suite.expander(), SuiteExpander::Xmd);
+            assert_eq!(suite.hash(), SuiteHash::Sha256);
+        }
+
+        check::<Bls12381G1XmdSha256SswuRo>(
+            CipherSuiteId::Bls12381G1XmdSha256SswuRo,
+            SuiteGroup::G1,
+        );
+        check::<Bls12381G1XmdSha256SswuNu>(
+            CipherSuiteId::Bls12381G1XmdSha256SswuNu,
+            SuiteGroup::G1,
+        );
+        check::<Bls12381G2XmdSha256SswuRo>(
+            CipherSuiteId::Bls12381G2XmdSha256SswuRo,
+            SuiteGroup::G2,
+        );
+        check::<Bls12381G2XmdSha256SswuNu>(
+            CipherSuiteId::Bls12381G2XmdSha256SswuNu,
+            SuiteGroup::G2,
+        );
+        check::<BlsSigBasic>(CipherSuiteId::BlsSigBasic, SuiteGroup::G2);
+        check::<BlsSigAug>(CipherSuiteId::BlsSigAug, SuiteGroup::G2);
+        check::<BlsSigPop>(CipherSuiteId::BlsSigPop, SuiteGroup::G2);
+        check::<BlsPopProof>(CipherSuiteId::BlsPopProof, SuiteGroup::G2);
+
+        assert_eq!(
+            CipherSuiteId::from_dst(BLS_SIG_POP_DST),
+            Some(CipherSuiteId::BlsSigPop)
+        );
+        assert_eq!(
+            CipherSuiteId::from_dst(b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP"),
+            None
+        );
+        assert_eq!(CipherSuiteId::from_dst(BLS12381G2_XMD_SHA256_SSWU_RO), None);
+    }
+}
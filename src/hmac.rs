@@ -0,0 +1,165 @@
+//! HMAC, as specified in [RFC 2104], and the HKDF key derivation function built on
+//! it, as specified in [RFC 5869], over the digests of the `digest` crate.
+//!
+//! [RFC 2104]: https://www.rfc-editor.org/rfc/rfc2104
+//! [RFC 5869]: https://www.rfc-editor.org/rfc/rfc5869
+
+use digest::{BlockInput, Digest};
+
+use crate::generic_array::{typenum::Unsigned, GenericArray};
+
+/// An incremental computation of $\mathrm{HMAC}_H(K, m)$.
+pub(crate) struct Hmac<H: Digest + BlockInput> {
+    inner: H,
+    outer_key: GenericArray<u8, H::BlockSize>,
+}
+
+impl<H: Digest + BlockInput> Hmac<H> {
+    /// Starts computing the HMAC of a message under `key`.
+    pub(crate) fn new(key: &[u8]) -> Self {
+        // Keys longer than a block are hashed, and shorter ones padded with zeros.
+        let mut padded = GenericArray::<u8, H::BlockSize>::default();
+        if key.len() > H::BlockSize::to_usize() {
+            let hashed = H::digest(key);
+            padded[..hashed.len()].copy_from_slice(&hashed);
+        } else {
+            padded[..key.len()].copy_from_slice(key);
+        }
+
+        for b in padded.iter_mut() {
+            *b ^= 0x36;
+        }
+        let inner = H::new().chain(&padded);
+        for b in padded.iter_mut() {
+            *b ^= 0x36 ^ 0x5c;
+        }
+
+        Hmac {
+            inner,
+            outer_key: padded,
+        }
+    }
+
+    /// Appends `data` to the message.
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Returns the HMAC of the message.
+    pub(crate) fn finalize(self) -> GenericArray<u8, H::OutputSize> {
+        H::new()
+            .chain(&self.outer_key)
+            .chain(self.inner.finalize())
+            .finalize()
+    }
+}
+
+/// Computes $\mathrm{HMAC}_H(K, m)$ for the key `key` and the concatenation $m$ of
+/// `parts`.
+pub(crate) fn hmac<H>(key: &[u8], parts: &[&[u8]]) -> GenericArray<u8, H::OutputSize>
+where
+    H: Digest + BlockInput,
+{
+    let mut mac = Hmac::<H>::new(key);
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize()
+}
+
+/// Computes the pseudorandom key $\mathrm{HKDF\text{-}Extract}(salt, IKM)$ for the
+/// input keying material given as the concatenation of `ikm`.
+pub(crate) fn hkdf_extract<H>(salt: &[u8], ikm: &[&[u8]]) -> GenericArray<u8, H::OutputSize>
+where
+    H: Digest + BlockInput,
+{
+    hmac::<H>(salt, ikm)
+}
+
+/// Fills `okm` with $\mathrm{HKDF\text{-}Expand}(PRK, info, L)$ for the pseudorandom
+/// key `prk`, the concatenation of `info`, and the length $L$ of `okm`.
+///
+/// # Panics
+///
+/// Panics if `okm` is longer than 255 outputs of `H`.
+pub(crate) fn hkdf_expand<H>(prk: &[u8], info: &[&[u8]], okm: &mut [u8])
+where
+    H: Digest + BlockInput,
+{
+    let hash_len = H::OutputSize::to_usize();
+    assert!(okm.len() <= 255 * hash_len);
+
+    // T(i) = HMAC(PRK, T(i - 1) || info || i), with T(0) empty.
+    let mut t = GenericArray::<u8, H::OutputSize>::default();
+    for (i, chunk) in okm.chunks_mut(hash_len).enumerate() {
+        let mut mac = Hmac::<H>::new(prk);
+        if i > 0 {
+            mac.update(&t);
+        }
+        for part in info {
+            mac.update(part);
+        }
+        mac.update(&[i as u8 + 1]);
+        t = mac.finalize();
+        chunk.copy_from_slice(&t[..chunk.len()]);
+    }
+}
+
+#[cfg(all(test, feature = "sha2"))]
+mod tests {
+    use super::*;
+    use sha2::Sha256;
+
+    fn hex(s: &str) -> std::vec::Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_rfc4231_vectors() {
+        // Test cases 2 and 6 of RFC 4231, with a short key and one longer than a
+        // block.
+        assert_eq!(
+            hmac::<Sha256>(b"Jefe", &[b"what do ya want ", b"for nothing?"])[..],
+            hex("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")[..]
+        );
+        assert_eq!(
+            hmac::<Sha256>(
+                &[0xaa; 131],
+                &[b"Test Using Larger Than Block-Size Key - Hash Key First"]
+            )[..],
+            hex("60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54")[..]
+        );
+    }
+
+    #[test]
+    fn test_rfc5869_vectors() {
+        // Test cases 1 and 3 of RFC 5869.
+        let prk = hkdf_extract::<Sha256>(&hex("000102030405060708090a0b0c"), &[&[0x0b; 22]]);
+        assert_eq!(
+            prk[..],
+            hex("077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5")[..]
+        );
+        let mut okm = [0u8; 42];
+        hkdf_expand::<Sha256>(&prk, &[&hex("f0f1f2f3f4"), &hex("f5f6f7f8f9")], &mut okm);
+        assert_eq!(
+            okm[..],
+            hex(concat!(
+                "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf",
+                "34007208d5b887185865"
+            ))[..]
+        );
+
+        let prk = hkdf_extract::<Sha256>(&[], &[&[0x0b; 22]]);
+        hkdf_expand::<Sha256>(&prk, &[], &mut okm);
+        assert_eq!(
+            okm[..],
+            hex(concat!(
+                "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d",
+                "9d201395faa4b61a96c8"
+            ))[..]
+        );
+    }
+}
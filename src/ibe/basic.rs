@@ -0,0 +1,123 @@
+//! The `BasicIdent` variant of Boneh-Franklin IBE.
+//!
+//! A message is encrypted by masking it with a key stream derived from the session
+//! key. This variant is malleable and only secure against chosen-plaintext attacks;
+//! use [`full`](super::full) unless the ciphertexts are otherwise authenticated.
+
+use ff::Field;
+use rand_core::RngCore;
+
+use super::{apply_mask, decapsulate, encapsulate, SESSION_KEY_DST};
+use crate::hash_to_curve::ExpandMessage;
+use crate::{G1Affine, G2Affine, Scalar};
+
+use alloc::vec::Vec;
+
+/// A `BasicIdent` ciphertext.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ciphertext {
+    u: G1Affine,
+    v: Vec<u8>,
+}
+
+impl Ciphertext {
+    /// Serializes this ciphertext as the compressed encoding of its point in
+    /// $\mathbb{G}_1$ followed by the masked message.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(48 + self.v.len());
+        bytes.extend_from_slice(&self.u.to_compressed());
+        bytes.extend_from_slice(&self.v);
+        bytes
+    }
+
+    /// Attempts to deserialize a ciphertext produced by [`Ciphertext::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 48 {
+            return None;
+        }
+
+        let mut u = [0u8; 48];
+        u.copy_from_slice(&bytes[..48]);
+        Option::from(G1Affine::from_compressed(&u)).map(|u| Ciphertext {
+            u,
+            v: bytes[48..].to_vec(),
+        })
+    }
+}
+
+/// Encrypts `message` to `identity` under the master public key `mpk`.
+pub fn encrypt<X: ExpandMessage, R: RngCore>(
+    mpk: &G1Affine,
+    identity: &[u8],
+    message: &[u8],
+    mut rng: R,
+) -> Ciphertext {
+    let r = Scalar::random(&mut rng);
+    let (u, key) = encapsulate::<X>(mpk, identity, &r);
+
+    let mut v = message.to_vec();
+    apply_mask::<X>(&key.to_compressed(), SESSION_KEY_DST, &mut v);
+
+    Ciphertext { u, v }
+}
+
+/// Decrypts `ciphertext` with the private key of the identity it was encrypted to.
+///
+/// Decrypting with the wrong key produces an unrelated message; this variant cannot
+/// detect it.
+pub fn decrypt<X: ExpandMessage>(identity_key: &G2Affine, ciphertext: &Ciphertext) -> Vec<u8> {
+    let key = decapsulate(identity_key, &ciphertext.u);
+
+    let mut message = ciphertext.v.clone();
+    apply_mask::<X>(&key.to_compressed(), SESSION_KEY_DST, &mut message);
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_to_curve::ExpandMsgXmd;
+    use crate::ibe::{extract, setup};
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    type Xmd = ExpandMsgXmd<sha2::Sha256>;
+
+    fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ])
+    }
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let mut rng = rng();
+        let (msk, mpk) = setup(&mut rng);
+        let alice = extract::<Xmd>(&msk, b"alice@example.com");
+        let bob = extract::<Xmd>(&msk, b"bob@example.com");
+
+        let message = b"attack at dawn";
+        let ciphertext = encrypt::<Xmd, _>(&mpk, b"alice@example.com", message, &mut rng);
+        assert_ne!(&ciphertext.v[..], &message[..]);
+        assert_eq!(decrypt::<Xmd>(&alice, &ciphertext), message);
+        assert_ne!(decrypt::<Xmd>(&bob, &ciphertext), message);
+
+        let bytes = ciphertext.to_bytes();
+        assert_eq!(Ciphertext::from_bytes(&bytes), Some(ciphertext));
+        assert_eq!(Ciphertext::from_bytes(&bytes[..47]), None);
+    }
+
+    #[test]
+    fn test_long_messages() {
+        let mut rng = rng();
+        let (msk, mpk) = setup(&mut rng);
+        let key = extract::<Xmd>(&msk, b"carol");
+
+        for len in [0, 1, 4096, 10_000] {
+            let message = (0..len).map(|i| i as u8).collect::<Vec<_>>();
+            let ciphertext = encrypt::<Xmd, _>(&mpk, b"carol", &message, &mut rng);
+            assert_eq!(decrypt::<Xmd>(&key, &ciphertext), message);
+        }
+    }
+}
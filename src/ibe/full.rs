@@ -0,0 +1,178 @@
+//! The `FullIdent` variant of Boneh-Franklin IBE.
+//!
+//! [`basic`](super::basic) encryption is made secure against chosen-ciphertext attacks
+//! with the Fujisaki-Okamoto transform: a random $\sigma$ is encrypted under the
+//! session key, the message is masked with a key stream derived from $\sigma$, and
+//! the encryption randomness is derived from both. Decryption recomputes the
+//! randomness and rejects ciphertexts that were not honestly generated.
+
+use rand_core::RngCore;
+use subtle::ConstantTimeEq;
+
+use super::{apply_mask, decapsulate, encapsulate, SESSION_KEY_DST};
+use crate::hash_to_curve::{ExpandMessage, HashToField};
+use crate::{G1Affine, G2Affine, Scalar};
+
+use alloc::vec::Vec;
+
+/// The domain separation tag used to derive the encryption randomness from $\sigma$
+/// and the message.
+pub const RANDOMNESS_DST: &[u8] = b"BF-IBE_BLS12381G2_XMD:SHA-256_SSWU_RO_RAND_";
+
+/// The domain separation tag used to derive the mask of the message from $\sigma$.
+pub const MESSAGE_DST: &[u8] = b"BF-IBE_BLS12381G2_XMD:SHA-256_SSWU_RO_MSG_";
+
+/// The length in bytes of $\sigma$.
+const SIGMA_LEN: usize = 32;
+
+/// A `FullIdent` ciphertext.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ciphertext {
+    u: G1Affine,
+    v: [u8; SIGMA_LEN],
+    w: Vec<u8>,
+}
+
+impl Ciphertext {
+    /// Serializes this ciphertext as the compressed encoding of its point in
+    /// $\mathbb{G}_1$, followed by the masked $\sigma$ and the masked message.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(48 + SIGMA_LEN + self.w.len());
+        bytes.extend_from_slice(&self.u.to_compressed());
+        bytes.extend_from_slice(&self.v);
+        bytes.extend_from_slice(&self.w);
+        bytes
+    }
+
+    /// Attempts to deserialize a ciphertext produced by [`Ciphertext::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 48 + SIGMA_LEN {
+            return None;
+        }
+
+        let mut u = [0u8; 48];
+        u.copy_from_slice(&bytes[..48]);
+        let mut v = [0u8; SIGMA_LEN];
+        v.copy_from_slice(&bytes[48..48 + SIGMA_LEN]);
+        Option::from(G1Affine::from_compressed(&u)).map(|u| Ciphertext {
+            u,
+            v,
+            w: bytes[48 + SIGMA_LEN..].to_vec(),
+        })
+    }
+}
+
+/// Derives the encryption randomness from $\sigma$ and the message.
+fn randomness<X: ExpandMessage>(sigma: &[u8; SIGMA_LEN], message: &[u8]) -> Scalar {
+    let mut input = Vec::with_capacity(SIGMA_LEN + message.len());
+    input.extend_from_slice(sigma);
+    input.extend_from_slice(message);
+
+    let mut r = [Scalar::zero()];
+    Scalar::hash_to_field::<X>(&input, RANDOMNESS_DST, &mut r);
+    r[0]
+}
+
+/// Encrypts `message` to `identity` under the master public key `mpk`.
+pub fn encrypt<X: ExpandMessage, R: RngCore>(
+    mpk: &G1Affine,
+    identity: &[u8],
+    message: &[u8],
+    mut rng: R,
+) -> Ciphertext {
+    let mut sigma = [0u8; SIGMA_LEN];
+    rng.fill_bytes(&mut sigma);
+
+    let r = randomness::<X>(&sigma, message);
+    let (u, key) = encapsulate::<X>(mpk, identity, &r);
+
+    let mut v = sigma;
+    apply_mask::<X>(&key.to_compressed(), SESSION_KEY_DST, &mut v);
+    let mut w = message.to_vec();
+    apply_mask::<X>(&sigma, MESSAGE_DST, &mut w);
+
+    Ciphertext { u, v, w }
+}
+
+/// Decrypts `ciphertext` with the private key of the identity it was encrypted to.
+///
+/// Returns `None` if the ciphertext was not produced by [`encrypt`] for this
+/// identity, including when it has been tampered with.
+pub fn decrypt<X: ExpandMessage>(
+    identity_key: &G2Affine,
+    ciphertext: &Ciphertext,
+) -> Option<Vec<u8>> {
+    let key = decapsulate(identity_key, &ciphertext.u);
+
+    let mut sigma = ciphertext.v;
+    apply_mask::<X>(&key.to_compressed(), SESSION_KEY_DST, &mut sigma);
+    let mut message = ciphertext.w.clone();
+    apply_mask::<X>(&sigma, MESSAGE_DST, &mut message);
+
+    let r = randomness::<X>(&sigma, &message);
+    let u = G1Affine::from(G1Affine::generator() * r);
+    if bool::from(u.ct_eq(&ciphertext.u)) {
+        Some(message)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_to_curve::ExpandMsgXmd;
+    use crate::ibe::{extract, setup};
+    use crate::G1Projective;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    type Xmd = ExpandMsgXmd<sha2::Sha256>;
+
+    fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ])
+    }
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let mut rng = rng();
+        let (msk, mpk) = setup(&mut rng);
+        let alice = extract::<Xmd>(&msk, b"alice@example.com");
+        let bob = extract::<Xmd>(&msk, b"bob@example.com");
+
+        let message = b"attack at dawn";
+        let ciphertext = encrypt::<Xmd, _>(&mpk, b"alice@example.com", message, &mut rng);
+        assert_eq!(decrypt::<Xmd>(&alice, &ciphertext).unwrap(), message);
+        assert_eq!(decrypt::<Xmd>(&bob, &ciphertext), None);
+
+        let bytes = ciphertext.to_bytes();
+        assert_eq!(Ciphertext::from_bytes(&bytes), Some(ciphertext));
+        assert_eq!(Ciphertext::from_bytes(&bytes[..79]), None);
+
+        let empty = encrypt::<Xmd, _>(&mpk, b"alice@example.com", b"", &mut rng);
+        assert_eq!(decrypt::<Xmd>(&alice, &empty).unwrap(), b"");
+    }
+
+    #[test]
+    fn test_tampering() {
+        let mut rng = rng();
+        let (msk, mpk) = setup(&mut rng);
+        let key = extract::<Xmd>(&msk, b"alice");
+        let ciphertext = encrypt::<Xmd, _>(&mpk, b"alice", b"pay 10 coins", &mut rng);
+
+        let mut tampered = ciphertext.clone();
+        tampered.w[4] ^= 1;
+        assert_eq!(decrypt::<Xmd>(&key, &tampered), None);
+
+        let mut tampered = ciphertext.clone();
+        tampered.v[0] ^= 1;
+        assert_eq!(decrypt::<Xmd>(&key, &tampered), None);
+
+        let mut tampered = ciphertext;
+        tampered.u = G1Affine::from(G1Projective::from(tampered.u) + G1Affine::generator());
+        assert_eq!(decrypt::<Xmd>(&key, &tampered), None);
+    }
+}
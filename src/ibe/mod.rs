@@ -0,0 +1,94 @@
+//! This module implements the identity-based encryption scheme of
+//! [Boneh and Franklin][bf-ibe].
+//!
+//! A private key generator holds a master secret key $s$ and publishes the master
+//! public key $s \cdot g_1 \in \mathbb{G}_1$. Identities are hashed to
+//! $\mathbb{G}_2$ using [`HashToCurve`] with the [`ExpandMessage`] variant `X` chosen
+//! by the caller, and the private key of an identity is its hash multiplied by $s$.
+//! Ciphertexts carry a point in $\mathbb{G}_1$ from which, together with the
+//! identity's private key, the recipient recovers a session key in $\mathbb{G}_T$.
+//!
+//! Two variants are provided:
+//!
+//! * [`basic`]: `BasicIdent`, which is only secure against chosen-plaintext attacks.
+//! * [`full`]: `FullIdent`, which applies the Fujisaki-Okamoto transform to be
+//!   secure against chosen-ciphertext attacks. This should be preferred.
+//!
+//! The domain separation tags assume that `X` is [`ExpandMsgXmd`] instantiated with
+//! SHA-256.
+//!
+//! [bf-ibe]: https://crypto.stanford.edu/~dabo/papers/bfibe.pdf
+//! [`ExpandMsgXmd`]: crate::hash_to_curve::ExpandMsgXmd
+
+use ff::Field;
+use rand_core::RngCore;
+
+use crate::hash_to_curve::{ExpandMessage, ExpandMessageState, HashToCurve};
+use crate::{pairing, G1Affine, G2Affine, G2Projective, Gt, Scalar};
+
+use alloc::vec::Vec;
+
+pub mod basic;
+pub mod full;
+
+/// The domain separation tag used to hash identities to $\mathbb{G}_2$.
+pub const IDENTITY_DST: &[u8] = b"BF-IBE_BLS12381G2_XMD:SHA-256_SSWU_RO_ID_";
+
+/// The domain separation tag used to derive the mask of the message from the session
+/// key.
+pub const SESSION_KEY_DST: &[u8] = b"BF-IBE_BLS12381G2_XMD:SHA-256_SSWU_RO_KEY_";
+
+/// The number of bytes of mask derived from each call to the [`ExpandMessage`]
+/// variant.
+const MASK_BLOCK_LEN: usize = 4096;
+
+/// Generates a random master secret key and the corresponding master public key.
+pub fn setup(mut rng: impl RngCore) -> (Scalar, G1Affine) {
+    let msk = Scalar::random(&mut rng);
+    (msk, G1Affine::from(G1Affine::generator() * msk))
+}
+
+/// Hashes `identity` to $\mathbb{G}_2$.
+fn hash_identity<X: ExpandMessage>(identity: &[u8]) -> G2Projective {
+    <G2Projective as HashToCurve<X>>::hash_to_curve(identity, IDENTITY_DST)
+}
+
+/// Extracts the private key of `identity` from the master secret key `msk`.
+pub fn extract<X: ExpandMessage>(msk: &Scalar, identity: &[u8]) -> G2Affine {
+    G2Affine::from(hash_identity::<X>(identity) * msk)
+}
+
+/// Returns the point to include in the ciphertext for encryption randomness `r`,
+/// and the session key it shares with the holder of the private key of `identity`.
+fn encapsulate<X: ExpandMessage>(mpk: &G1Affine, identity: &[u8], r: &Scalar) -> (G1Affine, Gt) {
+    let q = G2Affine::from(hash_identity::<X>(identity));
+    let u = G1Affine::from(G1Affine::generator() * r);
+    let key = pairing(&G1Affine::from(mpk * r), &q);
+    (u, key)
+}
+
+/// Recovers the session key from the point `u` of a ciphertext.
+fn decapsulate(identity_key: &G2Affine, u: &G1Affine) -> Gt {
+    pairing(u, identity_key)
+}
+
+/// XORs into `data` a mask derived from `seed` under `dst`.
+///
+/// The mask is produced in blocks of [`MASK_BLOCK_LEN`] bytes, each expanded from
+/// `seed` and the big-endian block index, so that `data` may be of any length.
+fn apply_mask<X: ExpandMessage>(seed: &[u8], dst: &[u8], data: &mut [u8]) {
+    let mut input = Vec::with_capacity(seed.len() + 4);
+    let mut mask = [0u8; MASK_BLOCK_LEN];
+
+    for (i, block) in data.chunks_mut(MASK_BLOCK_LEN).enumerate() {
+        input.clear();
+        input.extend_from_slice(seed);
+        input.extend_from_slice(&(i as u32).to_be_bytes());
+
+        let mask = &mut mask[..block.len()];
+        X::init_expand(&input, dst, block.len()).read_into(mask);
+        for (b, m) in block.iter_mut().zip(mask.iter()) {
+            *b ^= m;
+        }
+    }
+}
@@ -0,0 +1,622 @@
+//! KZG commitments to blobs, as specified for Ethereum by [EIP-4844].
+//!
+//! A blob is a sequence of field elements, each encoded in 32 big-endian bytes, that
+//! are the evaluations of a polynomial over the roots of unity of the evaluation
+//! domain in bit-reversed order. Commitments and proofs are computed in evaluation
+//! form, with the Lagrange basis of the [`TrustedSetup`], and use the same encodings
+//! as the [consensus specs]. The commitments are variable-time multi-scalar
+//! multiplications, as blobs are public.
+//!
+//! `compute_blob_kzg_proof` and `verify_blob_kzg_proof`, with the `sha2`
+//! feature, prove and check the evaluation of a blob at the Fiat-Shamir challenge
+//! that the specs derive from the blob and its commitment with SHA-256.
+//!
+//! [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+//! [consensus specs]: https://github.com/ethereum/consensus-specs/blob/dev/specs/deneb/polynomial-commitments.md
+
+use core::fmt;
+
+use group::Curve;
+
+use super::{verify, Srs};
+use crate::msm::multi_exp_auto_vartime;
+use crate::poly::{batch_invert, bit_reversal_permutation, LagrangeBasis};
+use crate::{G1Affine, G2Affine, Scalar};
+
+use alloc::vec::Vec;
+
+/// The number of bytes encoding a field element.
+pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
+
+/// The number of field elements in a blob on Ethereum.
+pub const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+
+/// The number of bytes in a blob on Ethereum.
+pub const BYTES_PER_BLOB: usize = BYTES_PER_FIELD_ELEMENT * FIELD_ELEMENTS_PER_BLOB;
+
+/// The number of bytes encoding a commitment or a proof.
+pub const BYTES_PER_COMMITMENT: usize = 48;
+
+/// The domain separator of the Fiat-Shamir challenge of [`compute_blob_kzg_proof`]
+/// and [`verify_blob_kzg_proof`].
+#[cfg(feature = "sha2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sha2")))]
+pub const FIAT_SHAMIR_PROTOCOL_DOMAIN: &[u8] = b"FSBLOBVERIFY_V1_";
+
+/// An error produced by the operations in this module.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The trusted setup could not be parsed.
+    MalformedSetup,
+    /// A point is not the encoding of an element of the prime-order subgroup.
+    InvalidPoint,
+    /// A field element is not the canonical encoding of a scalar.
+    InvalidFieldElement,
+    /// A blob does not have the length expected by the trusted setup.
+    InvalidBlobLength,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::MalformedSetup => "malformed trusted setup",
+            Error::InvalidPoint => "invalid point encoding",
+            Error::InvalidFieldElement => "invalid field element encoding",
+            Error::InvalidBlobLength => "invalid blob length",
+        })
+    }
+}
+
+/// The output of the Ethereum KZG ceremony: an [`Srs`] together with the powers of
+/// $\tau$ in $\mathbb{G}_1$ in the Lagrange basis of the evaluation domain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrustedSetup {
+    srs: Srs,
+    g1_lagrange_brp: Vec<G1Affine>,
+    basis: LagrangeBasis,
+}
+
+/// Decodes a hexadecimal string, with or without a `0x` prefix, into `out`.
+fn decode_hex(s: &str, out: &mut [u8]) -> Option<()> {
+    let s = s.strip_prefix("0x").unwrap_or(s).as_bytes();
+    if s.len() != 2 * out.len() {
+        return None;
+    }
+
+    fn nibble(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'a'..=b'f' => Some(c - b'a' + 10),
+            b'A'..=b'F' => Some(c - b'A' + 10),
+            _ => None,
+        }
+    }
+    for (byte, pair) in out.iter_mut().zip(s.chunks(2)) {
+        *byte = (nibble(pair[0])? << 4) | nibble(pair[1])?;
+    }
+
+    Some(())
+}
+
+impl TrustedSetup {
+    /// Constructs a trusted setup from the powers of $\tau$ in $\mathbb{G}_1$ in the
+    /// Lagrange basis of the evaluation domain, in natural order, and the monomial
+    /// powers in `srs`.
+    ///
+    /// Returns `None` unless there are as many Lagrange as monomial powers in
+    /// $\mathbb{G}_1$, and their number is a power of two that divides the order of
+    /// the multiplicative group of the scalar field. The two sets of powers are not
+    /// checked to be consistent with each other.
+    pub fn new(mut g1_lagrange: Vec<G1Affine>, srs: Srs) -> Option<Self> {
+        if g1_lagrange.len() != srs.g1_powers().len() {
+            return None;
+        }
+
+        let basis = LagrangeBasis::new_bit_reversed(g1_lagrange.len())?;
+        bit_reversal_permutation(&mut g1_lagrange);
+
+        Some(TrustedSetup {
+            srs,
+            g1_lagrange_brp: g1_lagrange,
+            basis,
+        })
+    }
+
+    /// Parses the trusted setup in the text format distributed with the Ethereum KZG
+    /// ceremony, as used by `c-kzg-4844`.
+    ///
+    /// The format consists of whitespace-separated tokens: the number $n$ of points in
+    /// $\mathbb{G}_1$, the number $m$ of points in $\mathbb{G}_2$, then the compressed
+    /// encodings in hexadecimal of the $n$ Lagrange powers in $\mathbb{G}_1$, the $m$
+    /// monomial powers in $\mathbb{G}_2$ and the $n$ monomial powers in
+    /// $\mathbb{G}_1$. Every point is checked to be in the prime-order subgroup.
+    pub fn from_text(text: &str) -> Result<Self, Error> {
+        let mut tokens = text.split_whitespace();
+        let mut count = || -> Result<usize, Error> {
+            tokens
+                .next()
+                .and_then(|t| t.parse().ok())
+                .ok_or(Error::MalformedSetup)
+        };
+        let n = count()?;
+        let m = count()?;
+
+        fn points<'a, P, const N: usize>(
+            tokens: &mut impl Iterator<Item = &'a str>,
+            count: usize,
+            decode: impl Fn(&[u8; N]) -> Option<P>,
+        ) -> Result<Vec<P>, Error> {
+            (0..count)
+                .map(|_| {
+                    let mut bytes = [0u8; N];
+                    tokens
+                        .next()
+                        .and_then(|t| decode_hex(t, &mut bytes))
+                        .ok_or(Error::MalformedSetup)?;
+                    decode(&bytes).ok_or(Error::InvalidPoint)
+                })
+                .collect()
+        }
+        let g1 = |b: &[u8; 48]| G1Affine::from_compressed(b).into();
+        let g2 = |b: &[u8; 96]| G2Affine::from_compressed(b).into();
+
+        let g1_lagrange = points(&mut tokens, n, g1)?;
+        let g2_monomial = points(&mut tokens, m, g2)?;
+        let g1_monomial = points(&mut tokens, n, g1)?;
+        if tokens.next().is_some() {
+            return Err(Error::MalformedSetup);
+        }
+
+        Srs::new(g1_monomial, g2_monomial)
+            .and_then(|srs| TrustedSetup::new(g1_lagrange, srs))
+            .ok_or(Error::MalformedSetup)
+    }
+
+    /// Returns the monomial powers of $\tau$.
+    pub fn srs(&self) -> &Srs {
+        &self.srs
+    }
+
+    /// Returns the number of field elements in a blob, which is
+    /// [`FIELD_ELEMENTS_PER_BLOB`] for the Ethereum ceremony.
+    pub fn field_elements_per_blob(&self) -> usize {
+        self.g1_lagrange_brp.len()
+    }
+
+    /// Returns the number of bytes in a blob.
+    pub fn bytes_per_blob(&self) -> usize {
+        BYTES_PER_FIELD_ELEMENT * self.field_elements_per_blob()
+    }
+
+    /// Computes $\sum_i c_i L_i(\tau) \cdot g_1$ for evaluations $c_i$ in
+    /// bit-reversed order.
+    fn commit_evaluations(&self, evaluations: &[Scalar]) -> G1Affine {
+        multi_exp_auto_vartime(&self.g1_lagrange_brp, evaluations).to_affine()
+    }
+}
+
+/// Decodes a big-endian field element.
+fn scalar_from_bytes(bytes: &[u8]) -> Result<Scalar, Error> {
+    let bytes = <&[u8; 32]>::try_from(bytes).map_err(|_| Error::InvalidFieldElement)?;
+    Option::from(Scalar::from_bytes_be(bytes)).ok_or(Error::InvalidFieldElement)
+}
+
+/// Decodes a compressed commitment or proof, which must be in the prime-order
+/// subgroup but may be the identity.
+fn point_from_bytes(bytes: &[u8; BYTES_PER_COMMITMENT]) -> Result<G1Affine, Error> {
+    Option::from(G1Affine::from_compressed(bytes)).ok_or(Error::InvalidPoint)
+}
+
+/// Decodes a blob into the evaluations of its polynomial.
+fn blob_to_polynomial(setup: &TrustedSetup, blob: &[u8]) -> Result<Vec<Scalar>, Error> {
+    if blob.len() != setup.bytes_per_blob() {
+        return Err(Error::InvalidBlobLength);
+    }
+
+    blob.chunks(BYTES_PER_FIELD_ELEMENT)
+        .map(scalar_from_bytes)
+        .collect()
+}
+
+/// Computes the commitment to `blob`.
+pub fn blob_to_kzg_commitment(
+    setup: &TrustedSetup,
+    blob: &[u8],
+) -> Result<[u8; BYTES_PER_COMMITMENT], Error> {
+    let polynomial = blob_to_polynomial(setup, blob)?;
+    Ok(setup.commit_evaluations(&polynomial).to_compressed())
+}
+
+/// Computes the proof of the evaluation of the polynomial of `blob` at `z`, and
+/// returns it together with the value $y$ of the polynomial there.
+pub fn compute_kzg_proof(
+    setup: &TrustedSetup,
+    blob: &[u8],
+    z: &[u8; BYTES_PER_FIELD_ELEMENT],
+) -> Result<([u8; BYTES_PER_COMMITMENT], [u8; BYTES_PER_FIELD_ELEMENT]), Error> {
+    let polynomial = blob_to_polynomial(setup, blob)?;
+    let (proof, y) = compute_proof(setup, &polynomial, scalar_from_bytes(z)?);
+    Ok((proof.to_compressed(), y.to_bytes_be()))
+}
+
+/// Computes the proof of the evaluation at `z` of the polynomial with the given
+/// evaluations, and the value of the polynomial there.
+fn compute_proof(setup: &TrustedSetup, polynomial: &[Scalar], z: Scalar) -> (G1Affine, Scalar) {
+    let y = setup.basis.evaluate(polynomial, &z);
+    let roots = setup.basis.points();
+
+    // The quotient (p(X) - y) / (X - z) in evaluation form. Where z is itself in the
+    // domain, the quotient's evaluation there is computed from the others.
+    let mut denominators = roots.iter().map(|w| w - z).collect::<Vec<_>>();
+    let in_domain = denominators.iter().position(|d| d == &Scalar::zero());
+    if let Some(m) = in_domain {
+        denominators[m] = Scalar::one();
+    }
+    batch_invert(&mut denominators);
+
+    let mut quotient = polynomial
+        .iter()
+        .zip(denominators.iter())
+        .map(|(p, d)| (p - y) * d)
+        .collect::<Vec<_>>();
+    if let Some(m) = in_domain {
+        let mut weights = roots.iter().map(|w| z * (z - w)).collect::<Vec<_>>();
+        weights[m] = Scalar::one();
+        batch_invert(&mut weights);
+
+        quotient[m] = polynomial
+            .iter()
+            .zip(roots.iter())
+            .zip(weights.iter())
+            .enumerate()
+            .filter(|(i, _)| *i != m)
+            .fold(Scalar::zero(), |acc, (_, ((p, w), d))| {
+                acc + (p - y) * w * d
+            });
+    }
+
+    (setup.commit_evaluations(&quotient), y)
+}
+
+/// Checks that `proof` shows that the polynomial committed to by `commitment` takes
+/// the value `y` at `z`.
+///
+/// Returns an error if any of the inputs is not a valid encoding.
+pub fn verify_kzg_proof(
+    setup: &TrustedSetup,
+    commitment: &[u8; BYTES_PER_COMMITMENT],
+    z: &[u8; BYTES_PER_FIELD_ELEMENT],
+    y: &[u8; BYTES_PER_FIELD_ELEMENT],
+    proof: &[u8; BYTES_PER_COMMITMENT],
+) -> Result<bool, Error> {
+    let commitment = point_from_bytes(commitment)?;
+    let z = scalar_from_bytes(z)?;
+    let y = scalar_from_bytes(y)?;
+    let proof = point_from_bytes(proof)?;
+
+    Ok(verify(&setup.srs, &commitment, &z, &y, &proof).into())
+}
+
+/// Derives the evaluation challenge for `blob` and its `commitment`, by hashing
+/// them with SHA-256 after the domain separator and the number of field elements in
+/// a blob, and reducing the big-endian digest modulo $q$.
+#[cfg(feature = "sha2")]
+fn compute_challenge(
+    setup: &TrustedSetup,
+    blob: &[u8],
+    commitment: &[u8; BYTES_PER_COMMITMENT],
+) -> Scalar {
+    use sha2::{Digest, Sha256};
+
+    let digest = Sha256::new()
+        .chain(FIAT_SHAMIR_PROTOCOL_DOMAIN)
+        .chain((setup.field_elements_per_blob() as u128).to_be_bytes())
+        .chain(blob)
+        .chain(commitment)
+        .finalize();
+
+    let mut wide = [0u8; 64];
+    wide[..32].copy_from_slice(&digest);
+    wide[..32].reverse();
+    Scalar::from_bytes_wide(&wide)
+}
+
+/// Computes the proof of the evaluation of the polynomial of `blob` at the
+/// Fiat-Shamir challenge for `blob` and its `commitment`.
+///
+/// The commitment is only checked to be a valid encoding, not to be the commitment
+/// to `blob`.
+#[cfg(feature = "sha2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sha2")))]
+pub fn compute_blob_kzg_proof(
+    setup: &TrustedSetup,
+    blob: &[u8],
+    commitment: &[u8; BYTES_PER_COMMITMENT],
+) -> Result<[u8; BYTES_PER_COMMITMENT], Error> {
+    point_from_bytes(commitment)?;
+    let polynomial = blob_to_polynomial(setup, blob)?;
+    let z = compute_challenge(setup, blob, commitment);
+    Ok(compute_proof(setup, &polynomial, z).0.to_compressed())
+}
+
+/// Checks that `proof` shows that `commitment` is a commitment to `blob`, by
+/// checking the evaluation of its polynomial at the Fiat-Shamir challenge for
+/// `blob` and `commitment`.
+///
+/// Returns an error if any of the inputs is not a valid encoding.
+#[cfg(feature = "sha2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sha2")))]
+pub fn verify_blob_kzg_proof(
+    setup: &TrustedSetup,
+    blob: &[u8],
+    commitment_bytes: &[u8; BYTES_PER_COMMITMENT],
+    proof: &[u8; BYTES_PER_COMMITMENT],
+) -> Result<bool, Error> {
+    let polynomial = blob_to_polynomial(setup, blob)?;
+    let commitment = point_from_bytes(commitment_bytes)?;
+    let proof = point_from_bytes(proof)?;
+
+    let z = compute_challenge(setup, blob, commitment_bytes);
+    let y = setup.basis.evaluate(&polynomial, &z);
+    Ok(verify(&setup.srs, &commitment, &z, &y, &proof).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg::{commit, open};
+    use crate::poly::Polynomial;
+    use ff::PrimeField;
+
+    const N: usize = 16;
+
+    fn hex(bytes: &[u8]) -> alloc::string::String {
+        bytes.iter().map(|b| alloc::format!("{:02x}", b)).collect()
+    }
+
+    /// Serializes an insecure setup of size `N` in the ceremony's text format.
+    fn setup_text(tau: &Scalar) -> alloc::string::String {
+        let srs = Srs::insecure_from_tau(tau, N, 3);
+
+        let mut omega = Scalar::root_of_unity();
+        for _ in N.trailing_zeros()..Scalar::S {
+            omega = omega.square();
+        }
+        // L_i(tau) = omega^i (tau^n - 1) / (n (tau - omega^i))
+        let scale =
+            (tau.pow_u64(N as u64) - Scalar::one()) * Scalar::from(N as u64).invert().unwrap();
+        let lagrange = core::iter::successors(Some(Scalar::one()), |w| Some(w * omega))
+            .take(N)
+            .map(|w| {
+                G1Affine::from(G1Affine::generator() * (w * scale * (tau - w).invert().unwrap()))
+            });
+
+        let mut text = alloc::format!("{}\n{}\n", N, srs.g2_powers().len());
+        for p in lagrange {
+            text += &hex(&p.to_compressed());
+            text += "\n";
+        }
+        for p in srs.g2_powers() {
+            text += &hex(&p.to_compressed());
+            text += "\n";
+        }
+        for p in srs.g1_powers() {
+            text += &hex(&p.to_compressed());
+            text += "\n";
+        }
+        text
+    }
+
+    fn coefficients() -> Vec<Scalar> {
+        (0..N as u64).map(|i| Scalar::from(i * 7 + 1)).collect()
+    }
+
+    /// Encodes the blob of the polynomial with the given coefficients.
+    fn blob(setup: &TrustedSetup, coefficients: &[Scalar]) -> Vec<u8> {
+        let polynomial = Polynomial::from_coefficients(coefficients.to_vec());
+        setup
+            .basis
+            .points()
+            .iter()
+            .flat_map(|w| polynomial.evaluate(w).to_bytes_be())
+            .collect()
+    }
+
+    #[test]
+    fn test_roots_of_unity() {
+        let setup = TrustedSetup::from_text(&setup_text(&Scalar::from(3))).unwrap();
+        let roots = setup.basis.points();
+
+        assert_eq!(roots[0], Scalar::one());
+        assert_eq!(roots[1], -Scalar::one());
+        for w in roots {
+            assert_eq!(w.pow_vartime(&[N as u64, 0, 0, 0]), Scalar::one());
+        }
+        assert_ne!(
+            roots[N / 2].pow_vartime(&[N as u64 / 2, 0, 0, 0]),
+            Scalar::one()
+        );
+    }
+
+    #[test]
+    fn test_blob_kzg() {
+        let tau = Scalar::from(0xdead_beef);
+        let setup = TrustedSetup::from_text(&setup_text(&tau)).unwrap();
+        assert_eq!(setup.field_elements_per_blob(), N);
+        assert_eq!(setup.srs(), &Srs::insecure_from_tau(&tau, N, 3));
+
+        let coefficients = coefficients();
+        let blob = blob(&setup, &coefficients);
+        let commitment = blob_to_kzg_commitment(&setup, &blob).unwrap();
+        assert_eq!(
+            commitment,
+            commit(setup.srs(), &coefficients).unwrap().to_compressed()
+        );
+
+        // Points outside and inside the evaluation domain.
+        for z in [Scalar::from(0x1234_5678), setup.basis.points()[5]] {
+            let z_bytes = z.to_bytes_be();
+            let (proof, y) = compute_kzg_proof(&setup, &blob, &z_bytes).unwrap();
+
+            let (value, expected) = open(setup.srs(), &coefficients, &z).unwrap();
+            assert_eq!(y, value.to_bytes_be());
+            assert_eq!(proof, expected.to_compressed());
+
+            assert_eq!(
+                verify_kzg_proof(&setup, &commitment, &z_bytes, &y, &proof),
+                Ok(true)
+            );
+            let wrong = (value + Scalar::one()).to_bytes_be();
+            assert_eq!(
+                verify_kzg_proof(&setup, &commitment, &z_bytes, &wrong, &proof),
+                Ok(false)
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let setup = TrustedSetup::from_text(&setup_text(&Scalar::from(5))).unwrap();
+        let mut blob = blob(&setup, &coefficients());
+
+        assert_eq!(
+            blob_to_kzg_commitment(&setup, &blob[1..]),
+            Err(Error::InvalidBlobLength)
+        );
+        blob[32..64].copy_from_slice(&[0xff; 32]);
+        assert_eq!(
+            blob_to_kzg_commitment(&setup, &blob),
+            Err(Error::InvalidFieldElement)
+        );
+        assert_eq!(
+            compute_kzg_proof(&setup, &blob, &[0; 32]),
+            Err(Error::InvalidFieldElement)
+        );
+
+        let point = G1Affine::generator().to_compressed();
+        let mut bad_point = point;
+        bad_point[0] ^= 0x80;
+        assert_eq!(
+            verify_kzg_proof(&setup, &bad_point, &[0; 32], &[0; 32], &point),
+            Err(Error::InvalidPoint)
+        );
+        assert_eq!(
+            verify_kzg_proof(&setup, &point, &[0xff; 32], &[0; 32], &point),
+            Err(Error::InvalidFieldElement)
+        );
+    }
+
+    #[test]
+    fn test_malformed_setup() {
+        let text = setup_text(&Scalar::from(7));
+        assert!(TrustedSetup::from_text(&text).is_ok());
+
+        assert_eq!(
+            TrustedSetup::from_text(&text[..text.len() - 10]),
+            Err(Error::MalformedSetup)
+        );
+        assert_eq!(
+            TrustedSetup::from_text(&(text.clone() + "00")),
+            Err(Error::MalformedSetup)
+        );
+        assert_eq!(
+            TrustedSetup::from_text(&text.replacen("16\n", "15\n", 1)),
+            Err(Error::MalformedSetup)
+        );
+
+        // Clearing the compression flag of the first Lagrange point invalidates it.
+        let mut lines = text
+            .lines()
+            .map(alloc::string::String::from)
+            .collect::<Vec<_>>();
+        lines[2].replace_range(0..1, "0");
+        assert_eq!(
+            TrustedSetup::from_text(&lines.join("\n")),
+            Err(Error::InvalidPoint)
+        );
+    }
+
+    /// Parses the trusted setup of the Ethereum KZG ceremony.
+    fn ceremony_setup() -> TrustedSetup {
+        let setup = TrustedSetup::from_text(include_str!("../tests/eip4844_trusted_setup.txt"));
+        let setup = setup.unwrap();
+        assert_eq!(setup.field_elements_per_blob(), FIELD_ELEMENTS_PER_BLOB);
+        setup
+    }
+
+    #[cfg(feature = "sha2")]
+    fn unhex(s: &str) -> Option<Vec<u8>> {
+        let mut out = alloc::vec![0u8; s.len() / 2];
+        decode_hex(s, &mut out).map(|()| out)
+    }
+
+    fn unhex_array<const N: usize>(s: &str) -> Option<[u8; N]> {
+        let mut out = [0u8; N];
+        decode_hex(s, &mut out).map(|()| out)
+    }
+
+    /// Parses the expected output of a test vector, where `null` means that the
+    /// inputs are invalid.
+    fn expected_output(s: &str) -> Option<bool> {
+        match s {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// The `verify_kzg_proof` vectors of the consensus spec tests, one per line as
+    /// the commitment, `z`, `y` and the proof in hexadecimal, where `-` is a missing
+    /// input, and the expected output.
+    #[test]
+    fn test_verify_kzg_proof_vectors() {
+        let setup = ceremony_setup();
+        let vectors = include_str!("../tests/eip4844_verify_kzg_proof_test_vectors.txt");
+
+        for line in vectors.lines() {
+            let fields = line.split(' ').collect::<Vec<_>>();
+            let result = (|| {
+                let commitment = unhex_array(fields[0])?;
+                let z = unhex_array(fields[1])?;
+                let y = unhex_array(fields[2])?;
+                let proof = unhex_array(fields[3])?;
+                verify_kzg_proof(&setup, &commitment, &z, &y, &proof).ok()
+            })();
+            assert_eq!(result, expected_output(fields[4]), "{}", line);
+        }
+    }
+
+    /// Cases of the `verify_blob_kzg_proof` vectors of the consensus spec tests, one
+    /// per line as the blob, the commitment and the proof in hexadecimal, and the
+    /// expected output: `correct_proof_19b3f3f8c98ea31e`,
+    /// `correct_proof_point_at_infinity_for_twos_poly`,
+    /// `incorrect_proof_19b3f3f8c98ea31e` and `invalid_blob_a3b9ff28507767f8`. The
+    /// correct proofs are also those of the `compute_blob_kzg_proof` vectors, for the
+    /// commitments of the `blob_to_kzg_commitment` vectors.
+    #[test]
+    #[cfg(feature = "sha2")]
+    fn test_blob_kzg_proof_vectors() {
+        let setup = ceremony_setup();
+        let vectors = include_str!("../tests/eip4844_verify_blob_kzg_proof_test_vectors.txt");
+
+        for line in vectors.lines() {
+            let fields = line.split(' ').collect::<Vec<_>>();
+            let blob = unhex(fields[0]).unwrap();
+            let commitment = unhex_array(fields[1]).unwrap();
+            let proof = unhex_array(fields[2]).unwrap();
+            let expected = expected_output(fields[3]);
+
+            assert_eq!(
+                verify_blob_kzg_proof(&setup, &blob, &commitment, &proof).ok(),
+                expected
+            );
+            if expected == Some(true) {
+                assert_eq!(blob_to_kzg_commitment(&setup, &blob), Ok(commitment));
+                assert_eq!(
+                    compute_blob_kzg_proof(&setup, &blob, &commitment),
+                    Ok(proof)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,565 @@
+//! This module implements the KZG polynomial commitment scheme of
+//! [Kate, Zaverucha and Goldberg][kzg].
+//!
+//! Polynomials are represented by their coefficients over [`Scalar`], in order of
+//! increasing degree, and are divided and interpolated as [`Polynomial`]s. A
+//! commitment to a polynomial $p$ is the point
+//! $p(\tau) \cdot g_1 \in \mathbb{G}_1$, where $\tau$ is the secret of a trusted setup that
+//! is only known through its powers in the [`Srs`].
+//!
+//! Commitments and proofs are computed with the variable-time multi-scalar
+//! multiplication of [`crate::msm`], so the polynomials are treated as public.
+//!
+//! [kzg]: https://www.iacr.org/archive/asiacrypt2010/6477178/6477178.pdf
+
+pub mod eip4844;
+
+use core::iter;
+
+use group::{Curve, Group};
+use rand_core::RngCore;
+use subtle::Choice;
+
+use crate::msm::multi_exp_auto_vartime;
+use crate::poly::Polynomial;
+use crate::{multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, Scalar};
+
+use alloc::vec::Vec;
+
+/// A structured reference string: the powers of a secret $\tau$ in both source
+/// groups, $\tau^i \cdot g_1$ for $0 \le i < n$ and $\tau^i \cdot g_2$ for
+/// $0 \le i < m$, where $g_1$ and $g_2$ are the generators.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Srs {
+    g1_powers: Vec<G1Affine>,
+    g2_powers: Vec<G2Affine>,
+}
+
+impl Srs {
+    /// Constructs a reference string from the powers of $\tau$ in each group, for
+    /// example as produced by a trusted setup ceremony.
+    ///
+    /// Returns `None` unless there is at least one power in $\mathbb{G}_1$ and at
+    /// least two in $\mathbb{G}_2$, as required to verify openings, and both lists
+    /// start with the generator. The powers are not otherwise checked to be
+    /// consistent with each other.
+    pub fn new(g1_powers: Vec<G1Affine>, g2_powers: Vec<G2Affine>) -> Option<Self> {
+        if g1_powers.first() != Some(&G1Affine::generator())
+            || g2_powers.len() < 2
+            || g2_powers[0] != G2Affine::generator()
+        {
+            return None;
+        }
+
+        Some(Srs {
+            g1_powers,
+            g2_powers,
+        })
+    }
+
+    /// Computes a reference string with `g1_len` powers in $\mathbb{G}_1$ and
+    /// `g2_len` powers in $\mathbb{G}_2$ from a known $\tau$.
+    ///
+    /// **This is insecure**: anyone who knows $\tau$ can open commitments to arbitrary
+    /// values. It is only intended for tests and benchmarks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `g1_len` is zero or `g2_len` is less than two.
+    pub fn insecure_from_tau(tau: &Scalar, g1_len: usize, g2_len: usize) -> Self {
+        assert!(g1_len > 0 && g2_len >= 2);
+
+        let powers = iter::successors(Some(Scalar::one()), |p| Some(p * tau));
+
+        let g1 = powers
+            .clone()
+            .take(g1_len)
+            .map(|p| G1Projective::generator() * p)
+            .collect::<Vec<_>>();
+        let mut g1_powers = alloc::vec![G1Affine::identity(); g1_len];
+        G1Projective::batch_normalize(&g1, &mut g1_powers);
+
+        let g2_powers = powers
+            .take(g2_len)
+            .map(|p| G2Affine::from(G2Affine::generator() * p))
+            .collect();
+
+        Srs {
+            g1_powers,
+            g2_powers,
+        }
+    }
+
+    /// Returns the powers $\tau^i \cdot g_1$.
+    pub fn g1_powers(&self) -> &[G1Affine] {
+        &self.g1_powers
+    }
+
+    /// Returns the powers $\tau^i \cdot g_2$.
+    pub fn g2_powers(&self) -> &[G2Affine] {
+        &self.g2_powers
+    }
+
+    /// Returns the maximum degree of the polynomials that can be committed to.
+    pub fn max_degree(&self) -> usize {
+        self.g1_powers.len() - 1
+    }
+}
+
+/// Computes $\sum_i c_i \tau^i \cdot g_1$ for the coefficients $c_i$ of `poly`,
+/// which must not have more coefficients than there are powers in $\mathbb{G}_1$.
+fn commit_unchecked(srs: &Srs, poly: &[Scalar]) -> G1Affine {
+    multi_exp_auto_vartime(&srs.g1_powers[..poly.len()], poly).to_affine()
+}
+
+/// Commits to the polynomial with coefficients `poly`.
+///
+/// Returns `None` if the degree of `poly` exceeds [`Srs::max_degree`].
+pub fn commit(srs: &Srs, poly: &[Scalar]) -> Option<G1Affine> {
+    if poly.len() > srs.g1_powers.len() {
+        return None;
+    }
+
+    Some(commit_unchecked(srs, poly))
+}
+
+/// Opens the polynomial with coefficients `poly` at `point`, returning its value
+/// $p(z)$ there and a proof of that evaluation: a commitment to the quotient
+/// $(p(X) - p(z)) / (X - z)$.
+///
+/// Returns `None` if the degree of `poly` exceeds [`Srs::max_degree`].
+pub fn open(srs: &Srs, poly: &[Scalar], point: &Scalar) -> Option<(Scalar, G1Affine)> {
+    if poly.len() > srs.g1_powers.len() {
+        return None;
+    }
+
+    let (quotient, value) = Polynomial::from_coefficients(poly.to_vec()).divide_by_linear(point);
+    Some((value, commit_unchecked(srs, quotient.coefficients())))
+}
+
+/// Checks that $e(A, g_2) \cdot e(-\pi, \tau \cdot g_2) = 1$.
+fn check_opening(srs: &Srs, lhs: G1Projective, proof: G1Projective) -> Choice {
+    let mut g1 = [G1Affine::identity(); 2];
+    G1Projective::batch_normalize(&[lhs, -proof], &mut g1);
+
+    let g2 = G2Prepared::from(G2Affine::generator());
+    let tau_g2 = G2Prepared::from(srs.g2_powers[1]);
+
+    multi_miller_loop(&[(&g1[0], &g2), (&g1[1], &tau_g2)])
+        .final_exponentiation()
+        .is_identity()
+}
+
+/// Checks that `proof` shows that the polynomial committed to by `commitment` takes
+/// the value `value` at `point`.
+///
+/// This checks that $e(C - v \cdot g_1, g_2) = e(\pi, (\tau - z) \cdot g_2)$, computed as
+/// $e(C - v \cdot g_1 + z \cdot \pi, g_2) \cdot e(-\pi, \tau \cdot g_2) = 1$ with a single
+/// multi-Miller loop.
+pub fn verify(
+    srs: &Srs,
+    commitment: &G1Affine,
+    point: &Scalar,
+    value: &Scalar,
+    proof: &G1Affine,
+) -> Choice {
+    let lhs = G1Projective::from(commitment) - G1Affine::generator() * value + proof * point;
+    check_opening(srs, lhs, proof.into())
+}
+
+/// Opens several polynomials at the same `point` with a single proof, returning the
+/// value of each polynomial there and a proof for their linear combination
+/// $\sum_i \gamma^i p_i$, where $\gamma$ is `challenge`.
+///
+/// The challenge must not be known to the prover before it has committed to the
+/// polynomials and learned the point. It is typically derived by hashing the
+/// commitments, the point and the values.
+///
+/// Returns `None` if the degree of any polynomial exceeds [`Srs::max_degree`].
+pub fn open_batch<P: AsRef<[Scalar]>>(
+    srs: &Srs,
+    polys: &[P],
+    point: &Scalar,
+    challenge: &Scalar,
+) -> Option<(Vec<Scalar>, G1Affine)> {
+    let len = polys.iter().map(|p| p.as_ref().len()).max().unwrap_or(0);
+    if len > srs.g1_powers.len() {
+        return None;
+    }
+
+    let polys = polys
+        .iter()
+        .map(|p| Polynomial::from_coefficients(p.as_ref().to_vec()))
+        .collect::<Vec<_>>();
+    let values = polys.iter().map(|p| p.evaluate(point)).collect();
+
+    // Horner's rule gives the combination with powers of the challenge.
+    let combined = polys
+        .iter()
+        .rev()
+        .fold(Polynomial::zero(), |acc, p| acc * challenge + p);
+    let (quotient, _) = combined.divide_by_linear(point);
+
+    Some((values, commit_unchecked(srs, quotient.coefficients())))
+}
+
+/// Checks a proof produced by [`open_batch`] that the polynomials committed to by
+/// `commitments` take the corresponding `values` at `point`.
+///
+/// Returns false if `commitments` and `values` have different lengths.
+pub fn verify_batch(
+    srs: &Srs,
+    commitments: &[G1Affine],
+    point: &Scalar,
+    values: &[Scalar],
+    challenge: &Scalar,
+    proof: &G1Affine,
+) -> Choice {
+    if commitments.len() != values.len() {
+        return Choice::from(0);
+    }
+
+    // Horner's rule gives the combinations with powers of the challenge.
+    let commitment = commitments
+        .iter()
+        .rev()
+        .fold(G1Projective::identity(), |acc, c| acc * challenge + c);
+    let value = values
+        .iter()
+        .rev()
+        .fold(Scalar::zero(), |acc, v| acc * challenge + v);
+
+    let lhs = commitment - G1Affine::generator() * value + proof * point;
+    check_opening(srs, lhs, proof.into())
+}
+
+/// Opens the polynomial with coefficients `poly` at several distinct `points` with a
+/// single proof, returning its value at each point and a commitment to the quotient
+/// $(p(X) - I(X)) / Z(X)$, where $I$ interpolates the values and $Z$ vanishes on the
+/// points.
+///
+/// Verifying the proof with [`verify_multi`] requires an [`Srs`] with more powers in
+/// $\mathbb{G}_2$ than there are points.
+///
+/// Returns `None` if the degree of `poly` exceeds [`Srs::max_degree`], or if the
+/// points are not distinct.
+pub fn open_multi(
+    srs: &Srs,
+    poly: &[Scalar],
+    points: &[Scalar],
+) -> Option<(Vec<Scalar>, G1Affine)> {
+    if poly.len() > srs.g1_powers.len() {
+        return None;
+    }
+
+    let poly = Polynomial::from_coefficients(poly.to_vec());
+    let values = points.iter().map(|z| poly.evaluate(z)).collect::<Vec<_>>();
+    // The values can only be interpolated if the points are distinct.
+    Polynomial::interpolate(points, &values)?;
+
+    let (quotient, _) = poly.divide_by_vanishing(points);
+    Some((values, commit_unchecked(srs, quotient.coefficients())))
+}
+
+/// Checks a proof produced by [`open_multi`] that the polynomial committed to by
+/// `commitment` takes the given `values` at `points`.
+///
+/// This checks that $e(C - I(\tau) \cdot g_1, g_2) = e(\pi, Z(\tau) \cdot g_2)$.
+/// Returns false if `points` and `values` have different lengths, if the points are
+/// not distinct, or if `srs` does not have more powers in $\mathbb{G}_2$ than there
+/// are points or has fewer in $\mathbb{G}_1$. This function is variable time in the
+/// points and values.
+pub fn verify_multi(
+    srs: &Srs,
+    commitment: &G1Affine,
+    points: &[Scalar],
+    values: &[Scalar],
+    proof: &G1Affine,
+) -> Choice {
+    if points.len() >= srs.g2_powers.len() || points.len() > srs.g1_powers.len() {
+        return Choice::from(0);
+    }
+    let interpolation = match Polynomial::interpolate(points, values) {
+        Some(interpolation) => interpolation,
+        None => return Choice::from(0),
+    };
+
+    let lhs = (G1Projective::from(commitment)
+        - G1Projective::from(commit_unchecked(srs, interpolation.coefficients())))
+    .to_affine();
+    let vanishing = Polynomial::vanishing(points);
+    let vanishing = multi_exp_auto_vartime(
+        &srs.g2_powers[..vanishing.coefficients().len()],
+        vanishing.coefficients(),
+    )
+    .to_affine();
+
+    let g2 = G2Prepared::from(G2Affine::generator());
+    let vanishing = G2Prepared::from(vanishing);
+
+    multi_miller_loop(&[(&lhs, &g2), (&-proof, &vanishing)])
+        .final_exponentiation()
+        .is_identity()
+}
+
+/// Checks many independent openings at once, each given as a tuple of a commitment,
+/// a point, the claimed value there and the proof.
+///
+/// The openings are combined with random 128-bit coefficients drawn from `rng` into a
+/// single pairing check, so an invalid opening is only accepted with negligible
+/// probability. Returns true if `openings` is empty.
+pub fn batch_verify<R: RngCore>(
+    srs: &Srs,
+    openings: &[(G1Affine, Scalar, Scalar, G1Affine)],
+    mut rng: R,
+) -> Choice {
+    let mut lhs = G1Projective::identity();
+    let mut proofs = G1Projective::identity();
+    let mut value = Scalar::zero();
+
+    for (commitment, point, v, proof) in openings {
+        let r = Scalar::from_raw([rng.next_u64(), rng.next_u64(), 0, 0]);
+        lhs += commitment * r + proof * (r * point);
+        proofs += proof * r;
+        value += r * v;
+    }
+    lhs -= G1Affine::generator() * value;
+
+    check_opening(srs, lhs, proofs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poly() -> Vec<Scalar> {
+        (1..=8u64).map(|i| Scalar::from(i * i + 3)).collect()
+    }
+
+    fn evaluate(poly: &[Scalar], point: &Scalar) -> Scalar {
+        Polynomial::from_coefficients(poly.to_vec()).evaluate(point)
+    }
+
+    #[test]
+    fn test_srs() {
+        let tau = Scalar::from(0x1234_5678);
+        let srs = Srs::insecure_from_tau(&tau, 4, 2);
+
+        assert_eq!(srs.max_degree(), 3);
+        assert_eq!(srs.g1_powers()[0], G1Affine::generator());
+        assert_eq!(
+            srs.g1_powers()[3],
+            G1Affine::from(G1Affine::generator() * tau.pow_vartime(&[3, 0, 0, 0]))
+        );
+        assert_eq!(
+            srs.g2_powers()[1],
+            G2Affine::from(G2Affine::generator() * tau)
+        );
+
+        let rebuilt = Srs::new(srs.g1_powers().to_vec(), srs.g2_powers().to_vec());
+        assert_eq!(rebuilt, Some(srs.clone()));
+        assert!(Srs::new(srs.g1_powers().to_vec(), srs.g2_powers()[..1].to_vec()).is_none());
+        assert!(Srs::new(Vec::new(), srs.g2_powers().to_vec()).is_none());
+        assert!(Srs::new(srs.g1_powers()[1..].to_vec(), srs.g2_powers().to_vec()).is_none());
+    }
+
+    #[test]
+    fn test_commit_open_verify() {
+        let tau = Scalar::from(0xdead_beef);
+        let srs = Srs::insecure_from_tau(&tau, 8, 2);
+        let poly = poly();
+
+        let commitment = commit(&srs, &poly).unwrap();
+        assert_eq!(
+            commitment,
+            G1Affine::from(G1Affine::generator() * evaluate(&poly, &tau))
+        );
+
+        let point = Scalar::from(42);
+        let (value, proof) = open(&srs, &poly, &point).unwrap();
+        assert_eq!(value, evaluate(&poly, &point));
+        assert!(bool::from(verify(
+            &srs,
+            &commitment,
+            &point,
+            &value,
+            &proof
+        )));
+
+        assert!(!bool::from(verify(
+            &srs,
+            &commitment,
+            &point,
+            &(value + Scalar::one()),
+            &proof
+        )));
+        assert!(!bool::from(verify(
+            &srs,
+            &commitment,
+            &(point + Scalar::one()),
+            &value,
+            &proof
+        )));
+
+        // Constant polynomials open with the identity as proof.
+        let (value, proof) = open(&srs, &poly[..1], &point).unwrap();
+        assert_eq!(value, poly[0]);
+        assert_eq!(proof, G1Affine::identity());
+        let commitment = commit(&srs, &poly[..1]).unwrap();
+        assert!(bool::from(verify(
+            &srs,
+            &commitment,
+            &point,
+            &value,
+            &proof
+        )));
+    }
+
+    #[test]
+    fn test_degree_too_large() {
+        let srs = Srs::insecure_from_tau(&Scalar::from(5), 4, 2);
+        let poly = poly();
+
+        assert!(commit(&srs, &poly).is_none());
+        assert!(open(&srs, &poly, &Scalar::one()).is_none());
+        assert!(commit(&srs, &poly[..4]).is_some());
+        assert!(open_batch(&srs, &[&poly[..4], &poly], &Scalar::one(), &Scalar::one()).is_none());
+        assert!(open_multi(&srs, &poly, &[Scalar::one()]).is_none());
+    }
+
+    #[test]
+    fn test_open_batch() {
+        let srs = Srs::insecure_from_tau(&Scalar::from(0xdead_beef), 8, 2);
+        let polys = [poly(), poly()[2..].to_vec(), alloc::vec![Scalar::from(9)]];
+        let commitments = polys
+            .iter()
+            .map(|p| commit(&srs, p).unwrap())
+            .collect::<Vec<_>>();
+        let point = Scalar::from(1234);
+        let challenge = Scalar::from(0x5eed);
+
+        let (values, proof) = open_batch(&srs, &polys, &point, &challenge).unwrap();
+        for (poly, value) in polys.iter().zip(values.iter()) {
+            assert_eq!(evaluate(poly, &point), *value);
+        }
+        assert!(bool::from(verify_batch(
+            &srs,
+            &commitments,
+            &point,
+            &values,
+            &challenge,
+            &proof
+        )));
+
+        let mut wrong = values.clone();
+        wrong[1] += Scalar::one();
+        assert!(!bool::from(verify_batch(
+            &srs,
+            &commitments,
+            &point,
+            &wrong,
+            &challenge,
+            &proof
+        )));
+        assert!(!bool::from(verify_batch(
+            &srs,
+            &commitments,
+            &point,
+            &values,
+            &(challenge + Scalar::one()),
+            &proof
+        )));
+        assert!(!bool::from(verify_batch(
+            &srs,
+            &commitments[..2],
+            &point,
+            &values,
+            &challenge,
+            &proof
+        )));
+
+        // A batch of one is an ordinary opening.
+        let (values, proof) = open_batch(&srs, &polys[..1], &point, &challenge).unwrap();
+        assert_eq!(open(&srs, &polys[0], &point), Some((values[0], proof)));
+    }
+
+    #[test]
+    fn test_open_multi() {
+        let srs = Srs::insecure_from_tau(&Scalar::from(0xdead_beef), 8, 4);
+        let poly = poly();
+        let commitment = commit(&srs, &poly).unwrap();
+        let points = [Scalar::from(2), Scalar::from(4), Scalar::from(8)];
+
+        let (values, proof) = open_multi(&srs, &poly, &points).unwrap();
+        for (z, v) in points.iter().zip(values.iter()) {
+            assert_eq!(evaluate(&poly, z), *v);
+        }
+        assert!(bool::from(verify_multi(
+            &srs,
+            &commitment,
+            &points,
+            &values,
+            &proof
+        )));
+
+        let mut wrong = values.clone();
+        wrong[2] += Scalar::one();
+        assert!(!bool::from(verify_multi(
+            &srs,
+            &commitment,
+            &points,
+            &wrong,
+            &proof
+        )));
+        assert!(!bool::from(verify_multi(
+            &srs,
+            &commitment,
+            &points[..2],
+            &values[..2],
+            &proof
+        )));
+
+        // A single point is an ordinary opening.
+        let (values, proof) = open_multi(&srs, &poly, &points[..1]).unwrap();
+        assert_eq!(open(&srs, &poly, &points[0]), Some((values[0], proof)));
+
+        // Opening at more points than the SRS supports cannot be verified.
+        let small = Srs::insecure_from_tau(&Scalar::from(0xdead_beef), 8, 3);
+        let (values, proof) = open_multi(&small, &poly, &points).unwrap();
+        assert!(!bool::from(verify_multi(
+            &small,
+            &commitment,
+            &points,
+            &values,
+            &proof
+        )));
+
+        assert!(open_multi(&srs, &poly, &[points[0], points[0]]).is_none());
+    }
+
+    #[test]
+    fn test_batch_verify() {
+        use rand_core::SeedableRng;
+        use rand_xorshift::XorShiftRng;
+
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let srs = Srs::insecure_from_tau(&Scalar::from(0xdead_beef), 8, 2);
+
+        let mut openings = (0..4u64)
+            .map(|i| {
+                let poly = poly()[i as usize..].to_vec();
+                let point = Scalar::from(i + 100);
+                let (value, proof) = open(&srs, &poly, &point).unwrap();
+                (commit(&srs, &poly).unwrap(), point, value, proof)
+            })
+            .collect::<Vec<_>>();
+        assert!(bool::from(batch_verify(&srs, &openings, &mut rng)));
+        assert!(bool::from(batch_verify(&srs, &[], &mut rng)));
+
+        openings[2].2 += Scalar::one();
+        assert!(!bool::from(batch_verify(&srs, &openings, &mut rng)));
+    }
+}
@@ -30,6 +30,9 @@ extern crate alloc;
 #[macro_use]
 extern crate std;
 
+#[cfg(all(feature = "parallel", not(test)))]
+extern crate std;
+
 #[cfg(test)]
 #[cfg(feature = "groups")]
 mod tests;
@@ -37,50 +40,98 @@ mod tests;
 #[macro_use]
 mod util;
 
+mod arith;
+
 /// Notes about how the BLS12-381 elliptic curve is designed, specified
 /// and implemented by this library.
 pub mod notes {
+    pub mod constant_time;
     pub mod design;
     pub mod serialization;
 }
 
 mod scalar;
 
-pub use scalar::Scalar;
+pub use scalar::{Scalar, ScalarAccumulator};
 
-#[cfg(feature = "groups")]
+#[cfg(feature = "tower")]
 pub mod fp;
-#[cfg(feature = "groups")]
+#[cfg(feature = "tower")]
 pub mod fp2;
+mod error;
+#[cfg(feature = "groups")]
+mod const_decompress;
 #[cfg(feature = "groups")]
 mod g1;
 #[cfg(feature = "groups")]
 mod g2;
 
+pub use error::Error;
 #[cfg(feature = "groups")]
-pub use g1::{G1Affine, G1Projective};
+pub use g1::{G1Affine, G1Projective, PointEncoding};
 #[cfg(feature = "groups")]
 pub use g2::{G2Affine, G2Projective};
 
 #[cfg(feature = "groups")]
-mod fp12;
+pub mod msm;
+
+#[cfg(all(feature = "groups", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "groups", feature = "alloc"))))]
+pub mod backend;
+
+#[cfg(all(feature = "groups", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "groups", feature = "alloc"))))]
+pub mod poly;
+
+#[cfg(feature = "tower")]
+pub mod fp12;
+#[cfg(feature = "tower")]
+pub mod fp6;
+
 #[cfg(feature = "groups")]
-mod fp6;
+pub mod twist;
+
+#[cfg(feature = "tower")]
+pub mod constants;
 
 // The BLS parameter x for BLS12-381 is -0xd201000000010000
-#[cfg(feature = "groups")]
-const BLS_X: u64 = 0xd201_0000_0001_0000;
-#[cfg(feature = "groups")]
-const BLS_X_IS_NEGATIVE: bool = true;
+#[cfg(feature = "tower")]
+use constants::{BLS_X, BLS_X_IS_NEGATIVE};
 
 #[cfg(feature = "pairings")]
 mod pairings;
 
 #[cfg(feature = "pairings")]
-pub use pairings::{pairing, Bls12, Gt, MillerLoopResult};
+pub use pairings::{evaluate_line, pairing, Bls12, Gt, MillerLoopResult};
 
 #[cfg(all(feature = "pairings", feature = "alloc"))]
-pub use pairings::{multi_miller_loop, G2Prepared};
+pub use pairings::{
+    multi_miller_loop, multi_miller_loop_scaled_vartime, G2Prepared, PairingCheck,
+};
+
+#[cfg(feature = "arkworks")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arkworks")))]
+pub mod arkworks;
+
+#[cfg(all(feature = "blst", not(feature = "groups")))]
+compile_error!("the `blst` feature requires the `groups` feature");
+
+#[cfg(all(feature = "rkyv", not(feature = "groups")))]
+compile_error!("the `rkyv` feature requires the `groups` feature");
+
+#[cfg(all(feature = "blst", feature = "groups", target_pointer_width = "64"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "blst", feature = "groups"))))]
+pub mod blst;
+
+#[cfg(all(feature = "rkyv", feature = "groups"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "rkyv", feature = "groups"))))]
+pub mod rkyv;
+
+#[cfg(feature = "serde")]
+mod serde_impls;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls;
 
 /// Use the generic_array re-exported by digest to avoid a version mismatch
 #[cfg(feature = "experimental")]
@@ -88,3 +139,91 @@ pub(crate) use digest::generic_array;
 
 #[cfg(feature = "experimental")]
 pub mod hash_to_curve;
+
+#[cfg(feature = "experimental")]
+mod hmac;
+
+#[cfg(feature = "parallel")]
+mod threads;
+
+#[cfg(feature = "experimental")]
+#[cfg_attr(docsrs, doc(cfg(feature = "experimental")))]
+pub mod nonce;
+
+#[cfg(all(feature = "experimental", feature = "groups"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "experimental", feature = "groups"))))]
+pub mod elligator;
+
+#[cfg(all(feature = "experimental", feature = "pairings", feature = "alloc"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "experimental", feature = "pairings", feature = "alloc")))
+)]
+pub mod signature;
+
+#[cfg(all(feature = "experimental", feature = "pairings", feature = "alloc"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "experimental", feature = "pairings", feature = "alloc")))
+)]
+pub mod kzg;
+
+#[cfg(all(feature = "experimental", feature = "pairings", feature = "alloc"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "experimental", feature = "pairings", feature = "alloc")))
+)]
+pub mod ibe;
+
+#[cfg(all(feature = "experimental", feature = "pairings", feature = "alloc"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "experimental", feature = "pairings", feature = "alloc")))
+)]
+pub mod accumulator;
+
+#[cfg(all(feature = "experimental", feature = "groups", feature = "alloc"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "experimental", feature = "groups", feature = "alloc")))
+)]
+pub mod elgamal;
+
+#[cfg(all(feature = "experimental", feature = "groups", feature = "alloc"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "experimental", feature = "groups", feature = "alloc")))
+)]
+pub mod schnorr;
+
+#[cfg(all(feature = "experimental", feature = "groups", feature = "alloc"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "experimental", feature = "groups", feature = "alloc")))
+)]
+pub mod shamir;
+
+#[cfg(all(feature = "experimental", feature = "groups", feature = "alloc"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "experimental", feature = "groups", feature = "alloc")))
+)]
+pub mod transcript;
+
+#[cfg(all(feature = "experimental", feature = "pairings", feature = "alloc"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "experimental", feature = "pairings", feature = "alloc")))
+)]
+pub mod tlock;
+
+#[cfg(all(feature = "experimental", feature = "pairings", feature = "alloc"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "experimental", feature = "pairings", feature = "alloc")))
+)]
+pub mod groth16;
+
+#[cfg(feature = "ffi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ffi")))]
+pub mod ffi;
@@ -0,0 +1,892 @@
+//! Multi-scalar multiplication.
+//!
+//! These functions compute $\sum_i s_i P_i$ with Pippenger's bucket method, using
+//! signed digits: each window of $c$ bits of a scalar is recoded as a digit in
+//! $[-2^{c-1}, 2^{c-1}]$, and a negative digit adds the negated point to the bucket
+//! of its absolute value. This halves the number of buckets, and with it the cost
+//! of summing them, compared to unsigned digits.
+//! [`multi_exp_vartime`] and [`multi_exp_vartime_with_scratch`] use a fixed window
+//! and a bucket array that lives either on the stack or in a buffer provided by the
+//! caller, for verifiers on targets without a heap, which can also provide the
+//! buffer for the encodings of the scalars to [`multi_exp_vartime_with_buffers`], and [`multi_exp_array_vartime`]
+//! takes arrays whose lengths are checked at compile time. [`multi_exp_auto_vartime`]
+//! allocates its buckets for the window that [`recommended_window`] chooses from
+//! the number of pairs, with a table measured from $2^8$ to $2^{16}$ pairs by the
+//! `msm` benchmarks.
+//! [`multi_exp_small_vartime`] takes scalars of at most 32 bits, such as bounded
+//! polynomial coefficients, and processes only the windows that they span.
+//! `StreamingMultiExp` consumes its points and scalars one pair at a time, for
+//! inputs too large to hold in memory, and `msm_with_precompute` uses tables of
+//! window-shifted copies of a fixed set of points, for provers that reuse the same
+//! SRS for every proof.
+//!
+//! Like `pow_vartime`, these functions are variable time with respect to the
+//! scalars, which makes them suitable for public inputs only.
+
+use core::ops::AddAssign;
+
+use group::prime::PrimeCurveAffine;
+use group::Group;
+
+#[cfg(feature = "alloc")]
+use group::{Curve, UncompressedEncoding};
+
+use crate::Scalar;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// The window of [`multi_exp_vartime`], in bits.
+pub const WINDOW: usize = 4;
+
+/// The number of buckets used by [`multi_exp_vartime`].
+pub const BUCKETS: usize = 1 << (WINDOW - 1);
+
+/// The bit length of the scalar field modulus.
+const SCALAR_BITS: usize = 255;
+
+/// Returns the `width` bits of `bytes` starting at bit `offset`, little-endian.
+fn window(bytes: &[u8; 32], offset: usize, width: usize) -> usize {
+    (offset..(offset + width).min(256))
+        .map(|bit| usize::from((bytes[bit / 8] >> (bit % 8)) & 1) << (bit - offset))
+        .sum()
+}
+
+/// Returns the number of signed digits of `width` bits in a scalar.
+///
+/// The top digit may absorb a carry out of the most significant bit.
+fn num_windows(width: usize) -> usize {
+    SCALAR_BITS / width + 1
+}
+
+/// Returns the signed digit of `bytes` in the window of `width` bits starting at
+/// bit `offset`.
+///
+/// A window whose top bit is set is recoded as its value minus $2^c$, for a carry
+/// of one into the next window. As the carry depends only on the top bit of the
+/// window below, each digit can be computed independently of the others.
+fn signed_window(bytes: &[u8; 32], offset: usize, width: usize) -> isize {
+    let value = window(bytes, offset, width) as isize;
+    let carry = if offset == 0 {
+        0
+    } else {
+        window(bytes, offset - 1, 1) as isize
+    };
+    value + carry - ((value >> (width - 1)) << width)
+}
+
+/// Adds `base` to the bucket of the signed digit `digit`, if it is nonzero.
+fn add_to_bucket<G>(buckets: &mut [G::Curve], base: &G, digit: isize)
+where
+    G: PrimeCurveAffine,
+    G::Curve: AddAssign<G>,
+{
+    match digit {
+        0 => (),
+        d if d > 0 => buckets[d as usize - 1] += *base,
+        d => buckets[(-d) as usize - 1] += -*base,
+    }
+}
+
+/// Returns $\sum_j (j + 1) B_j$ for the buckets $B_j$.
+fn sum_buckets<C: Group>(buckets: &[C]) -> C {
+    let mut running = C::identity();
+    let mut sum = C::identity();
+    for bucket in buckets.iter().rev() {
+        running += bucket;
+        sum += running;
+    }
+    sum
+}
+
+/// Computes $\sum_i s_i P_i$ for the points `bases` and the `scalars`, with a
+/// window of [`WINDOW`] bits and [`BUCKETS`] buckets on the stack.
+///
+/// # Panics
+///
+/// Panics if `bases` and `scalars` do not have the same length.
+pub fn multi_exp_vartime<G>(bases: &[G], scalars: &[Scalar]) -> G::Curve
+where
+    G: PrimeCurveAffine<Scalar = Scalar>,
+    G::Curve: AddAssign<G> + AddAssign,
+{
+    let mut buckets = [G::Curve::identity(); BUCKETS];
+    multi_exp_vartime_with_scratch(bases, scalars, &mut buckets)
+}
+
+/// Computes $\sum_i s_i P_i$ for the `N` points `bases` and the `N` `scalars`, as
+/// [`multi_exp_vartime`] does.
+///
+/// Since the lengths are part of the types, this cannot panic.
+pub fn multi_exp_array_vartime<G, const N: usize>(bases: &[G; N], scalars: &[Scalar; N]) -> G::Curve
+where
+    G: PrimeCurveAffine<Scalar = Scalar>,
+    G::Curve: AddAssign<G> + AddAssign,
+{
+    multi_exp_vartime(bases, scalars)
+}
+
+/// Computes $\sum_i s_i P_i$ for the points `bases` and the `scalars`, using
+/// `scratch` as buckets.
+///
+/// The window is the largest $c \leq 16$ such that `scratch` holds $2^{c-1}$
+/// buckets; any further elements of `scratch` are unused. Its contents on return
+/// are unspecified. With the `alloc` feature, the scalars are converted to their
+/// canonical encodings once, in an allocated buffer; without it, they are converted
+/// again in every window, which [`multi_exp_vartime_with_buffers`] avoids.
+///
+/// # Panics
+///
+/// Panics if `bases` and `scalars` do not have the same length, or if `scratch`
+/// is empty.
+pub fn multi_exp_vartime_with_scratch<G>(
+    bases: &[G],
+    scalars: &[Scalar],
+    scratch: &mut [G::Curve],
+) -> G::Curve
+where
+    G: PrimeCurveAffine<Scalar = Scalar>,
+    G::Curve: AddAssign<G> + AddAssign,
+{
+    assert_eq!(bases.len(), scalars.len());
+
+    #[cfg(feature = "alloc")]
+    {
+        let bytes = scalars.iter().map(Scalar::to_bytes).collect::<Vec<_>>();
+        multi_exp_bytes(bases, |i| bytes[i], scratch)
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    multi_exp_bytes(bases, |i| scalars[i].to_bytes(), scratch)
+}
+
+/// Computes $\sum_i s_i P_i$ for the points `bases` and the `scalars`, as
+/// [`multi_exp_vartime_with_scratch`] does, using `scratch` as buckets and the
+/// first `scalars.len()` elements of `bytes` for the canonical encodings of the
+/// scalars, so that each of them is converted once without an allocator.
+///
+/// # Panics
+///
+/// Panics if `bases` and `scalars` do not have the same length, if `scratch` is
+/// empty, or if `bytes` is shorter than `scalars`.
+pub fn multi_exp_vartime_with_buffers<G>(
+    bases: &[G],
+    scalars: &[Scalar],
+    scratch: &mut [G::Curve],
+    bytes: &mut [[u8; 32]],
+) -> G::Curve
+where
+    G: PrimeCurveAffine<Scalar = Scalar>,
+    G::Curve: AddAssign<G> + AddAssign,
+{
+    assert_eq!(bases.len(), scalars.len());
+    assert!(bytes.len() >= scalars.len());
+
+    let bytes = &mut bytes[..scalars.len()];
+    for (bytes, scalar) in bytes.iter_mut().zip(scalars.iter()) {
+        *bytes = scalar.to_bytes();
+    }
+    let bytes = &*bytes;
+    multi_exp_bytes(bases, |i| bytes[i], scratch)
+}
+
+/// Computes $\sum_i s_i P_i$ for the points `bases`, where `scalar(i)` is the
+/// little-endian encoding of $s_i$, using `scratch` as buckets.
+fn multi_exp_bytes<G, F>(bases: &[G], scalar: F, scratch: &mut [G::Curve]) -> G::Curve
+where
+    G: PrimeCurveAffine<Scalar = Scalar>,
+    G::Curve: AddAssign<G> + AddAssign,
+    F: Fn(usize) -> [u8; 32],
+{
+    assert!(!scratch.is_empty());
+
+    let width = (1..=16)
+        .take_while(|c| 1 << (c - 1) <= scratch.len())
+        .last()
+        .unwrap();
+    let buckets = &mut scratch[..1 << (width - 1)];
+
+    let mut acc = G::Curve::identity();
+    for j in (0..num_windows(width)).rev() {
+        // The accumulator is the identity before the top window, so it needs no
+        // doubling.
+        if j + 1 < num_windows(width) {
+            for _ in 0..width {
+                acc = acc.double();
+            }
+        }
+
+        for bucket in buckets.iter_mut() {
+            *bucket = G::Curve::identity();
+        }
+        for (i, base) in bases.iter().enumerate() {
+            let digit = signed_window(&scalar(i), j * width, width);
+            add_to_bucket(buckets, base, digit);
+        }
+
+        acc += sum_buckets(buckets);
+    }
+
+    acc
+}
+
+/// The fastest window for $2^k$ pairs, indexed by $k$. These were measured for
+/// [`multi_exp_vartime_with_scratch`] over $\mathbb{G}_1$ up to $2^{16}$ pairs by
+/// `benches/msm.rs`, which times the windows next to each entry, and extrapolated
+/// beyond, adding a bit for every two doublings of the number of pairs.
+const RECOMMENDED_WINDOWS: [usize; 25] = [
+    1, 2, 2, 3, 3, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 12, 12, 13, 13, 14, 14, 15, 15, 16, 16,
+];
+
+/// Returns the window, in bits, that suits a multi-scalar multiplication of `len`
+/// pairs with [`multi_exp_vartime_with_scratch`], which needs $2^{c-1}$ buckets for a
+/// window of $c$ bits.
+pub fn recommended_window(len: usize) -> usize {
+    let log_len = (usize::BITS - len.max(1).leading_zeros() - 1) as usize;
+    RECOMMENDED_WINDOWS[log_len.min(RECOMMENDED_WINDOWS.len() - 1)]
+}
+
+/// Returns the [`recommended_window`] for `len` pairs of points of type `G`, reduced
+/// if needed so that its buckets fit in a cache of `cache_size` bytes, such as the
+/// L2 cache of the core running the computation.
+///
+/// Returns a window of at least one bit, however small the cache.
+pub fn recommended_window_for_cache<G: PrimeCurveAffine>(len: usize, cache_size: usize) -> usize {
+    let bucket_size = core::mem::size_of::<G::Curve>();
+    let mut window = recommended_window(len);
+    while window > 1 && (bucket_size << (window - 1)) > cache_size {
+        window -= 1;
+    }
+    window
+}
+
+/// Computes $\sum_i s_i P_i$ for the points `bases` and the `scalars`, with buckets
+/// allocated for the [`recommended_window`] for the number of pairs.
+///
+/// # Panics
+///
+/// Panics if `bases` and `scalars` do not have the same length.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn multi_exp_auto_vartime<G>(bases: &[G], scalars: &[Scalar]) -> G::Curve
+where
+    G: PrimeCurveAffine<Scalar = Scalar>,
+    G::Curve: AddAssign<G> + AddAssign,
+{
+    let window = recommended_window(bases.len());
+    let mut buckets = alloc::vec![G::Curve::identity(); 1 << (window - 1)];
+    multi_exp_vartime_with_scratch(bases, scalars, &mut buckets)
+}
+
+/// An unsigned integer type of at most 32 bits, for the scalars of
+/// [`multi_exp_small_vartime`].
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub trait SmallScalar: Copy + Into<u32> {
+    /// The bit length of the type, which bounds the bit length of its values.
+    const BITS: usize;
+}
+
+#[cfg(feature = "alloc")]
+impl SmallScalar for u8 {
+    const BITS: usize = 8;
+}
+
+#[cfg(feature = "alloc")]
+impl SmallScalar for u16 {
+    const BITS: usize = 16;
+}
+
+#[cfg(feature = "alloc")]
+impl SmallScalar for u32 {
+    const BITS: usize = 32;
+}
+
+/// Returns the window, in bits, that minimizes the number of additions of a
+/// multi-scalar multiplication of `len` pairs with scalars of `bits` bits.
+///
+/// Each of the $\lfloor b / c \rfloor + 1$ windows of $c$ bits costs an addition
+/// per pair and two per bucket, of which there are $2^{c-1}$. A window wider than
+/// the scalars takes them whole, so that there are no doublings at all.
+#[cfg(feature = "alloc")]
+fn small_window(len: usize, bits: usize) -> usize {
+    (1..=(bits + 1).min(16))
+        .min_by_key(|c| (bits / c + 1) * (len + (1 << c)))
+        .unwrap()
+}
+
+/// Computes $\sum_i s_i P_i$ for the points `bases` and the small unsigned integer
+/// `scalars`, such as the coefficients of a polynomial with bounded coefficients or
+/// the entries of a lookup table.
+///
+/// Only the windows that the bit length of `S` spans are processed, and the window
+/// is chosen for that bit length and the number of pairs. On $2^{12}$ scalars of
+/// type `u8`, this is more than seven times faster than [`multi_exp_auto_vartime`]
+/// on the same scalars converted to [`Scalar`], as the `msm` benchmarks show.
+///
+/// # Panics
+///
+/// Panics if `bases` and `scalars` do not have the same length.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn multi_exp_small_vartime<G, S>(bases: &[G], scalars: &[S]) -> G::Curve
+where
+    G: PrimeCurveAffine<Scalar = Scalar>,
+    G::Curve: AddAssign<G> + AddAssign,
+    S: SmallScalar,
+{
+    assert_eq!(bases.len(), scalars.len());
+
+    let width = small_window(bases.len(), S::BITS);
+    let windows = S::BITS / width + 1;
+    let mut buckets = alloc::vec![G::Curve::identity(); 1 << (width - 1)];
+
+    let mut acc = G::Curve::identity();
+    for j in (0..windows).rev() {
+        if j + 1 < windows {
+            for _ in 0..width {
+                acc = acc.double();
+            }
+        }
+
+        for bucket in buckets.iter_mut() {
+            *bucket = G::Curve::identity();
+        }
+        for (base, scalar) in bases.iter().zip(scalars.iter()) {
+            let mut bytes = [0; 32];
+            bytes[..4].copy_from_slice(&(*scalar).into().to_le_bytes());
+            add_to_bucket(&mut buckets, base, signed_window(&bytes, j * width, width));
+        }
+
+        acc += sum_buckets(&buckets);
+    }
+
+    acc
+}
+
+/// A multi-scalar multiplication over pairs of points and scalars that are added
+/// incrementally, so that the points can be streamed from disk rather than held in
+/// memory.
+///
+/// The buckets of every window are kept for the whole computation, which lets each
+/// pair be consumed as soon as it is added: with a window of $c$ bits this holds
+/// $(\lfloor 255 / c \rfloor + 1) 2^{c-1}$ projective points, whatever the number of
+/// pairs. The [`recommended_window`] for $n$ pairs suits it too.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug)]
+pub struct StreamingMultiExp<G: PrimeCurveAffine> {
+    width: usize,
+    buckets: Vec<G::Curve>,
+}
+
+#[cfg(feature = "alloc")]
+impl<G> StreamingMultiExp<G>
+where
+    G: PrimeCurveAffine<Scalar = Scalar>,
+    G::Curve: AddAssign<G> + AddAssign,
+{
+    /// Starts a multi-scalar multiplication with a window of `window` bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is zero or greater than 16.
+    pub fn new(window: usize) -> Self {
+        assert!((1..=16).contains(&window));
+
+        StreamingMultiExp {
+            width: window,
+            buckets: alloc::vec![G::Curve::identity(); num_windows(window) << (window - 1)],
+        }
+    }
+
+    /// Adds $s P$ to the sum, for `base` $P$ and `scalar` $s$.
+    pub fn add(&mut self, base: &G, scalar: &Scalar) {
+        let bytes = scalar.to_bytes();
+        let buckets = 1 << (self.width - 1);
+        for (j, buckets) in self.buckets.chunks_exact_mut(buckets).enumerate() {
+            add_to_bucket(
+                buckets,
+                base,
+                signed_window(&bytes, j * self.width, self.width),
+            );
+        }
+    }
+
+    /// Returns the sum of the pairs added so far.
+    pub fn finish(&self) -> G::Curve {
+        let mut windows = self.buckets.chunks_exact(1 << (self.width - 1)).rev();
+        // The top window needs no doubling.
+        let mut acc = sum_buckets(windows.next().unwrap());
+        for buckets in windows {
+            for _ in 0..self.width {
+                acc = acc.double();
+            }
+            acc += sum_buckets(buckets);
+        }
+        acc
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<G> Extend<(G, Scalar)> for StreamingMultiExp<G>
+where
+    G: PrimeCurveAffine<Scalar = Scalar>,
+    G::Curve: AddAssign<G> + AddAssign,
+{
+    fn extend<I: IntoIterator<Item = (G, Scalar)>>(&mut self, pairs: I) {
+        for (base, scalar) in pairs {
+            self.add(&base, &scalar);
+        }
+    }
+}
+
+/// Computes $\sum_i s_i P_i$ for the pairs $(P_i, s_i)$ yielded by `pairs`, with a
+/// window of `window` bits, consuming each pair as it is yielded.
+///
+/// See [`StreamingMultiExp`] for the memory used and the choice of window.
+///
+/// # Panics
+///
+/// Panics if `window` is zero or greater than 16.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn multi_exp_iter_vartime<G, I>(pairs: I, window: usize) -> G::Curve
+where
+    G: PrimeCurveAffine<Scalar = Scalar>,
+    G::Curve: AddAssign<G> + AddAssign,
+    I: IntoIterator<Item = (G, Scalar)>,
+{
+    let mut msm = StreamingMultiExp::new(window);
+    msm.extend(pairs);
+    msm.finish()
+}
+
+/// Window-shifted copies $2^{jc} P_i$ of a fixed set of points $P_i$, for every
+/// window $j$ of $c$ bits, which speed up [`msm_with_precompute`] over those points.
+///
+/// With these copies, the digits of every window share a single set of buckets, so
+/// a multi-scalar multiplication needs no doublings and sums its buckets only once.
+/// This costs $\lfloor 255 / c \rfloor + 1$ affine points of memory per point.
+///
+/// A table can be serialized with [`MsmPrecompute::to_bytes`] so that it is built
+/// only once.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsmPrecompute<G> {
+    width: usize,
+    len: usize,
+    // The copies of each point, in order of increasing shift.
+    bases: Vec<G>,
+}
+
+#[cfg(feature = "alloc")]
+impl<G> MsmPrecompute<G>
+where
+    G: PrimeCurveAffine<Scalar = Scalar> + UncompressedEncoding,
+    G::Curve: AddAssign<G> + AddAssign,
+{
+    /// Precomputes the window-shifted copies of `bases` for a window of `window`
+    /// bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is zero or greater than 16.
+    pub fn new(bases: &[G], window: usize) -> Self {
+        assert!((1..=16).contains(&window));
+
+        let mut shifted = Vec::with_capacity(bases.len() * num_windows(window));
+        for base in bases {
+            let mut p = base.to_curve();
+            for _ in 0..num_windows(window) {
+                shifted.push(p);
+                for _ in 0..window {
+                    p = p.double();
+                }
+            }
+        }
+        let mut affine = alloc::vec![G::identity(); shifted.len()];
+        G::Curve::batch_normalize(&shifted, &mut affine);
+
+        MsmPrecompute {
+            width: window,
+            len: bases.len(),
+            bases: affine,
+        }
+    }
+
+    /// Returns the number of points in this table.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if this table holds no points.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the window of this table, in bits.
+    pub fn window(&self) -> usize {
+        self.width
+    }
+
+    /// Serializes this table: its window as one byte, its number of points as four
+    /// little-endian bytes, and the uncompressed encodings of the copies of each
+    /// point in order of increasing shift.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = alloc::vec![self.width as u8];
+        bytes.extend_from_slice(&(self.len as u32).to_le_bytes());
+        for base in &self.bases {
+            bytes.extend_from_slice(base.to_uncompressed().as_ref());
+        }
+        bytes
+    }
+
+    /// Deserializes a table written by [`MsmPrecompute::to_bytes`].
+    ///
+    /// Returns `None` if `bytes` is not a valid encoding, or if any of the points is
+    /// not in the prime-order subgroup. This does not check that the points are
+    /// window-shifted copies of each other, so a table must come from a trusted
+    /// source to give correct results.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let point_len = G::Uncompressed::default().as_ref().len();
+        let width = usize::from(*bytes.first()?);
+        if !(1..=16).contains(&width) || bytes.len() < 5 {
+            return None;
+        }
+        let mut len = [0u8; 4];
+        len.copy_from_slice(&bytes[1..5]);
+        let len = u32::from_le_bytes(len) as usize;
+
+        let points = &bytes[5..];
+        if points.len() / point_len / num_windows(width) != len
+            || points.len() % (point_len * num_windows(width)) != 0
+        {
+            return None;
+        }
+        let bases = points
+            .chunks_exact(point_len)
+            .map(|chunk| {
+                let mut encoding = G::Uncompressed::default();
+                encoding.as_mut().copy_from_slice(chunk);
+                Option::from(G::from_uncompressed(&encoding))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(MsmPrecompute { width, len, bases })
+    }
+}
+
+/// Computes $\sum_i s_i P_i$ for the points $P_i$ of `precompute` and the `scalars`.
+///
+/// # Panics
+///
+/// Panics if `scalars` does not have as many elements as `precompute` has points.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn msm_with_precompute<G>(precompute: &MsmPrecompute<G>, scalars: &[Scalar]) -> G::Curve
+where
+    G: PrimeCurveAffine<Scalar = Scalar> + UncompressedEncoding,
+    G::Curve: AddAssign<G> + AddAssign,
+{
+    assert_eq!(precompute.len, scalars.len());
+
+    let width = precompute.width;
+    let mut buckets = alloc::vec![G::Curve::identity(); 1 << (width - 1)];
+    for (shifted, scalar) in precompute
+        .bases
+        .chunks_exact(num_windows(width))
+        .zip(scalars.iter())
+    {
+        let bytes = scalar.to_bytes();
+        for (j, base) in shifted.iter().enumerate() {
+            add_to_bucket(&mut buckets, base, signed_window(&bytes, j * width, width));
+        }
+    }
+
+    sum_buckets(&buckets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{G1Affine, G1Projective, G2Affine, G2Projective};
+    use ff::Field;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[cfg(feature = "alloc")]
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_multi_exp() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        let mut scalars = [Scalar::zero(); 5];
+        for s in scalars.iter_mut() {
+            *s = Scalar::random(&mut rng);
+        }
+        scalars[1] = -Scalar::one();
+        scalars[3] = Scalar::zero();
+        let mut bases = [G1Affine::generator(); 5];
+        for (i, base) in bases.iter_mut().enumerate() {
+            *base = G1Affine::from(G1Affine::generator() * Scalar::from(i as u64 + 2));
+        }
+        bases[2] = G1Affine::identity();
+
+        let expected = bases
+            .iter()
+            .zip(scalars.iter())
+            .fold(G1Projective::identity(), |acc, (p, s)| acc + p * s);
+        assert_eq!(multi_exp_vartime(&bases, &scalars), expected);
+        assert_eq!(multi_exp_array_vartime(&bases, &scalars), expected);
+        for len in [1, 2, 3, 7, 8, 100] {
+            let mut scratch = [G1Projective::identity(); 100];
+            let result = multi_exp_vartime_with_scratch(&bases, &scalars, &mut scratch[..len]);
+            assert_eq!(result, expected);
+
+            let mut bytes = [[0xff; 32]; 6];
+            let result =
+                multi_exp_vartime_with_buffers(&bases, &scalars, &mut scratch[..len], &mut bytes);
+            assert_eq!(result, expected);
+        }
+
+        let q = [G2Affine::generator(), G2Affine::generator()];
+        assert_eq!(
+            multi_exp_vartime(&q, &scalars[..2]),
+            G2Projective::generator() * (scalars[0] + scalars[1])
+        );
+        assert_eq!(
+            multi_exp_vartime::<G1Affine>(&[], &[]),
+            G1Projective::identity()
+        );
+    }
+
+    #[test]
+    fn test_recommended_window() {
+        assert_eq!(recommended_window(0), 1);
+        assert_eq!(recommended_window(1), 1);
+        assert_eq!(recommended_window(255), 5);
+        assert_eq!(recommended_window(256), 6);
+        assert_eq!(recommended_window(1 << 20), 14);
+        assert_eq!(recommended_window(usize::MAX), 16);
+        for len in 1..(1 << 12) {
+            assert!(recommended_window(len) <= recommended_window(len + 1));
+        }
+
+        // Buckets of 144 bytes, of which 2^12 fit in 1 MiB.
+        assert_eq!(core::mem::size_of::<G1Projective>(), 144);
+        assert_eq!(
+            recommended_window_for_cache::<G1Affine>(1 << 20, 1 << 20),
+            13
+        );
+        assert_eq!(
+            recommended_window_for_cache::<G1Affine>(1 << 10, 1 << 20),
+            8
+        );
+        assert_eq!(recommended_window_for_cache::<G2Affine>(1 << 10, 0), 1);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_multi_exp_auto() {
+        let bases = (1..=300)
+            .map(|i| G1Affine::from(G1Affine::generator() * Scalar::from(i)))
+            .collect::<Vec<_>>();
+        let scalars = (1..=300)
+            .map(|i| Scalar::from(i).invert().unwrap())
+            .collect::<Vec<_>>();
+        for len in [0, 1, 5, 300] {
+            assert_eq!(
+                multi_exp_auto_vartime(&bases[..len], &scalars[..len]),
+                multi_exp_vartime(&bases[..len], &scalars[..len])
+            );
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_small_window() {
+        assert_eq!(small_window(0, 8), 1);
+        assert_eq!(small_window(1, 8), 2);
+        assert_eq!(small_window(1 << 10, 8), 9);
+        assert_eq!(small_window(1 << 16, 16), 9);
+
+        // Three windows of 11 bits span 32 bits with fewer buckets than of 16 bits.
+        assert_eq!(small_window(1 << 16, 32), 11);
+        assert_eq!(small_window(1 << 28, 32), 11);
+        for bits in [8, 16, 32] {
+            for len in 1..(1 << 12) {
+                assert!(small_window(len, bits) <= small_window(len + 1, bits));
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_multi_exp_small() {
+        fn check<S: SmallScalar>(bases: &[G1Affine], scalars: &[S]) {
+            let expected = scalars
+                .iter()
+                .map(|&s| Scalar::from(u64::from(s.into())))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                multi_exp_small_vartime(bases, scalars),
+                multi_exp_vartime(bases, &expected)
+            );
+        }
+
+        let bases = (1..=300)
+            .map(|i| G1Affine::from(G1Affine::generator() * Scalar::from(i)))
+            .collect::<Vec<_>>();
+        let scalars = (0..300u32)
+            .map(|i| i.wrapping_mul(0x9e37_79b9))
+            .collect::<Vec<_>>();
+        for len in [0, 1, 5, 300] {
+            let scalars = &scalars[..len];
+            check(&bases[..len], scalars);
+            check(
+                &bases[..len],
+                &scalars.iter().map(|&s| s as u16).collect::<Vec<_>>(),
+            );
+            check(
+                &bases[..len],
+                &scalars.iter().map(|&s| s as u8).collect::<Vec<_>>(),
+            );
+        }
+
+        // The extreme values, whose top windows carry.
+        let q = [G2Affine::generator(), G2Affine::generator()];
+        assert_eq!(
+            multi_exp_small_vartime(&q, &[u32::MAX, 1]),
+            G2Projective::generator() * Scalar::from(1 << 32)
+        );
+        assert_eq!(
+            multi_exp_small_vartime(&q, &[u8::MAX, u8::MAX]),
+            G2Projective::generator() * Scalar::from(510)
+        );
+    }
+
+    #[test]
+    fn test_signed_windows() {
+        let scalars = [
+            Scalar::zero(),
+            Scalar::one(),
+            -Scalar::one(),
+            Scalar::from(0xffff_ffff_ffff_ffff),
+            Scalar::from_raw([1, 2, 3, 4]).invert().unwrap(),
+        ];
+        for width in 1..=16 {
+            for scalar in scalars.iter() {
+                let bytes = scalar.to_bytes();
+                let half = 1 << (width - 1);
+                let recoded = (0..num_windows(width))
+                    .rev()
+                    .fold(Scalar::zero(), |acc, j| {
+                        let digit = signed_window(&bytes, j * width, width);
+                        assert!(-half <= digit && digit <= half);
+                        let d = Scalar::from(digit.unsigned_abs() as u64);
+                        let d = if digit < 0 { -d } else { d };
+                        (0..width).fold(acc, |acc, _| acc.double()) + d
+                    });
+                assert_eq!(recoded, *scalar);
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_streaming_multi_exp() {
+        let bases = (1..=20)
+            .map(|i| G1Affine::from(G1Affine::generator() * Scalar::from(i)))
+            .collect::<Vec<_>>();
+        let scalars = (1..=20)
+            .map(|i| Scalar::from(i).invert().unwrap())
+            .collect::<Vec<_>>();
+        let expected = multi_exp_vartime(&bases, &scalars);
+        assert_eq!(expected, G1Projective::generator() * Scalar::from(20));
+
+        for window in [1, 3, 5] {
+            let pairs = bases.iter().copied().zip(scalars.iter().copied());
+            assert_eq!(multi_exp_iter_vartime(pairs, window), expected);
+        }
+
+        // Adding the pairs in chunks, interleaved with other work.
+        let mut msm = StreamingMultiExp::new(4);
+        for (bases, scalars) in bases.chunks(6).zip(scalars.chunks(6)) {
+            msm.extend(bases.iter().copied().zip(scalars.iter().copied()));
+        }
+        assert_eq!(msm.finish(), expected);
+        msm.add(&G1Affine::generator(), &-Scalar::from(20));
+        assert_eq!(msm.finish(), G1Projective::identity());
+
+        let msm = StreamingMultiExp::<G2Affine>::new(8);
+        assert_eq!(msm.finish(), G2Projective::identity());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_msm_with_precompute() {
+        let bases = (1..=5)
+            .map(|i| G1Affine::from(G1Affine::generator() * Scalar::from(i)))
+            .collect::<Vec<_>>();
+        let scalars = (1..=5)
+            .map(|i| -Scalar::from(i).invert().unwrap())
+            .collect::<Vec<_>>();
+        let expected = -G1Projective::generator() * Scalar::from(5);
+
+        for window in [1, 4, 7] {
+            let precompute = MsmPrecompute::new(&bases, window);
+            assert_eq!(precompute.len(), 5);
+            assert_eq!(precompute.window(), window);
+            assert_eq!(msm_with_precompute(&precompute, &scalars), expected);
+        }
+
+        let precompute = MsmPrecompute::new(&bases, 6);
+        let bytes = precompute.to_bytes();
+        assert_eq!(bytes.len(), 5 + 5 * 43 * 96);
+        assert_eq!(MsmPrecompute::from_bytes(&bytes), Some(precompute));
+        assert_eq!(
+            MsmPrecompute::<G1Affine>::from_bytes(&bytes[..bytes.len() - 1]),
+            None
+        );
+        let mut invalid = bytes.clone();
+        invalid[0] = 5;
+        assert_eq!(MsmPrecompute::<G1Affine>::from_bytes(&invalid), None);
+        let mut invalid = bytes;
+        invalid[6] ^= 1;
+        assert_eq!(MsmPrecompute::<G1Affine>::from_bytes(&invalid), None);
+
+        let q = MsmPrecompute::new(&[G2Affine::generator()], 3);
+        assert!(!q.is_empty());
+        assert_eq!(
+            msm_with_precompute(&q, &[Scalar::from(9)]),
+            G2Projective::generator() * Scalar::from(9)
+        );
+        let empty = MsmPrecompute::<G2Affine>::new(&[], 3);
+        assert!(empty.is_empty());
+        assert_eq!(msm_with_precompute(&empty, &[]), G2Projective::identity());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_multi_exp_length_mismatch() {
+        multi_exp_vartime(&[G1Affine::generator()], &[]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_multi_exp_short_bytes() {
+        let mut scratch = [G1Projective::identity(); 8];
+        multi_exp_vartime_with_buffers(
+            &[G1Affine::generator(); 2],
+            &[Scalar::one(); 2],
+            &mut scratch,
+            &mut [[0; 32]; 1],
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! Deterministic derivation of nonces from a secret scalar and a message, following
+//! [RFC 6979].
+//!
+//! Protocols that prove knowledge of a secret $x \in \mathbb{F}_q$, such as Schnorr
+//! signatures or DLEQ proofs, need a fresh nonce $k$ for every message: reusing a
+//! nonce, or using a biased one, reveals the secret. [`derive_nonce`] computes $k$
+//! from the secret and the message with the HMAC-DRBG of section 3.2 of RFC 6979, so
+//! that the same inputs always yield the same nonce and no source of randomness is
+//! needed. The domain separation tag is the additional data $k'$ of section 3.6, so
+//! that protocols sharing a secret key derive independent nonces.
+//!
+//! [RFC 6979]: https://www.rfc-editor.org/rfc/rfc6979
+
+use digest::{BlockInput, Digest};
+
+use crate::generic_array::GenericArray;
+use crate::hmac::hmac;
+use crate::Scalar;
+
+/// Derives the nonce for signing `message` with `secret` in the protocol identified
+/// by the domain separation tag `dst`, with the HMAC-DRBG of RFC 6979 instantiated
+/// with the hash function `H`.
+///
+/// The nonce is uniformly distributed in $[1, q)$ as long as `H` behaves as a random
+/// oracle. Candidates outside of this range are rejected, so this function is
+/// variable time in the number of candidates it tries, which reveals nothing about
+/// the nonce it returns.
+pub fn derive_nonce<H>(secret: &Scalar, message: &[u8], dst: &[u8]) -> Scalar
+where
+    H: Digest + BlockInput,
+{
+    let x = secret.to_bytes_be();
+    let h = Scalar::from_raw(bits2int(&H::digest(message))).to_bytes_be();
+
+    let mut v = GenericArray::<u8, H::OutputSize>::default();
+    for b in v.iter_mut() {
+        *b = 0x01;
+    }
+    let k = GenericArray::<u8, H::OutputSize>::default();
+    let k = hmac::<H>(&k, &[&v, &[0x00], &x, &h, dst]);
+    let v = hmac::<H>(&k, &[&v]);
+    let mut k = hmac::<H>(&k, &[&v, &[0x01], &x, &h, dst]);
+    let mut v = hmac::<H>(&k, &[&v]);
+
+    loop {
+        // The leftmost 255 bits of the output of the generator, the bit length of q.
+        let mut t = [0u8; 32];
+        let mut filled = 0;
+        while filled < t.len() {
+            v = hmac::<H>(&k, &[&v]);
+            let n = v.len().min(t.len() - filled);
+            t[filled..filled + n].copy_from_slice(&v[..n]);
+            filled += n;
+        }
+
+        if let Some(nonce) = Option::<Scalar>::from(Scalar::from_raw_checked(bits2int(&t))) {
+            if nonce != Scalar::zero() {
+                return nonce;
+            }
+        }
+
+        k = hmac::<H>(&k, &[&v, &[0x00]]);
+        v = hmac::<H>(&k, &[&v]);
+    }
+}
+
+/// Converts the leftmost 255 bits of the big-endian `bytes`, or all of them if there
+/// are fewer, to an integer given as little-endian limbs, as `bits2int` does in
+/// RFC 6979.
+fn bits2int(bytes: &[u8]) -> [u64; 4] {
+    let mut buf = [0u8; 32];
+    if bytes.len() >= 32 {
+        buf.copy_from_slice(&bytes[..32]);
+        for i in (0..32).rev() {
+            let carry = if i > 0 { buf[i - 1] << 7 } else { 0 };
+            buf[i] = (buf[i] >> 1) | carry;
+        }
+    } else {
+        buf[32 - bytes.len()..].copy_from_slice(bytes);
+    }
+
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(buf.rchunks_exact(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+    }
+    limbs
+}
+
+#[cfg(all(test, feature = "sha2"))]
+mod tests {
+    use super::*;
+    use sha2::{Sha256, Sha512};
+
+    #[test]
+    fn test_bits2int() {
+        let mut bytes = [0u8; 40];
+        bytes[0] = 0x80;
+        bytes[31] = 0x03;
+        bytes[32] = 0xff;
+        assert_eq!(bits2int(&bytes), [1, 0, 0, 0x4000_0000_0000_0000]);
+        assert_eq!(bits2int(&[0x01, 0x02]), [0x0102, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_derive_nonce() {
+        let secret = Scalar::from_raw([
+            0x0123_4567_89ab_cdef,
+            0xfedc_ba98_7654_3210,
+            0x0f1e_2d3c_4b5a_6978,
+            0x0011_2233_4455_6677,
+        ]);
+        let dst = b"TEST_NONCE_DST";
+
+        // Computed with an independent implementation of section 3.2 of RFC 6979.
+        assert_eq!(
+            derive_nonce::<Sha256>(&secret, b"sample", dst),
+            Scalar::from_raw(NONCE_SHA256)
+        );
+        assert_eq!(
+            derive_nonce::<Sha512>(&secret, b"sample", dst),
+            Scalar::from_raw(NONCE_SHA512)
+        );
+
+        let nonce = derive_nonce::<Sha256>(&secret, b"sample", dst);
+        assert_eq!(derive_nonce::<Sha256>(&secret, b"sample", dst), nonce);
+        assert_ne!(derive_nonce::<Sha256>(&secret, b"test", dst), nonce);
+        assert_ne!(derive_nonce::<Sha256>(&secret, b"sample", b"OTHER"), nonce);
+        assert_ne!(
+            derive_nonce::<Sha256>(&(secret + Scalar::one()), b"sample", dst),
+            nonce
+        );
+    }
+
+    const NONCE_SHA256: [u64; 4] = [
+        0xa3a1_2832_1b49_f54f,
+        0x478a_7203_99c8_3f18,
+        0x9984_c637_fa61_4e79,
+        0x6dab_c1cb_1e70_bd0c,
+    ];
+    const NONCE_SHA512: [u64; 4] = [
+        0xa1b1_fae2_bf3f_7557,
+        0x0145_c1cc_0fa1_8ddb,
+        0xfc07_6d94_cf2f_1115,
+        0x41df_c4c9_fa16_6910,
+    ];
+}
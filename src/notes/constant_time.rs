@@ -0,0 +1,55 @@
+//! # Constant-time guarantees
+//!
+//! The arithmetic of this crate on field elements, scalars and group elements is
+//! constant time: its running time and memory access pattern do not depend on the
+//! values of its inputs, so it is safe to use with secrets. Functions whose result
+//! depends on a secret condition return a `Choice` or a `CtOption` rather than a
+//! `bool` or an `Option`, and `==` on these types is computed with
+//! `ConstantTimeEq`, from the `subtle` crate. Every field, scalar and group type
+//! also implements `ConditionallySelectable`, with its `conditional_swap`, and
+//! `ConditionallyNegatable`, which constant-time ladders and sorting networks can
+//! use instead of pairs of `conditional_select` calls.
+//!
+//! Functions that are faster on public data, at the cost of being variable time,
+//! follow a naming convention so that callers can tell them apart:
+//!
+//! * Functions whose name ends in `_vartime` are variable time with respect to the
+//!   inputs given in their documentation. `G1Projective::mul_vartime` and
+//!   `G2Projective::mul_vartime` depend on both the point and the scalar, and are
+//!   meant for verifiers, whose inputs are all public. The exponentiations
+//!   `pow_vartime` of the fields and of `Scalar`, and `Gt::mul_vartime`, only
+//!   depend on the exponent, so they are safe for secret bases raised to public
+//!   exponents: the field inversions and square roots of this crate are computed
+//!   this way, with the fixed exponents of the field.
+//! * `Scalar::pow_u64`, the exponentiation by exponents of a single limb, is
+//!   variable time with respect to the exponent, as `Scalar::pow_vartime` is.
+//! * `G1Projective::mul_by_x_vartime` and `G2Projective::mul_by_x_vartime` only
+//!   depend on whether the point is the identity. `mul_by_x`, which they
+//!   otherwise compute the same way, is constant time.
+//! * `Fp::invert_vartime` and `Scalar::invert_vartime` use the binary extended
+//!   Euclidean algorithm, whose running time depends on the element, rather than
+//!   the exponentiation of `invert`.
+//! * The multi-scalar multiplications of `msm`, and those of `Backend`, are
+//!   variable time with respect to the scalars, whether or not their names end in
+//!   `_vartime`.
+//! * The decoders whose names start with `try_from_`, `from_bytes_any`, and the
+//!   `TryFrom<&[u8]>` implementations of the group elements, return an `Error` that
+//!   reveals why an encoding is invalid, and stop as soon as they find out. The
+//!   `from_compressed` and `from_uncompressed` decoders are their constant-time
+//!   counterparts.
+//!
+//! There are no variable-time counterparts of comparisons and of the checked
+//! decoders, since a variable-time implementation of these would not be
+//! significantly faster.
+//!
+//! The protocols built on this arithmetic, such as the polynomial commitments of
+//! `kzg` or the polynomials of `poly`, document the inputs in which they are
+//! variable time, which are only ever public ones.
+//!
+//! Constant time does not protect against side channels that measure more than
+//! time, such as the power draw or the electromagnetic emissions of a device, from
+//! which the operations on a secret scalar may still be inferred across many
+//! multiplications. `G1Projective::mul_blinded` and `G2Projective::mul_blinded`
+//! randomize the scalar and the coordinates of every multiplication with
+//! randomness from the caller, for deployments where such measurements are a
+//! concern.
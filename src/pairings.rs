@@ -2,16 +2,18 @@ use crate::fp::Fp;
 use crate::fp12::Fp12;
 use crate::fp2::Fp2;
 use crate::fp6::Fp6;
-use crate::{G1Affine, G1Projective, G2Affine, G2Projective, Scalar, BLS_X, BLS_X_IS_NEGATIVE};
+use crate::{
+    Error, G1Affine, G1Projective, G2Affine, G2Projective, Scalar, BLS_X, BLS_X_IS_NEGATIVE,
+};
 
 use core::borrow::Borrow;
 use core::fmt;
 use core::iter::Sum;
 use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
-use group::Group;
+use group::{Group, GroupEncoding, UncompressedEncoding};
 use pairing::{Engine, PairingCurveAffine};
 use rand_core::RngCore;
-use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+use subtle::{Choice, ConditionallyNegatable, ConditionallySelectable, ConstantTimeEq, CtOption};
 
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
@@ -41,96 +43,32 @@ impl ConditionallySelectable for MillerLoopResult {
 }
 
 impl MillerLoopResult {
+    /// Serializes this result as the 576-byte big-endian encoding of its value in
+    /// $\mathbb{F}_{p^{12}}$, laid out as in `Gt::to_uncompressed`, so that it can be
+    /// final exponentiated later or elsewhere.
+    pub fn to_bytes(&self) -> [u8; 576] {
+        self.0.to_bytes()
+    }
+
+    /// Deserializes a result serialized with [`MillerLoopResult::to_bytes`], failing
+    /// if the encoding is not canonical or if the value is zero, which no Miller loop
+    /// outputs.
+    ///
+    /// Any other value could be the output of some Miller loop, so this cannot check
+    /// that `bytes` were produced by one: a result loaded from an untrusted source is
+    /// only as trustworthy as that source.
+    pub fn from_bytes(bytes: &[u8; 576]) -> CtOption<Self> {
+        Fp12::from_bytes_unchecked(bytes).and_then(|f| {
+            let is_nonzero = !f.is_zero();
+            CtOption::new(MillerLoopResult(f), is_nonzero)
+        })
+    }
+
     /// This performs a "final exponentiation" routine to convert the result
     /// of a Miller loop into an element of `Gt` with help of efficient squaring
     /// operation in the so-called `cyclotomic subgroup` of `Fq6` so that
     /// it can be compared with other elements of `Gt`.
     pub fn final_exponentiation(&self) -> Gt {
-        #[must_use]
-        fn fp4_square(a: Fp2, b: Fp2) -> (Fp2, Fp2) {
-            let t0 = a.square();
-            let t1 = b.square();
-            let mut t2 = t1.mul_by_nonresidue();
-            let c0 = t2 + t0;
-            t2 = a + b;
-            t2 = t2.square();
-            t2 -= t0;
-            let c1 = t2 - t1;
-
-            (c0, c1)
-        }
-        // Adaptation of Algorithm 5.5.4, Guide to Pairing-Based Cryptography
-        // Faster Squaring in the Cyclotomic Subgroup of Sixth Degree Extensions
-        // https://eprint.iacr.org/2009/565.pdf
-        #[must_use]
-        fn cyclotomic_square(f: Fp12) -> Fp12 {
-            let mut z0 = f.c0.c0;
-            let mut z4 = f.c0.c1;
-            let mut z3 = f.c0.c2;
-            let mut z2 = f.c1.c0;
-            let mut z1 = f.c1.c1;
-            let mut z5 = f.c1.c2;
-
-            let (t0, t1) = fp4_square(z0, z1);
-
-            // For A
-            z0 = t0 - z0;
-            z0 = z0 + z0 + t0;
-
-            z1 = t1 + z1;
-            z1 = z1 + z1 + t1;
-
-            let (mut t0, t1) = fp4_square(z2, z3);
-            let (t2, t3) = fp4_square(z4, z5);
-
-            // For C
-            z4 = t0 - z4;
-            z4 = z4 + z4 + t0;
-
-            z5 = t1 + z5;
-            z5 = z5 + z5 + t1;
-
-            // For B
-            t0 = t3.mul_by_nonresidue();
-            z2 = t0 + z2;
-            z2 = z2 + z2 + t0;
-
-            z3 = t2 - z3;
-            z3 = z3 + z3 + t2;
-
-            Fp12 {
-                c0: Fp6 {
-                    c0: z0,
-                    c1: z4,
-                    c2: z3,
-                },
-                c1: Fp6 {
-                    c0: z2,
-                    c1: z1,
-                    c2: z5,
-                },
-            }
-        }
-        #[must_use]
-        fn cycolotomic_exp(f: Fp12) -> Fp12 {
-            let x = BLS_X;
-            let mut tmp = Fp12::one();
-            let mut found_one = false;
-            for i in (0..64).rev().map(|b| ((x >> b) & 1) == 1) {
-                if found_one {
-                    tmp = cyclotomic_square(tmp)
-                } else {
-                    found_one = i;
-                }
-
-                if i {
-                    tmp *= f;
-                }
-            }
-
-            tmp.conjugate()
-        }
-
         let mut f = self.0;
         let mut t0 = f
             .frobenius_map()
@@ -145,15 +83,15 @@ impl MillerLoopResult {
                 t1 = t2;
                 t2 = t2.frobenius_map().frobenius_map();
                 t2 *= t1;
-                t1 = cyclotomic_square(t2).conjugate();
-                let mut t3 = cycolotomic_exp(t2);
-                let mut t4 = cyclotomic_square(t3);
+                t1 = t2.cyclotomic_square().conjugate();
+                let mut t3 = t2.cyclotomic_exp_by_x();
+                let mut t4 = t3.cyclotomic_square();
                 let mut t5 = t1 * t3;
-                t1 = cycolotomic_exp(t5);
-                t0 = cycolotomic_exp(t1);
-                let mut t6 = cycolotomic_exp(t0);
+                t1 = t5.cyclotomic_exp_by_x();
+                t0 = t1.cyclotomic_exp_by_x();
+                let mut t6 = t0.cyclotomic_exp_by_x();
                 t6 *= t4;
-                t4 = cycolotomic_exp(t6);
+                t4 = t6.cyclotomic_exp_by_x();
                 t5 = t5.conjugate();
                 t4 *= t5 * t2;
                 t5 = t2.conjugate();
@@ -206,6 +144,12 @@ impl<'b> AddAssign<&'b MillerLoopResult> for MillerLoopResult {
 ///
 /// Typically, $\mathbb{G}_T$ is written multiplicatively but we will write it additively to
 /// keep code and abstractions consistent.
+///
+/// Multiplying an element by a [`Scalar`], as exponentiation is written here, is
+/// constant time with the `*` operator and with [`Gt::generator_mul`], so either can
+/// be used with secret scalars, as in the decryption of identity-based or ElGamal
+/// ciphertexts. [`Gt::mul_vartime`] is faster for public multipliers, but its
+/// running time depends on them.
 #[cfg_attr(docsrs, doc(cfg(feature = "pairings")))]
 #[derive(Copy, Clone, Debug)]
 pub struct Gt(pub(crate) Fp12);
@@ -216,9 +160,24 @@ impl Default for Gt {
     }
 }
 
+/// Formats the compressed encoding in hexadecimal with a `0x` prefix, or the
+/// uncompressed encoding with the alternate flag (`{:#}`).
 impl fmt::Display for Gt {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
+        f.write_str("0x")?;
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+/// Formats the compressed encoding in hexadecimal, or the uncompressed encoding
+/// with the alternate flag (`{:#x}`).
+impl fmt::LowerHex for Gt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            crate::util::write_hex(f, &self.to_uncompressed())
+        } else {
+            crate::util::write_hex(f, &self.to_compressed())
+        }
     }
 }
 
@@ -243,16 +202,253 @@ impl PartialEq for Gt {
 }
 
 impl Gt {
+    /// The generator of the group, which is the pairing of [`G1Affine::generator`]
+    /// and [`G2Affine::generator`].
+    pub const GENERATOR: Gt = Gt(Fp12 {
+        c0: Fp6 {
+            c0: Fp2 {
+                c0: Fp::from_raw_unchecked([
+                    0x1972_e433_a01f_85c5,
+                    0x97d3_2b76_fd77_2538,
+                    0xc8ce_546f_c96b_cdf9,
+                    0xcef6_3e73_66d4_0614,
+                    0xa611_3427_8184_3780,
+                    0x13f3_448a_3fc6_d825,
+                ]),
+                c1: Fp::from_raw_unchecked([
+                    0xd263_31b0_2e9d_6995,
+                    0x9d68_a482_f779_7e7d,
+                    0x9c9b_2924_8d39_ea92,
+                    0xf480_1ca2_e131_07aa,
+                    0xa16c_0732_bdbc_b066,
+                    0x083c_a4af_ba36_0478,
+                ]),
+            },
+            c1: Fp2 {
+                c0: Fp::from_raw_unchecked([
+                    0x59e2_61db_0916_b641,
+                    0x2716_b6f4_b23e_960d,
+                    0xc8e5_5b10_a0bd_9c45,
+                    0x0bdb_0bd9_9c4d_eda8,
+                    0x8cf8_9ebf_57fd_aac5,
+                    0x12d6_b792_9e77_7a5e,
+                ]),
+                c1: Fp::from_raw_unchecked([
+                    0x5fc8_5188_b0e1_5f35,
+                    0x34a0_6e3a_8f09_6365,
+                    0xdb31_26a6_e02a_d62c,
+                    0xfc6f_5aa9_7d9a_990b,
+                    0xa12f_55f5_eb89_c210,
+                    0x1723_703a_926f_8889,
+                ]),
+            },
+            c2: Fp2 {
+                c0: Fp::from_raw_unchecked([
+                    0x9358_8f29_7182_8778,
+                    0x43f6_5b86_11ab_7585,
+                    0x3183_aaf5_ec27_9fdf,
+                    0xfa73_d7e1_8ac9_9df6,
+                    0x64e1_76a6_a64c_99b0,
+                    0x179f_a78c_5838_8f1f,
+                ]),
+                c1: Fp::from_raw_unchecked([
+                    0x672a_0a11_ca2a_ef12,
+                    0x0d11_b9b5_2aa3_f16b,
+                    0xa444_12d0_699d_056e,
+                    0xc01d_0177_221a_5ba5,
+                    0x66e0_cede_6c73_5529,
+                    0x05f5_a71e_9fdd_c339,
+                ]),
+            },
+        },
+        c1: Fp6 {
+            c0: Fp2 {
+                c0: Fp::from_raw_unchecked([
+                    0xd30a_88a1_b062_c679,
+                    0x5ac5_6a5d_35fc_8304,
+                    0xd0c8_34a6_a81f_290d,
+                    0xcd54_30c2_da37_07c7,
+                    0xf0c2_7ff7_8050_0af0,
+                    0x0924_5da6_e2d7_2eae,
+                ]),
+                c1: Fp::from_raw_unchecked([
+                    0x9f2e_0676_791b_5156,
+                    0xe2d1_c823_4918_fe13,
+                    0x4c9e_459f_3c56_1bf4,
+                    0xa3e8_5e53_b9d3_e3c1,
+                    0x820a_121e_21a7_0020,
+                    0x15af_6183_41c5_9acc,
+                ]),
+            },
+            c1: Fp2 {
+                c0: Fp::from_raw_unchecked([
+                    0x7c95_658c_2499_3ab1,
+                    0x73eb_3872_1ca8_86b9,
+                    0x5256_d749_4774_34bc,
+                    0x8ba4_1902_ea50_4a8b,
+                    0x04a3_d3f8_0c86_ce6d,
+                    0x18a6_4a87_fb68_6eaa,
+                ]),
+                c1: Fp::from_raw_unchecked([
+                    0xbb83_e71b_b920_cf26,
+                    0x2a52_77ac_92a7_3945,
+                    0xfc0e_e59f_94f0_46a0,
+                    0x7158_cdf3_7860_58f7,
+                    0x7cc1_061b_82f9_45f6,
+                    0x03f8_47aa_9fdb_e567,
+                ]),
+            },
+            c2: Fp2 {
+                c0: Fp::from_raw_unchecked([
+                    0x8078_dba5_6134_e657,
+                    0x1cd7_ec9a_4399_8a6e,
+                    0xb1aa_599a_1a99_3766,
+                    0xc9a0_f62f_0842_ee44,
+                    0x8e15_9be3_b605_dffa,
+                    0x0c86_ba0d_4af1_3fc2,
+                ]),
+                c1: Fp::from_raw_unchecked([
+                    0xe80f_f2a0_6a52_ffb1,
+                    0x7694_ca48_721a_906c,
+                    0x7583_183e_03b0_8514,
+                    0xf567_afdd_40ce_e4e2,
+                    0x9a6d_96d2_e526_a5fc,
+                    0x197e_9f49_861f_2242,
+                ]),
+            },
+        },
+    });
+
     /// Returns the group identity, which is $1$.
     pub fn identity() -> Gt {
         Gt(Fp12::one())
     }
 
+    /// Returns the fixed generator [`Gt::GENERATOR`] of the group.
+    pub fn generator() -> Gt {
+        Gt::GENERATOR
+    }
+
+    /// Multiplies [`Gt::GENERATOR`] by `scalar`, computing $e(G_1, G_2)^s$ without a
+    /// pairing.
+    ///
+    /// This is constant time, and about twice as fast as `Gt::GENERATOR * scalar`:
+    /// `scalar` is split into four digits of 64 bits in base $|x|$, and the images of
+    /// the generator by the Frobenius endomorphism, which acts on $\mathbb{G}_T$ as
+    /// multiplication by $p \equiv x \pmod{q}$, are multiplied by these digits
+    /// simultaneously, with 64 doublings instead of 255.
+    pub fn generator_mul(scalar: &Scalar) -> Gt {
+        Gt::GENERATOR.mul_by_frobenius_split(scalar)
+    }
+
     /// Doubles this group element.
     pub fn double(&self) -> Gt {
         Gt(self.0.square())
     }
 
+    /// Doubles this group element `n` times, computing $[2^n] P$ with
+    /// [`Fp12::cyclotomic_square_n`].
+    pub fn double_n(&self, n: usize) -> Gt {
+        Gt(self.0.cyclotomic_square_n(n))
+    }
+
+    /// Multiplies this group element by the integer represented in little endian
+    /// by `by`, which need not be reduced modulo $q$.
+    ///
+    /// `by` is recoded in width-5 NAF, whose negative digits are cheap since the
+    /// negation of an element of $\mathbb{G}_T$ is its conjugate, and the doublings
+    /// between the nonzero digits are computed with [`Gt::double_n`], which makes
+    /// this fast for large, sparse multipliers. Although this is labeled "vartime",
+    /// it is only variable time with respect to `by`.
+    pub fn mul_vartime(&self, by: &[u64]) -> Gt {
+        const WINDOW: usize = 5;
+        const WIDTH: u64 = 1 << WINDOW;
+
+        // The `WINDOW` bits of `by` starting at `pos`, which may straddle two limbs.
+        let bits_at = |pos: usize| {
+            let (limb, bit) = (pos / 64, pos % 64);
+            let mut bits = by.get(limb).map_or(0, |limb| limb >> bit);
+            if bit + WINDOW > 64 {
+                bits |= by.get(limb + 1).map_or(0, |limb| limb << (64 - bit));
+            }
+            bits & (WIDTH - 1)
+        };
+
+        // The digits are found from the least significant one, and the digit
+        // ±(2j + 1) at position i adds ±[2^i] P to buckets[j], so that the product
+        // is the sum of the [2j + 1] buckets[j].
+        let mut buckets = [Gt::identity(); 1 << (WINDOW - 2)];
+        let mut base = *self;
+        let mut base_pos = 0;
+        let mut carry = 0;
+        let mut pos = 0;
+        while pos < 64 * by.len() || carry != 0 {
+            let window = carry + bits_at(pos);
+            if window & 1 == 0 {
+                // Only the lowest bit is consumed, so a window of `WIDTH` leaves the
+                // carry for the next position.
+                pos += 1;
+                continue;
+            }
+
+            base = base.double_n(pos - base_pos);
+            base_pos = pos;
+            if window < WIDTH / 2 {
+                carry = 0;
+                buckets[(window / 2) as usize] += base;
+            } else {
+                carry = 1;
+                buckets[((WIDTH - window) / 2) as usize] -= base;
+            }
+            pos += WINDOW;
+        }
+
+        // The sum of the [j] buckets[j] for j > 0, doubled, plus all of the buckets.
+        let mut running = Gt::identity();
+        let mut weighted = Gt::identity();
+        for bucket in buckets[1..].iter().rev() {
+            running += bucket;
+            weighted += running;
+        }
+
+        weighted.double() + running + buckets[0]
+    }
+
+    /// Multiplies this element by `scalar` in constant time, as `scalar` $= s_0 + s_1
+    /// |x| + s_2 |x|^2 + s_3 |x|^3$ times it, with $[|x|^i] P$ computed as $\pm
+    /// \pi^i(P)$ for the Frobenius endomorphism $\pi$.
+    fn mul_by_frobenius_split(&self, scalar: &Scalar) -> Gt {
+        let digits = split_by_x(scalar);
+
+        // [x] P = π(P) and x is negative, so [|x|] P is the conjugate of π(P).
+        let p1 = self.0.frobenius_map();
+        let p2 = p1.frobenius_map();
+        let p3 = p2.frobenius_map();
+        let bases = [self.0, p1.conjugate(), p2, p3.conjugate()];
+
+        // The sums of the subsets of bases, indexed by the bits of their index.
+        let mut table = [Fp12::one(); 16];
+        for m in 1..16 {
+            table[m] = table[m & (m - 1)] * bases[m.trailing_zeros() as usize];
+        }
+
+        let mut acc = Fp12::one();
+        for bit in (0..64).rev() {
+            acc = acc.cyclotomic_square();
+
+            let index = digits.iter().enumerate().fold(0u64, |index, (i, digit)| {
+                index | (((digit >> bit) & 1) << i)
+            });
+            let mut term = Fp12::one();
+            for (m, entry) in table.iter().enumerate() {
+                term.conditional_assign(entry, (m as u64).ct_eq(&index));
+            }
+            acc *= term;
+        }
+
+        Gt(acc)
+    }
+
     /// Serializes this element into uncompressed form. See [`notes::serialization`](crate::notes::serialization)
     /// for details about how group elements are serialized.
     pub fn to_uncompressed(&self) -> [u8; 576] {
@@ -309,20 +505,99 @@ impl Gt {
             // c_0^2 = 1 + v * c_1^2
             let xc0 = (Fp6::one() + c1.square().mul_by_nonresidue()).sqrt();
 
-            xc0.and_then(|c0| {
-                let p = Fp12 {
-                    c0: Fp6::conditional_select(
-                        &c0,
-                        &-c0,
-                        c0.lexicographically_largest() ^ sort_flag_set,
-                    ),
-                    c1,
-                };
+            xc0.and_then(|mut c0| {
+                c0.conditional_negate(c0.lexicographically_largest() ^ sort_flag_set);
+                let p = Fp12 { c0, c1 };
 
                 CtOption::new(Gt(p), compression_flag_set)
             })
         })
     }
+
+    /// Attempts to deserialize an uncompressed element, reporting why it is invalid
+    /// if it is. See [`notes::serialization`](crate::notes::serialization) for
+    /// details about how group elements are serialized.
+    ///
+    /// **This is not constant time**; use `from_uncompressed()` for secret inputs.
+    pub fn try_from_uncompressed(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes = <&[u8; 576]>::try_from(bytes).map_err(|_| Error::InvalidLength)?;
+        let f = Option::<Fp12>::from(Fp12::from_bytes_unchecked(bytes))
+            .ok_or(Error::NonCanonicalField)?;
+        if bool::from(f.is_element()) {
+            Ok(Gt(f))
+        } else {
+            Err(Error::WrongSubgroup)
+        }
+    }
+
+    /// Attempts to deserialize a compressed element, reporting why it is invalid if
+    /// it is. See [`notes::serialization`](crate::notes::serialization) for details
+    /// about how group elements are serialized.
+    ///
+    /// **This is not constant time**; use `from_compressed()` for secret inputs.
+    pub fn try_from_compressed(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes = <&[u8; 288]>::try_from(bytes).map_err(|_| Error::InvalidLength)?;
+        if bytes[0] >> 7 == 0 {
+            return Err(Error::BadFlags);
+        }
+
+        let mut c1 = *bytes;
+        c1[0] &= 0b0011_1111;
+        if bool::from(Fp6::from_bytes_unchecked(&c1).is_none()) {
+            return Err(Error::NonCanonicalField);
+        }
+
+        // With a valid flag and c_1, only the recovery of c_0 can fail.
+        let gt = Option::<Self>::from(Self::from_compressed_unchecked(bytes))
+            .ok_or(Error::NotOnCurve)?;
+        if bool::from(gt.0.is_element()) {
+            Ok(gt)
+        } else {
+            Err(Error::WrongSubgroup)
+        }
+    }
+}
+
+/// Decodes the compressed encoding of [`Gt::to_compressed`], which must be exactly
+/// 288 bytes long, as [`Gt::try_from_compressed`] does.
+///
+/// **This is not constant time**; use `from_compressed()` for secret inputs.
+/// Writes `scalar` as $s_0 + s_1 |x| + s_2 |x|^2 + s_3 |x|^3$ with $0 \le s_i < |x|$,
+/// in constant time.
+fn split_by_x(scalar: &Scalar) -> [u64; 4] {
+    let bytes = scalar.to_bytes();
+    let mut n = [0u64; 4];
+    for (limb, chunk) in n.iter_mut().zip(bytes.chunks_exact(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+
+    let mut digits = [0u64; 4];
+    for digit in digits.iter_mut().take(3) {
+        // Long division of n by |x|, one bit at a time, with each bit of n replaced
+        // by the bit of the quotient once it has been shifted into the remainder.
+        let mut rem = 0u128;
+        for i in (0..256).rev() {
+            let (limb, shift) = (i / 64, i % 64);
+            rem = (rem << 1) | u128::from((n[limb] >> shift) & 1);
+            let (reduced, borrow) = rem.overflowing_sub(u128::from(BLS_X));
+            let mask = u128::from(borrow).wrapping_sub(1);
+            rem = (reduced & mask) | (rem & !mask);
+            n[limb] = (n[limb] & !(1 << shift)) | (((mask & 1) as u64) << shift);
+        }
+        *digit = rem as u64;
+    }
+    // q < |x|^4, so the last quotient is a single digit.
+    digits[3] = n[0];
+
+    digits
+}
+
+impl<'a> TryFrom<&'a [u8]> for Gt {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Gt, Error> {
+        Gt::try_from_compressed(bytes)
+    }
 }
 
 impl<'a> Neg for &'a Gt {
@@ -366,23 +641,29 @@ impl<'a, 'b> Mul<&'b Scalar> for &'a Gt {
     type Output = Gt;
 
     fn mul(self, other: &'b Scalar) -> Self::Output {
+        // This is a fixed-window implementation of group element multiplication
+        // with 4-bit windows, moving from most significant to least significant
+        // window of the scalar. Each window's multiple of `self` is selected by
+        // scanning the whole table, so that the memory access pattern does not
+        // depend on the scalar, and the doublings are cyclotomic squarings.
+        let mut table = [Gt::identity(); 16];
+        let mut multiple = Gt::identity();
+        for entry in table.iter_mut().skip(1) {
+            multiple += self;
+            *entry = multiple;
+        }
+
         let mut acc = Gt::identity();
+        for byte in other.to_bytes().iter().rev() {
+            for window in [byte >> 4, byte & 0xf] {
+                acc = acc.double_n(4);
 
-        // This is a simple double-and-add implementation of group element
-        // multiplication, moving from most significant to least
-        // significant bit of the scalar.
-        //
-        // We skip the leading bit because it's always unset for Fq
-        // elements.
-        for bit in other
-            .to_bytes()
-            .iter()
-            .rev()
-            .flat_map(|byte| (0..8).rev().map(move |i| Choice::from((byte >> i) & 1u8)))
-            .skip(1)
-        {
-            acc = acc.double();
-            acc = Gt::conditional_select(&acc, &(acc + self), bit);
+                let mut addend = Gt::identity();
+                for (i, entry) in table.iter().enumerate() {
+                    addend.conditional_assign(entry, (i as u8).ct_eq(&window));
+                }
+                acc += addend;
+            }
         }
 
         acc
@@ -425,121 +706,7 @@ impl Group for Gt {
     }
 
     fn generator() -> Self {
-        // pairing(&G1Affine::generator(), &G2Affine::generator())
-        Gt(Fp12 {
-            c0: Fp6 {
-                c0: Fp2 {
-                    c0: Fp::from_raw_unchecked([
-                        0x1972_e433_a01f_85c5,
-                        0x97d3_2b76_fd77_2538,
-                        0xc8ce_546f_c96b_cdf9,
-                        0xcef6_3e73_66d4_0614,
-                        0xa611_3427_8184_3780,
-                        0x13f3_448a_3fc6_d825,
-                    ]),
-                    c1: Fp::from_raw_unchecked([
-                        0xd263_31b0_2e9d_6995,
-                        0x9d68_a482_f779_7e7d,
-                        0x9c9b_2924_8d39_ea92,
-                        0xf480_1ca2_e131_07aa,
-                        0xa16c_0732_bdbc_b066,
-                        0x083c_a4af_ba36_0478,
-                    ]),
-                },
-                c1: Fp2 {
-                    c0: Fp::from_raw_unchecked([
-                        0x59e2_61db_0916_b641,
-                        0x2716_b6f4_b23e_960d,
-                        0xc8e5_5b10_a0bd_9c45,
-                        0x0bdb_0bd9_9c4d_eda8,
-                        0x8cf8_9ebf_57fd_aac5,
-                        0x12d6_b792_9e77_7a5e,
-                    ]),
-                    c1: Fp::from_raw_unchecked([
-                        0x5fc8_5188_b0e1_5f35,
-                        0x34a0_6e3a_8f09_6365,
-                        0xdb31_26a6_e02a_d62c,
-                        0xfc6f_5aa9_7d9a_990b,
-                        0xa12f_55f5_eb89_c210,
-                        0x1723_703a_926f_8889,
-                    ]),
-                },
-                c2: Fp2 {
-                    c0: Fp::from_raw_unchecked([
-                        0x9358_8f29_7182_8778,
-                        0x43f6_5b86_11ab_7585,
-                        0x3183_aaf5_ec27_9fdf,
-                        0xfa73_d7e1_8ac9_9df6,
-                        0x64e1_76a6_a64c_99b0,
-                        0x179f_a78c_5838_8f1f,
-                    ]),
-                    c1: Fp::from_raw_unchecked([
-                        0x672a_0a11_ca2a_ef12,
-                        0x0d11_b9b5_2aa3_f16b,
-                        0xa444_12d0_699d_056e,
-                        0xc01d_0177_221a_5ba5,
-                        0x66e0_cede_6c73_5529,
-                        0x05f5_a71e_9fdd_c339,
-                    ]),
-                },
-            },
-            c1: Fp6 {
-                c0: Fp2 {
-                    c0: Fp::from_raw_unchecked([
-                        0xd30a_88a1_b062_c679,
-                        0x5ac5_6a5d_35fc_8304,
-                        0xd0c8_34a6_a81f_290d,
-                        0xcd54_30c2_da37_07c7,
-                        0xf0c2_7ff7_8050_0af0,
-                        0x0924_5da6_e2d7_2eae,
-                    ]),
-                    c1: Fp::from_raw_unchecked([
-                        0x9f2e_0676_791b_5156,
-                        0xe2d1_c823_4918_fe13,
-                        0x4c9e_459f_3c56_1bf4,
-                        0xa3e8_5e53_b9d3_e3c1,
-                        0x820a_121e_21a7_0020,
-                        0x15af_6183_41c5_9acc,
-                    ]),
-                },
-                c1: Fp2 {
-                    c0: Fp::from_raw_unchecked([
-                        0x7c95_658c_2499_3ab1,
-                        0x73eb_3872_1ca8_86b9,
-                        0x5256_d749_4774_34bc,
-                        0x8ba4_1902_ea50_4a8b,
-                        0x04a3_d3f8_0c86_ce6d,
-                        0x18a6_4a87_fb68_6eaa,
-                    ]),
-                    c1: Fp::from_raw_unchecked([
-                        0xbb83_e71b_b920_cf26,
-                        0x2a52_77ac_92a7_3945,
-                        0xfc0e_e59f_94f0_46a0,
-                        0x7158_cdf3_7860_58f7,
-                        0x7cc1_061b_82f9_45f6,
-                        0x03f8_47aa_9fdb_e567,
-                    ]),
-                },
-                c2: Fp2 {
-                    c0: Fp::from_raw_unchecked([
-                        0x8078_dba5_6134_e657,
-                        0x1cd7_ec9a_4399_8a6e,
-                        0xb1aa_599a_1a99_3766,
-                        0xc9a0_f62f_0842_ee44,
-                        0x8e15_9be3_b605_dffa,
-                        0x0c86_ba0d_4af1_3fc2,
-                    ]),
-                    c1: Fp::from_raw_unchecked([
-                        0xe80f_f2a0_6a52_ffb1,
-                        0x7694_ca48_721a_906c,
-                        0x7583_183e_03b0_8514,
-                        0xf567_afdd_40ce_e4e2,
-                        0x9a6d_96d2_e526_a5fc,
-                        0x197e_9f49_861f_2242,
-                    ]),
-                },
-            },
-        })
+        Self::generator()
     }
 
     fn is_identity(&self) -> Choice {
@@ -552,6 +719,126 @@ impl Group for Gt {
     }
 }
 
+#[derive(Clone, Copy)]
+pub struct GtCompressed([u8; 288]);
+
+impl fmt::Debug for GtCompressed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0[..].fmt(f)
+    }
+}
+
+impl Default for GtCompressed {
+    fn default() -> Self {
+        GtCompressed([0; 288])
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::DefaultIsZeroes for GtCompressed {}
+
+impl AsRef<[u8]> for GtCompressed {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsMut<[u8]> for GtCompressed {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl ConstantTimeEq for GtCompressed {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl Eq for GtCompressed {}
+impl PartialEq for GtCompressed {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        bool::from(self.ct_eq(other))
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct GtUncompressed([u8; 576]);
+
+impl fmt::Debug for GtUncompressed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0[..].fmt(f)
+    }
+}
+
+impl Default for GtUncompressed {
+    fn default() -> Self {
+        GtUncompressed([0; 576])
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::DefaultIsZeroes for GtUncompressed {}
+
+impl AsRef<[u8]> for GtUncompressed {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsMut<[u8]> for GtUncompressed {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl ConstantTimeEq for GtUncompressed {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl Eq for GtUncompressed {}
+impl PartialEq for GtUncompressed {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        bool::from(self.ct_eq(other))
+    }
+}
+
+impl GroupEncoding for Gt {
+    type Repr = GtCompressed;
+
+    fn from_bytes(bytes: &Self::Repr) -> CtOption<Self> {
+        Self::from_compressed(&bytes.0)
+    }
+
+    fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
+        Self::from_compressed_unchecked(&bytes.0)
+    }
+
+    fn to_bytes(&self) -> Self::Repr {
+        GtCompressed(self.to_compressed())
+    }
+}
+
+impl UncompressedEncoding for Gt {
+    type Uncompressed = GtUncompressed;
+
+    fn from_uncompressed(bytes: &Self::Uncompressed) -> CtOption<Self> {
+        Self::from_uncompressed(&bytes.0)
+    }
+
+    fn from_uncompressed_unchecked(bytes: &Self::Uncompressed) -> CtOption<Self> {
+        Fp12::from_bytes_unchecked(&bytes.0).map(Gt)
+    }
+
+    fn to_uncompressed(&self) -> Self::Uncompressed {
+        GtUncompressed(self.to_uncompressed())
+    }
+}
+
 #[cfg(feature = "alloc")]
 #[cfg_attr(docsrs, doc(cfg(all(feature = "pairings", feature = "alloc"))))]
 #[derive(Clone, Debug)]
@@ -562,6 +849,10 @@ impl Group for Gt {
 /// conjunction with the [`multi_miller_loop`](crate::multi_miller_loop)
 /// function provided by this crate.
 ///
+/// Preparing a point does not check that it is an element of $\mathbb{G}_2$, so
+/// points from untrusted sources must be checked before they are prepared, as
+/// [`G2Affine::from_compressed`] does.
+///
 /// Requires the `alloc` and `pairing` crate features to be enabled.
 pub struct G2Prepared {
     infinity: Choice,
@@ -613,6 +904,52 @@ impl From<G2Affine> for G2Prepared {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl G2Prepared {
+    /// Returns the coefficients of the line functions of the Miller loop, in the
+    /// order in which they are evaluated with [`evaluate_line`].
+    ///
+    /// The Miller loop runs over the bits of $|x|$ after the leading one, from the
+    /// most significant: each bit contributes the line of a doubling step, then the
+    /// line of an addition step if the bit is set, after which the accumulator is
+    /// squared. A final doubling step follows, and the accumulator is conjugated,
+    /// as $x$ is negative. This gives 68 lines.
+    ///
+    /// If this element was prepared from the identity, these are the coefficients
+    /// for the generator, and the lines must be skipped; see
+    /// [`G2Prepared::is_identity`].
+    pub fn coefficients(&self) -> &[(Fp2, Fp2, Fp2)] {
+        &self.coeffs
+    }
+
+    /// Returns true if this element was prepared from the identity, whose Miller
+    /// loop is one.
+    pub fn is_identity(&self) -> Choice {
+        self.infinity
+    }
+
+    /// Prepares the point with the uncompressed encoding `bytes`, as decoded by
+    /// [`G2Affine::from_uncompressed_unchecked`], for points that were checked
+    /// when they were first received, such as verification keys validated when they
+    /// are installed.
+    ///
+    /// **The point is checked to be neither on the curve nor in $\mathbb{G}_2$.**
+    /// Returns `None` only if `bytes` is not an encoding of a pair of coordinates.
+    pub fn from_uncompressed_unchecked(bytes: &[u8; 192]) -> CtOption<Self> {
+        G2Affine::from_uncompressed_unchecked(bytes).map(G2Prepared::from)
+    }
+
+    /// Prepares the point with the compressed encoding `bytes`, as decoded by
+    /// [`G2Affine::from_compressed_unchecked`], which checks that the point is on
+    /// the curve but not that it is in $\mathbb{G}_2$.
+    ///
+    /// Like [`G2Prepared::from_uncompressed_unchecked`], this is meant for points that
+    /// were checked when they were first received.
+    pub fn from_compressed_unchecked(bytes: &[u8; 96]) -> CtOption<Self> {
+        G2Affine::from_compressed_unchecked(bytes).map(G2Prepared::from)
+    }
+}
+
 #[cfg(feature = "alloc")]
 #[cfg_attr(docsrs, doc(cfg(all(feature = "pairings", feature = "alloc"))))]
 /// Computes $$\sum_{i=1}^n \textbf{ML}(a_i, b_i)$$ given a series of terms
@@ -670,6 +1007,87 @@ pub fn multi_miller_loop(terms: &[(&G1Affine, &G2Prepared)]) -> MillerLoopResult
     MillerLoopResult(tmp)
 }
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "pairings", feature = "alloc"))))]
+/// Computes a Miller loop whose final exponentiation is
+/// $$\prod_{i=1}^n e(a_i, b_i)^{s_i}$$ given a series of terms
+/// $$(a_1, b_1, s_1), (a_2, b_2, s_2), ..., (a_n, b_n, s_n).$$
+///
+/// Each $a_i$ is multiplied by $s_i$, the products are converted to affine
+/// coordinates together, and the terms $e(s_i a_i, b_i)$ are computed with a
+/// single [`multi_miller_loop`], as when a batch verifier combines pairing
+/// equations with random coefficients. The multiplications are those of
+/// [`G1Projective::mul_vartime`], so this is variable time in the scalars, which
+/// must not be secret, and the result is only correct if every $a_i$ is in
+/// $\mathbb{G}_1$. Scaling the inputs in $\mathbb{G}_1$ rather than the results in
+/// $\mathbb{G}_T$ is what makes this cheaper than separate pairings: only the
+/// final exponentiation is multiplicative, so the Miller loop of $s_i a_i$ is not
+/// the Miller loop of $a_i$ raised to $s_i$, but the results agree.
+///
+/// Requires the `alloc` and `pairing` crate features to be enabled.
+pub fn multi_miller_loop_scaled_vartime(
+    terms: &[(&G1Affine, &G2Prepared, &Scalar)],
+) -> MillerLoopResult {
+    let scaled = terms
+        .iter()
+        .map(|(a, _, s)| G1Projective::from(*a).mul_vartime(s))
+        .collect::<Vec<_>>();
+    let mut g1 = alloc::vec![G1Affine::identity(); scaled.len()];
+    G1Projective::batch_normalize(&scaled, &mut g1);
+
+    let terms = g1
+        .iter()
+        .zip(terms.iter())
+        .map(|(a, (_, b, _))| (a, *b))
+        .collect::<Vec<_>>();
+    multi_miller_loop(&terms)
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "pairings", feature = "alloc"))))]
+#[derive(Clone, Debug, Default)]
+/// A pairing-product equation $$\prod_{i=1}^n e(a_i, b_i) = 1,$$ built up one
+/// term at a time and checked with a single [`multi_miller_loop`] and final
+/// exponentiation.
+///
+/// Requires the `alloc` and `pairing` crate features to be enabled.
+pub struct PairingCheck {
+    terms: Vec<(G1Affine, G2Prepared)>,
+}
+
+#[cfg(feature = "alloc")]
+impl PairingCheck {
+    /// Returns the empty equation, which holds.
+    pub fn new() -> Self {
+        PairingCheck { terms: Vec::new() }
+    }
+
+    /// Adds the term $e(a, b)$.
+    pub fn add(&mut self, a: &G1Affine, b: &G2Affine) -> &mut Self {
+        self.add_prepared(a, G2Prepared::from(*b))
+    }
+
+    /// Adds the term $e(a, b)^{-1} = e(-a, b)$.
+    pub fn add_negated(&mut self, a: &G1Affine, b: &G2Affine) -> &mut Self {
+        self.add(&-a, b)
+    }
+
+    /// Adds the term $e(a, b)$ for an already prepared $b$.
+    pub fn add_prepared(&mut self, a: &G1Affine, b: G2Prepared) -> &mut Self {
+        self.terms.push((*a, b));
+        self
+    }
+
+    /// Returns true if the product of the terms is the identity of
+    /// $\mathbb{G}_T$.
+    pub fn verify(&self) -> Choice {
+        let terms = self.terms.iter().map(|(a, b)| (a, b)).collect::<Vec<_>>();
+        multi_miller_loop(&terms)
+            .final_exponentiation()
+            .is_identity()
+    }
+}
+
 /// Invoke the pairing function without the use of precomputation and other optimizations.
 #[cfg_attr(docsrs, doc(cfg(feature = "pairings")))]
 pub fn pairing(p: &G1Affine, q: &G2Affine) -> Gt {
@@ -761,6 +1179,17 @@ fn miller_loop<D: MillerLoopDriver>(driver: &mut D) -> D::Output {
     f
 }
 
+/// Evaluates the line function with the coefficients `coeffs`, one of
+/// [`G2Prepared::coefficients`], at `p`, which must not be the identity.
+///
+/// The result is the sparse element $c_0 + c_1 v + c_4 v w$ of $\mathbb{F}_{p^{12}}$
+/// by which the Miller loop multiplies its accumulator, where $c_0$ is `coeffs.2`,
+/// $c_1$ is `coeffs.1` scaled by the $x$-coordinate of `p`, and $c_4$ is `coeffs.0`
+/// scaled by its $y$-coordinate.
+pub fn evaluate_line(coeffs: &(Fp2, Fp2, Fp2), p: &G1Affine) -> Fp12 {
+    ell(Fp12::one(), coeffs, p)
+}
+
 fn ell(f: Fp12, coeffs: &(Fp2, Fp2, Fp2), p: &G1Affine) -> Fp12 {
     let mut c0 = coeffs.0;
     let mut c1 = coeffs.1;
@@ -856,6 +1285,10 @@ impl PairingCurveAffine for G2Affine {
 }
 
 /// A [`pairing::Engine`] for BLS12-381 pairing operations.
+///
+/// With the `alloc` feature, this also implements [`pairing::MultiMillerLoop`] with
+/// [`G2Prepared`], so that code generic over the `pairing` traits, such as SNARK
+/// backends, can be instantiated with this type directly.
 #[cfg_attr(docsrs, doc(cfg(feature = "pairings")))]
 #[derive(Clone, Debug)]
 pub struct Bls12;
@@ -897,6 +1330,59 @@ fn test_gt_generator() {
         Gt::generator(),
         pairing(&G1Affine::generator(), &G2Affine::generator())
     );
+    assert_eq!(<Gt as Group>::generator(), Gt::GENERATOR);
+}
+
+#[test]
+fn test_gt_generator_mul() {
+    let x = Scalar::from(BLS_X);
+    let scalars = [
+        Scalar::zero(),
+        Scalar::one(),
+        -Scalar::one(),
+        x,
+        x * x * x - Scalar::one(),
+        Scalar::from_raw([
+            0xfedc_ba98_7654_3210,
+            0x0123_4567_89ab_cdef,
+            0x1f2e_3d4c_5b6a_7988,
+            0x1234_5678_9abc_def0,
+        ]),
+    ];
+    for s in scalars.iter() {
+        let digits = split_by_x(s);
+        assert!(digits.iter().all(|digit| *digit < BLS_X));
+        assert_eq!(
+            digits
+                .iter()
+                .rev()
+                .fold(Scalar::zero(), |acc, digit| acc * x + Scalar::from(*digit)),
+            *s
+        );
+        assert_eq!(Gt::generator_mul(s), Gt::GENERATOR * s);
+    }
+}
+
+#[test]
+fn test_gt_mul() {
+    let gt = Gt::GENERATOR.double() + Gt::GENERATOR;
+
+    let mut expected = Gt::identity();
+    for k in 0..40 {
+        assert_eq!(gt * Scalar::from(k), expected);
+        expected += gt;
+    }
+
+    assert_eq!(gt * -Scalar::one(), -gt);
+    let a = Scalar::from_raw([
+        0xfedc_ba98_7654_3210,
+        0x0123_4567_89ab_cdef,
+        0x1f2e_3d4c_5b6a_7988,
+        0x1234_5678_9abc_def0,
+    ]);
+    let b = a.square() + Scalar::one();
+    assert_eq!((gt * a) * b, gt * (a * b));
+    assert_eq!(gt * a + gt * b, gt * (a + b));
 }
 
 #[test]
@@ -949,6 +1435,11 @@ fn test_uncompressed() {
     let gt2 = Gt::from_uncompressed(&buf).unwrap();
 
     assert_eq!(gt, gt2);
+
+    // An element of Fp12 outside of the order-q subgroup.
+    let mut buf = [0u8; 576];
+    buf[575] = 2;
+    assert!(bool::from(Gt::from_uncompressed(&buf).is_none()));
 }
 
 #[test]
@@ -1032,6 +1523,273 @@ fn test_multi_miller_loop() {
     assert_eq!(expected, test);
 }
 
+#[test]
+fn test_cyclotomic_square_n() {
+    let gt = pairing(&G1Affine::generator(), &G2Affine::generator()) * Scalar::from(1234);
+    let f = gt.0;
+    let mut expected = f;
+    for n in 0..100 {
+        assert_eq!(f.cyclotomic_square_n(n), expected);
+        expected = expected.cyclotomic_square();
+    }
+    assert_eq!(Fp12::one().cyclotomic_square_n(100), Fp12::one());
+}
+
+#[test]
+fn test_gt_mul_vartime() {
+    let gt = pairing(&G1Affine::generator(), &G2Affine::generator());
+    let a = Scalar::from_raw([0x1234_5678, 0, 0x9abc_def0, 0x42]);
+    let limbs = |s: &Scalar| {
+        let bytes = s.to_bytes();
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+            *limb = u64::from_le_bytes(<[u8; 8]>::try_from(chunk).unwrap());
+        }
+        limbs
+    };
+    assert_eq!(gt.mul_vartime(&limbs(&a)), gt * a);
+    assert_eq!(gt.mul_vartime(&[]), Gt::identity());
+    assert_eq!(gt.mul_vartime(&[0, 0]), Gt::identity());
+
+    // A long chain of doublings.
+    let two_200 = Scalar::from(2).pow_vartime(&[200, 0, 0, 0]);
+    assert_eq!(gt.double_n(200), gt * two_200);
+    assert_eq!(
+        gt.mul_vartime(&[1, 0, 0, 1 << 8]),
+        gt * (two_200 + Scalar::one())
+    );
+    assert_eq!(gt.double_n(3), gt.double().double().double());
+
+    // Multipliers with runs of ones, whose digits carry, and beyond q.
+    for by in [
+        [u64::MAX, 0, 0, 0],
+        [u64::MAX; 4],
+        [0xf0f0_f0f0_f0f0_f0f0, 0x7777_7777_7777_7777, 3, 1 << 63],
+    ] {
+        let expected = gt * Scalar::from_raw(by);
+        assert_eq!(gt.mul_vartime(&by), expected);
+    }
+    for k in 1..64u64 {
+        assert_eq!(gt.mul_vartime(&[k]), gt * Scalar::from(k));
+    }
+}
+
+#[test]
+fn test_cyclotomic_exp_by_x() {
+    let f = pairing(&G1Affine::generator(), &G2Affine::generator()).0;
+    assert_eq!(f.cyclotomic_square(), f.square());
+    assert_eq!(
+        f.cyclotomic_exp_by_x(),
+        f.pow_vartime(&[BLS_X]).invert().unwrap()
+    );
+
+    // In Gt, this agrees with multiplication by the scalar x.
+    let gt = pairing(&G1Affine::generator(), &G2Affine::generator());
+    assert_eq!(Gt(f.cyclotomic_exp_by_x()), gt * -Scalar::from(BLS_X));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_line_evaluations() {
+    let p = G1Affine::from(G1Affine::generator() * Scalar::from(5));
+    let q = G2Affine::from(G2Affine::generator() * Scalar::from(7));
+    let prepared = G2Prepared::from(q);
+    assert!(!bool::from(prepared.is_identity()));
+    assert_eq!(prepared.coefficients().len(), 68);
+
+    // Replay the Miller loop from its lines.
+    let mut lines = prepared.coefficients().iter();
+    let mut f = Fp12::one();
+    let mut found_one = false;
+    for i in (0..64).rev().map(|b| (((BLS_X >> 1) >> b) & 1) == 1) {
+        if !found_one {
+            found_one = i;
+            continue;
+        }
+        f *= evaluate_line(lines.next().unwrap(), &p);
+        if i {
+            f *= evaluate_line(lines.next().unwrap(), &p);
+        }
+        f = f.square();
+    }
+    f *= evaluate_line(lines.next().unwrap(), &p);
+    assert!(lines.next().is_none());
+
+    let f = MillerLoopResult(f.conjugate());
+    assert_eq!(f.final_exponentiation(), pairing(&p, &q));
+    assert_eq!(f.0, multi_miller_loop(&[(&p, &prepared)]).0);
+
+    assert!(bool::from(
+        G2Prepared::from(G2Affine::identity()).is_identity()
+    ));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_g2_prepared_from_bytes_unchecked() {
+    let q = G2Affine::from(G2Affine::generator() * Scalar::from(0x1234_5678));
+    for q in [q, G2Affine::generator(), G2Affine::identity()] {
+        let prepared = G2Prepared::from(q);
+        for unchecked in [
+            G2Prepared::from_uncompressed_unchecked(&q.to_uncompressed()).unwrap(),
+            G2Prepared::from_compressed_unchecked(&q.to_compressed()).unwrap(),
+        ] {
+            assert_eq!(unchecked.coefficients(), prepared.coefficients());
+            assert_eq!(
+                bool::from(unchecked.is_identity()),
+                bool::from(q.is_identity())
+            );
+        }
+    }
+
+    // A point that is not on the curve is not rejected.
+    let mut bytes = G2Affine::generator().to_uncompressed();
+    bytes[191] ^= 1;
+    assert!(bool::from(G2Affine::from_uncompressed(&bytes).is_none()));
+    assert!(bool::from(
+        G2Prepared::from_uncompressed_unchecked(&bytes).is_some()
+    ));
+
+    // An encoding without the compression flag is rejected.
+    let mut bytes = G2Affine::generator().to_compressed();
+    bytes[0] &= 0x7f;
+    assert!(bool::from(
+        G2Prepared::from_compressed_unchecked(&bytes).is_none()
+    ));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_multi_miller_loop_scaled_vartime() {
+    let a = [
+        G1Affine::generator(),
+        G1Affine::from(G1Affine::generator() * Scalar::from(5)),
+        G1Affine::identity(),
+    ];
+    let b = [
+        G2Affine::from(G2Affine::generator() * Scalar::from(3)),
+        G2Affine::generator(),
+        G2Affine::generator(),
+    ];
+    let s = [
+        Scalar::from_raw([0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210, 0, 0]),
+        -Scalar::from(7),
+        Scalar::from(11),
+    ];
+    let prepared = b.iter().map(|b| G2Prepared::from(*b)).collect::<Vec<_>>();
+    let terms = (0..3)
+        .map(|i| (&a[i], &prepared[i], &s[i]))
+        .collect::<Vec<_>>();
+
+    let expected = (0..3).fold(Gt::identity(), |acc, i| acc + pairing(&a[i], &b[i]) * s[i]);
+    assert_eq!(
+        multi_miller_loop_scaled_vartime(&terms).final_exponentiation(),
+        expected
+    );
+    assert_eq!(
+        multi_miller_loop_scaled_vartime(&terms[..1]).final_exponentiation(),
+        pairing(&a[0], &b[0]) * s[0]
+    );
+    assert_eq!(
+        multi_miller_loop_scaled_vartime(&[]).final_exponentiation(),
+        Gt::identity()
+    );
+
+    // The pairing equation e(5 g1, g2) = e(g1, g2)^5 holds with any coefficient.
+    let g2 = G2Prepared::from(G2Affine::generator());
+    let (five, minus_one) = (Scalar::from(5), -Scalar::one());
+    assert!(bool::from(
+        multi_miller_loop_scaled_vartime(&[
+            (&a[1], &g2, &s[0]),
+            (&a[0], &g2, &(s[0] * five * minus_one)),
+        ])
+        .final_exponentiation()
+        .is_identity()
+    ));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_pairing_check() {
+    let a = Scalar::from(3);
+    let b = Scalar::from(11);
+    let p = G1Affine::generator();
+    let q = G2Affine::generator();
+    let ap = G1Affine::from(p * a);
+    let bq = G2Affine::from(q * b);
+    let abp = G1Affine::from(p * (a * b));
+
+    assert!(bool::from(PairingCheck::new().verify()));
+    assert!(bool::from(
+        PairingCheck::new()
+            .add(&ap, &bq)
+            .add_negated(&abp, &q)
+            .verify()
+    ));
+    assert!(!bool::from(
+        PairingCheck::new().add(&ap, &bq).add(&abp, &q).verify()
+    ));
+
+    // Terms involving the identity are one.
+    let mut check = PairingCheck::new();
+    check
+        .add(&G1Affine::identity(), &q)
+        .add_prepared(&p, G2Prepared::from(G2Affine::identity()));
+    assert!(bool::from(check.verify()));
+    check.add(&p, &q);
+    assert!(!bool::from(check.verify()));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_engine_traits() {
+    use group::{prime::PrimeCurveAffine, Curve};
+    use pairing::MillerLoopResult as _;
+
+    // Exercises the pairing traits the way a generic proof system would.
+    fn check<E: MultiMillerLoop>(a: E::Fr) {
+        let p = E::G1Affine::generator();
+        let q = E::G2Affine::generator();
+        let ap = (p * a).to_affine();
+        let aq = (q * a).to_affine();
+
+        assert_eq!(E::pairing(&ap, &q), E::pairing(&p, &aq));
+        assert_eq!(ap.pairing_with(&q), E::pairing(&ap, &q));
+        assert_eq!(aq.pairing_with(&p), E::pairing(&p, &aq));
+
+        let result = E::multi_miller_loop(&[
+            (&ap, &E::G2Prepared::from(q)),
+            (&p, &E::G2Prepared::from(-aq)),
+        ])
+        .final_exponentiation();
+        assert_eq!(result, E::Gt::identity());
+    }
+
+    check::<Bls12>(Scalar::from_raw([1, 2, 3, 4]).invert().unwrap().square());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_miller_loop_result_serialization() {
+    let p = G1Affine::from(G1Affine::generator() * Scalar::from(5));
+    let result = multi_miller_loop(&[(&p, &G2Affine::generator().into())]);
+
+    let bytes = result.to_bytes();
+    let decoded = MillerLoopResult::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.0, result.0);
+    assert_eq!(
+        decoded.final_exponentiation(),
+        pairing(&p, &G2Affine::generator())
+    );
+
+    assert!(bool::from(
+        MillerLoopResult::from_bytes(&[0; 576]).is_none()
+    ));
+    let mut bytes = bytes;
+    bytes[0] = 0xff;
+    assert!(bool::from(MillerLoopResult::from_bytes(&bytes).is_none()));
+}
+
 #[test]
 fn test_miller_loop_result_default() {
     assert_eq!(
@@ -0,0 +1,347 @@
+//! Evaluation domains: multiplicative subgroups of $\mathbb{F}_q^*$ of order $2^k$.
+
+use ff::PrimeField;
+
+use super::{LagrangeBasis, Polynomial};
+use crate::backend::{Backend, Cpu};
+use crate::Scalar;
+
+use alloc::vec::Vec;
+
+/// The subgroup $\{\omega^0, \ldots, \omega^{n - 1}\}$ of $n$th roots of unity, for
+/// a power of two $n$ dividing $2^{32}$, where $\omega$ is the primitive $n$th
+/// root of unity obtained by squaring [`PrimeField::root_of_unity`].
+///
+/// Polynomials of degree less than $n$ are converted between their coefficients and
+/// their evaluations over the domain with [`Domain::fft`] and [`Domain::ifft`], or
+/// over a coset $g \cdot \{\omega^i\}$ of it with [`Domain::coset_fft`] and
+/// [`Domain::coset_ifft`]. Cosets are disjoint from the domain for any $g$ that is
+/// not itself a $n$th root of unity, such as
+/// [`PrimeField::multiplicative_generator`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Domain {
+    size: usize,
+    generator: Scalar,
+}
+
+impl Domain {
+    /// Returns the domain of the $n$th roots of unity, or `None` if $n$ is not a
+    /// power of two dividing $2^{32}$.
+    pub fn new(n: usize) -> Option<Self> {
+        if !n.is_power_of_two() || n.trailing_zeros() > Scalar::S {
+            return None;
+        }
+
+        let mut generator = Scalar::root_of_unity();
+        for _ in n.trailing_zeros()..Scalar::S {
+            generator = generator.square();
+        }
+        Some(Domain { size: n, generator })
+    }
+
+    /// Returns the smallest domain with at least `n` elements, or `None` if there is
+    /// none.
+    pub fn with_min_size(n: usize) -> Option<Self> {
+        Domain::new(n.max(1).checked_next_power_of_two()?)
+    }
+
+    /// Returns the number $n$ of elements of the domain.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns $\log_2 n$.
+    pub fn log_size(&self) -> u32 {
+        self.size.trailing_zeros()
+    }
+
+    /// Returns the generator $\omega$ of the domain.
+    pub fn generator(&self) -> Scalar {
+        self.generator
+    }
+
+    /// Returns the element $\omega^i$.
+    pub fn element(&self, i: usize) -> Scalar {
+        self.generator.pow_u64((i % self.size) as u64)
+    }
+
+    /// Returns an iterator over the elements $\omega^0, \ldots, \omega^{n - 1}$ of
+    /// the domain.
+    pub fn elements(&self) -> impl Iterator<Item = Scalar> {
+        let generator = self.generator;
+        core::iter::successors(Some(Scalar::one()), move |x| Some(x * generator)).take(self.size)
+    }
+
+    /// Evaluates the vanishing polynomial $Z(X) = X^n - 1$ of the domain at `z`.
+    pub fn evaluate_vanishing(&self, z: &Scalar) -> Scalar {
+        z.pow_u64(self.size as u64) - Scalar::one()
+    }
+
+    /// Returns the vanishing polynomial $X^n - 1$ of the domain.
+    pub fn vanishing_polynomial(&self) -> Polynomial {
+        let mut coefficients = alloc::vec![Scalar::zero(); self.size + 1];
+        coefficients[0] = -Scalar::one();
+        coefficients[self.size] = Scalar::one();
+        Polynomial::from_coefficients(coefficients)
+    }
+
+    /// Divides `poly` by the vanishing polynomial $X^n - 1$ of the domain, in time
+    /// linear in the degree of `poly`, returning the quotient and the remainder.
+    pub fn divide_by_vanishing(&self, poly: &Polynomial) -> (Polynomial, Polynomial) {
+        let n = self.size;
+        let mut remainder = poly.coefficients().to_vec();
+        if remainder.len() <= n {
+            return (Polynomial::zero(), poly.clone());
+        }
+
+        // X^k = X^(k - n) (X^n - 1) + X^(k - n), from the leading term down.
+        let mut quotient = alloc::vec![Scalar::zero(); remainder.len() - n];
+        for k in (n..remainder.len()).rev() {
+            quotient[k - n] = remainder[k];
+            let reduced = remainder[k];
+            remainder[k - n] += reduced;
+        }
+        remainder.truncate(n);
+
+        (
+            Polynomial::from_coefficients(quotient),
+            Polynomial::from_coefficients(remainder),
+        )
+    }
+
+    /// Returns the Lagrange basis over the domain, in natural order.
+    pub fn lagrange_basis(&self) -> LagrangeBasis {
+        LagrangeBasis::from_domain(self)
+    }
+
+    /// Replaces the coefficients of a polynomial of degree less than $n$ with its
+    /// evaluations at $\omega^0, \ldots, \omega^{n - 1}$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are not $n$ values.
+    pub fn fft(&self, values: &mut [Scalar]) {
+        self.fft_with(values, &Cpu)
+    }
+
+    /// Computes [`Domain::fft`] with the transforms of `backend`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are not $n$ values.
+    pub fn fft_with(&self, values: &mut [Scalar], backend: &impl Backend) {
+        assert_eq!(values.len(), self.size);
+        backend.ntt(values)
+    }
+
+    /// Inverts [`Domain::fft`], replacing evaluations with coefficients.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are not $n$ values.
+    pub fn ifft(&self, values: &mut [Scalar]) {
+        self.ifft_with(values, &Cpu)
+    }
+
+    /// Computes [`Domain::ifft`] with the transforms of `backend`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are not $n$ values.
+    pub fn ifft_with(&self, values: &mut [Scalar], backend: &impl Backend) {
+        assert_eq!(values.len(), self.size);
+        backend.intt(values)
+    }
+
+    /// Replaces the coefficients of a polynomial of degree less than $n$ with its
+    /// evaluations at $g \omega^0, \ldots, g \omega^{n - 1}$ for the coset `shift`
+    /// $= g$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are not $n$ values.
+    pub fn coset_fft(&self, values: &mut [Scalar], shift: &Scalar) {
+        assert_eq!(values.len(), self.size);
+        scale_by_powers(values, shift);
+        self.fft(values)
+    }
+
+    /// Inverts [`Domain::coset_fft`], replacing evaluations over the coset `shift`
+    /// $= g$ with coefficients.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are not $n$ values, or if `shift` is zero.
+    pub fn coset_ifft(&self, values: &mut [Scalar], shift: &Scalar) {
+        self.ifft(values);
+        scale_by_powers(values, &shift.invert().unwrap());
+    }
+
+    /// Returns the evaluations of `poly` over the domain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the degree of `poly` is $n$ or more.
+    pub fn evaluate(&self, poly: &Polynomial) -> Vec<Scalar> {
+        assert!(poly.coefficients().len() <= self.size);
+        let mut values = poly.coefficients().to_vec();
+        values.resize(self.size, Scalar::zero());
+        self.fft(&mut values);
+        values
+    }
+
+    /// Returns the polynomial of degree less than $n$ with the given `evaluations`
+    /// over the domain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are not $n$ evaluations.
+    pub fn interpolate(&self, mut evaluations: Vec<Scalar>) -> Polynomial {
+        self.ifft(&mut evaluations);
+        Polynomial::from_coefficients(evaluations)
+    }
+}
+
+/// Multiplies the $i$th value by $g^i$.
+fn scale_by_powers(values: &mut [Scalar], g: &Scalar) {
+    let mut power = Scalar::one();
+    for value in values.iter_mut() {
+        *value *= power;
+        power *= g;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ff::Field;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ])
+    }
+
+    #[test]
+    fn test_new() {
+        let domain = Domain::new(16).unwrap();
+        assert_eq!(domain.size(), 16);
+        assert_eq!(domain.log_size(), 4);
+        assert_eq!(domain.element(0), Scalar::one());
+        assert_eq!(domain.element(8), -Scalar::one());
+        assert_eq!(domain.element(17), domain.generator());
+
+        let elements = domain.elements().collect::<Vec<_>>();
+        assert_eq!(elements.len(), 16);
+        for (i, w) in elements.iter().enumerate() {
+            assert_eq!(*w, domain.element(i));
+            assert_eq!(domain.evaluate_vanishing(w), Scalar::zero());
+        }
+        assert_eq!(domain.lagrange_basis().points(), elements);
+
+        assert_eq!(Domain::with_min_size(9), Domain::new(16));
+        assert_eq!(Domain::with_min_size(0), Domain::new(1));
+        assert!(Domain::new(12).is_none());
+        assert!(Domain::with_min_size(usize::MAX).is_none());
+        assert_eq!(
+            Domain::new(1 << Scalar::S).unwrap().generator(),
+            Scalar::root_of_unity()
+        );
+    }
+
+    #[test]
+    fn test_vanishing_polynomial() {
+        let mut rng = rng();
+        let domain = Domain::new(8).unwrap();
+        let z = Scalar::random(&mut rng);
+        assert_eq!(
+            domain.vanishing_polynomial().evaluate(&z),
+            domain.evaluate_vanishing(&z)
+        );
+        let elements = domain.elements().collect::<Vec<_>>();
+        assert_eq!(
+            domain.vanishing_polynomial(),
+            Polynomial::vanishing(&elements)
+        );
+    }
+
+    #[test]
+    fn test_divide_by_vanishing() {
+        let mut rng = rng();
+        let domain = Domain::new(8).unwrap();
+        for len in [0, 5, 8, 9, 30] {
+            let poly =
+                Polynomial::from_coefficients((0..len).map(|_| Scalar::random(&mut rng)).collect());
+            let expected = poly.divide(&domain.vanishing_polynomial()).unwrap();
+            assert_eq!(domain.divide_by_vanishing(&poly), expected);
+        }
+
+        // A polynomial that vanishes on the domain is a multiple of X^n - 1.
+        let elements = domain.elements().collect::<Vec<_>>();
+        let multiple = Polynomial::vanishing(&elements) * Scalar::from(3);
+        assert_eq!(
+            domain.divide_by_vanishing(&multiple),
+            (Polynomial::constant(Scalar::from(3)), Polynomial::zero())
+        );
+    }
+
+    #[test]
+    fn test_fft() {
+        let mut rng = rng();
+        let domain = Domain::new(32).unwrap();
+        let poly =
+            Polynomial::from_coefficients((0..32).map(|_| Scalar::random(&mut rng)).collect());
+
+        let evaluations = domain.evaluate(&poly);
+        for (w, e) in domain.elements().zip(evaluations.iter()) {
+            assert_eq!(poly.evaluate(&w), *e);
+        }
+        assert_eq!(domain.interpolate(evaluations), poly);
+
+        // Polynomials of lower degree are padded.
+        let short = Polynomial::from_coefficients(poly.coefficients()[..5].to_vec());
+        let evaluations = domain.evaluate(&short);
+        assert_eq!(evaluations[3], short.evaluate(&domain.element(3)));
+        assert_eq!(domain.interpolate(evaluations), short);
+    }
+
+    #[test]
+    fn test_coset_fft() {
+        let mut rng = rng();
+        let domain = Domain::new(16).unwrap();
+        let coefficients = (0..16)
+            .map(|_| Scalar::random(&mut rng))
+            .collect::<Vec<_>>();
+        let poly = Polynomial::from_coefficients(coefficients.clone());
+        let shift = Scalar::multiplicative_generator();
+
+        let mut values = coefficients.clone();
+        domain.coset_fft(&mut values, &shift);
+        for (w, v) in domain.elements().zip(values.iter()) {
+            assert_eq!(poly.evaluate(&(shift * w)), *v);
+        }
+        // The vanishing polynomial is nonzero everywhere on the coset.
+        assert!(domain
+            .elements()
+            .all(|w| domain.evaluate_vanishing(&(shift * w)) != Scalar::zero()));
+
+        domain.coset_ifft(&mut values, &shift);
+        assert_eq!(values, coefficients);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fft_invalid_length() {
+        Domain::new(8).unwrap().fft(&mut [Scalar::one(); 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_evaluate_degree_too_high() {
+        let poly = Polynomial::from_coefficients(alloc::vec![Scalar::one(); 9]);
+        Domain::new(8).unwrap().evaluate(&poly);
+    }
+}
@@ -0,0 +1,192 @@
+//! The Lagrange basis over a subgroup of roots of unity, and the barycentric
+//! evaluation of polynomials given by their evaluations over it.
+
+use super::{batch_invert, bit_reversal_permutation, Domain};
+use crate::Scalar;
+
+use alloc::vec::Vec;
+
+/// The Lagrange basis of the polynomials of degree less than $n$ over the
+/// [`Domain`] of $n$th roots of unity $\omega^0, \ldots, \omega^{n - 1}$.
+///
+/// A polynomial in this basis is given by its evaluations $p_i = p(\omega^i)$ at the
+/// points of the domain. The basis precomputes the barycentric weights
+/// $\omega^i / n$, with which
+/// $p(z) = \frac{z^n - 1}{n} \sum_i p_i \frac{\omega^i}{z - \omega^i}$ is evaluated
+/// with a single inversion for points $z$ outside the domain.
+///
+/// The points are in natural order, or in bit-reversed order as used by EIP-4844,
+/// and evaluations must be given in the same order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LagrangeBasis {
+    points: Vec<Scalar>,
+    weights: Vec<Scalar>,
+}
+
+impl LagrangeBasis {
+    /// Returns the Lagrange basis over the $n$th roots of unity, in natural order, or
+    /// `None` if $n$ is not a power of two dividing $2^{32}$.
+    pub fn new(n: usize) -> Option<Self> {
+        Domain::new(n).map(|domain| LagrangeBasis::from_domain(&domain))
+    }
+
+    /// Returns the Lagrange basis over `domain`, in natural order.
+    pub(crate) fn from_domain(domain: &Domain) -> Self {
+        let points = domain.elements().collect::<Vec<_>>();
+
+        // The domain size is a power of two below the modulus, so it is invertible.
+        let n_inv = Scalar::from(domain.size() as u64).invert().unwrap();
+        let weights = points.iter().map(|w| w * n_inv).collect();
+
+        LagrangeBasis { points, weights }
+    }
+
+    /// Returns the Lagrange basis over the $n$th roots of unity, in bit-reversed
+    /// order, or `None` if $n$ is not a power of two dividing $2^{32}$.
+    pub fn new_bit_reversed(n: usize) -> Option<Self> {
+        let mut basis = LagrangeBasis::new(n)?;
+        bit_reversal_permutation(&mut basis.points);
+        bit_reversal_permutation(&mut basis.weights);
+        Some(basis)
+    }
+
+    /// Returns the number $n$ of points in the domain.
+    pub fn size(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns the points of the domain.
+    pub fn points(&self) -> &[Scalar] {
+        &self.points
+    }
+
+    /// Returns the barycentric weights $\omega^i / n$ of the points of the domain.
+    pub fn weights(&self) -> &[Scalar] {
+        &self.weights
+    }
+
+    /// Returns the evaluations $L_i(z)$ at `z` of the Lagrange basis polynomials,
+    /// which are one at the $i$th point of the domain and zero at the others.
+    ///
+    /// This function is variable time in whether `z` is in the domain.
+    pub fn evaluate_basis(&self, z: &Scalar) -> Vec<Scalar> {
+        if let Some(i) = self.points.iter().position(|w| w == z) {
+            let mut basis = alloc::vec![Scalar::zero(); self.size()];
+            basis[i] = Scalar::one();
+            return basis;
+        }
+
+        let mut basis = self.points.iter().map(|w| z - w).collect::<Vec<_>>();
+        batch_invert(&mut basis);
+        let vanishing = self.vanishing(z);
+        for (l, weight) in basis.iter_mut().zip(self.weights.iter()) {
+            *l *= weight * vanishing;
+        }
+        basis
+    }
+
+    /// Evaluates at `z` the polynomial of degree less than $n$ with the given
+    /// `evaluations` at the points of the domain, with the barycentric formula.
+    ///
+    /// This function is variable time in whether `z` is in the domain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are not as many evaluations as points in the domain.
+    pub fn evaluate(&self, evaluations: &[Scalar], z: &Scalar) -> Scalar {
+        assert_eq!(evaluations.len(), self.size());
+
+        if let Some(i) = self.points.iter().position(|w| w == z) {
+            return evaluations[i];
+        }
+
+        let mut denominators = self.points.iter().map(|w| z - w).collect::<Vec<_>>();
+        batch_invert(&mut denominators);
+        let sum = evaluations
+            .iter()
+            .zip(self.weights.iter())
+            .zip(denominators.iter())
+            .fold(Scalar::zero(), |acc, ((p, weight), d)| acc + p * weight * d);
+
+        sum * self.vanishing(z)
+    }
+
+    /// Evaluates the vanishing polynomial $z^n - 1$ of the domain.
+    fn vanishing(&self, z: &Scalar) -> Scalar {
+        z.pow_u64(self.size() as u64) - Scalar::one()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::Polynomial;
+    use ff::Field;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    #[test]
+    fn test_new() {
+        let basis = LagrangeBasis::new(8).unwrap();
+        assert_eq!(basis.size(), 8);
+        assert_eq!(basis.points()[0], Scalar::one());
+        assert_eq!(basis.points()[4], -Scalar::one());
+        for w in basis.points() {
+            assert_eq!(w.pow_vartime(&[8, 0, 0, 0]), Scalar::one());
+        }
+        assert_ne!(basis.points()[1].pow_vartime(&[4, 0, 0, 0]), Scalar::one());
+
+        let reversed = LagrangeBasis::new_bit_reversed(8).unwrap();
+        assert_eq!(reversed.points()[1], -Scalar::one());
+        assert_eq!(reversed.points()[6], basis.points()[3]);
+        assert_eq!(reversed.weights()[6], basis.weights()[3]);
+
+        assert_eq!(LagrangeBasis::new(1).unwrap().points(), [Scalar::one()]);
+        assert!(LagrangeBasis::new(0).is_none());
+        assert!(LagrangeBasis::new(6).is_none());
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let poly =
+            Polynomial::from_coefficients((0..16).map(|_| Scalar::random(&mut rng)).collect());
+
+        for basis in [
+            LagrangeBasis::new(16).unwrap(),
+            LagrangeBasis::new_bit_reversed(16).unwrap(),
+        ] {
+            let evaluations = basis
+                .points()
+                .iter()
+                .map(|w| poly.evaluate(w))
+                .collect::<Vec<_>>();
+
+            // Points outside and inside the domain.
+            for z in [Scalar::random(&mut rng), basis.points()[3]] {
+                assert_eq!(basis.evaluate(&evaluations, &z), poly.evaluate(&z));
+
+                let lagrange = basis.evaluate_basis(&z);
+                assert_eq!(lagrange.iter().sum::<Scalar>(), Scalar::one());
+                assert_eq!(
+                    lagrange
+                        .iter()
+                        .zip(evaluations.iter())
+                        .map(|(l, p)| l * p)
+                        .sum::<Scalar>(),
+                    poly.evaluate(&z)
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_evaluate_invalid_length() {
+        let basis = LagrangeBasis::new(4).unwrap();
+        basis.evaluate(&[Scalar::one(); 3], &Scalar::zero());
+    }
+}
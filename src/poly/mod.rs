@@ -0,0 +1,638 @@
+//! Dense univariate polynomials over $\mathbb{F}_q$.
+//!
+//! A [`Polynomial`] is represented by its coefficients over [`Scalar`], in order of
+//! increasing degree, without trailing zero coefficients. Products are computed with
+//! the schoolbook method for small operands and with number-theoretic transforms
+//! otherwise.
+//!
+//! Polynomials can also be given by their evaluations over a [`Domain`] of roots of
+//! unity, computed from their coefficients by fast Fourier transforms, and evaluated
+//! elsewhere with the barycentric formula of a [`LagrangeBasis`].
+//!
+//! All operations are variable time in the degrees of the polynomials, but not in
+//! their coefficients, except where noted.
+
+mod domain;
+mod lagrange;
+
+pub use domain::Domain;
+pub use lagrange::LagrangeBasis;
+
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use ff::Field;
+
+use crate::backend::{Backend, Cpu};
+use crate::Scalar;
+
+use alloc::vec::Vec;
+
+/// The length of the shorter operand from which multiplying polynomials computes
+/// their product with number-theoretic transforms rather than the schoolbook method.
+const FFT_THRESHOLD: usize = 128;
+
+/// A univariate polynomial with coefficients in $\mathbb{F}_q$.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Polynomial {
+    coefficients: Vec<Scalar>,
+}
+
+impl Polynomial {
+    /// Returns the zero polynomial.
+    pub fn zero() -> Self {
+        Polynomial {
+            coefficients: Vec::new(),
+        }
+    }
+
+    /// Returns the constant polynomial `c`.
+    pub fn constant(c: Scalar) -> Self {
+        Polynomial::from_coefficients(alloc::vec![c])
+    }
+
+    /// Returns the polynomial with the given coefficients, in order of increasing
+    /// degree. Trailing zero coefficients are removed.
+    pub fn from_coefficients(coefficients: Vec<Scalar>) -> Self {
+        let mut poly = Polynomial { coefficients };
+        poly.trim();
+        poly
+    }
+
+    /// Returns the coefficients of this polynomial, in order of increasing degree.
+    /// The zero polynomial has no coefficients.
+    pub fn coefficients(&self) -> &[Scalar] {
+        &self.coefficients
+    }
+
+    /// Returns the coefficients of this polynomial, as
+    /// [`Polynomial::coefficients`] does.
+    pub fn into_coefficients(self) -> Vec<Scalar> {
+        self.coefficients
+    }
+
+    /// Returns the degree of this polynomial, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    /// Returns true if this is the zero polynomial.
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    /// Evaluates this polynomial at `x` with Horner's rule.
+    pub fn evaluate(&self, x: &Scalar) -> Scalar {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Scalar::zero(), |acc, c| acc * x + c)
+    }
+
+    /// Divides this polynomial by $(X - z)$, returning the quotient and the
+    /// remainder, which is the value of this polynomial at `z`.
+    pub fn divide_by_linear(&self, z: &Scalar) -> (Polynomial, Scalar) {
+        if self.is_zero() {
+            return (Polynomial::zero(), Scalar::zero());
+        }
+
+        let mut quotient = alloc::vec![Scalar::zero(); self.coefficients.len() - 1];
+        let mut carry = Scalar::zero();
+        for i in (1..self.coefficients.len()).rev() {
+            carry = self.coefficients[i] + carry * z;
+            quotient[i - 1] = carry;
+        }
+        let remainder = self.coefficients[0] + carry * z;
+
+        (Polynomial::from_coefficients(quotient), remainder)
+    }
+
+    /// Divides this polynomial by $(X - z_i)$ for each of `points`, returning the
+    /// quotients $(p(X) - p(z_i)) / (X - z_i)$ of the KZG openings of this polynomial
+    /// at the points, together with the values $p(z_i)$.
+    pub fn divide_by_linear_batch(&self, points: &[Scalar]) -> Vec<(Polynomial, Scalar)> {
+        points.iter().map(|z| self.divide_by_linear(z)).collect()
+    }
+
+    /// Divides this polynomial by `divisor`, returning the quotient and the
+    /// remainder, whose degree is less than that of the divisor.
+    ///
+    /// Returns `None` if the divisor is zero.
+    pub fn divide(&self, divisor: &Polynomial) -> Option<(Polynomial, Polynomial)> {
+        let d = divisor.degree()?;
+        if self.coefficients.len() <= d {
+            return Some((Polynomial::zero(), self.clone()));
+        }
+
+        // The leading coefficient is nonzero, since trailing zeros are trimmed.
+        let leading_inv = divisor.coefficients[d].invert().unwrap();
+        let mut remainder = self.coefficients.clone();
+        let mut quotient = alloc::vec![Scalar::zero(); remainder.len() - d];
+        for i in (0..quotient.len()).rev() {
+            let q = remainder[i + d] * leading_inv;
+            for (r, c) in remainder[i..].iter_mut().zip(divisor.coefficients.iter()) {
+                *r -= q * c;
+            }
+            quotient[i] = q;
+        }
+        remainder.truncate(d);
+
+        Some((
+            Polynomial::from_coefficients(quotient),
+            Polynomial::from_coefficients(remainder),
+        ))
+    }
+
+    /// Divides this polynomial by the [`Polynomial::vanishing`] polynomial $Z(X)$ of
+    /// `points`, returning the quotient and the remainder.
+    ///
+    /// The remainder $I(X)$ is the polynomial of degree less than `points.len()` that
+    /// agrees with this one on the points, so that the quotient $(p(X) - I(X)) / Z(X)$
+    /// is that of a KZG opening of this polynomial at all of the points at once.
+    pub fn divide_by_vanishing(&self, points: &[Scalar]) -> (Polynomial, Polynomial) {
+        // The vanishing polynomial is monic, hence nonzero.
+        self.divide(&Polynomial::vanishing(points)).unwrap()
+    }
+
+    /// Returns the vanishing polynomial $\prod_i (X - z_i)$ of `points`, the monic
+    /// polynomial of degree `points.len()` whose roots are the points.
+    pub fn vanishing(points: &[Scalar]) -> Self {
+        let mut coefficients = Vec::with_capacity(points.len() + 1);
+        coefficients.push(Scalar::one());
+        for z in points {
+            coefficients.push(Scalar::zero());
+            for i in (0..coefficients.len()).rev() {
+                let lower = if i > 0 {
+                    coefficients[i - 1]
+                } else {
+                    Scalar::zero()
+                };
+                coefficients[i] = lower - coefficients[i] * z;
+            }
+        }
+        Polynomial { coefficients }
+    }
+
+    /// Returns the polynomial of degree less than `points.len()` that takes the
+    /// given `values` at `points`, by Lagrange interpolation.
+    ///
+    /// Returns `None` if `points` and `values` have different lengths, or the points
+    /// are not distinct. This function is variable time in the points.
+    pub fn interpolate(points: &[Scalar], values: &[Scalar]) -> Option<Self> {
+        if points.len() != values.len() {
+            return None;
+        }
+
+        let vanishing = Polynomial::vanishing(points);
+        let mut coefficients = alloc::vec![Scalar::zero(); points.len()];
+        for (z, v) in points.iter().zip(values) {
+            // The Lagrange basis polynomial for z, up to its normalization, which is
+            // zero exactly when z is repeated.
+            let (basis, _) = vanishing.divide_by_linear(z);
+            let scale = v * Option::<Scalar>::from(basis.evaluate(z).invert())?;
+            for (c, b) in coefficients.iter_mut().zip(basis.coefficients.iter()) {
+                *c += b * scale;
+            }
+        }
+
+        Some(Polynomial::from_coefficients(coefficients))
+    }
+
+    /// Multiplies two polynomials with the schoolbook method, in time proportional
+    /// to the product of their lengths.
+    pub fn mul_schoolbook(&self, rhs: &Polynomial) -> Polynomial {
+        if self.is_zero() || rhs.is_zero() {
+            return Polynomial::zero();
+        }
+
+        let mut coefficients =
+            alloc::vec![Scalar::zero(); self.coefficients.len() + rhs.coefficients.len() - 1];
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (c, b) in coefficients[i..].iter_mut().zip(rhs.coefficients.iter()) {
+                *c += a * b;
+            }
+        }
+        Polynomial { coefficients }
+    }
+
+    /// Multiplies two polynomials with number-theoretic transforms on the [`Cpu`]
+    /// backend, in time quasi-linear in the degree of the product.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the degree of the product is $2^{32}$ or more.
+    pub fn mul_fft(&self, rhs: &Polynomial) -> Polynomial {
+        self.mul_fft_with(rhs, &Cpu)
+    }
+
+    /// Multiplies two polynomials as [`Polynomial::mul_fft`] does, with the
+    /// transforms of `backend`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the degree of the product is $2^{32}$ or more.
+    pub fn mul_fft_with(&self, rhs: &Polynomial, backend: &impl Backend) -> Polynomial {
+        if self.is_zero() || rhs.is_zero() {
+            return Polynomial::zero();
+        }
+
+        // The transforms must be long enough for the product not to wrap around.
+        let len = self.coefficients.len() + rhs.coefficients.len() - 1;
+        let n = len.next_power_of_two();
+
+        let mut a = self.coefficients.clone();
+        a.resize(n, Scalar::zero());
+        let mut b = rhs.coefficients.clone();
+        b.resize(n, Scalar::zero());
+
+        backend.ntt(&mut a);
+        backend.ntt(&mut b);
+        for (x, y) in a.iter_mut().zip(b.iter()) {
+            *x *= y;
+        }
+        backend.intt(&mut a);
+
+        a.truncate(len);
+        Polynomial::from_coefficients(a)
+    }
+
+    /// Removes trailing zero coefficients.
+    fn trim(&mut self) {
+        let len = self
+            .coefficients
+            .iter()
+            .rposition(|c| !bool::from(c.is_zero()))
+            .map_or(0, |i| i + 1);
+        self.coefficients.truncate(len);
+    }
+}
+
+/// Inverts every element of `values`, all of which must be nonzero, with a single
+/// field inversion.
+pub(crate) fn batch_invert(values: &mut [Scalar]) {
+    let mut products = Vec::with_capacity(values.len());
+    let mut acc = Scalar::one();
+    for v in values.iter() {
+        products.push(acc);
+        acc *= v;
+    }
+
+    let mut inv = acc.invert().unwrap();
+    for (v, product) in values.iter_mut().zip(products).rev() {
+        let next = inv * *v;
+        *v = inv * product;
+        inv = next;
+    }
+}
+
+/// Permutes `values`, whose length must be a power of two, into bit-reversed order
+/// of their indices.
+pub(crate) fn bit_reversal_permutation<T>(values: &mut [T]) {
+    let n = values.len();
+    let bits = n.trailing_zeros();
+    for i in 1..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+}
+
+impl From<Scalar> for Polynomial {
+    fn from(c: Scalar) -> Polynomial {
+        Polynomial::constant(c)
+    }
+}
+
+impl<'a> Neg for &'a Polynomial {
+    type Output = Polynomial;
+
+    #[inline]
+    fn neg(self) -> Polynomial {
+        Polynomial {
+            coefficients: self.coefficients.iter().map(|c| -c).collect(),
+        }
+    }
+}
+
+impl Neg for Polynomial {
+    type Output = Polynomial;
+
+    #[inline]
+    fn neg(self) -> Polynomial {
+        -&self
+    }
+}
+
+impl<'a, 'b> Add<&'b Polynomial> for &'a Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, rhs: &'b Polynomial) -> Polynomial {
+        let (long, short) = if self.coefficients.len() >= rhs.coefficients.len() {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+        let mut coefficients = long.coefficients.clone();
+        for (c, s) in coefficients.iter_mut().zip(short.coefficients.iter()) {
+            *c += s;
+        }
+        Polynomial::from_coefficients(coefficients)
+    }
+}
+
+impl<'a, 'b> Sub<&'b Polynomial> for &'a Polynomial {
+    type Output = Polynomial;
+
+    fn sub(self, rhs: &'b Polynomial) -> Polynomial {
+        let mut coefficients = self.coefficients.clone();
+        coefficients.resize(
+            self.coefficients.len().max(rhs.coefficients.len()),
+            Scalar::zero(),
+        );
+        for (c, r) in coefficients.iter_mut().zip(rhs.coefficients.iter()) {
+            *c -= r;
+        }
+        Polynomial::from_coefficients(coefficients)
+    }
+}
+
+impl<'a, 'b> Mul<&'b Polynomial> for &'a Polynomial {
+    type Output = Polynomial;
+
+    /// Multiplies two polynomials with [`Polynomial::mul_schoolbook`] if either is
+    /// short, and with [`Polynomial::mul_fft`] otherwise.
+    fn mul(self, rhs: &'b Polynomial) -> Polynomial {
+        if self.coefficients.len().min(rhs.coefficients.len()) < FFT_THRESHOLD {
+            self.mul_schoolbook(rhs)
+        } else {
+            self.mul_fft(rhs)
+        }
+    }
+}
+
+impl<'a, 'b> Mul<&'b Scalar> for &'a Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, rhs: &'b Scalar) -> Polynomial {
+        Polynomial::from_coefficients(self.coefficients.iter().map(|c| c * rhs).collect())
+    }
+}
+
+impl_binops_additive!(Polynomial, Polynomial);
+impl_binops_multiplicative!(Polynomial, Polynomial);
+impl_binops_multiplicative_mixed!(Polynomial, Scalar, Polynomial);
+
+impl MulAssign<Scalar> for Polynomial {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Scalar) {
+        *self = &*self * rhs;
+    }
+}
+
+impl<'b> MulAssign<&'b Scalar> for Polynomial {
+    #[inline]
+    fn mul_assign(&mut self, rhs: &'b Scalar) {
+        *self = &*self * rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ])
+    }
+
+    fn random_polynomial(len: usize, rng: &mut XorShiftRng) -> Polynomial {
+        Polynomial::from_coefficients((0..len).map(|_| Scalar::random(&mut *rng)).collect())
+    }
+
+    #[test]
+    fn test_representation() {
+        let poly = Polynomial::from_coefficients(alloc::vec![
+            Scalar::from(1),
+            Scalar::from(2),
+            Scalar::zero(),
+            Scalar::zero(),
+        ]);
+        assert_eq!(poly.coefficients(), [Scalar::from(1), Scalar::from(2)]);
+        assert_eq!(poly.degree(), Some(1));
+        assert_eq!(poly.evaluate(&Scalar::from(10)), Scalar::from(21));
+
+        let zero = Polynomial::from_coefficients(alloc::vec![Scalar::zero(); 3]);
+        assert!(zero.is_zero());
+        assert_eq!(zero, Polynomial::zero());
+        assert_eq!(zero.degree(), None);
+        assert_eq!(zero.evaluate(&Scalar::from(10)), Scalar::zero());
+        assert_eq!(Polynomial::from(Scalar::zero()), zero);
+        assert_eq!(Polynomial::from(Scalar::one()).degree(), Some(0));
+    }
+
+    #[test]
+    fn test_addition() {
+        let mut rng = rng();
+        let a = random_polynomial(5, &mut rng);
+        let b = random_polynomial(3, &mut rng);
+        let x = Scalar::random(&mut rng);
+
+        assert_eq!((&a + &b).evaluate(&x), a.evaluate(&x) + b.evaluate(&x));
+        assert_eq!(&a + &b, b.clone() + &a);
+        assert_eq!((&b - &a).evaluate(&x), b.evaluate(&x) - a.evaluate(&x));
+        assert_eq!((-&a).evaluate(&x), -a.evaluate(&x));
+        assert!((&a - a.clone()).is_zero());
+        assert_eq!(&a + Polynomial::zero(), a);
+
+        // Cancelling leading coefficients lowers the degree.
+        let c = &a
+            + Polynomial::from_coefficients(alloc::vec![
+                Scalar::zero(),
+                Scalar::zero(),
+                Scalar::zero(),
+                Scalar::zero(),
+                -a.coefficients()[4],
+            ]);
+        assert_eq!(c.degree(), Some(3));
+
+        let mut d = a.clone();
+        d += &b;
+        d -= b;
+        assert_eq!(d, a);
+    }
+
+    #[test]
+    fn test_multiplication() {
+        let mut rng = rng();
+        for (la, lb) in [(1, 1), (3, 7), (40, 100), (128, 128), (130, 200)] {
+            let a = random_polynomial(la, &mut rng);
+            let b = random_polynomial(lb, &mut rng);
+            let x = Scalar::random(&mut rng);
+
+            let product = a.mul_schoolbook(&b);
+            assert_eq!(product.degree(), Some(la + lb - 2));
+            assert_eq!(product.evaluate(&x), a.evaluate(&x) * b.evaluate(&x));
+            assert_eq!(a.mul_fft(&b), product);
+            assert_eq!(&a * &b, product);
+            assert_eq!(&b * &a, product);
+        }
+
+        let a = random_polynomial(4, &mut rng);
+        assert!(a.mul_schoolbook(&Polynomial::zero()).is_zero());
+        assert!(Polynomial::zero().mul_fft(&a).is_zero());
+
+        let c = Scalar::random(&mut rng);
+        assert_eq!(&a * c, &a * Polynomial::constant(c));
+        assert!((&a * Scalar::zero()).is_zero());
+        let mut b = a.clone();
+        b *= c;
+        b *= &a;
+        assert_eq!(b, &a * &a * c);
+    }
+
+    #[test]
+    fn test_divide_by_linear() {
+        let mut rng = rng();
+        let poly = random_polynomial(6, &mut rng);
+        let z = Scalar::random(&mut rng);
+
+        let (quotient, remainder) = poly.divide_by_linear(&z);
+        assert_eq!(remainder, poly.evaluate(&z));
+        let linear = Polynomial::from_coefficients(alloc::vec![-z, Scalar::one()]);
+        assert_eq!(&quotient * &linear + Polynomial::constant(remainder), poly);
+
+        assert_eq!(
+            Polynomial::zero().divide_by_linear(&z),
+            (Polynomial::zero(), Scalar::zero())
+        );
+    }
+
+    #[test]
+    fn test_divide_by_linear_batch() {
+        let mut rng = rng();
+        let poly = random_polynomial(6, &mut rng);
+        let points = (0..3).map(|_| Scalar::random(&mut rng)).collect::<Vec<_>>();
+
+        let quotients = poly.divide_by_linear_batch(&points);
+        assert_eq!(quotients.len(), 3);
+        for (z, quotient) in points.iter().zip(quotients) {
+            assert_eq!(quotient, poly.divide_by_linear(z));
+        }
+        assert!(poly.divide_by_linear_batch(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_divide() {
+        let mut rng = rng();
+        for (la, lb) in [(1, 1), (6, 3), (10, 10), (3, 6), (20, 1)] {
+            let a = random_polynomial(la, &mut rng);
+            let b = random_polynomial(lb, &mut rng);
+
+            let (quotient, remainder) = a.divide(&b).unwrap();
+            assert!(remainder.degree() < b.degree());
+            assert_eq!(&quotient * &b + &remainder, a);
+        }
+
+        let a = random_polynomial(5, &mut rng);
+        let b = random_polynomial(3, &mut rng);
+        assert_eq!((&a * &b).divide(&b), Some((a.clone(), Polynomial::zero())));
+        assert_eq!(
+            Polynomial::zero().divide(&b),
+            Some((Polynomial::zero(), Polynomial::zero()))
+        );
+        assert_eq!(a.divide(&Polynomial::zero()), None);
+    }
+
+    #[test]
+    fn test_divide_by_vanishing() {
+        let mut rng = rng();
+        let poly = random_polynomial(10, &mut rng);
+        let points = (0..4).map(|_| Scalar::random(&mut rng)).collect::<Vec<_>>();
+
+        let (quotient, remainder) = poly.divide_by_vanishing(&points);
+        let values = points.iter().map(|z| poly.evaluate(z)).collect::<Vec<_>>();
+        assert_eq!(
+            remainder,
+            Polynomial::interpolate(&points, &values).unwrap()
+        );
+        assert_eq!(
+            &quotient * &Polynomial::vanishing(&points) + &remainder,
+            poly
+        );
+
+        // With a single point, this is the division by a linear polynomial.
+        let (quotient, remainder) = poly.divide_by_vanishing(&points[..1]);
+        let (expected, value) = poly.divide_by_linear(&points[0]);
+        assert_eq!(
+            (quotient, remainder),
+            (expected, Polynomial::constant(value))
+        );
+        assert_eq!(poly.divide_by_vanishing(&[]).0, poly);
+        assert!(poly.divide_by_vanishing(&[]).1.is_zero());
+    }
+
+    #[test]
+    fn test_batch_invert() {
+        let mut rng = rng();
+        let values = (0..5).map(|_| Scalar::random(&mut rng)).collect::<Vec<_>>();
+        let mut inverses = values.clone();
+        batch_invert(&mut inverses);
+        for (v, inv) in values.iter().zip(inverses.iter()) {
+            assert_eq!(v * inv, Scalar::one());
+        }
+        batch_invert(&mut []);
+    }
+
+    #[test]
+    fn test_bit_reversal_permutation() {
+        let mut values = [0, 1, 2, 3, 4, 5, 6, 7];
+        bit_reversal_permutation(&mut values);
+        assert_eq!(values, [0, 4, 2, 6, 1, 5, 3, 7]);
+
+        let mut single = [9];
+        bit_reversal_permutation(&mut single);
+        assert_eq!(single, [9]);
+    }
+
+    #[test]
+    fn test_vanishing_and_interpolation() {
+        let points = [Scalar::from(3), Scalar::from(7), -Scalar::from(11)];
+        let vanishing = Polynomial::vanishing(&points);
+        assert_eq!(vanishing.degree(), Some(3));
+        assert_eq!(vanishing.coefficients()[3], Scalar::one());
+        for z in &points {
+            assert_eq!(vanishing.evaluate(z), Scalar::zero());
+        }
+        assert_eq!(
+            Polynomial::vanishing(&[]),
+            Polynomial::constant(Scalar::one())
+        );
+
+        let values = [Scalar::from(1), Scalar::from(2), Scalar::from(3)];
+        let interpolation = Polynomial::interpolate(&points, &values).unwrap();
+        assert!(interpolation.degree().unwrap() < 3);
+        for (z, v) in points.iter().zip(values.iter()) {
+            assert_eq!(interpolation.evaluate(z), *v);
+        }
+
+        // Interpolating the evaluations of a polynomial recovers it.
+        let mut rng = rng();
+        let poly = random_polynomial(10, &mut rng);
+        let points = (0..10)
+            .map(|_| Scalar::random(&mut rng))
+            .collect::<Vec<_>>();
+        let values = points.iter().map(|z| poly.evaluate(z)).collect::<Vec<_>>();
+        assert_eq!(Polynomial::interpolate(&points, &values).unwrap(), poly);
+
+        assert_eq!(
+            Polynomial::interpolate(&[], &[]).unwrap(),
+            Polynomial::zero()
+        );
+        assert!(Polynomial::interpolate(&[points[0], points[0]], &values[..2]).is_none());
+        assert!(Polynomial::interpolate(&points[..2], &values[..3]).is_none());
+    }
+}
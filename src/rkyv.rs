@@ -0,0 +1,161 @@
+//! Zero-copy [`rkyv`] archives of [`G1Affine`] and [`G2Affine`].
+//!
+//! A point is archived as its uncompressed encoding, which needs no alignment and
+//! does not depend on the endianness of the target. Checking an archive with
+//! `rkyv::check_archived_root` only checks its structure, so it is cheap even for a
+//! memory-mapped proving key or SRS holding millions of points; each point is
+//! validated when it is accessed, with [`ArchivedG1Affine::get`] or
+//! [`ArchivedG2Affine::get`].
+//!
+//! [`rkyv`]: https://docs.rs/rkyv
+
+// Implementing `Archive` requires writing to a raw pointer.
+#![allow(unsafe_code)]
+
+use core::convert::Infallible;
+
+use ::rkyv::bytecheck::CheckBytes;
+use ::rkyv::{Archive, Deserialize, Fallible, Serialize};
+use subtle::CtOption;
+
+use crate::{Error, G1Affine, G2Affine};
+
+macro_rules! impl_archive {
+    ($affine:ident, $archived:ident, $len:expr) => {
+        #[doc = concat!("The archived form of a [`", stringify!($affine), "`], which holds")]
+        /// its uncompressed encoding.
+        #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+        #[repr(transparent)]
+        pub struct $archived([u8; $len]);
+
+        impl $archived {
+            /// Returns the archived point, if it is the encoding of a point in the
+            /// prime-order subgroup.
+            pub fn get(&self) -> CtOption<$affine> {
+                $affine::from_uncompressed(&self.0)
+            }
+
+            /// Returns the archived point, if it is the encoding of a point on the
+            /// curve, without checking that it is in the prime-order subgroup.
+            ///
+            /// **This is dangerous to call unless you trust the archive you are
+            /// reading; otherwise, API invariants may be broken.** Please consider
+            /// using `get()` instead.
+            pub fn get_unchecked(&self) -> CtOption<$affine> {
+                $affine::from_uncompressed_unchecked(&self.0)
+                    .and_then(|p| CtOption::new(p, p.is_on_curve()))
+            }
+
+            /// Returns the uncompressed encoding of the archived point.
+            pub fn as_bytes(&self) -> &[u8; $len] {
+                &self.0
+            }
+        }
+
+        impl Archive for $affine {
+            type Archived = $archived;
+            type Resolver = ();
+
+            unsafe fn resolve(&self, _: usize, _: (), out: *mut $archived) {
+                out.write($archived(self.to_uncompressed()));
+            }
+        }
+
+        impl<S: Fallible + ?Sized> Serialize<S> for $affine {
+            fn serialize(&self, _: &mut S) -> Result<(), S::Error> {
+                Ok(())
+            }
+        }
+
+        impl<D> Deserialize<$affine, D> for $archived
+        where
+            D: Fallible + ?Sized,
+            D::Error: From<Error>,
+        {
+            fn deserialize(&self, _: &mut D) -> Result<$affine, D::Error> {
+                Ok($affine::try_from_uncompressed(&self.0)?)
+            }
+        }
+
+        // Every byte string is structurally valid; the point itself is validated
+        // when it is accessed.
+        impl<C: ?Sized> CheckBytes<C> for $archived {
+            type Error = Infallible;
+
+            unsafe fn check_bytes<'a>(
+                value: *const Self,
+                _: &mut C,
+            ) -> Result<&'a Self, Infallible> {
+                Ok(&*value)
+            }
+        }
+    };
+}
+
+impl_archive!(G1Affine, ArchivedG1Affine, 96);
+impl_archive!(G2Affine, ArchivedG2Affine, 192);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Scalar;
+
+    struct Deserializer;
+
+    impl Fallible for Deserializer {
+        type Error = Error;
+    }
+
+    fn deserialize<T, A: Deserialize<T, Deserializer>>(archived: &A) -> Result<T, Error> {
+        archived.deserialize(&mut Deserializer)
+    }
+
+    #[test]
+    fn test_archive_g1() {
+        let p = G1Affine::from(G1Affine::generator() * Scalar::from_raw([1, 2, 3, 4]));
+        let points = [p, G1Affine::identity(), G1Affine::generator()];
+
+        let bytes = ::rkyv::to_bytes::<_, 256>(&p).unwrap();
+        assert_eq!(&bytes[..], &p.to_uncompressed()[..]);
+
+        let bytes = ::rkyv::to_bytes::<_, 512>(&points).unwrap();
+        let archived = ::rkyv::check_archived_root::<[G1Affine; 3]>(&bytes).unwrap();
+        for (a, p) in archived.iter().zip(points.iter()) {
+            assert_eq!(a.get().unwrap(), *p);
+            assert_eq!(a.get_unchecked().unwrap(), *p);
+            assert_eq!(deserialize(a), Ok(*p));
+        }
+
+        // Not a point on the curve.
+        let archived = ::rkyv::check_archived_root::<G1Affine>(&[0u8; 96][..]).unwrap();
+        assert!(bool::from(archived.get().is_none()));
+        assert!(bool::from(archived.get_unchecked().is_none()));
+        assert_eq!(
+            deserialize::<G1Affine, _>(archived),
+            Err(Error::NotOnCurve)
+        );
+    }
+
+    #[test]
+    fn test_archive_g2() {
+        let q = G2Affine::from(G2Affine::generator() * Scalar::from_raw([1, 2, 3, 4]));
+        let points = [q, G2Affine::identity()];
+
+        let bytes = ::rkyv::to_bytes::<_, 512>(&points).unwrap();
+        assert_eq!(&bytes[..192], &q.to_uncompressed()[..]);
+        let archived = ::rkyv::check_archived_root::<[G2Affine; 2]>(&bytes).unwrap();
+        for (a, q) in archived.iter().zip(points.iter()) {
+            assert_eq!(a.get().unwrap(), *q);
+            assert_eq!(deserialize(a), Ok(*q));
+        }
+
+        let mut bytes = q.to_uncompressed();
+        bytes[191] ^= 1;
+        let archived = ::rkyv::check_archived_root::<G2Affine>(&bytes[..]).unwrap();
+        assert!(bool::from(archived.get().is_none()));
+        assert_eq!(
+            deserialize::<G2Affine, _>(archived),
+            Err(Error::NotOnCurve)
+        );
+    }
+}
@@ -3,15 +3,23 @@
 
 use core::fmt;
 use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use core::str::FromStr;
 use rand_core::RngCore;
 
+#[cfg(feature = "experimental")]
+use digest::{BlockInput, Digest};
+
 use ff::{Field, PrimeField};
 use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
 #[cfg(feature = "bits")]
 use ff::{FieldBits, PrimeFieldBits};
 
+use crate::arith::{Backend, FieldParams, Selected, Unrolled};
+#[cfg(feature = "tower")]
+use crate::fp::Fp;
 use crate::util::{adc, mac, sbb};
+use crate::Error;
 
 /// Represents an element of the scalar field $\mathbb{F}_q$ of the BLS12-381 elliptic
 /// curve construction.
@@ -32,9 +40,52 @@ impl fmt::Debug for Scalar {
     }
 }
 
+/// Formats the canonical representation of the scalar in decimal, as its [`FromStr`]
+/// implementation parses it. The alternate flag and the padding options of integers are supported.
+///
+/// **This is not constant time**, as the number of digits depends on the scalar,
+/// and neither is the [`fmt::LowerHex`] implementation.
 impl fmt::Display for Scalar {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+        // q < 10^77, so that the scalar has at most 77 digits.
+        let mut digits = [0u8; 77];
+        let mut limbs =
+            Scalar::montgomery_reduce(self.0[0], self.0[1], self.0[2], self.0[3], 0, 0, 0, 0).0;
+        let mut start = digits.len();
+        loop {
+            // Divide by 10, from the most significant limb down.
+            let mut rem = 0u128;
+            for limb in limbs.iter_mut().rev() {
+                let n = (rem << 64) | u128::from(*limb);
+                *limb = (n / 10) as u64;
+                rem = n % 10;
+            }
+            start -= 1;
+            digits[start] = b'0' + rem as u8;
+            if limbs == [0; 4] {
+                break;
+            }
+        }
+
+        f.pad_integral(true, "", core::str::from_utf8(&digits[start..]).unwrap())
+    }
+}
+
+/// Formats the canonical representation of the scalar in lowercase hexadecimal,
+/// without leading zeros, and with a `0x` prefix if the alternate flag is set.
+impl fmt::LowerHex for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+
+        let bytes = self.to_bytes_be();
+        let mut digits = [0u8; 64];
+        for (pair, byte) in digits.chunks_exact_mut(2).zip(bytes.iter()) {
+            pair[0] = HEX[usize::from(byte >> 4)];
+            pair[1] = HEX[usize::from(byte & 0xf)];
+        }
+        let start = digits.iter().position(|&d| d != b'0').unwrap_or(63);
+
+        f.pad_integral(true, "0x", core::str::from_utf8(&digits[start..]).unwrap())
     }
 }
 
@@ -109,7 +160,7 @@ impl<'a> Neg for &'a Scalar {
 
     #[inline]
     fn neg(self) -> Scalar {
-        self.neg()
+        Scalar(<Selected as Backend<ScalarParams, 4>>::neg(&self.0))
     }
 }
 
@@ -127,7 +178,7 @@ impl<'a, 'b> Sub<&'b Scalar> for &'a Scalar {
 
     #[inline]
     fn sub(self, rhs: &'b Scalar) -> Scalar {
-        self.sub(rhs)
+        Scalar(<Selected as Backend<ScalarParams, 4>>::sub(&self.0, &rhs.0))
     }
 }
 
@@ -136,7 +187,7 @@ impl<'a, 'b> Add<&'b Scalar> for &'a Scalar {
 
     #[inline]
     fn add(self, rhs: &'b Scalar) -> Scalar {
-        self.add(rhs)
+        Scalar(<Selected as Backend<ScalarParams, 4>>::add(&self.0, &rhs.0))
     }
 }
 
@@ -145,7 +196,7 @@ impl<'a, 'b> Mul<&'b Scalar> for &'a Scalar {
 
     #[inline]
     fn mul(self, rhs: &'b Scalar) -> Scalar {
-        self.mul(rhs)
+        Scalar(<Selected as Backend<ScalarParams, 4>>::mul(&self.0, &rhs.0))
     }
 }
 
@@ -155,6 +206,41 @@ impl_binops_multiplicative!(Scalar, Scalar);
 /// INV = -(q^{-1} mod 2^64) mod 2^64
 const INV: u64 = 0xffff_fffe_ffff_ffff;
 
+/// The parameters of `Scalar` for the arithmetic backends.
+pub(crate) struct ScalarParams;
+
+impl FieldParams<4> for ScalarParams {
+    const MODULUS: [u64; 4] = MODULUS.0;
+    const INV: u64 = INV;
+}
+
+impl Backend<ScalarParams, 4> for Unrolled {
+    #[inline(always)]
+    fn add(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+        Scalar::add(&Scalar(*a), &Scalar(*b)).0
+    }
+
+    #[inline(always)]
+    fn sub(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+        Scalar::sub(&Scalar(*a), &Scalar(*b)).0
+    }
+
+    #[inline(always)]
+    fn neg(a: &[u64; 4]) -> [u64; 4] {
+        Scalar::neg(&Scalar(*a)).0
+    }
+
+    #[inline(always)]
+    fn mul(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+        Scalar::mul(&Scalar(*a), &Scalar(*b)).0
+    }
+
+    #[inline(always)]
+    fn square(a: &[u64; 4]) -> [u64; 4] {
+        Scalar::square(&Scalar(*a)).0
+    }
+}
+
 /// R = 2^256 mod q
 const R: Scalar = Scalar([
     0x0000_0001_ffff_fffe,
@@ -207,6 +293,22 @@ impl Default for Scalar {
 impl zeroize::DefaultIsZeroes for Scalar {}
 
 impl Scalar {
+    /// A primitive cube root of unity, $-z^2 \bmod q$ for the parameter $z$ of the
+    /// curve, the counterpart of [`WithSmallOrderMulGroup<3>::ZETA`][zeta] in later
+    /// versions of `ff`.
+    ///
+    /// It is the eigenvalue of the endomorphism $(x, y) \mapsto (\zeta x, y)$ of
+    /// $\mathbb{G}_1$, with $\zeta$ the constant `Fp::ZETA` of the base field: the
+    /// endomorphism multiplies the points of $\mathbb{G}_1$ by this scalar.
+    ///
+    /// [zeta]: https://docs.rs/ff/0.13/ff/trait.WithSmallOrderMulGroup.html#associatedconstant.ZETA
+    pub const ZETA: Scalar = Scalar::from_raw([
+        0xffff_fffe_0000_0001,
+        0xa778_0001_fffc_b7fc,
+        0x3339_d808_09a1_d804,
+        0x73ed_a753_299d_7d48,
+    ]);
+
     /// Returns zero, the additive identity.
     #[inline]
     pub const fn zero() -> Scalar {
@@ -229,29 +331,12 @@ impl Scalar {
     /// Attempts to convert a little-endian byte representation of
     /// a scalar into a `Scalar`, failing if the input is not canonical.
     pub fn from_bytes(bytes: &[u8; 32]) -> CtOption<Scalar> {
-        let mut tmp = Scalar([0, 0, 0, 0]);
-
-        tmp.0[0] = u64::from_le_bytes(<[u8; 8]>::try_from(&bytes[0..8]).unwrap());
-        tmp.0[1] = u64::from_le_bytes(<[u8; 8]>::try_from(&bytes[8..16]).unwrap());
-        tmp.0[2] = u64::from_le_bytes(<[u8; 8]>::try_from(&bytes[16..24]).unwrap());
-        tmp.0[3] = u64::from_le_bytes(<[u8; 8]>::try_from(&bytes[24..32]).unwrap());
-
-        // Try to subtract the modulus
-        let (_, borrow) = sbb(tmp.0[0], MODULUS.0[0], 0);
-        let (_, borrow) = sbb(tmp.0[1], MODULUS.0[1], borrow);
-        let (_, borrow) = sbb(tmp.0[2], MODULUS.0[2], borrow);
-        let (_, borrow) = sbb(tmp.0[3], MODULUS.0[3], borrow);
-
-        // If the element is smaller than MODULUS then the
-        // subtraction will underflow, producing a borrow value
-        // of 0xffff...ffff. Otherwise, it'll be zero.
-        let is_some = (borrow as u8) & 1;
-
-        // Convert to Montgomery form by computing
-        // (a.R^0 * R^2) / R = a.R
-        tmp *= &R2;
-
-        CtOption::new(tmp, Choice::from(is_some))
+        Scalar::from_raw_checked([
+            u64::from_le_bytes(<[u8; 8]>::try_from(&bytes[0..8]).unwrap()),
+            u64::from_le_bytes(<[u8; 8]>::try_from(&bytes[8..16]).unwrap()),
+            u64::from_le_bytes(<[u8; 8]>::try_from(&bytes[16..24]).unwrap()),
+            u64::from_le_bytes(<[u8; 8]>::try_from(&bytes[24..32]).unwrap()),
+        ])
     }
 
     /// Converts an element of `Scalar` into a byte representation in
@@ -270,6 +355,22 @@ impl Scalar {
         res
     }
 
+    /// Attempts to convert a big-endian byte representation of
+    /// a scalar into a `Scalar`, failing if the input is not canonical.
+    pub fn from_bytes_be(bytes: &[u8; 32]) -> CtOption<Scalar> {
+        let mut le = *bytes;
+        le.reverse();
+        Scalar::from_bytes(&le)
+    }
+
+    /// Converts an element of `Scalar` into a byte representation in
+    /// big-endian byte order.
+    pub fn to_bytes_be(&self) -> [u8; 32] {
+        let mut res = self.to_bytes();
+        res.reverse();
+        res
+    }
+
     /// Converts a 512-bit little endian integer into
     /// a `Scalar` by reducing by the modulus.
     pub fn from_bytes_wide(bytes: &[u8; 64]) -> Scalar {
@@ -285,6 +386,30 @@ impl Scalar {
         ])
     }
 
+    /// Derives a scalar from `seed` under the domain separation label `label`, so
+    /// that the same seed yields independent scalars under distinct labels.
+    ///
+    /// The scalar is the 64 bytes $\mathrm{HKDF\text{-}Expand}(PRK, label, 64)$,
+    /// where $PRK = \mathrm{HKDF\text{-}Extract}(\epsilon, seed)$ with the empty
+    /// salt and the hash function `H`, read as a big-endian integer and reduced
+    /// modulo $q$, which leaves a bias of about $2^{-257}$. This suits reproducible
+    /// test keys and deterministic protocol parameters. Secret keys for signatures
+    /// should be derived with `signature::SecretKey::key_gen` instead.
+    #[cfg(feature = "experimental")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "experimental")))]
+    pub fn derive_from_seed<H>(seed: &[u8], label: &[u8]) -> Scalar
+    where
+        H: Digest + BlockInput,
+    {
+        use crate::hmac::{hkdf_expand, hkdf_extract};
+
+        let prk = hkdf_extract::<H>(&[], &[seed]);
+        let mut okm = [0u8; 64];
+        hkdf_expand::<H>(&prk, &[label], &mut okm);
+        okm.reverse();
+        Scalar::from_bytes_wide(&okm)
+    }
+
     fn from_u512(limbs: [u64; 8]) -> Scalar {
         // We reduce an arbitrary 512-bit number by decomposing it into two 256-bit digits
         // with the higher bits multiplied by 2^256. Thus, we perform two reductions
@@ -311,6 +436,207 @@ impl Scalar {
         (&Scalar(val)).mul(&R2)
     }
 
+    /// Attempts to convert the big-endian encoding of [`Scalar::to_bytes_be`], which
+    /// must be exactly 32 bytes long, into a `Scalar`, as [`Scalar::from_bytes_be`]
+    /// does, reporting why it is invalid if it is.
+    ///
+    /// **This is not constant time**; use `from_bytes_be()` for secret inputs.
+    pub fn try_from_bytes_be(bytes: &[u8]) -> Result<Scalar, Error> {
+        let bytes = <&[u8; 32]>::try_from(bytes).map_err(|_| Error::InvalidLength)?;
+        Option::from(Scalar::from_bytes_be(bytes)).ok_or(Error::NonCanonicalField)
+    }
+
+    /// Attempts to convert an integer represented in little endian into a `Scalar`,
+    /// as [`Scalar::from_raw_checked`] does, failing with
+    /// [`Error::NonCanonicalField`] if it is not canonical.
+    ///
+    /// **This is not constant time**; use `from_raw_checked()` for secret inputs.
+    pub fn try_from_raw(val: [u64; 4]) -> Result<Scalar, Error> {
+        Option::from(Scalar::from_raw_checked(val)).ok_or(Error::NonCanonicalField)
+    }
+
+    /// Parses a scalar from a string of digits in the given radix, such as
+    /// `"52435875175126190479447740508185965837690552500527637822603658699938581184512"`
+    /// for $-1$ in decimal, failing with [`Error::InvalidDigit`] if the string is empty
+    /// or has a character that is not a digit, and with [`Error::NonCanonicalField`] if
+    /// the integer is not smaller than $q$. Digits above 9 are letters of either case,
+    /// and an optional `+` sign may precede them, as for the integer types.
+    ///
+    /// **This is not constant time.**
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not in the range from 2 to 36.
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Scalar, Error> {
+        assert!(
+            (2..=36).contains(&radix),
+            "from_str_radix: radix must lie in the range `[2, 36]`"
+        );
+
+        let digits = s.strip_prefix('+').unwrap_or(s);
+        if digits.is_empty() {
+            return Err(Error::InvalidDigit);
+        }
+
+        let mut limbs = [0u64; 4];
+        for c in digits.chars() {
+            let digit = c.to_digit(radix).ok_or(Error::InvalidDigit)?;
+            // limbs = limbs * radix + digit, which must not overflow.
+            let mut carry = u64::from(digit);
+            for limb in limbs.iter_mut() {
+                let (lo, hi) = mac(0, *limb, u64::from(radix), carry);
+                *limb = lo;
+                carry = hi;
+            }
+            if carry != 0 {
+                return Err(Error::NonCanonicalField);
+            }
+        }
+
+        Scalar::try_from_raw(limbs)
+    }
+
+    /// Converts the element `fp` of the base field into a `Scalar`, reducing its
+    /// canonical representation modulo $q$.
+    #[cfg(feature = "tower")]
+    pub fn from_fp_reduced(fp: &Fp) -> Scalar {
+        let bytes = fp.to_bytes();
+        let mut limbs = [0u64; 8];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes.rchunks_exact(8)) {
+            *limb = u64::from_be_bytes(<[u8; 8]>::try_from(chunk).unwrap());
+        }
+        Scalar::from_u512(limbs)
+    }
+
+    /// Attempts to convert an integer represented in little endian
+    /// into a `Scalar`, failing if the input is not canonical. Unlike
+    /// [`Scalar::from_raw`], this does not reduce the input.
+    pub fn from_raw_checked(val: [u64; 4]) -> CtOption<Scalar> {
+        let mut tmp = Scalar(val);
+
+        // Try to subtract the modulus
+        let (_, borrow) = sbb(tmp.0[0], MODULUS.0[0], 0);
+        let (_, borrow) = sbb(tmp.0[1], MODULUS.0[1], borrow);
+        let (_, borrow) = sbb(tmp.0[2], MODULUS.0[2], borrow);
+        let (_, borrow) = sbb(tmp.0[3], MODULUS.0[3], borrow);
+
+        // If the element is smaller than MODULUS then the
+        // subtraction will underflow, producing a borrow value
+        // of 0xffff...ffff. Otherwise, it'll be zero.
+        let is_some = (borrow as u8) & 1;
+
+        // Convert to Montgomery form by computing
+        // (a.R^0 * R^2) / R = a.R
+        tmp *= &R2;
+
+        CtOption::new(tmp, Choice::from(is_some))
+    }
+
+    /// Converts integers represented in little endian into their (congruent)
+    /// `Scalar` representations, as [`Scalar::from_raw`] does, writing them to
+    /// `out`. This function will panic if `limbs.len() != out.len()`.
+    ///
+    /// The conversions are independent, and are computed four at a time so
+    /// that their reductions can be interleaved.
+    pub fn from_u64_limbs_batch(limbs: &[[u64; 4]], out: &mut [Scalar]) {
+        assert_eq!(limbs.len(), out.len());
+
+        let mut out_chunks = out.chunks_exact_mut(BATCH_LANES);
+        let mut limb_chunks = limbs.chunks_exact(BATCH_LANES);
+        for (out, limbs) in (&mut out_chunks).zip(&mut limb_chunks) {
+            let out: &mut [Scalar; BATCH_LANES] = out.try_into().unwrap();
+            *out = [
+                Scalar::from_raw(limbs[0]),
+                Scalar::from_raw(limbs[1]),
+                Scalar::from_raw(limbs[2]),
+                Scalar::from_raw(limbs[3]),
+            ];
+        }
+        for (out, limbs) in out_chunks
+            .into_remainder()
+            .iter_mut()
+            .zip(limb_chunks.remainder())
+        {
+            *out = Scalar::from_raw(*limbs);
+        }
+    }
+
+    /// Converts scalars into the little-endian limbs of their canonical
+    /// representations, writing them to `out`. This is the inverse of
+    /// [`Scalar::from_u64_limbs_batch`] for canonical inputs, and computes the
+    /// conversions four at a time like it. This function will panic if
+    /// `scalars.len() != out.len()`.
+    pub fn to_canonical_batch(scalars: &[Scalar], out: &mut [[u64; 4]]) {
+        assert_eq!(scalars.len(), out.len());
+
+        let mut out_chunks = out.chunks_exact_mut(BATCH_LANES);
+        let mut scalar_chunks = scalars.chunks_exact(BATCH_LANES);
+        for (out, scalars) in (&mut out_chunks).zip(&mut scalar_chunks) {
+            let out: &mut [[u64; 4]; BATCH_LANES] = out.try_into().unwrap();
+            *out = [
+                <[u64; 4]>::from(&scalars[0]),
+                <[u64; 4]>::from(&scalars[1]),
+                <[u64; 4]>::from(&scalars[2]),
+                <[u64; 4]>::from(&scalars[3]),
+            ];
+        }
+        for (out, scalar) in out_chunks
+            .into_remainder()
+            .iter_mut()
+            .zip(scalar_chunks.remainder())
+        {
+            *out = scalar.into();
+        }
+    }
+
+    /// Recodes this element in width-`w` non-adjacent form: digits $d_i$, least
+    /// significant first, with $\sum_i d_i 2^i$ equal to the canonical
+    /// representation, where every nonzero digit is odd and less than $2^{w - 1}$ in
+    /// absolute value, and is followed by at least $w - 1$ zero digits.
+    ///
+    /// This function is variable time with respect to the element.
+    #[cfg(feature = "groups")]
+    pub(crate) fn wnaf(&self, w: usize) -> [i8; 256] {
+        debug_assert!((2..=8).contains(&w));
+
+        let mut limbs = [0u64; 5];
+        limbs[..4].copy_from_slice(&<[u64; 4]>::from(self));
+
+        let width = 1u64 << w;
+        let mask = width - 1;
+        let mut digits = [0i8; 256];
+        let mut carry = 0;
+        let mut pos = 0;
+        while pos < 256 {
+            // The `w` bits starting at `pos`, which may straddle two limbs.
+            let (limb, bit) = (pos / 64, pos % 64);
+            let bits = if bit + w <= 64 {
+                limbs[limb] >> bit
+            } else {
+                (limbs[limb] >> bit) | (limbs[limb + 1] << (64 - bit))
+            };
+
+            let window = carry + (bits & mask);
+            if window & 1 == 0 {
+                // Only the lowest bit is consumed, so a window of `width` leaves the
+                // carry for the next position.
+                pos += 1;
+                continue;
+            }
+
+            if window < width / 2 {
+                carry = 0;
+                digits[pos] = window as i8;
+            } else {
+                carry = 1;
+                digits[pos] = (window as i64 - width as i64) as i8;
+            }
+            pos += w;
+        }
+
+        digits
+    }
+
     /// Squares this element.
     #[inline]
     pub const fn square(&self) -> Scalar {
@@ -392,6 +718,34 @@ impl Scalar {
         )
     }
 
+    /// Computes the Legendre symbol of this element: 1 if it is a nonzero square,
+    /// -1 if it is not a square, and 0 if it is zero.
+    ///
+    /// This is Euler's criterion, $a^{(r - 1) / 2}$, which is constant time because
+    /// the exponent is fixed.
+    pub fn legendre(&self) -> i8 {
+        // (r - 1) / 2 = t * 2^(S - 1)
+        let s = self.pow_vartime(&[
+            0x7fff_ffff_8000_0000,
+            0xa9de_d201_7fff_2dff,
+            0x199c_ec04_04d0_ec02,
+            0x39f6_d3a9_94ce_bea4,
+        ]);
+
+        let mut res = i8::conditional_select(&0, &1, s.ct_eq(&Scalar::one()));
+        res.conditional_assign(&-1, s.ct_eq(&-Scalar::one()));
+        res
+    }
+
+    /// Returns whether this element is a quadratic residue, that is, a square. Zero
+    /// is a square.
+    ///
+    /// This is faster than checking whether [`Scalar::sqrt`] succeeds, and is also
+    /// constant time.
+    pub fn is_quadratic_residue(&self) -> Choice {
+        !self.legendre().ct_eq(&-1)
+    }
+
     /// Exponentiates `self` by `by`, where `by` is a
     /// little-endian order integer exponent.
     pub fn pow(&self, by: &[u64; 4]) -> Self {
@@ -413,20 +767,49 @@ impl Scalar {
     /// **This operation is variable time with respect
     /// to the exponent.** If the exponent is fixed,
     /// this operation is effectively constant time.
+    ///
+    /// The leading zeros of the exponent are skipped, so small exponents are fast.
     pub fn pow_vartime(&self, by: &[u64; 4]) -> Self {
         let mut res = Self::one();
+        let mut found_one = false;
         for e in by.iter().rev() {
             for i in (0..64).rev() {
-                res = res.square();
+                if found_one {
+                    res = res.square();
+                }
 
                 if ((*e >> i) & 1) == 1 {
                     res.mul_assign(self);
+                    found_one = true;
                 }
             }
         }
         res
     }
 
+    /// Exponentiates `self` by the integer `by`, as [`Scalar::pow_vartime`] does for
+    /// exponents of a single limb, such as the size $n$ of an evaluation domain in
+    /// $z^n - 1$.
+    ///
+    /// **This operation is variable time with respect to the exponent.** It takes
+    /// one squaring per bit of the exponent after the first and one multiplication
+    /// per set bit, so that $z^{2^k}$ is computed with $k$ squarings.
+    pub fn pow_u64(&self, by: u64) -> Self {
+        if by == 0 {
+            return Self::one();
+        }
+
+        let mut res = *self;
+        for i in (0..63 - by.leading_zeros()).rev() {
+            res = res.square();
+
+            if ((by >> i) & 1) == 1 {
+                res.mul_assign(self);
+            }
+        }
+        res
+    }
+
     /// Computes the multiplicative inverse of this element,
     /// failing if the element is zero.
     pub fn invert(&self) -> CtOption<Self> {
@@ -526,6 +909,59 @@ impl Scalar {
         CtOption::new(t0, !self.ct_eq(&Self::zero()))
     }
 
+    /// Computes the multiplicative inverse of this element with the binary extended
+    /// Euclidean algorithm, failing if the element is zero. This is several times
+    /// faster than [`Scalar::invert`], as the inversion benchmarks of `Scalar` in
+    /// `benches/groups.rs` show, for use on public values such as the indices of
+    /// Lagrange coefficients.
+    ///
+    /// **This operation is variable time with respect to the element.**
+    pub fn invert_vartime(&self) -> CtOption<Self> {
+        // The inverse of aR is a^-1 R^-1, which is brought back to the Montgomery
+        // form of a^-1 by a multiplication with R^3.
+        match crate::util::invert_vartime(&self.0, &MODULUS.0) {
+            Some(inverse) => CtOption::new(Scalar(inverse) * R3, Choice::from(1)),
+            None => CtOption::new(Scalar::zero(), Choice::from(0)),
+        }
+    }
+
+    /// Inverts every nonzero element of `values` with a single inversion, leaving
+    /// the zero elements unchanged, in constant time.
+    ///
+    /// The partial products are kept in an array of `N` elements on the stack, so
+    /// this needs no allocator.
+    pub fn batch_invert<const N: usize>(values: &mut [Scalar; N]) {
+        let mut products = [Scalar::one(); N];
+        let mut acc = Scalar::one();
+        for (value, product) in values.iter().zip(products.iter_mut()) {
+            *product = acc;
+            // Zero elements are skipped.
+            acc = Scalar::conditional_select(&(acc * value), &acc, value.ct_eq(&Scalar::zero()));
+        }
+
+        // The product is nonzero, since only nonzero elements were multiplied.
+        let mut inv = acc.invert().unwrap();
+        for (value, product) in values.iter_mut().zip(products.iter()).rev() {
+            let skip = value.ct_eq(&Scalar::zero());
+            let next = Scalar::conditional_select(&(inv * *value), &inv, skip);
+            *value = Scalar::conditional_select(&(inv * product), value, skip);
+            inv = next;
+        }
+    }
+
+    /// Inverts every nonzero element of `values`, leaving the zero elements
+    /// unchanged, in constant time and on up to `threads` threads.
+    ///
+    /// Each thread multiplies the elements of a contiguous chunk of `values`, the
+    /// products of the chunks are inverted with a single inversion, and each thread
+    /// then recovers the inverses of the elements of its own chunk.
+    #[cfg(feature = "parallel")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+    pub fn batch_invert_parallel(values: &mut [Scalar], threads: usize) {
+        let inverses = crate::threads::map_inverses(values, threads, |v| *v, |_, inv| inv);
+        values.copy_from_slice(&inverses);
+    }
+
     #[inline(always)]
     const fn montgomery_reduce(
         r0: u64,
@@ -576,6 +1012,18 @@ impl Scalar {
     /// Multiplies `rhs` by `self`, returning the result.
     #[inline]
     pub const fn mul(&self, rhs: &Self) -> Self {
+        Scalar::montgomery_reduce_wide(&self.mul_wide(rhs))
+    }
+
+    /// Returns the unreduced product of `self` and `rhs` as a 512-bit little-endian
+    /// integer.
+    ///
+    /// This is a low-level building block. Scalars are held in Montgomery form, as
+    /// $aR \bmod q$ with $R = 2^{256}$, and the result is the integer product of
+    /// these representations, $(aR \bmod q)(bR \bmod q) < q^2$, which
+    /// [`Scalar::montgomery_reduce_wide`] maps back to $a \cdot b$.
+    #[inline]
+    pub const fn mul_wide(&self, rhs: &Self) -> [u64; 8] {
         // Schoolbook multiplication
 
         let (r0, carry) = mac(0, self.0[0], rhs.0[0], 0);
@@ -598,7 +1046,44 @@ impl Scalar {
         let (r5, carry) = mac(r5, self.0[3], rhs.0[2], carry);
         let (r6, r7) = mac(r6, self.0[3], rhs.0[3], carry);
 
-        Scalar::montgomery_reduce(r0, r1, r2, r3, r4, r5, r6, r7)
+        [r0, r1, r2, r3, r4, r5, r6, r7]
+    }
+
+    /// Computes $T R^{-1} \bmod q$ for the 512-bit little-endian integer $T$, with
+    /// $R = 2^{256}$, as a `Scalar` in Montgomery form. In particular, this reduces
+    /// the output of [`Scalar::mul_wide`] to the product of the two scalars.
+    ///
+    /// This is a low-level building block, and it does not validate its input: the
+    /// result is only correct if $T < qR$, which holds for the sum of up to two
+    /// outputs of [`Scalar::mul_wide`], and is unspecified otherwise.
+    #[inline]
+    pub const fn montgomery_reduce_wide(limbs: &[u64; 8]) -> Self {
+        Scalar::montgomery_reduce(
+            limbs[0], limbs[1], limbs[2], limbs[3], limbs[4], limbs[5], limbs[6], limbs[7],
+        )
+    }
+
+    /// Computes $a \cdot b + c$ with a single modular reduction.
+    pub fn mul_add(&self, b: &Self, c: &Self) -> Self {
+        let mut acc = ScalarAccumulator::new();
+        acc.add_product(self, b);
+        acc.add(c);
+        acc.finish()
+    }
+
+    /// Computes the inner product $\sum_i a_i b_i$ of `a` and `b`, reducing
+    /// modulo $q$ only once. See [`ScalarAccumulator`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` and `b` do not have the same length.
+    pub fn dot(a: &[Self], b: &[Self]) -> Self {
+        assert_eq!(a.len(), b.len());
+        let mut acc = ScalarAccumulator::new();
+        for (a, b) in a.iter().zip(b.iter()) {
+            acc.add_product(a, b);
+        }
+        acc.finish()
     }
 
     /// Subtracts `rhs` from `self`, returning the result.
@@ -651,6 +1136,82 @@ impl Scalar {
     }
 }
 
+/// A sum of products of scalars that is reduced modulo $q$ only when it is
+/// finished.
+///
+/// Each product is accumulated as an unreduced 512-bit integer, which saves the
+/// Montgomery reduction that a multiplication would perform. The accumulator
+/// holds up to $2^{64}$ products.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ScalarAccumulator([u64; 9]);
+
+impl ScalarAccumulator {
+    /// Returns an empty accumulator, whose sum is zero.
+    pub fn new() -> Self {
+        ScalarAccumulator([0; 9])
+    }
+
+    /// Adds $a \cdot b$ to the sum.
+    pub fn add_product(&mut self, a: &Scalar, b: &Scalar) {
+        // The product of two values in Montgomery form is the product of the
+        // scalars multiplied by R^2.
+        let acc = &mut self.0;
+        for i in 0..4 {
+            let mut carry = 0;
+            for j in 0..4 {
+                let (v, c) = mac(acc[i + j], a.0[i], b.0[j], carry);
+                acc[i + j] = v;
+                carry = c;
+            }
+            for limb in acc[i + 4..].iter_mut() {
+                let (v, c) = adc(*limb, carry, 0);
+                *limb = v;
+                carry = c;
+            }
+        }
+    }
+
+    /// Adds $a$ to the sum.
+    pub fn add(&mut self, a: &Scalar) {
+        // Shift the value in Montgomery form by R to match the products.
+        let acc = &mut self.0;
+        let mut carry = 0;
+        for (limb, a) in acc[4..8].iter_mut().zip(a.0.iter()) {
+            let (v, c) = adc(*limb, *a, carry);
+            *limb = v;
+            carry = c;
+        }
+        let (v, _) = adc(acc[8], carry, 0);
+        acc[8] = v;
+    }
+
+    /// Returns the sum, reduced modulo $q$.
+    pub fn finish(&self) -> Scalar {
+        // Divide by R with a Montgomery reduction of all nine limbs, which leaves a
+        // 320-bit value in the upper five.
+        let mut t = self.0;
+        for i in 0..4 {
+            let k = t[i].wrapping_mul(INV);
+            let mut carry = 0;
+            for j in 0..4 {
+                let (v, c) = mac(t[i + j], k, MODULUS.0[j], carry);
+                t[i + j] = v;
+                carry = c;
+            }
+            for limb in t[i + 4..].iter_mut() {
+                let (v, c) = adc(*limb, carry, 0);
+                *limb = v;
+                carry = c;
+            }
+        }
+
+        // Reduce the lower four limbs by a Montgomery reduction followed by a
+        // multiplication by R^2, and the top limb, of weight R, by a conversion to
+        // Montgomery form.
+        Scalar::montgomery_reduce(t[4], t[5], t[6], t[7], 0, 0, 0, 0) * R2 + Scalar::from(t[8])
+    }
+}
+
 impl From<Scalar> for [u8; 32] {
     fn from(value: Scalar) -> [u8; 32] {
         value.to_bytes()
@@ -663,6 +1224,27 @@ impl<'a> From<&'a Scalar> for [u8; 32] {
     }
 }
 
+/// Decodes the canonical little-endian encoding of [`Scalar::to_bytes`], which must
+/// be exactly 32 bytes long.
+impl<'a> TryFrom<&'a [u8]> for Scalar {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Scalar, Error> {
+        let bytes = <&[u8; 32]>::try_from(bytes).map_err(|_| Error::InvalidLength)?;
+        Option::from(Scalar::from_bytes(bytes)).ok_or(Error::NonCanonicalField)
+    }
+}
+
+/// Parses the decimal representation of a scalar, as [`Scalar::from_str_radix`] does
+/// with a radix of 10.
+impl FromStr for Scalar {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Scalar, Error> {
+        Scalar::from_str_radix(s, 10)
+    }
+}
+
 impl Field for Scalar {
     fn random(mut rng: impl RngCore) -> Self {
         let mut buf = [0; 64];
@@ -785,6 +1367,10 @@ where
     }
 }
 
+/// The number of elements processed together by the batch conversions on
+/// [`Scalar`], as for the batch operations on `Fp`.
+const BATCH_LANES: usize = 4;
+
 impl<'a> From<&'a Scalar> for [u64; 4] {
     fn from(value: &'a Scalar) -> [u64; 4] {
         let res =
@@ -826,6 +1412,78 @@ fn test_debug() {
 }
 
 #[test]
+fn test_zeta() {
+    let z = Scalar::from(0xd201_0000_0001_0000);
+    assert_eq!(Scalar::ZETA, -z.square());
+    assert_ne!(Scalar::ZETA, Scalar::one());
+    assert_eq!(Scalar::ZETA.square() * Scalar::ZETA, Scalar::one());
+}
+
+#[test]
+fn test_display() {
+    let minus_one = "52435875175126190479447740508185965837690552500527637822603658699938581184512";
+    assert_eq!(format!("{}", Scalar::zero()), "0");
+    assert_eq!(format!("{}", Scalar::one()), "1");
+    assert_eq!(format!("{}", Scalar::from(1234567890u64)), "1234567890");
+    assert_eq!(format!("{}", -Scalar::one()), minus_one);
+    assert_eq!(format!("{:>6}", Scalar::from(42u64)), "    42");
+    assert_eq!(format!("{:+05}", Scalar::from(42u64)), "+0042");
+
+    assert_eq!(format!("{:x}", Scalar::zero()), "0");
+    assert_eq!(format!("{:#x}", Scalar::from(0xabcdu64)), "0xabcd");
+    assert_eq!(
+        format!("{:x}", -Scalar::one()),
+        "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000000"
+    );
+    // The most significant hexadecimal digit of R2 is not zero.
+    assert_eq!(format!("{:#x}", R2), format!("{:?}", R2));
+}
+
+#[test]
+fn test_from_str() {
+    let minus_one = "52435875175126190479447740508185965837690552500527637822603658699938581184512";
+    assert_eq!("0".parse::<Scalar>(), Ok(Scalar::zero()));
+    assert_eq!("+1".parse::<Scalar>(), Ok(Scalar::one()));
+    assert_eq!("0001234567890".parse(), Ok(Scalar::from(1234567890u64)));
+    assert_eq!(minus_one.parse(), Ok(-Scalar::one()));
+    assert_eq!(
+        Scalar::from_str_radix(
+            "73EDA753299D7D483339D80809A1D80553BDA402FFFE5BFEFFFFFFFF00000000",
+            16
+        ),
+        Ok(-Scalar::one())
+    );
+    assert_eq!(Scalar::from_str_radix("-1", 10), Err(Error::InvalidDigit));
+    assert_eq!(Scalar::from_str_radix("101", 2), Ok(Scalar::from(5u64)));
+    assert_eq!(Scalar::from_str_radix("zz", 36), Ok(Scalar::from(1295u64)));
+
+    for s in ["", "+", "12a", " 1", "0x1"] {
+        assert_eq!(s.parse::<Scalar>(), Err(Error::InvalidDigit), "{:?}", s);
+    }
+    // q, and an integer that does not fit in 256 bits.
+    let q = "52435875175126190479447740508185965837690552500527637822603658699938581184513";
+    assert_eq!(q.parse::<Scalar>(), Err(Error::NonCanonicalField));
+    assert_eq!(
+        format!("1{}", "0".repeat(80)).parse::<Scalar>(),
+        Err(Error::NonCanonicalField)
+    );
+
+    let mut tmp = R2;
+    for _ in 0..100 {
+        assert_eq!(format!("{}", tmp).parse(), Ok(tmp));
+        assert_eq!(Scalar::from_str_radix(&format!("{:x}", tmp), 16), Ok(tmp));
+        tmp = tmp.square() + R2;
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_from_str_radix_panics() {
+    let _ = Scalar::from_str_radix("1", 37);
+}
+
+#[test]
+#[allow(clippy::eq_op)]
 fn test_equality() {
     assert_eq!(Scalar::zero(), Scalar::zero());
     assert_eq!(Scalar::one(), Scalar::one());
@@ -870,6 +1528,240 @@ fn test_to_bytes() {
     );
 }
 
+#[test]
+fn test_bytes_be() {
+    let a = Scalar::from_raw([
+        0x0102_0304_0506_0708,
+        0x090a_0b0c_0d0e_0f10,
+        0x1112_1314_1516_1718,
+        0x191a_1b1c_1d1e_1f20,
+    ]);
+    let expected = [
+        0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f, 0x20, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+        0x18, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06,
+        0x07, 0x08,
+    ];
+    assert_eq!(a.to_bytes_be(), expected);
+    assert_eq!(Scalar::from_bytes_be(&expected).unwrap(), a);
+
+    let mut be = (-&Scalar::one()).to_bytes_be();
+    assert_eq!(Scalar::from_bytes_be(&be).unwrap(), -&Scalar::one());
+    be[31] += 1;
+    assert!(bool::from(Scalar::from_bytes_be(&be).is_none()));
+}
+
+#[test]
+fn test_dot() {
+    let a = (0..100)
+        .map(|i| Scalar::from(i as u64 + 1).invert().unwrap())
+        .collect::<std::vec::Vec<_>>();
+    let mut b = (0..100)
+        .map(|i| Scalar::from(i as u64 + 1) * -Scalar::one())
+        .collect::<std::vec::Vec<_>>();
+    assert_eq!(Scalar::dot(&a, &b), Scalar::from(100) * -Scalar::one());
+
+    // Products of the largest values in Montgomery form.
+    let max = Scalar([MODULUS.0[0] - 1, MODULUS.0[1], MODULUS.0[2], MODULUS.0[3]]);
+    b = vec![max; 100];
+    let naive = a.iter().fold(Scalar::zero(), |acc, a| acc + a * max);
+    assert_eq!(Scalar::dot(&a, &b), naive);
+    assert_eq!(Scalar::dot(&b, &b), Scalar::from(100) * max.square());
+    assert_eq!(Scalar::dot(&[], &[]), Scalar::zero());
+}
+
+#[test]
+fn test_mul_add() {
+    let a = Scalar::from_raw([1, 2, 3, 4]);
+    let b = -Scalar::from(7);
+    let c = -Scalar::one();
+    assert_eq!(a.mul_add(&b, &c), a * b + c);
+
+    let mut acc = ScalarAccumulator::new();
+    for _ in 0..10 {
+        acc.add(&c);
+        acc.add_product(&a, &b);
+    }
+    assert_eq!(acc.finish(), (a * b + c) * Scalar::from(10));
+}
+
+#[test]
+fn test_mul_wide() {
+    let a = Scalar::from_raw([1, 2, 3, 4]);
+    let b = -Scalar::from(7);
+    assert_eq!(Scalar::montgomery_reduce_wide(&a.mul_wide(&b)), a * b);
+    assert_eq!(
+        Scalar::montgomery_reduce_wide(&a.mul_wide(&Scalar::zero())),
+        Scalar::zero()
+    );
+
+    // The sum of two products of the largest values in Montgomery form.
+    let max = Scalar([MODULUS.0[0] - 1, MODULUS.0[1], MODULUS.0[2], MODULUS.0[3]]);
+    let wide = max.mul_wide(&max);
+    let mut sum = [0; 8];
+    let mut carry = 0;
+    for i in 0..8 {
+        let (v, c) = adc(wide[i], wide[i], carry);
+        sum[i] = v;
+        carry = c;
+    }
+    assert_eq!(carry, 0);
+    assert_eq!(Scalar::montgomery_reduce_wide(&sum), max.square().double());
+}
+
+#[test]
+fn test_from_raw_checked() {
+    assert_eq!(Scalar::from_raw_checked([0; 4]).unwrap(), Scalar::zero());
+    assert_eq!(
+        Scalar::from_raw_checked([1, 2, 3, 4]).unwrap(),
+        Scalar::from_raw([1, 2, 3, 4])
+    );
+
+    let limbs = [MODULUS.0[0] - 1, MODULUS.0[1], MODULUS.0[2], MODULUS.0[3]];
+    assert_eq!(Scalar::from_raw_checked(limbs).unwrap(), -Scalar::one());
+    assert!(bool::from(Scalar::from_raw_checked(MODULUS.0).is_none()));
+}
+
+#[test]
+fn test_batch_conversions() {
+    let limbs = (0..11)
+        .map(|i| [i, i * 3, 0xffff_ffff_ffff_ffff, i << 60])
+        .chain(core::iter::once(MODULUS.0))
+        .collect::<std::vec::Vec<_>>();
+    let mut scalars = vec![Scalar::zero(); limbs.len()];
+    Scalar::from_u64_limbs_batch(&limbs, &mut scalars);
+    for (s, l) in scalars.iter().zip(limbs.iter()) {
+        assert_eq!(*s, Scalar::from_raw(*l));
+    }
+    assert_eq!(scalars[11], Scalar::zero());
+
+    let mut canonical = vec![[0u64; 4]; scalars.len()];
+    Scalar::to_canonical_batch(&scalars, &mut canonical);
+    for (c, s) in canonical.iter().zip(scalars.iter()) {
+        assert_eq!(Scalar::from_raw_checked(*c).unwrap(), *s);
+    }
+    // Canonical inputs round-trip.
+    assert_eq!(canonical[0], limbs[0]);
+
+    Scalar::from_u64_limbs_batch(&[], &mut []);
+}
+
+#[test]
+#[should_panic]
+fn test_batch_conversions_length_mismatch() {
+    Scalar::from_u64_limbs_batch(&[[1, 0, 0, 0]], &mut [Scalar::zero(); 2]);
+}
+
+#[cfg(feature = "experimental")]
+#[test]
+fn test_derive_from_seed() {
+    use sha2::{Sha256, Sha512};
+
+    // Computed with the HKDF of the Python standard library.
+    assert_eq!(
+        Scalar::derive_from_seed::<Sha256>(b"test seed", b"TEST_LABEL"),
+        Scalar::from_raw([
+            0x31bd_bc62_71f9_502f,
+            0x1f6c_8684_9f79_0ef0,
+            0x5a09_14db_9fa9_a4cd,
+            0x1cd3_a025_0494_2648,
+        ])
+    );
+    assert_eq!(
+        Scalar::derive_from_seed::<Sha512>(b"test seed", b"TEST_LABEL"),
+        Scalar::from_raw([
+            0x6007_dee8_3eb1_a7a4,
+            0x8efb_b65c_d65e_fe40,
+            0x9758_0d32_49fa_e32b,
+            0x49b6_9e68_d125_70fd,
+        ])
+    );
+
+    let scalar = Scalar::derive_from_seed::<Sha256>(b"test seed", b"TEST_LABEL");
+    assert_ne!(
+        Scalar::derive_from_seed::<Sha256>(b"test seed", b"OTHER_LABEL"),
+        scalar
+    );
+    assert_ne!(
+        Scalar::derive_from_seed::<Sha256>(b"other seed", b"TEST_LABEL"),
+        scalar
+    );
+}
+
+#[cfg(feature = "tower")]
+#[test]
+fn test_from_fp_reduced() {
+    use crate::constants::{FP_MODULUS, SCALAR_MODULUS};
+
+    assert_eq!(Scalar::from_fp_reduced(&Fp::zero()), Scalar::zero());
+    assert_eq!(Scalar::from_fp_reduced(&Fp::one()), Scalar::one());
+
+    // Multiples of q reduce to zero, and p - 1 to the scalar of the same integer.
+    let mut q = [0u64; 6];
+    q[..4].copy_from_slice(&SCALAR_MODULUS);
+    assert_eq!(
+        Scalar::from_fp_reduced(&Fp::from_raw(q).unwrap()),
+        Scalar::zero()
+    );
+    let mut wide = [0u8; 64];
+    for (w, b) in wide.iter_mut().zip((-Fp::one()).to_bytes().iter().rev()) {
+        *w = *b;
+    }
+    assert_eq!(u64::from(wide[0]), (FP_MODULUS[0] - 1) & 0xff);
+    assert_eq!(
+        Scalar::from_fp_reduced(&-Fp::one()),
+        Scalar::from_bytes_wide(&wide)
+    );
+    let small = |n: u64| Fp::from_raw([n, 0, 0, 0, 0, 0]).unwrap();
+    assert_eq!(
+        Scalar::from_fp_reduced(&(Fp::from_raw(q).unwrap() * small(3) + small(5))),
+        Scalar::from(5)
+    );
+}
+
+#[cfg(feature = "groups")]
+#[test]
+fn test_wnaf() {
+    // The digits sum back to the canonical representation.
+    let scalars = [
+        Scalar::zero(),
+        Scalar::one(),
+        Scalar::from(0x1f),
+        -Scalar::one(),
+        Scalar::from_raw([u64::MAX, u64::MAX, u64::MAX, 0x0fff_ffff_ffff_ffff]),
+        Scalar::from_raw([
+            0x2b56_8297_a56d_a71c,
+            0xd8c3_9ecb_0ef3_75d1,
+            0x435c_38da_67bf_bf96,
+            0x1088_a050_26b6_59b2,
+        ]),
+    ];
+    for scalar in &scalars {
+        for w in 2..=8 {
+            let digits = scalar.wnaf(w);
+            let mut acc = Scalar::zero();
+            let mut last = None;
+            for (i, &d) in digits.iter().enumerate().rev() {
+                acc = acc.double();
+                match d.cmp(&0) {
+                    core::cmp::Ordering::Greater => acc += Scalar::from(d as u64),
+                    core::cmp::Ordering::Less => acc -= Scalar::from(-d as u64),
+                    core::cmp::Ordering::Equal => {}
+                }
+
+                if d != 0 {
+                    assert_eq!(d % 2, d.signum());
+                    assert!(i32::from(d).abs() < 1 << (w - 1));
+                    if let Some(j) = last {
+                        assert!(j - i >= w);
+                    }
+                    last = Some(i);
+                }
+            }
+            assert_eq!(acc, *scalar);
+        }
+    }
+}
+
 #[test]
 fn test_from_bytes() {
     assert_eq!(
@@ -1155,6 +2047,63 @@ fn test_inversion() {
     }
 }
 
+#[test]
+fn test_invert_vartime() {
+    assert!(bool::from(Scalar::zero().invert_vartime().is_none()));
+    assert_eq!(Scalar::one().invert_vartime().unwrap(), Scalar::one());
+    assert_eq!((-&Scalar::one()).invert_vartime().unwrap(), -&Scalar::one());
+
+    let mut tmp = R2;
+    for _ in 0..100 {
+        assert_eq!(tmp.invert_vartime().unwrap(), tmp.invert().unwrap());
+        tmp = tmp.square() + R2;
+    }
+}
+
+#[test]
+fn test_batch_invert() {
+    let mut values = [Scalar::zero(); 6];
+    for (i, value) in values.iter_mut().enumerate() {
+        *value = Scalar::from(i as u64 + 2);
+    }
+    values[2] = Scalar::zero();
+    let mut inverses = values;
+    Scalar::batch_invert(&mut inverses);
+    for (value, inverse) in values.iter().zip(inverses.iter()) {
+        assert_eq!(*inverse, value.invert().unwrap_or(Scalar::zero()));
+    }
+
+    Scalar::batch_invert::<0>(&mut []);
+    let mut zeros = [Scalar::zero(); 2];
+    Scalar::batch_invert(&mut zeros);
+    assert_eq!(zeros, [Scalar::zero(); 2]);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_batch_invert_parallel() {
+    let mut values = [Scalar::zero(); 13];
+    for (i, value) in values.iter_mut().enumerate() {
+        *value = Scalar::from(i as u64 + 2);
+    }
+    values[2] = Scalar::zero();
+    values[12] = Scalar::zero();
+    let mut expected = values;
+    for value in expected.iter_mut() {
+        *value = value.invert().unwrap_or(Scalar::zero());
+    }
+
+    for threads in [0, 1, 2, 5, 13, 20] {
+        let mut inverses = values;
+        Scalar::batch_invert_parallel(&mut inverses, threads);
+        assert_eq!(inverses, expected);
+    }
+    Scalar::batch_invert_parallel(&mut [], 3);
+    let mut zeros = [Scalar::zero(); 3];
+    Scalar::batch_invert_parallel(&mut zeros, 2);
+    assert_eq!(zeros, [Scalar::zero(); 3]);
+}
+
 #[test]
 fn test_invert_is_pow() {
     let q_minus_2 = [
@@ -1182,6 +2131,25 @@ fn test_invert_is_pow() {
     }
 }
 
+#[test]
+fn test_pow_u64() {
+    let a = Scalar::from_raw([
+        0x0123_4567_89ab_cdef,
+        0xfedc_ba98_7654_3210,
+        0x0f1e_2d3c_4b5a_6978,
+        0x0011_2233_4455_6677,
+    ]);
+    for by in [0, 1, 2, 3, 4, 5, 255, 256, 1 << 32, 0xdead_beef, u64::MAX] {
+        assert_eq!(a.pow_u64(by), a.pow(&[by, 0, 0, 0]));
+        assert_eq!(a.pow_vartime(&[by, 0, 0, 0]), a.pow(&[by, 0, 0, 0]));
+    }
+    assert_eq!(a.pow_u64(2), a.square());
+    assert_eq!(Scalar::zero().pow_u64(0), Scalar::one());
+    assert_eq!(Scalar::zero().pow_u64(7), Scalar::zero());
+    assert_eq!(a.pow_vartime(&[0; 4]), Scalar::one());
+    assert_eq!(a.pow_vartime(&[0, 0, 0, 1]), a.pow(&[0, 0, 0, 1]));
+}
+
 #[test]
 fn test_sqrt() {
     {
@@ -1210,6 +2178,26 @@ fn test_sqrt() {
     assert_eq!(49, none_count);
 }
 
+#[test]
+fn test_legendre() {
+    assert_eq!(Scalar::zero().legendre(), 0);
+    assert!(bool::from(Scalar::zero().is_quadratic_residue()));
+    assert_eq!(Scalar::one().legendre(), 1);
+    assert_eq!(GENERATOR.legendre(), -1);
+    assert_eq!(ROOT_OF_UNITY.legendre(), -1);
+    assert_eq!(ROOT_OF_UNITY.square().legendre(), 1);
+
+    let mut a = -Scalar::one();
+    for _ in 0..100 {
+        let legendre = a.legendre();
+        assert_eq!(legendre == 1, bool::from(a.sqrt().is_some()));
+        assert_eq!(legendre == 1, bool::from(a.is_quadratic_residue()));
+        assert_eq!((a.square() * GENERATOR).legendre(), -1);
+        assert_eq!((a * GENERATOR).legendre(), -legendre);
+        a -= Scalar::one();
+    }
+}
+
 #[test]
 fn test_from_raw() {
     assert_eq!(
@@ -0,0 +1,230 @@
+//! This module implements Schnorr signatures over $\mathbb{G}_1$.
+//!
+//! Keys are the same as for BLS signatures in this crate: a secret key
+//! $x \in \mathbb{F}_q$ and the public key $P = x \cdot g_1$. A signature of a message
+//! $m$ is a pair $(R, s)$ with $R = k \cdot g_1$ and $s = k + e x$, where the challenge
+//! $e$ is squeezed from a [`Transcript`] under [`CHALLENGE_DST`] that absorbs $R$, $P$
+//! and $m$. Verification does not require a pairing.
+//!
+//! Nonces are deterministic, in the spirit of [RFC 6979] and [RFC 8032]: $k$ is
+//! derived by hashing the secret key and the message, so signing does not need a
+//! source of randomness and never reuses a nonce for different messages.
+//!
+//! Hashing uses [`HashToField`] with the [`ExpandMessage`] variant `X` chosen by the
+//! caller. The domain separation tags assume that `X` is [`ExpandMsgXmd`]
+//! instantiated with SHA-256.
+//!
+//! [RFC 6979]: https://www.rfc-editor.org/rfc/rfc6979
+//! [RFC 8032]: https://www.rfc-editor.org/rfc/rfc8032
+//! [`ExpandMsgXmd`]: crate::hash_to_curve::ExpandMsgXmd
+
+use rand_core::RngCore;
+use subtle::{Choice, ConstantTimeEq};
+
+use crate::hash_to_curve::{ExpandMessage, HashToField};
+use crate::transcript::Transcript;
+use crate::{G1Affine, G1Projective, Scalar};
+
+use alloc::vec::Vec;
+
+/// The domain separation tag used to derive nonces.
+pub const NONCE_DST: &[u8] = b"SCHNORR_BLS12381G1_XMD:SHA-256_NONCE_";
+
+/// The domain separation tag used to derive challenges.
+pub const CHALLENGE_DST: &[u8] = b"SCHNORR_BLS12381G1_XMD:SHA-256_CHALLENGE_";
+
+/// A Schnorr signature $(R, s)$.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Signature {
+    r: G1Affine,
+    s: Scalar,
+}
+
+impl Signature {
+    /// Serializes this signature as the compressed encoding of $R$ followed by the
+    /// little-endian encoding of $s$.
+    pub fn to_bytes(&self) -> [u8; 80] {
+        let mut bytes = [0u8; 80];
+        bytes[..48].copy_from_slice(&self.r.to_compressed());
+        bytes[48..].copy_from_slice(&self.s.to_bytes());
+        bytes
+    }
+
+    /// Attempts to deserialize a signature produced by [`Signature::to_bytes`].
+    pub fn from_bytes(bytes: &[u8; 80]) -> Option<Self> {
+        let mut r = [0u8; 48];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&bytes[..48]);
+        s.copy_from_slice(&bytes[48..]);
+
+        let r = Option::from(G1Affine::from_compressed(&r))?;
+        let s = Option::from(Scalar::from_bytes(&s))?;
+        Some(Signature { r, s })
+    }
+}
+
+/// Hashes the concatenation of `parts` to a scalar under `dst`.
+fn hash_to_scalar<X: ExpandMessage>(parts: &[&[u8]], dst: &[u8]) -> Scalar {
+    let message = parts.concat();
+    let mut output = [Scalar::zero()];
+    Scalar::hash_to_field::<X>(&message, dst, &mut output);
+    output[0]
+}
+
+/// Computes the challenge for the nonce commitment `r`, public key `pk` and
+/// `message`.
+fn challenge<X: ExpandMessage>(r: &G1Affine, pk: &G1Affine, message: &[u8]) -> Scalar {
+    let mut transcript = Transcript::<X>::new(CHALLENGE_DST);
+    transcript.append_g1(b"R", r);
+    transcript.append_g1(b"P", pk);
+    transcript.append_message(b"m", message);
+    transcript.challenge_scalar(b"e")
+}
+
+/// Signs `message` with the secret key `sk`.
+pub fn sign<X: ExpandMessage>(sk: &Scalar, message: &[u8]) -> Signature {
+    let pk = G1Affine::from(G1Affine::generator() * sk);
+    let k = hash_to_scalar::<X>(&[&sk.to_bytes(), message], NONCE_DST);
+    let r = G1Affine::from(G1Affine::generator() * k);
+    let e = challenge::<X>(&r, &pk, message);
+
+    Signature { r, s: k + e * sk }
+}
+
+/// Checks that `signature` is a signature of `message` under `pk`.
+///
+/// Returns false if `pk` is the identity.
+pub fn verify<X: ExpandMessage>(pk: &G1Affine, message: &[u8], signature: &Signature) -> Choice {
+    let e = challenge::<X>(&signature.r, pk, message);
+    let lhs = G1Affine::generator() * signature.s;
+    let rhs = pk * e + signature.r;
+
+    lhs.ct_eq(&rhs) & !pk.is_identity()
+}
+
+/// Checks many independent `(public key, message, signature)` triples at once, which
+/// is faster than verifying each of them with [`verify`].
+///
+/// The triples are combined with random 128-bit coefficients drawn from `rng`, which
+/// must be cryptographically secure, into a single check. If that check fails, each
+/// triple is verified individually and the indices of the invalid ones are returned,
+/// in increasing order.
+pub fn batch_verify<X: ExpandMessage, M: AsRef<[u8]>, R: RngCore>(
+    items: &[(G1Affine, M, Signature)],
+    mut rng: R,
+) -> Result<(), Vec<usize>> {
+    let mut valid = Choice::from(1);
+    let mut acc = G1Projective::identity();
+    let mut s = Scalar::zero();
+
+    for (pk, message, signature) in items {
+        valid &= !pk.is_identity();
+
+        let z = Scalar::from_raw([rng.next_u64(), rng.next_u64(), 0, 0]);
+        let e = challenge::<X>(&signature.r, pk, message.as_ref());
+        acc += signature.r * z + pk * (z * e);
+        s += z * signature.s;
+    }
+
+    if bool::from(valid & (G1Affine::generator() * s).ct_eq(&acc)) {
+        return Ok(());
+    }
+
+    Err(items
+        .iter()
+        .enumerate()
+        .filter(|(_, (pk, message, signature))| {
+            !bool::from(verify::<X>(pk, message.as_ref(), signature))
+        })
+        .map(|(i, _)| i)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_to_curve::ExpandMsgXmd;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    type Xmd = ExpandMsgXmd<sha2::Sha256>;
+
+    #[test]
+    fn test_sign_verify() {
+        let sk = Scalar::from(0x0123_4567_89ab_cdef);
+        let pk = G1Affine::from(G1Affine::generator() * sk);
+        let message = b"schnorr over bls12-381";
+
+        let signature = sign::<Xmd>(&sk, message);
+        assert!(bool::from(verify::<Xmd>(&pk, message, &signature)));
+
+        // Nonces are deterministic, and differ between messages.
+        assert_eq!(sign::<Xmd>(&sk, message), signature);
+        assert_ne!(sign::<Xmd>(&sk, b"another message").r, signature.r);
+
+        assert!(!bool::from(verify::<Xmd>(
+            &pk,
+            b"another message",
+            &signature
+        )));
+        let other = G1Affine::from(G1Projective::from(pk) + G1Affine::generator());
+        assert!(!bool::from(verify::<Xmd>(&other, message, &signature)));
+
+        let tampered = Signature {
+            s: signature.s + Scalar::one(),
+            ..signature
+        };
+        assert!(!bool::from(verify::<Xmd>(&pk, message, &tampered)));
+
+        // Signatures under the zero key with a zero nonce would otherwise verify.
+        let forged = Signature {
+            r: G1Affine::identity(),
+            s: Scalar::zero(),
+        };
+        assert!(!bool::from(verify::<Xmd>(
+            &G1Affine::identity(),
+            message,
+            &forged
+        )));
+    }
+
+    #[test]
+    fn test_serialization() {
+        let signature = sign::<Xmd>(&Scalar::from(42), b"message");
+        let bytes = signature.to_bytes();
+        assert_eq!(Signature::from_bytes(&bytes), Some(signature));
+
+        let mut invalid = bytes;
+        invalid[79] = 0xff;
+        assert_eq!(Signature::from_bytes(&invalid), None);
+    }
+
+    #[test]
+    fn test_batch_verify() {
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        let mut items = (1..=5u64)
+            .map(|i| {
+                let sk = Scalar::from(i * 1000 + 7);
+                let message = [i as u8; 8];
+                (
+                    G1Affine::from(G1Affine::generator() * sk),
+                    message,
+                    sign::<Xmd>(&sk, &message),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(batch_verify::<Xmd, _, _>(&items, &mut rng), Ok(()));
+        assert_eq!(batch_verify::<Xmd, [u8; 8], _>(&[], &mut rng), Ok(()));
+
+        items[1].1[0] ^= 1;
+        items[3].2 = items[4].2;
+        assert_eq!(
+            batch_verify::<Xmd, _, _>(&items, &mut rng),
+            Err(alloc::vec![1, 3])
+        );
+    }
+}
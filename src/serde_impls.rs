@@ -0,0 +1,343 @@
+//! [`serde`] support for the field, scalar and group types of this crate, and for
+//! the keys of its BLS signatures.
+//!
+//! Every type is serialized with its canonical byte encoding: the compressed
+//! encoding for points and elements of $\mathbb{G}_T$, the little-endian encoding for
+//! [`Scalar`], the big-endian encodings for [`Fp`] and [`Fp2`], and the encodings of
+//! `draft-irtf-cfrg-bls-signature-05` for secret and public keys. Human-readable
+//! formats such as JSON or TOML receive these bytes as a `0x`-prefixed lowercase hex
+//! string, and binary formats such as bincode or CBOR receive them as raw bytes.
+//! Deserialization accepts the same representations, and rejects encodings that are
+//! not canonical, or points that are not in the prime-order subgroup.
+
+use core::convert::TryFrom;
+use core::fmt;
+
+use serde::de::{self, Deserializer, SeqAccess, Unexpected, Visitor};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+use crate::Scalar;
+
+#[cfg(feature = "tower")]
+use crate::{fp::Fp, fp2::Fp2};
+#[cfg(feature = "groups")]
+use crate::{G1Affine, G1Projective, G2Affine, G2Projective};
+
+#[cfg(feature = "pairings")]
+use crate::Gt;
+
+#[cfg(all(feature = "experimental", feature = "pairings", feature = "alloc"))]
+use crate::signature::{PublicKey, SecretKey, Signature};
+
+/// The length of the longest encoding, that of an element of $\mathbb{G}_T$.
+const MAX_LEN: usize = 288;
+
+/// Serializes `bytes` as a hex string or as raw bytes, depending on the format.
+fn serialize_bytes<S: Serializer, const N: usize>(
+    bytes: &[u8; N],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+
+        let mut buf = [0u8; 2 + 2 * MAX_LEN];
+        buf[..2].copy_from_slice(b"0x");
+        for (i, b) in bytes.iter().enumerate() {
+            buf[2 + 2 * i] = HEX[usize::from(b >> 4)];
+            buf[3 + 2 * i] = HEX[usize::from(b & 0x0f)];
+        }
+        // The buffer only contains ASCII characters.
+        serializer.serialize_str(core::str::from_utf8(&buf[..2 + 2 * N]).unwrap())
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+/// Deserializes the bytes written by [`serialize_bytes`].
+fn deserialize_bytes<'de, D: Deserializer<'de>, const N: usize>(
+    deserializer: D,
+) -> Result<[u8; N], D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(BytesVisitor::<N>)
+    } else {
+        deserializer.deserialize_bytes(BytesVisitor::<N>)
+    }
+}
+
+struct BytesVisitor<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for BytesVisitor<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} bytes, or a 0x-prefixed hex string encoding them", N)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        fn nibble(c: u8) -> Option<u8> {
+            match c {
+                b'0'..=b'9' => Some(c - b'0'),
+                b'a'..=b'f' => Some(c - b'a' + 10),
+                b'A'..=b'F' => Some(c - b'A' + 10),
+                _ => None,
+            }
+        }
+
+        let invalid = || E::invalid_value(Unexpected::Str(v), &self);
+        let hex = v.strip_prefix("0x").ok_or_else(invalid)?.as_bytes();
+        if hex.len() != 2 * N {
+            return Err(E::invalid_length(hex.len() / 2, &self));
+        }
+
+        let mut bytes = [0u8; N];
+        for (byte, pair) in bytes.iter_mut().zip(hex.chunks(2)) {
+            *byte = (nibble(pair[0]).ok_or_else(invalid)? << 4)
+                | nibble(pair[1]).ok_or_else(invalid)?;
+        }
+        Ok(bytes)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        <[u8; N]>::try_from(v).map_err(|_| E::invalid_length(v.len(), &self))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = [0u8; N];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        Ok(bytes)
+    }
+}
+
+macro_rules! impl_serde {
+    ($type:ty, $len:expr, $to_bytes:expr, $from_bytes:expr, $name:expr) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_bytes::<S, $len>(&$to_bytes(self), serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let bytes = deserialize_bytes::<D, $len>(deserializer)?;
+                Option::from($from_bytes(&bytes))
+                    .ok_or_else(|| de::Error::custom(concat!("invalid ", $name, " encoding")))
+            }
+        }
+    };
+}
+
+impl_serde!(
+    Scalar,
+    32,
+    |s: &Scalar| s.to_bytes(),
+    Scalar::from_bytes,
+    "scalar"
+);
+
+#[cfg(feature = "tower")]
+impl_serde!(
+    Fp,
+    48,
+    |f: &Fp| f.to_bytes(),
+    Fp::from_bytes,
+    "field element"
+);
+
+#[cfg(feature = "tower")]
+impl_serde!(
+    Fp2,
+    96,
+    |f: &Fp2| f.to_bytes(),
+    Fp2::from_bytes_unchecked,
+    "field element"
+);
+
+#[cfg(feature = "groups")]
+impl_serde!(
+    G1Affine,
+    48,
+    |p: &G1Affine| p.to_compressed(),
+    G1Affine::from_compressed,
+    "G1 point"
+);
+
+#[cfg(feature = "groups")]
+impl_serde!(
+    G1Projective,
+    48,
+    |p: &G1Projective| G1Affine::from(p).to_compressed(),
+    |bytes| G1Affine::from_compressed(bytes).map(G1Projective::from),
+    "G1 point"
+);
+
+#[cfg(feature = "groups")]
+impl_serde!(
+    G2Affine,
+    96,
+    |p: &G2Affine| p.to_compressed(),
+    G2Affine::from_compressed,
+    "G2 point"
+);
+
+#[cfg(feature = "groups")]
+impl_serde!(
+    G2Projective,
+    96,
+    |p: &G2Projective| G2Affine::from(p).to_compressed(),
+    |bytes| G2Affine::from_compressed(bytes).map(G2Projective::from),
+    "G2 point"
+);
+
+#[cfg(feature = "pairings")]
+impl_serde!(
+    Gt,
+    288,
+    |g: &Gt| g.to_compressed(),
+    Gt::from_compressed,
+    "Gt element"
+);
+
+#[cfg(all(feature = "experimental", feature = "pairings", feature = "alloc"))]
+impl_serde!(
+    SecretKey,
+    32,
+    SecretKey::to_bytes,
+    SecretKey::from_bytes,
+    "secret key"
+);
+
+#[cfg(all(feature = "experimental", feature = "pairings", feature = "alloc"))]
+impl_serde!(
+    PublicKey,
+    48,
+    PublicKey::to_bytes,
+    PublicKey::from_bytes,
+    "public key"
+);
+
+#[cfg(all(feature = "experimental", feature = "pairings", feature = "alloc"))]
+impl_serde!(
+    Signature,
+    96,
+    Signature::to_bytes,
+    Signature::from_bytes,
+    "signature"
+);
+
+#[cfg(all(test, feature = "pairings"))]
+mod tests {
+    use super::*;
+    use core::fmt::Debug;
+
+    fn round_trip<T>(value: &T, len: usize)
+    where
+        T: Serialize + for<'de> Deserialize<'de> + PartialEq + Debug,
+    {
+        let json = serde_json::to_string(value).unwrap();
+        assert_eq!(json.len(), 2 + 2 + 2 * len);
+        assert_eq!(&serde_json::from_str::<T>(&json).unwrap(), value);
+
+        let binary = bincode::serialize(value).unwrap();
+        assert_eq!(binary.len(), 8 + len);
+        assert_eq!(&bincode::deserialize::<T>(&binary).unwrap(), value);
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let s = Scalar::from_raw([1, 2, 3, 4]).invert().unwrap();
+        let p = G1Affine::generator() * s;
+        let q = G2Affine::generator() * s;
+
+        round_trip(&s, 32);
+        round_trip(&G1Affine::generator().x, 48);
+        round_trip(&G2Affine::generator().x, 96);
+        round_trip(&p, 48);
+        round_trip(&G1Affine::from(p), 48);
+        round_trip(&G1Affine::identity(), 48);
+        round_trip(&q, 96);
+        round_trip(&G2Affine::from(q), 96);
+        round_trip(&(Gt::generator() * s), 288);
+    }
+
+    #[test]
+    fn test_hex_encoding() {
+        assert_eq!(
+            serde_json::to_string(&G1Affine::generator()).unwrap(),
+            "\"0x97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Scalar::one()).unwrap(),
+            "\"0x0100000000000000000000000000000000000000000000000000000000000000\""
+        );
+        assert_eq!(
+            serde_json::from_str::<Scalar>(
+                "\"0x0100000000000000000000000000000000000000000000000000000000000000\""
+            )
+            .unwrap(),
+            Scalar::one()
+        );
+        assert_eq!(
+            serde_json::from_str::<Scalar>(
+                "\"0x0A00000000000000000000000000000000000000000000000000000000000000\""
+            )
+            .unwrap(),
+            Scalar::from(10)
+        );
+    }
+
+    #[test]
+    fn test_invalid_encodings() {
+        // Missing prefix, odd length, wrong length and invalid digits.
+        for json in [
+            "\"0100000000000000000000000000000000000000000000000000000000000000\"",
+            "\"0x010000000000000000000000000000000000000000000000000000000000000\"",
+            "\"0x01\"",
+            "\"0xg100000000000000000000000000000000000000000000000000000000000000\"",
+        ] {
+            assert!(serde_json::from_str::<Scalar>(json).is_err());
+        }
+
+        // Not canonical.
+        let json = "\"0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\"";
+        assert!(serde_json::from_str::<Scalar>(json).is_err());
+
+        // Not a point.
+        let mut bytes = G1Affine::generator().to_compressed();
+        bytes[47] ^= 1;
+        let binary = bincode::serialize(&G1Affine::generator()).unwrap();
+        let mut tampered = binary.clone();
+        let len = tampered.len();
+        tampered[len - 48..].copy_from_slice(&bytes);
+        assert!(bincode::deserialize::<G1Affine>(&tampered).is_err());
+        assert!(bincode::deserialize::<G1Affine>(&binary[..len - 1]).is_err());
+    }
+
+    #[cfg(all(feature = "experimental", feature = "alloc"))]
+    #[test]
+    fn test_keys() {
+        use subtle::ConstantTimeEq;
+
+        let sk = SecretKey::from_scalar(Scalar::from(0x1234_5678)).unwrap();
+        let json = serde_json::to_string(&sk).unwrap();
+        assert_eq!(
+            json,
+            "\"0x0000000000000000000000000000000000000000000000000000000012345678\""
+        );
+        let decoded = serde_json::from_str::<SecretKey>(&json).unwrap();
+        assert!(bool::from(decoded.ct_eq(&sk)));
+        assert!(serde_json::from_str::<SecretKey>(
+            "\"0x0000000000000000000000000000000000000000000000000000000000000000\""
+        )
+        .is_err());
+
+        round_trip(&sk.public_key(), 48);
+        round_trip(&Signature::from_point(&G2Affine::generator()).unwrap(), 96);
+        let identity = bincode::serialize(&G1Affine::identity()).unwrap();
+        assert!(bincode::deserialize::<PublicKey>(&identity).is_err());
+    }
+}
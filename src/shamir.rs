@@ -0,0 +1,300 @@
+//! This module implements Shamir secret sharing over $\mathbb{F}_q$, with Feldman
+//! commitments for verifying shares.
+//!
+//! A secret $s$ is shared among `n` participants with a random polynomial $f$ of
+//! degree `t - 1` such that $f(0) = s$: the share of the participant with the nonzero
+//! index $i$ is $f(i)$. Any `t` shares determine $f$ by Lagrange interpolation, and
+//! hence the secret, while fewer reveal nothing about it. Publishing
+//! [`Commitments`] to the coefficients of $f$ allows every participant to check its
+//! share without learning the others.
+//!
+//! This is the building block of the threshold signatures and distributed key
+//! generation in `signature::threshold` and `signature::dkg`.
+
+use ff::Field;
+use group::{prime::PrimeCurveAffine, Curve, Group};
+use rand_core::RngCore;
+use subtle::{Choice, ConstantTimeEq};
+
+use crate::{G1Affine, Scalar};
+
+use alloc::vec::Vec;
+
+/// The share $f(i)$ of a secret, held by the participant with a nonzero index $i$.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Share {
+    index: u32,
+    value: Scalar,
+}
+
+impl Share {
+    /// Constructs the share of participant `index`, for example after receiving it
+    /// from a dealer. Use [`Commitments::verify_share`] to check it.
+    pub fn new(index: u32, value: Scalar) -> Self {
+        Share { index, value }
+    }
+
+    /// Returns the index of the participant holding this share.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns the evaluation of the sharing polynomial at the participant's index.
+    pub fn value(&self) -> &Scalar {
+        &self.value
+    }
+}
+
+/// Feldman commitments $g^{a_j}$ to the coefficients $a_j$ of a sharing polynomial,
+/// where $g$ is the generator of `G`.
+///
+/// The commitment to the constant coefficient is the public key of the shared
+/// secret.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Commitments<G = G1Affine>(Vec<G>);
+
+impl<G> Commitments<G>
+where
+    G: PrimeCurveAffine<Scalar = Scalar> + ConstantTimeEq,
+{
+    /// Constructs commitments from the points committing to each coefficient, in
+    /// order of increasing degree. Returns `None` if `points` is empty.
+    pub fn new(points: Vec<G>) -> Option<Self> {
+        if points.is_empty() {
+            None
+        } else {
+            Some(Commitments(points))
+        }
+    }
+
+    /// Commits to the given coefficients, in order of increasing degree.
+    pub(crate) fn from_coefficients(coefficients: &[Scalar]) -> Self {
+        let points = coefficients
+            .iter()
+            .map(|a| G::generator() * a)
+            .collect::<Vec<_>>();
+        let mut affine = alloc::vec![G::identity(); points.len()];
+        G::Curve::batch_normalize(&points, &mut affine);
+        Commitments(affine)
+    }
+
+    /// Returns the points committing to each coefficient, in order of increasing
+    /// degree.
+    pub fn points(&self) -> &[G] {
+        &self.0
+    }
+
+    /// Returns the number of shares required to reconstruct the secret.
+    pub fn threshold(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the public key of the shared secret.
+    pub fn public_key(&self) -> G {
+        self.0[0]
+    }
+
+    /// Returns the public key corresponding to the share of participant `index`.
+    pub fn public_key_share(&self, index: u32) -> G {
+        let x = Scalar::from(u64::from(index));
+        self.0
+            .iter()
+            .rev()
+            .fold(G::Curve::identity(), |acc, c| acc * x + c)
+            .to_affine()
+    }
+
+    /// Checks that `share` is consistent with these commitments.
+    pub fn verify_share(&self, share: &Share) -> Choice {
+        let expected = self.public_key_share(share.index);
+        (G::generator() * share.value).to_affine().ct_eq(&expected)
+            & !Choice::from((share.index == 0) as u8)
+    }
+}
+
+/// Evaluates the polynomial with the given coefficients at every participant index
+/// in `1..=num_shares`.
+pub(crate) fn evaluate_shares(coefficients: &[Scalar], num_shares: u32) -> Vec<Share> {
+    (1..=num_shares)
+        .map(|index| {
+            let x = Scalar::from(u64::from(index));
+            let value = coefficients
+                .iter()
+                .rev()
+                .fold(Scalar::zero(), |acc, a| acc * x + a);
+            Share { index, value }
+        })
+        .collect()
+}
+
+/// Splits `secret` into `num_shares` shares, any `threshold` of which can
+/// reconstruct it, and returns them together with Feldman commitments in
+/// $\mathbb{G}_1$ to the sharing polynomial.
+///
+/// Shares are assigned the indices `1..=num_shares`.
+///
+/// # Panics
+///
+/// Panics if `threshold` is zero or greater than `num_shares`, or if `num_shares`
+/// does not fit in a `u32`.
+pub fn split(
+    secret: &Scalar,
+    threshold: usize,
+    num_shares: usize,
+    mut rng: impl RngCore,
+) -> (Commitments, Vec<Share>) {
+    assert!(threshold > 0 && threshold <= num_shares);
+    assert!(num_shares <= u32::MAX as usize);
+
+    let mut coefficients = Vec::with_capacity(threshold);
+    coefficients.push(*secret);
+    coefficients.extend((1..threshold).map(|_| Scalar::random(&mut rng)));
+
+    (
+        Commitments::from_coefficients(&coefficients),
+        evaluate_shares(&coefficients, num_shares as u32),
+    )
+}
+
+/// Returns the Lagrange coefficients at `x` for the given indices, which weight the
+/// evaluations of a polynomial at those indices into its evaluation at `x`.
+///
+/// Returns `None` if the indices are not distinct and nonzero. This function is
+/// variable time in the indices.
+pub fn lagrange_coefficients(indices: &[u32], x: &Scalar) -> Option<Vec<Scalar>> {
+    for (i, index) in indices.iter().enumerate() {
+        if *index == 0 || indices[..i].contains(index) {
+            return None;
+        }
+    }
+
+    let xs = indices
+        .iter()
+        .map(|&i| Scalar::from(u64::from(i)))
+        .collect::<Vec<_>>();
+
+    Some(
+        xs.iter()
+            .map(|x_i| {
+                let (num, den) = xs
+                    .iter()
+                    .filter(|x_j| x_j != &x_i)
+                    .fold((Scalar::one(), Scalar::one()), |(num, den), x_j| {
+                        (num * (x - x_j), den * (x_i - x_j))
+                    });
+                // The denominator is nonzero because the indices are distinct.
+                num * den.invert().unwrap()
+            })
+            .collect(),
+    )
+}
+
+/// Evaluates the sharing polynomial at `x`, given at least as many `shares` as its
+/// threshold.
+///
+/// Returns `None` if `shares` is empty or the shares do not have distinct, nonzero
+/// indices. With fewer shares than the threshold the result is unrelated to the
+/// sharing polynomial.
+pub fn interpolate(shares: &[Share], x: &Scalar) -> Option<Scalar> {
+    if shares.is_empty() {
+        return None;
+    }
+
+    let indices = shares.iter().map(|s| s.index).collect::<Vec<_>>();
+    let lambdas = lagrange_coefficients(&indices, x)?;
+    Some(
+        lambdas
+            .iter()
+            .zip(shares.iter())
+            .map(|(lambda, s)| lambda * s.value)
+            .sum(),
+    )
+}
+
+/// Reconstructs the secret from at least as many `shares` as the threshold it was
+/// split with.
+///
+/// This is [`interpolate`] at zero.
+pub fn reconstruct(shares: &[Share]) -> Option<Scalar> {
+    interpolate(shares, &Scalar::zero())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ])
+    }
+
+    #[test]
+    fn test_split_reconstruct() {
+        let secret = Scalar::from(0x0123_4567_89ab_cdef);
+        let (commitments, shares) = split(&secret, 3, 5, rng());
+
+        assert_eq!(commitments.threshold(), 3);
+        assert_eq!(
+            commitments.public_key(),
+            G1Affine::from(G1Affine::generator() * secret)
+        );
+        for share in &shares {
+            assert!(bool::from(commitments.verify_share(share)));
+        }
+
+        for subset in [&[0, 1, 2][..], &[4, 2, 0], &[1, 3, 4], &[0, 1, 2, 3, 4]] {
+            let subset = subset.iter().map(|&i| shares[i]).collect::<Vec<_>>();
+            assert_eq!(reconstruct(&subset), Some(secret));
+
+            // Interpolation at an index recovers the share of that participant.
+            assert_eq!(
+                interpolate(&subset, &Scalar::from(4)),
+                Some(shares[3].value)
+            );
+        }
+
+        assert_ne!(reconstruct(&shares[..2]), Some(secret));
+        assert_eq!(reconstruct(&[]), None);
+        assert_eq!(reconstruct(&[shares[0], shares[0], shares[1]]), None);
+        assert_eq!(reconstruct(&[Share::new(0, secret)]), None);
+    }
+
+    #[test]
+    fn test_lagrange_coefficients() {
+        // The coefficients at any point sum to one, as they interpolate the constant
+        // polynomial.
+        let lambdas = lagrange_coefficients(&[1, 3, 7], &Scalar::from(5)).unwrap();
+        assert_eq!(lambdas.iter().sum::<Scalar>(), Scalar::one());
+
+        let lambdas = lagrange_coefficients(&[2, 4], &Scalar::zero()).unwrap();
+        assert_eq!(lambdas, [Scalar::from(2), -Scalar::one()]);
+
+        assert_eq!(lagrange_coefficients(&[1, 2, 1], &Scalar::zero()), None);
+    }
+
+    #[test]
+    fn test_invalid_shares() {
+        let (commitments, shares) = split(&Scalar::from(99), 2, 3, rng());
+
+        let tampered = Share::new(shares[0].index, shares[0].value + Scalar::one());
+        assert!(!bool::from(commitments.verify_share(&tampered)));
+
+        let swapped = Share::new(shares[1].index, shares[0].value);
+        assert!(!bool::from(commitments.verify_share(&swapped)));
+
+        // Index zero would reveal the secret, so a share for it is rejected even
+        // though the secret is the evaluation of the polynomial at zero.
+        let zero = Share::new(0, Scalar::from(99));
+        assert!(!bool::from(commitments.verify_share(&zero)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_split_threshold_too_large() {
+        split(&Scalar::one(), 4, 3, rng());
+    }
+}
@@ -0,0 +1,246 @@
+//! The message augmentation scheme from
+//! [section 3.2 of `draft-irtf-cfrg-bls-signature-05`][aug].
+//!
+//! Every message is prefixed with the compressed public key of its signer before it
+//! is hashed, so that signatures under distinct keys are always over distinct
+//! messages. This prevents rogue-key attacks against [`aggregate_verify`] without
+//! requiring the messages to be distinct or the keys to come with proofs of
+//! possession, at the cost of hashing a longer message.
+//!
+//! [aug]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-3.2
+
+use rand_core::RngCore;
+use subtle::Choice;
+
+use super::{batch_verify_or_find_invalid, core_aggregate_verify, core_sign, core_verify};
+use crate::hash_to_curve::ExpandMessage;
+use crate::{G1Affine, G2Affine, Scalar};
+
+use alloc::vec::Vec;
+
+/// The domain separation tag used for signatures in the
+/// `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG_` ciphersuite.
+pub const DST: &[u8] = crate::hash_to_curve::BLS_SIG_AUG_DST;
+
+/// Returns the message $pk \parallel m$ that is actually signed for `message` under
+/// `pk`.
+fn augment(pk: &G1Affine, message: &[u8]) -> Vec<u8> {
+    let mut augmented = Vec::with_capacity(48 + message.len());
+    augmented.extend_from_slice(&pk.to_compressed());
+    augmented.extend_from_slice(message);
+    augmented
+}
+
+/// Signs `message` with `sk`, augmented with the public key of `sk`.
+///
+/// Implements `Sign` from
+/// [section 3.2.1 of `draft-irtf-cfrg-bls-signature-05`][sign].
+///
+/// [sign]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-3.2.1
+pub fn sign<X: ExpandMessage>(sk: &Scalar, message: &[u8]) -> G2Affine {
+    let pk = super::sk_to_pk(sk);
+    core_sign::<X>(sk, &augment(&pk, message), DST)
+}
+
+/// Checks that `signature` is a signature of `message` under `pk`.
+///
+/// Implements `Verify` from
+/// [section 3.2.2 of `draft-irtf-cfrg-bls-signature-05`][verify].
+///
+/// [verify]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-3.2.2
+pub fn verify<X: ExpandMessage>(pk: &G1Affine, message: &[u8], signature: &G2Affine) -> Choice {
+    core_verify::<X>(pk, &augment(pk, message), signature, DST)
+}
+
+/// Checks that `signature` is an aggregate of signatures of `messages[i]` under
+/// `public_keys[i]` for every `i`.
+///
+/// Implements `AggregateVerify` from
+/// [section 3.2.3 of `draft-irtf-cfrg-bls-signature-05`][aggregate_verify]. As in the
+/// proof-of-possession scheme, the messages do not need to be distinct. Returns false
+/// if the slices are empty or have different lengths.
+///
+/// [aggregate_verify]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-3.2.3
+pub fn aggregate_verify<X: ExpandMessage, M: AsRef<[u8]>>(
+    public_keys: &[G1Affine],
+    messages: &[M],
+    signature: &G2Affine,
+) -> Choice {
+    if public_keys.len() != messages.len() {
+        return Choice::from(0);
+    }
+
+    core_aggregate_verify::<X, _, _>(
+        public_keys
+            .iter()
+            .zip(messages.iter())
+            .map(|(pk, message)| (pk, augment(pk, message.as_ref()))),
+        signature,
+        DST,
+    )
+}
+
+/// Checks many independent `(public key, message, signature)` triples at once, which
+/// is considerably faster than verifying each of them with [`verify`].
+///
+/// The triples are combined with random coefficients drawn from `rng`, which must be
+/// cryptographically secure, and checked with a single multi-pairing. If that check
+/// fails, each triple is verified individually and the indices of the invalid ones
+/// are returned, in increasing order.
+pub fn batch_verify<X: ExpandMessage, M: AsRef<[u8]>, R: RngCore>(
+    items: &[(G1Affine, M, G2Affine)],
+    rng: R,
+) -> Result<(), Vec<usize>> {
+    let augmented = items
+        .iter()
+        .map(|(pk, message, signature)| (*pk, augment(pk, message.as_ref()), *signature))
+        .collect::<Vec<_>>();
+    batch_verify_or_find_invalid::<X, _, R>(&augmented, DST, rng)
+}
+
+/// Computes [`aggregate_verify`] with the augmented messages hashed and paired on up
+/// to `threads` threads.
+#[cfg(feature = "parallel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+pub fn aggregate_verify_parallel<X: ExpandMessage + 'static, M: AsRef<[u8]>>(
+    public_keys: &[G1Affine],
+    messages: &[M],
+    signature: &G2Affine,
+    threads: usize,
+) -> Choice {
+    if public_keys.len() != messages.len() {
+        return Choice::from(0);
+    }
+
+    let items = public_keys
+        .iter()
+        .zip(messages.iter())
+        .map(|(pk, message)| (*pk, augment(pk, message.as_ref())))
+        .collect::<Vec<_>>();
+    super::parallel::core_aggregate_verify::<X>(&items, signature, DST, threads)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_to_curve::ExpandMsgXmd;
+    use crate::signature::{aggregate, pop, sk_to_pk};
+    use crate::G1Projective;
+
+    type Xmd = ExpandMsgXmd<sha2::Sha256>;
+
+    #[test]
+    fn test_sign_verify() {
+        let sk = Scalar::from(0x0bad_cafe);
+        let pk = sk_to_pk(&sk);
+        let sig = sign::<Xmd>(&sk, b"message");
+
+        assert!(bool::from(verify::<Xmd>(&pk, b"message", &sig)));
+        assert!(!bool::from(verify::<Xmd>(&pk, b"massage", &sig)));
+        assert!(!bool::from(verify::<Xmd>(
+            &sk_to_pk(&Scalar::from(7)),
+            b"message",
+            &sig
+        )));
+
+        // The signature is over the augmented message, under the scheme's DST.
+        let mut augmented = pk.to_compressed().to_vec();
+        augmented.extend_from_slice(b"message");
+        assert_eq!(sig, core_sign::<Xmd>(&sk, &augmented, DST));
+        assert_ne!(sig, pop::sign::<Xmd>(&sk, b"message"));
+        assert!(!bool::from(pop::verify::<Xmd>(&pk, b"message", &sig)));
+    }
+
+    #[test]
+    fn test_aggregate_verify() {
+        let sks = [Scalar::from(31), Scalar::from(37), Scalar::from(41)];
+        let pks = sks.iter().map(sk_to_pk).collect::<Vec<_>>();
+        // Repeated messages are allowed in the message augmentation scheme.
+        let messages: [&[u8]; 3] = [b"same", b"same", b"other"];
+        let sig = aggregate(
+            sks.iter()
+                .zip(messages.iter())
+                .map(|(sk, msg)| sign::<Xmd>(sk, msg)),
+        );
+
+        assert!(bool::from(aggregate_verify::<Xmd, _>(
+            &pks, &messages, &sig
+        )));
+        assert!(!bool::from(aggregate_verify::<Xmd, _>(
+            &pks,
+            &messages[..2],
+            &sig
+        )));
+        assert!(!bool::from(aggregate_verify::<Xmd, &[u8]>(
+            &[],
+            &[],
+            &G2Affine::identity()
+        )));
+        let mut swapped = messages;
+        swapped.swap(1, 2);
+        assert!(!bool::from(aggregate_verify::<Xmd, _>(
+            &pks, &swapped, &sig
+        )));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_aggregate_verify_parallel() {
+        let sks = (1..=5u64).map(Scalar::from).collect::<Vec<_>>();
+        let pks = sks.iter().map(sk_to_pk).collect::<Vec<_>>();
+        let messages = [b"same"; 5];
+        let sig = aggregate(sks.iter().map(|sk| sign::<Xmd>(sk, b"same")));
+
+        for threads in [1, 2, 5] {
+            assert!(bool::from(aggregate_verify_parallel::<Xmd, _>(
+                &pks, &messages, &sig, threads
+            )));
+            assert!(!bool::from(aggregate_verify_parallel::<Xmd, _>(
+                &pks[1..],
+                &messages[1..],
+                &sig,
+                threads
+            )));
+        }
+    }
+
+    #[test]
+    fn test_rogue_key_rejected() {
+        // With the basic scheme, a rogue key pk' = g1^a - pk lets an attacker forge
+        // an aggregate signature of one message over {pk, pk'}. Here the two keys
+        // sign different augmented messages, so the sum of the keys is of no use.
+        let message = b"attestation";
+        let victim = sk_to_pk(&Scalar::from(23));
+        let a = Scalar::from(29);
+        let rogue = G1Affine::from(G1Projective::generator() * a - victim);
+        let forged = sign::<Xmd>(&a, message);
+
+        assert!(!bool::from(aggregate_verify::<Xmd, _>(
+            &[victim, rogue],
+            &[message, message],
+            &forged
+        )));
+    }
+
+    #[test]
+    fn test_batch_verify() {
+        use rand_core::SeedableRng;
+
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let mut items = [41u64, 43, 47]
+            .iter()
+            .map(|&i| {
+                let sk = Scalar::from(i);
+                let message = i.to_le_bytes();
+                (sk_to_pk(&sk), message, sign::<Xmd>(&sk, &message))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(batch_verify::<Xmd, _, _>(&items, &mut rng), Ok(()));
+
+        items[2].1 = [0; 8];
+        assert_eq!(batch_verify::<Xmd, _, _>(&items, &mut rng), Err(vec![2]));
+    }
+}
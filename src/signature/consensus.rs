@@ -0,0 +1,197 @@
+//! Encodings of public keys and signatures following the rules of the
+//! [Ethereum consensus specifications][bls].
+//!
+//! Public keys are 48-byte and signatures 96-byte compressed points, and decoding
+//! is strict: the length must be exact, the flag bits must be consistent, the
+//! coordinates canonical, and the point must be on the curve and in the prime-order
+//! subgroup. The public key at infinity is rejected, as `KeyValidate` requires,
+//! while the signature at infinity, [`G2_POINT_AT_INFINITY`], is a valid encoding.
+//!
+//! Public keys and signatures are public, so decoding reports why an encoding is
+//! invalid and is not constant time.
+//!
+//! [bls]: https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/beacon-chain.md#bls-signatures
+
+use super::PublicKey;
+use crate::{Error, G1Affine, G2Affine};
+
+/// The encoding of the signature at infinity, the identity of $\mathbb{G}_2$.
+pub const G2_POINT_AT_INFINITY: [u8; 96] = {
+    let mut bytes = [0; 96];
+    bytes[0] = 0xc0;
+    bytes
+};
+
+/// Decodes a 48-byte compressed public key, rejecting the point at infinity with
+/// [`Error::Identity`].
+pub fn decode_public_key(bytes: &[u8]) -> Result<PublicKey, Error> {
+    let point = G1Affine::try_from_compressed(bytes)?;
+    if bool::from(point.is_identity()) {
+        return Err(Error::Identity);
+    }
+
+    // The point is in the prime-order subgroup and is not the identity.
+    Ok(PublicKey::from_point(&point).unwrap())
+}
+
+/// Encodes `pk` as a 48-byte compressed point.
+pub fn encode_public_key(pk: &PublicKey) -> [u8; 48] {
+    pk.to_bytes()
+}
+
+/// Decodes a 96-byte compressed signature, which may be the signature at infinity.
+pub fn decode_signature(bytes: &[u8]) -> Result<G2Affine, Error> {
+    G2Affine::try_from_compressed(bytes)
+}
+
+/// Encodes `signature` as a 96-byte compressed point.
+pub fn encode_signature(signature: &G2Affine) -> [u8; 96] {
+    signature.to_compressed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    fn decode_hex(s: &str) -> Vec<u8> {
+        hex::decode(s).unwrap()
+    }
+
+    // The cases of the `deserialization_G1` and `deserialization_G2` tests of the
+    // consensus specifications.
+
+    #[test]
+    fn test_deserialization_g1() {
+        let correct = decode_hex(
+            "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20f\
+             d6e10c1b77654d067c0618f6e5a7f79a",
+        );
+        let pk = decode_public_key(&correct).unwrap();
+        assert_eq!(encode_public_key(&pk)[..], correct[..]);
+
+        let mut infinity = [0u8; 48];
+        infinity[0] = 0xc0;
+        assert_eq!(decode_public_key(&infinity), Err(Error::Identity));
+
+        let cases: [(&str, Error); 11] = [
+            // The infinity flag is set together with the sort flag.
+            ("e0", Error::BadFlags),
+            // x = 0 without the infinity flag, a point of order 3.
+            ("80", Error::WrongSubgroup),
+            // The correct point without the compression flag.
+            (
+                "2491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20f\
+                 d6e10c1b77654d067c0618f6e5a7f79a",
+                Error::BadFlags,
+            ),
+            // The infinity flag with a nonzero x-coordinate.
+            (
+                "c123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef\
+                 0123456789abcdef0123456789abcdef",
+                Error::BadFlags,
+            ),
+            // The infinity flag without the compression flag.
+            ("40", Error::BadFlags),
+            // x = p.
+            (
+                "9a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f624\
+                 1eabfffeb153ffffb9feffffffffaaab",
+                Error::NonCanonicalField,
+            ),
+            // x = p + 1.
+            (
+                "9a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f624\
+                 1eabfffeb153ffffb9feffffffffaaac",
+                Error::NonCanonicalField,
+            ),
+            // A point of the curve outside of the prime-order subgroup.
+            (
+                "8123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef\
+                 0123456789abcdef0123456789abcdef",
+                Error::WrongSubgroup,
+            ),
+            // An x-coordinate for which x^3 + 4 is not a square.
+            (
+                "8123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef\
+                 0123456789abcdef0123456789abcde0",
+                Error::NotOnCurve,
+            ),
+            // Too few bytes.
+            (
+                "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20f\
+                 d6e10c1b77654d067c0618f6e5a7f7",
+                Error::InvalidLength,
+            ),
+            // Too many bytes.
+            (
+                "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20f\
+                 d6e10c1b77654d067c0618f6e5a7f79a00",
+                Error::InvalidLength,
+            ),
+        ];
+        for (input, error) in cases.iter() {
+            let mut bytes = decode_hex(input);
+            if bytes.len() == 1 {
+                // Only the flags are given, and the x-coordinate is zero.
+                bytes.resize(48, 0);
+            }
+            assert_eq!(decode_public_key(&bytes), Err(*error), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_deserialization_g2() {
+        let correct = decode_hex(
+            "b6ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6\
+             076334f91e2366c96e9ab279fb5158090352ea1c5b0c9274504f4f0e7053af24\
+             802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55",
+        );
+        let signature = decode_signature(&correct).unwrap();
+        assert_eq!(encode_signature(&signature)[..], correct[..]);
+
+        assert_eq!(
+            decode_signature(&G2_POINT_AT_INFINITY),
+            Ok(G2Affine::identity())
+        );
+        assert_eq!(
+            encode_signature(&G2Affine::identity()),
+            G2_POINT_AT_INFINITY
+        );
+
+        let modulus = "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f624\
+                       1eabfffeb153ffffb9feffffffffaaab";
+        let mut cases: Vec<(Vec<u8>, Error)> = Vec::new();
+        // The infinity flag is set together with the sort flag.
+        let mut bytes = G2_POINT_AT_INFINITY;
+        bytes[0] |= 0x20;
+        cases.push((bytes.to_vec(), Error::BadFlags));
+        // The infinity flag with a nonzero x-coordinate.
+        let mut bytes = G2_POINT_AT_INFINITY;
+        bytes[95] = 1;
+        cases.push((bytes.to_vec(), Error::BadFlags));
+        // The correct point without the compression flag.
+        let mut bytes = correct.clone();
+        bytes[0] &= 0x7f;
+        cases.push((bytes, Error::BadFlags));
+        // Either component of x is p.
+        let mut bytes = decode_hex(modulus);
+        bytes[0] |= 0x80;
+        bytes.extend_from_slice(&[0; 48]);
+        cases.push((bytes, Error::NonCanonicalField));
+        let mut bytes = std::vec![0x80];
+        bytes.extend_from_slice(&[0; 47]);
+        bytes.extend_from_slice(&decode_hex(modulus));
+        cases.push((bytes, Error::NonCanonicalField));
+        // Too few and too many bytes.
+        cases.push((correct[..95].to_vec(), Error::InvalidLength));
+        let mut bytes = correct;
+        bytes.push(0);
+        cases.push((bytes, Error::InvalidLength));
+
+        for (input, error) in cases.iter() {
+            assert_eq!(decode_signature(input), Err(*error));
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! A verifier for a fixed public key and domain separation tag.
+
+use core::fmt;
+
+use group::Group;
+use subtle::Choice;
+
+use super::{PublicKey, Signature};
+use crate::hash_to_curve::{ExpandMessage, HashToCurveContext, ProcessDst};
+use crate::{multi_miller_loop, G1Affine, G2Prepared, G2Projective};
+
+/// Verifies signatures under a single public key and domain separation tag, for
+/// verifiers that check many signatures against the same keys.
+///
+/// The work that depends only on the key and the tag is done once, when the
+/// context is created: the public key is validated by [`PublicKey`] itself, the
+/// domain separation tag is processed, and the negated generator of
+/// $\mathbb{G}_1$ is computed. Public keys are elements of $\mathbb{G}_1$ and
+/// both arguments of the pairings in $\mathbb{G}_2$ depend on the message and the
+/// signature, so there are no line functions to cache; see
+/// [`PreparedVerifyingKey`](crate::groth16::PreparedVerifyingKey) for a verifier
+/// with fixed points in $\mathbb{G}_2$.
+///
+/// ```
+/// use bls12_381::hash_to_curve::ExpandMsgXmd;
+/// use bls12_381::signature::{pop, SecretKey, Signature, VerificationContext};
+/// use bls12_381::Scalar;
+///
+/// type Xmd = ExpandMsgXmd<sha2::Sha256>;
+///
+/// let sk = SecretKey::from_scalar(Scalar::from(42)).unwrap();
+/// let context = VerificationContext::<Xmd>::new(&sk.public_key(), pop::DST);
+/// let signature = pop::sign::<Xmd>(sk.as_scalar(), b"message");
+/// let signature = Signature::from_point(&signature).unwrap();
+/// assert!(bool::from(context.verify(b"message", &signature)));
+/// ```
+pub struct VerificationContext<X> {
+    pk: PublicKey,
+    neg_generator: G1Affine,
+    hasher: HashToCurveContext<G2Projective, X>,
+}
+
+impl<X> VerificationContext<X>
+where
+    X: ExpandMessage + ProcessDst,
+{
+    /// Creates a context for signatures under `pk` with the domain separation tag
+    /// `dst`, for instance [`pop::DST`](super::pop::DST).
+    pub fn new(pk: &PublicKey, dst: &[u8]) -> Self {
+        VerificationContext {
+            pk: *pk,
+            neg_generator: -G1Affine::generator(),
+            hasher: HashToCurveContext::new(dst),
+        }
+    }
+
+    /// Returns the public key of the context.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.pk
+    }
+
+    /// Returns the processed domain separation tag.
+    pub fn dst(&self) -> &[u8] {
+        self.hasher.dst()
+    }
+
+    /// Checks that `signature` is a signature of `message` under the public key and
+    /// domain separation tag of the context, as `CoreVerify` does.
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> Choice {
+        let q = G2Prepared::from(self.hasher.hash_affine(message));
+        let signature = G2Prepared::from(*signature.as_point());
+        multi_miller_loop(&[(self.pk.as_point(), &q), (&self.neg_generator, &signature)])
+            .final_exponentiation()
+            .is_identity()
+    }
+}
+
+impl<X> Clone for VerificationContext<X> {
+    fn clone(&self) -> Self {
+        VerificationContext {
+            pk: self.pk,
+            neg_generator: self.neg_generator,
+            hasher: self.hasher.clone(),
+        }
+    }
+}
+
+impl<X> fmt::Debug for VerificationContext<X> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VerificationContext")
+            .field("pk", &self.pk)
+            .field("hasher", &self.hasher)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_to_curve::ExpandMsgXmd;
+    use crate::signature::{aug, core_sign, pop, SecretKey};
+    use crate::{G2Affine, Scalar};
+
+    type Xmd = ExpandMsgXmd<sha2::Sha256>;
+
+    #[test]
+    fn test_verification_context() {
+        let sk = SecretKey::from_scalar(Scalar::from(7)).unwrap();
+        let pk = sk.public_key();
+        let context = VerificationContext::<Xmd>::new(&pk, pop::DST);
+        assert_eq!(context.public_key(), &pk);
+        assert_eq!(context.dst(), pop::DST);
+
+        for message in [&b""[..], b"message", &[0xff; 300]] {
+            let sig = pop::sign::<Xmd>(sk.as_scalar(), message);
+            let signature = Signature::from_point(&sig).unwrap();
+            assert!(bool::from(context.verify(message, &signature)));
+            assert!(!bool::from(context.verify(b"massage", &signature)));
+        }
+
+        // A signature under another key or another tag is rejected.
+        let other = SecretKey::from_scalar(Scalar::from(8)).unwrap();
+        let sig = pop::sign::<Xmd>(other.as_scalar(), b"message");
+        let signature = Signature::from_point(&sig).unwrap();
+        assert!(!bool::from(context.verify(b"message", &signature)));
+        let sig = core_sign::<Xmd>(sk.as_scalar(), b"message", aug::DST);
+        let signature = Signature::from_point(&sig).unwrap();
+        assert!(!bool::from(context.verify(b"message", &signature)));
+        assert!(!bool::from(context.verify(
+            b"message",
+            &Signature::from_point(&G2Affine::identity()).unwrap()
+        )));
+    }
+
+    #[test]
+    fn test_verification_context_long_dst() {
+        let dst = [0x42; 300];
+        let sk = SecretKey::from_scalar(Scalar::from(7)).unwrap();
+        let context = VerificationContext::<Xmd>::new(&sk.public_key(), &dst);
+        assert_eq!(context.dst().len(), 32);
+
+        let sig = core_sign::<Xmd>(sk.as_scalar(), b"message", &dst);
+        let signature = Signature::from_point(&sig).unwrap();
+        assert!(bool::from(context.verify(b"message", &signature)));
+        assert_eq!(context.clone().dst(), context.dst());
+    }
+}
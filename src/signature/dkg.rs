@@ -0,0 +1,313 @@
+//! Distributed key generation for threshold BLS signatures.
+//!
+//! This implements the joint-Feldman DKG of Pedersen, in which each of the `n`
+//! participants acts as a dealer of a random secret:
+//!
+//! 1. Every participant creates a [`Dealing`], broadcasts its [`Commitments`] and sends
+//!    each other participant its share over a private channel.
+//! 2. Every participant checks each share it receives with
+//!    [`Commitments::verify_share`], and broadcasts a [`Complaint`] against each
+//!    dealer whose share is invalid.
+//! 3. A dealer answers each complaint by broadcasting the share it sent to the
+//!    accuser, which everyone checks with [`resolve_complaint`]. Dealers that fail
+//!    to answer, or answer with an invalid share, are disqualified.
+//! 4. The participants combine the dealings from the qualified dealers with
+//!    [`combine_commitments`] and [`combine_shares`]. The resulting commitments
+//!    determine the group public key and every participant's public key share.
+//!
+//! Commitments can be computed in either $\mathbb{G}_1$ or $\mathbb{G}_2$. The
+//! resulting keys are compatible with [`threshold`](super::threshold) signing when
+//! the commitments are in $\mathbb{G}_1$.
+
+use rand_core::RngCore;
+use subtle::ConstantTimeEq;
+
+use super::threshold::{Commitments, SecretKeyShare};
+use crate::shamir::evaluate_shares;
+use crate::{G1Affine, Scalar};
+
+use alloc::vec::Vec;
+use ff::Field;
+use group::{prime::PrimeCurveAffine, Group};
+
+/// The secret-sharing of a random secret produced by one dealer.
+#[derive(Clone, Debug)]
+pub struct Dealing<G = G1Affine> {
+    commitments: Commitments<G>,
+    shares: Vec<SecretKeyShare>,
+}
+
+impl<G> Dealing<G>
+where
+    G: PrimeCurveAffine<Scalar = Scalar> + ConstantTimeEq,
+{
+    /// Deals a random secret to `num_participants` participants, any `threshold` of
+    /// which can reconstruct it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is zero or greater than `num_participants`, or if
+    /// `num_participants` does not fit in a `u32`.
+    pub fn new(threshold: usize, num_participants: usize, mut rng: impl RngCore) -> Self {
+        assert!(threshold > 0 && threshold <= num_participants);
+        assert!(num_participants <= u32::MAX as usize);
+
+        let coefficients = (0..threshold)
+            .map(|_| Scalar::random(&mut rng))
+            .collect::<Vec<_>>();
+
+        Dealing {
+            commitments: Commitments::from_coefficients(&coefficients),
+            shares: evaluate_shares(&coefficients, num_participants as u32),
+        }
+    }
+
+    /// Returns the commitments that the dealer broadcasts to all participants.
+    pub fn commitments(&self) -> &Commitments<G> {
+        &self.commitments
+    }
+
+    /// Returns the share that the dealer privately sends to participant `index`,
+    /// or `None` if there is no such participant.
+    pub fn share(&self, index: u32) -> Option<&SecretKeyShare> {
+        index
+            .checked_sub(1)
+            .and_then(|i| self.shares.get(i as usize))
+    }
+}
+
+/// A complaint by participant `accuser` that the share it received from participant
+/// `dealer` is not consistent with the dealer's commitments.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Complaint {
+    /// The index of the dealer being accused.
+    pub dealer: u32,
+    /// The index of the participant raising the complaint.
+    pub accuser: u32,
+}
+
+/// The outcome of a [`Complaint`] once the accused dealer has revealed the
+/// disputed share.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ComplaintResolution {
+    /// The revealed share is valid, so the dealer remains qualified and the accuser
+    /// uses the revealed share.
+    Rejected,
+    /// The dealer did not reveal a valid share for the accuser, and must be
+    /// excluded from the set of qualified dealers.
+    DealerDisqualified,
+}
+
+/// Checks the share that a dealer revealed in answer to `complaint`, given the
+/// dealer's broadcast `commitments`. Passing `None` indicates that the dealer did not
+/// answer.
+pub fn resolve_complaint<G>(
+    commitments: &Commitments<G>,
+    complaint: &Complaint,
+    revealed: Option<&SecretKeyShare>,
+) -> ComplaintResolution
+where
+    G: PrimeCurveAffine<Scalar = Scalar> + ConstantTimeEq,
+{
+    match revealed {
+        Some(share)
+            if share.index() == complaint.accuser
+                && bool::from(commitments.verify_share(share)) =>
+        {
+            ComplaintResolution::Rejected
+        }
+        _ => ComplaintResolution::DealerDisqualified,
+    }
+}
+
+/// Combines the commitments broadcast by every qualified dealer into commitments to
+/// the jointly generated polynomial.
+///
+/// The [`public_key`](Commitments::public_key) of the result is the group public key.
+/// Returns `None` if `commitments` is empty or the dealers used different thresholds.
+pub fn combine_commitments<G>(commitments: &[Commitments<G>]) -> Option<Commitments<G>>
+where
+    G: PrimeCurveAffine<Scalar = Scalar> + ConstantTimeEq,
+{
+    let threshold = commitments.first()?.threshold();
+    if commitments.iter().any(|c| c.threshold() != threshold) {
+        return None;
+    }
+
+    let points = (0..threshold)
+        .map(|j| {
+            commitments
+                .iter()
+                .fold(G::Curve::identity(), |acc, c| acc + c.points()[j])
+        })
+        .collect::<Vec<_>>();
+    let mut affine = alloc::vec![G::identity(); threshold];
+    <G::Curve as group::Curve>::batch_normalize(&points, &mut affine);
+
+    Commitments::new(affine)
+}
+
+/// Combines the shares that one participant received from every qualified dealer
+/// into its share of the group secret key.
+///
+/// Returns `None` if `shares` is empty or the shares are not all for the same
+/// participant.
+pub fn combine_shares(shares: &[SecretKeyShare]) -> Option<SecretKeyShare> {
+    let index = shares.first()?.index();
+    if shares.iter().any(|s| s.index() != index) {
+        return None;
+    }
+
+    Some(SecretKeyShare::new(
+        index,
+        shares.iter().map(|s| s.value()).sum(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_to_curve::ExpandMsgXmd;
+    use crate::signature::{pop, threshold};
+    use crate::G2Affine;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    type Xmd = ExpandMsgXmd<sha2::Sha256>;
+
+    fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ])
+    }
+
+    #[test]
+    fn test_dkg() {
+        const T: usize = 3;
+        const N: u32 = 4;
+        let mut rng = rng();
+
+        let dealings = (0..N)
+            .map(|_| Dealing::<G1Affine>::new(T, N as usize, &mut rng))
+            .collect::<Vec<_>>();
+
+        // Every share verifies against its dealer's commitments.
+        for dealing in &dealings {
+            for j in 1..=N {
+                let share = dealing.share(j).unwrap();
+                assert!(bool::from(dealing.commitments().verify_share(share)));
+            }
+            assert!(dealing.share(0).is_none());
+            assert!(dealing.share(N + 1).is_none());
+        }
+
+        let commitments = dealings
+            .iter()
+            .map(|d| d.commitments().clone())
+            .collect::<Vec<_>>();
+        let group = combine_commitments(&commitments).unwrap();
+        let shares = (1..=N)
+            .map(|j| {
+                let received = dealings
+                    .iter()
+                    .map(|d| *d.share(j).unwrap())
+                    .collect::<Vec<_>>();
+                combine_shares(&received).unwrap()
+            })
+            .collect::<Vec<_>>();
+        for share in &shares {
+            assert!(bool::from(group.verify_share(share)));
+        }
+
+        // The jointly generated key can be used for threshold signing.
+        let message = b"beacon round";
+        let partials = shares[1..]
+            .iter()
+            .map(|share| threshold::sign_partial::<Xmd>(share, message))
+            .collect::<Vec<_>>();
+        for partial in &partials {
+            assert!(bool::from(threshold::verify_partial::<Xmd>(
+                &group, partial, message
+            )));
+        }
+        let signature = threshold::combine(T, &partials).unwrap();
+        assert!(bool::from(pop::verify::<Xmd>(
+            &group.public_key(),
+            message,
+            &signature
+        )));
+    }
+
+    #[test]
+    fn test_dkg_g2() {
+        let mut rng = rng();
+        let dealings = (0..3)
+            .map(|_| Dealing::<G2Affine>::new(2, 3, &mut rng))
+            .collect::<Vec<_>>();
+        let commitments = dealings
+            .iter()
+            .map(|d| d.commitments().clone())
+            .collect::<Vec<_>>();
+        let group = combine_commitments(&commitments).unwrap();
+
+        for j in 1..=3 {
+            let received = dealings
+                .iter()
+                .map(|d| *d.share(j).unwrap())
+                .collect::<Vec<_>>();
+            let share = combine_shares(&received).unwrap();
+            assert!(bool::from(group.verify_share(&share)));
+            assert_eq!(
+                group.public_key_share(j),
+                G2Affine::from(G2Affine::generator() * share.value())
+            );
+        }
+    }
+
+    #[test]
+    fn test_complaints() {
+        let mut rng = rng();
+        let dealing = Dealing::<G1Affine>::new(2, 3, &mut rng);
+        let complaint = Complaint {
+            dealer: 1,
+            accuser: 2,
+        };
+
+        let honest = dealing.share(2).unwrap();
+        assert_eq!(
+            resolve_complaint(dealing.commitments(), &complaint, Some(honest)),
+            ComplaintResolution::Rejected
+        );
+
+        let tampered = SecretKeyShare::new(2, honest.value() + Scalar::one());
+        assert_eq!(
+            resolve_complaint(dealing.commitments(), &complaint, Some(&tampered)),
+            ComplaintResolution::DealerDisqualified
+        );
+
+        // A valid share for a different participant does not answer the complaint.
+        let other = dealing.share(3).unwrap();
+        assert_eq!(
+            resolve_complaint(dealing.commitments(), &complaint, Some(other)),
+            ComplaintResolution::DealerDisqualified
+        );
+
+        assert_eq!(
+            resolve_complaint(dealing.commitments(), &complaint, None),
+            ComplaintResolution::DealerDisqualified
+        );
+    }
+
+    #[test]
+    fn test_combine_mismatched() {
+        let mut rng = rng();
+        let a = Dealing::<G1Affine>::new(2, 3, &mut rng);
+        let b = Dealing::<G1Affine>::new(3, 3, &mut rng);
+        assert!(combine_commitments(&[a.commitments().clone(), b.commitments().clone()]).is_none());
+        assert!(combine_commitments::<G1Affine>(&[]).is_none());
+
+        assert!(combine_shares(&[*a.share(1).unwrap(), *b.share(2).unwrap()]).is_none());
+        assert!(combine_shares(&[]).is_none());
+    }
+}
@@ -0,0 +1,153 @@
+//! This module implements the derivation of a tree of secret keys from a seed, as
+//! specified for Ethereum validators by [EIP-2333].
+//!
+//! The master secret key is derived from a seed of at least 32 bytes with
+//! [`derive_master_sk`], and the child of a secret key at some index with
+//! [`derive_child_sk`]. Children are derived through Lamport one-time keys, so that
+//! a child key does not reveal its parent even to an adversary that can compute
+//! discrete logarithms.
+//! Together with the paths of [EIP-2334], such as `m/12381/3600/0/0/0`, this derives
+//! the keys of Ethereum validators.
+//!
+//! [EIP-2333]: https://eips.ethereum.org/EIPS/eip-2333
+//! [EIP-2334]: https://eips.ethereum.org/EIPS/eip-2334
+
+use digest::Digest;
+use sha2::Sha256;
+
+use crate::hmac::{hkdf_expand, hkdf_extract};
+use crate::Scalar;
+
+/// The salt of the key generation procedure.
+const KEYGEN_SALT: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
+
+/// The number of 32-byte chunks in a Lamport secret key.
+const LAMPORT_CHUNKS: usize = 255;
+
+/// Derives a nonzero secret key from the input keying material `ikm` and the
+/// `key_info`, as `HKDF_mod_r` of EIP-2333 and `KeyGen` of
+/// `draft-irtf-cfrg-bls-signature-05` do.
+pub(crate) fn hkdf_mod_r(ikm: &[u8], key_info: &[u8]) -> Scalar {
+    let mut salt = Sha256::digest(KEYGEN_SALT);
+    loop {
+        let prk = hkdf_extract::<Sha256>(&salt, &[ikm, &[0]]);
+        let mut okm = [0u8; 48];
+        hkdf_expand::<Sha256>(&prk, &[key_info, &48u16.to_be_bytes()], &mut okm);
+
+        // The output is a big-endian integer, reduced modulo q.
+        let mut wide = [0u8; 64];
+        for (w, o) in wide.iter_mut().zip(okm.iter().rev()) {
+            *w = *o;
+        }
+        let sk = Scalar::from_bytes_wide(&wide);
+        if sk != Scalar::zero() {
+            return sk;
+        }
+
+        salt = Sha256::digest(&salt);
+    }
+}
+
+/// Derives the master secret key from `seed`, as `derive_master_SK` of EIP-2333
+/// does.
+///
+/// Returns `None` if `seed` is shorter than 32 bytes.
+pub fn derive_master_sk(seed: &[u8]) -> Option<Scalar> {
+    if seed.len() < 32 {
+        return None;
+    }
+
+    Some(hkdf_mod_r(seed, &[]))
+}
+
+/// Derives the child of the secret key `parent` at `index`, as `derive_child_SK` of
+/// EIP-2333 does.
+pub fn derive_child_sk(parent: &Scalar, index: u32) -> Scalar {
+    hkdf_mod_r(&parent_sk_to_lamport_pk(parent, index), &[])
+}
+
+/// Computes the compressed Lamport public key from which the child of `parent` at
+/// `index` is derived, as `parent_SK_to_lamport_PK` of EIP-2333 does.
+fn parent_sk_to_lamport_pk(parent: &Scalar, index: u32) -> [u8; 32] {
+    let salt = index.to_be_bytes();
+    let ikm = parent.to_bytes_be();
+    let not_ikm = ikm.map(|b| !b);
+
+    // The Lamport secret keys derived from the parent and its complement, each
+    // hashed chunk by chunk into the Lamport public key.
+    let mut lamport_pk = Sha256::new();
+    let mut lamport_sk = [0u8; 32 * LAMPORT_CHUNKS];
+    for ikm in [ikm, not_ikm] {
+        let prk = hkdf_extract::<Sha256>(&salt, &[&ikm]);
+        hkdf_expand::<Sha256>(&prk, &[], &mut lamport_sk);
+        for chunk in lamport_sk.chunks_exact(32) {
+            lamport_pk.update(Sha256::digest(chunk));
+        }
+    }
+
+    let mut compressed = [0u8; 32];
+    compressed.copy_from_slice(&lamport_pk.finalize());
+    compressed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ff::PrimeField;
+
+    fn hex(s: &str) -> std::vec::Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_eip2333_vectors() {
+        // The test cases of EIP-2333.
+        let vectors = [
+            (
+                concat!(
+                    "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e5349553",
+                    "1f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+                ),
+                "6083874454709270928345386274498605044986640685124978867557563392430687146096",
+                0,
+                "20397789859736650942317412262472558107875392172444076792671091975210932703118",
+            ),
+            (
+                "3141592653589793238462643383279502884197169399375105820974944592",
+                "29757020647961307431480504535336562678282505419141012933316116377660817309383",
+                3141592653,
+                "25457201688850691947727629385191704516744796114925897962676248250929345014287",
+            ),
+            (
+                "0099ff991111002299dd7744ee3355bbdd8844115566cc55663355668888cc00",
+                "27580842291869792442942448775674722299803720648445448686099262467207037398656",
+                4294967295,
+                "29358610794459428860402234341874281240803786294062035874021252734817515685787",
+            ),
+            (
+                "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+                "19022158461524446591288038168518313374041767046816487870552872741050760015818",
+                42,
+                "31372231650479070279774297061823572166496564838472787488249775572789064611981",
+            ),
+        ];
+
+        for (seed, master_sk, index, child_sk) in vectors.iter() {
+            let master = derive_master_sk(&hex(seed)).unwrap();
+            assert_eq!(master, Scalar::from_str_vartime(master_sk).unwrap());
+            assert_eq!(
+                derive_child_sk(&master, *index),
+                Scalar::from_str_vartime(child_sk).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_short_seed() {
+        assert!(derive_master_sk(&[0x42; 31]).is_none());
+        assert!(derive_master_sk(&[0x42; 32]).is_some());
+    }
+}
@@ -0,0 +1,359 @@
+//! Secret keys, public keys and signatures.
+//!
+//! A [`SecretKey`] is a nonzero scalar, generated as specified in
+//! [section 2.3 of `draft-irtf-cfrg-bls-signature-05`][key_gen], a
+//! [`PublicKey`] is a point of $\mathbb{G}_1$ that passes
+//! [`key_validate`](super::key_validate), and a [`Signature`] is a point of
+//! $\mathbb{G}_2$. All are encoded as in the draft: secret keys as 32 big-endian
+//! bytes, and public keys and signatures as compressed points, which is also how the
+//! `serde` feature serializes them. Decoding rejects invalid keys and signatures, so
+//! holding one of these types means it is valid.
+//! With the `zeroize` feature, secret keys are zeroized when dropped.
+//!
+//! [key_gen]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-2.3
+
+use core::fmt;
+
+#[cfg(feature = "sha2")]
+use rand_core::RngCore;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use crate::{G1Affine, G2Affine, Scalar};
+
+/// A BLS secret key: a nonzero scalar.
+///
+/// Secret keys are derived from input keying material with `SecretKey::key_gen`,
+/// or drawn from a random number generator with `SecretKey::random`, rather than
+/// by reducing random bytes modulo $q$. Their [`Debug`](fmt::Debug) output does not
+/// include the key.
+#[derive(Clone)]
+pub struct SecretKey(Scalar);
+
+impl SecretKey {
+    /// Derives a secret key from the input keying material `ikm`, which must be at
+    /// least 32 bytes of secret randomness, and the optional `key_info`, which may
+    /// be empty.
+    ///
+    /// Implements `KeyGen` from
+    /// [section 2.3 of `draft-irtf-cfrg-bls-signature-05`][key_gen]: the key is
+    /// derived with HKDF-SHA-256 under a salt that is rehashed until the key is
+    /// nonzero. Returns `None` if `ikm` is shorter than 32 bytes.
+    ///
+    /// [key_gen]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-2.3
+    #[cfg(feature = "sha2")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sha2")))]
+    pub fn key_gen(ikm: &[u8], key_info: &[u8]) -> Option<Self> {
+        if ikm.len() < 32 {
+            return None;
+        }
+
+        Some(SecretKey(super::eip2333::hkdf_mod_r(ikm, key_info)))
+    }
+
+    /// Generates a secret key with [`SecretKey::key_gen`] from 32 bytes of input
+    /// keying material drawn from `rng`, which must be cryptographically secure.
+    #[cfg(feature = "sha2")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sha2")))]
+    pub fn random(mut rng: impl RngCore) -> Self {
+        let mut ikm = [0u8; 32];
+        rng.fill_bytes(&mut ikm);
+        SecretKey(super::eip2333::hkdf_mod_r(&ikm, &[]))
+    }
+
+    /// Wraps `scalar` as a secret key, if it is nonzero.
+    pub fn from_scalar(scalar: Scalar) -> CtOption<Self> {
+        CtOption::new(SecretKey(scalar), !scalar.ct_eq(&Scalar::zero()))
+    }
+
+    /// Decodes a secret key from its 32-byte big-endian encoding, returning `None` if
+    /// it is not the canonical encoding of a nonzero scalar.
+    pub fn from_bytes(bytes: &[u8; 32]) -> CtOption<Self> {
+        Scalar::from_bytes_be(bytes).and_then(SecretKey::from_scalar)
+    }
+
+    /// Encodes the secret key as 32 big-endian bytes.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes_be()
+    }
+
+    /// Returns the scalar of the secret key.
+    pub fn as_scalar(&self) -> &Scalar {
+        &self.0
+    }
+
+    /// Computes the public key of the secret key with [`sk_to_pk`](super::sk_to_pk).
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey(super::sk_to_pk(&self.0))
+    }
+}
+
+impl ConstantTimeEq for SecretKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey(..)")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+/// A BLS public key: a point of the prime-order subgroup $\mathbb{G}_1$ other than
+/// the identity.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PublicKey(G1Affine);
+
+impl PublicKey {
+    /// Wraps `point` as a public key, if it passes [`key_validate`](super::key_validate).
+    pub fn from_point(point: &G1Affine) -> CtOption<Self> {
+        CtOption::new(PublicKey(*point), super::key_validate(point))
+    }
+
+    /// Decodes a public key from its compressed encoding, returning `None` if it does
+    /// not encode a valid public key.
+    pub fn from_bytes(bytes: &[u8; 48]) -> CtOption<Self> {
+        G1Affine::from_compressed(bytes).and_then(|point| PublicKey::from_point(&point))
+    }
+
+    /// Encodes the public key as a compressed point.
+    pub fn to_bytes(&self) -> [u8; 48] {
+        self.0.to_compressed()
+    }
+
+    /// Returns the point of the public key.
+    pub fn as_point(&self) -> &G1Affine {
+        &self.0
+    }
+}
+
+impl ConstantTimeEq for PublicKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl ConditionallySelectable for PublicKey {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        PublicKey(G1Affine::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl From<PublicKey> for G1Affine {
+    fn from(pk: PublicKey) -> G1Affine {
+        pk.0
+    }
+}
+
+impl<'a> From<&'a PublicKey> for G1Affine {
+    fn from(pk: &'a PublicKey) -> G1Affine {
+        pk.0
+    }
+}
+
+/// A BLS signature: a point of the prime-order subgroup $\mathbb{G}_2$.
+///
+/// Unlike a public key, a signature may be the identity, which is the aggregate of
+/// no signatures.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Signature(G2Affine);
+
+impl Signature {
+    /// Wraps `point` as a signature, if it is a point of $\mathbb{G}_2$.
+    ///
+    /// This is how a signature computed outside of this crate, for instance by
+    /// signing a point that the caller hashed to the curve, is brought into it.
+    pub fn from_point(point: &G2Affine) -> CtOption<Self> {
+        CtOption::new(
+            Signature(*point),
+            point.is_on_curve() & point.is_torsion_free(),
+        )
+    }
+
+    /// Wraps `point`, which the caller knows to be a point of $\mathbb{G}_2$, as a
+    /// signature.
+    pub(crate) fn from_point_unchecked(point: G2Affine) -> Self {
+        Signature(point)
+    }
+
+    /// Decodes a signature from its compressed encoding, returning `None` if it does
+    /// not encode a point of $\mathbb{G}_2$.
+    pub fn from_bytes(bytes: &[u8; 96]) -> CtOption<Self> {
+        G2Affine::from_compressed(bytes).map(Signature)
+    }
+
+    /// Encodes the signature as a compressed point.
+    pub fn to_bytes(&self) -> [u8; 96] {
+        self.0.to_compressed()
+    }
+
+    /// Returns the point of the signature.
+    pub fn as_point(&self) -> &G2Affine {
+        &self.0
+    }
+}
+
+impl ConstantTimeEq for Signature {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl ConditionallySelectable for Signature {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Signature(G2Affine::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl From<Signature> for G2Affine {
+    fn from(signature: Signature) -> G2Affine {
+        signature.0
+    }
+}
+
+impl<'a> From<&'a Signature> for G2Affine {
+    fn from(signature: &'a Signature) -> G2Affine {
+        signature.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fp::Fp;
+
+    #[test]
+    fn test_from_scalar() {
+        assert!(bool::from(SecretKey::from_scalar(Scalar::zero()).is_none()));
+
+        let sk = SecretKey::from_scalar(Scalar::from(7)).unwrap();
+        assert_eq!(*sk.as_scalar(), Scalar::from(7));
+        assert_eq!(
+            *sk.public_key().as_point(),
+            super::super::sk_to_pk(&Scalar::from(7))
+        );
+        assert_eq!(std::format!("{:?}", sk), "SecretKey(..)");
+    }
+
+    #[test]
+    fn test_encodings() {
+        let sk = SecretKey::from_scalar(Scalar::from(0x1234_5678)).unwrap();
+        let bytes = sk.to_bytes();
+        assert_eq!(bytes[28..], [0x12, 0x34, 0x56, 0x78]);
+        assert!(bool::from(
+            SecretKey::from_bytes(&bytes).unwrap().ct_eq(&sk)
+        ));
+        assert!(bool::from(SecretKey::from_bytes(&[0; 32]).is_none()));
+        assert!(bool::from(SecretKey::from_bytes(&[0xff; 32]).is_none()));
+
+        let pk = sk.public_key();
+        assert_eq!(PublicKey::from_bytes(&pk.to_bytes()).unwrap(), pk);
+        assert_eq!(G1Affine::from(pk), *pk.as_point());
+
+        // The identity, and points outside of the prime-order subgroup, are rejected.
+        let identity = G1Affine::identity();
+        assert!(bool::from(PublicKey::from_point(&identity).is_none()));
+        assert!(bool::from(
+            PublicKey::from_bytes(&identity.to_compressed()).is_none()
+        ));
+        let torsion = G1Affine {
+            x: Fp::from_raw_unchecked([
+                0x0aba_f895_b97e_43c8,
+                0xba4c_6432_eb9b_61b0,
+                0x1250_6f52_adfe_307f,
+                0x7502_8c34_3933_6b72,
+                0x8474_4f05_b8e9_bd71,
+                0x113d_554f_b095_54f7,
+            ]),
+            y: Fp::from_raw_unchecked([
+                0x73e9_0e88_f5cf_01c0,
+                0x3700_7b65_dd31_97e2,
+                0x5cf9_a199_2f0d_7c78,
+                0x4f83_c10b_9eb3_330d,
+                0xf6a6_3f6f_07f6_0961,
+                0x0c53_b5b9_7e63_4df3,
+            ]),
+            infinity: Choice::from(0u8),
+        };
+        assert!(bool::from(torsion.is_on_curve()));
+        assert!(bool::from(
+            PublicKey::from_bytes(&torsion.to_compressed()).is_none()
+        ));
+        assert!(bool::from(PublicKey::from_point(&torsion).is_none()));
+    }
+
+    #[test]
+    fn test_signature_encodings() {
+        let sig = Signature::from_point(&G2Affine::generator()).unwrap();
+        assert_eq!(Signature::from_bytes(&sig.to_bytes()).unwrap(), sig);
+        assert_eq!(G2Affine::from(sig), G2Affine::generator());
+
+        // The identity is a valid signature.
+        let identity = G2Affine::identity();
+        assert!(bool::from(Signature::from_point(&identity).is_some()));
+        assert!(bool::from(
+            Signature::from_bytes(&identity.to_compressed()).is_some()
+        ));
+        assert!(bool::from(Signature::from_bytes(&[0; 96]).is_none()));
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize() {
+        use zeroize::Zeroize;
+
+        let mut sk = SecretKey::from_scalar(Scalar::from(7)).unwrap();
+        sk.zeroize();
+        assert_eq!(*sk.as_scalar(), Scalar::zero());
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn test_key_gen() {
+        use ff::PrimeField;
+        use rand_core::SeedableRng;
+
+        let ikm = (0..32).collect::<std::vec::Vec<u8>>();
+
+        // Computed with an independent implementation of section 2.3 of the draft.
+        assert_eq!(
+            *SecretKey::key_gen(&ikm, &[]).unwrap().as_scalar(),
+            Scalar::from_str_vartime(
+                "15926454122213904454507208963543799348312092371334784788973904405790746948694"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            *SecretKey::key_gen(&ikm, b"BLS12381G2 key info")
+                .unwrap()
+                .as_scalar(),
+            Scalar::from_str_vartime(
+                "19304431837216817780001811451634874934504935660618996875823250841898835607796"
+            )
+            .unwrap()
+        );
+        assert!(SecretKey::key_gen(&ikm[..31], &[]).is_none());
+
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let a = SecretKey::random(&mut rng);
+        let b = SecretKey::random(&mut rng);
+        assert!(!bool::from(a.ct_eq(&b)));
+    }
+}
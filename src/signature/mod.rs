@@ -0,0 +1,440 @@
+//! This module implements BLS signatures as specified in
+//! [`draft-irtf-cfrg-bls-signature-05`][bls-sig].
+//!
+//! Only the "minimal-pubkey-size" variant is provided: public keys are elements of
+//! $\mathbb{G}_1$ and signatures are elements of $\mathbb{G}_2$. Messages are hashed
+//! to $\mathbb{G}_2$ using [`HashToCurve`] with the [`ExpandMessage`] variant `X`
+//! chosen by the caller. The domain separation tags defined by each scheme assume
+//! that `X` is [`ExpandMsgXmd`] instantiated with SHA-256.
+//!
+//! [bls-sig]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05
+//! [`ExpandMsgXmd`]: crate::hash_to_curve::ExpandMsgXmd
+
+use core::borrow::Borrow;
+
+use group::Group;
+use rand_core::RngCore;
+use subtle::{Choice, CtOption};
+
+use crate::hash_to_curve::{ExpandMessage, HashToCurve};
+use crate::{
+    multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, Scalar,
+};
+
+use alloc::vec::Vec;
+
+pub mod aug;
+pub mod consensus;
+mod context;
+pub mod dkg;
+#[cfg(feature = "sha2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sha2")))]
+pub mod eip2333;
+pub mod keys;
+#[cfg(feature = "parallel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+pub mod parallel;
+pub mod pop;
+pub mod threshold;
+
+pub use context::VerificationContext;
+pub use keys::{PublicKey, SecretKey, Signature};
+
+/// Computes the public key corresponding to the secret key `sk`.
+///
+/// Implements [section 2.4 of `draft-irtf-cfrg-bls-signature-05`][sk_to_pk].
+///
+/// [sk_to_pk]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-2.4
+pub fn sk_to_pk(sk: &Scalar) -> G1Affine {
+    G1Affine::from(G1Affine::generator() * sk)
+}
+
+/// Checks that `pk` is a valid public key, i.e. that it is a point of the
+/// prime-order subgroup $\mathbb{G}_1$ other than the identity.
+///
+/// Implements [section 2.5 of `draft-irtf-cfrg-bls-signature-05`][key_validate].
+///
+/// [key_validate]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-2.5
+pub fn key_validate(pk: &G1Affine) -> Choice {
+    !pk.is_identity() & pk.is_on_curve() & pk.is_torsion_free()
+}
+
+/// Aggregates a collection of signatures into a single signature.
+///
+/// Implements [section 2.8 of `draft-irtf-cfrg-bls-signature-05`][aggregate].
+/// The caller is responsible for rejecting an empty collection, for which the
+/// specification defines no aggregate.
+///
+/// [aggregate]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-2.8
+pub fn aggregate<I>(signatures: I) -> G2Affine
+where
+    I: IntoIterator,
+    I::Item: Borrow<G2Affine>,
+{
+    G2Affine::from(
+        signatures
+            .into_iter()
+            .fold(G2Projective::identity(), |acc, sig| acc + sig.borrow()),
+    )
+}
+
+/// Signs `point` with `sk`, for protocols that hash their messages to $\mathbb{G}_2$
+/// themselves, for instance under a domain separation tag of their own, or that
+/// blind the point before it is signed.
+///
+/// Returns `None` if `point` is not in $\mathbb{G}_2$, which must be checked when it
+/// comes from another party, as in blind signing. The output of [`HashToCurve`] is
+/// always in $\mathbb{G}_2$.
+pub fn sign_point(sk: &SecretKey, point: &G2Affine) -> CtOption<Signature> {
+    let valid = point.is_on_curve() & point.is_torsion_free();
+    // The product of a point of the subgroup with a scalar stays in the subgroup.
+    let signature = Signature::from_point_unchecked(G2Affine::from(point * sk.as_scalar()));
+    CtOption::new(signature, valid)
+}
+
+/// Checks that `signature` is a signature of `point` under `pk`, as produced by
+/// [`sign_point`]. Returns false if `point` is not in $\mathbb{G}_2$.
+pub fn verify_point(pk: &PublicKey, point: &G2Affine, signature: &Signature) -> Choice {
+    let valid = point.is_on_curve() & point.is_torsion_free();
+    let point = G2Prepared::from(*point);
+    let signature = G2Prepared::from(*signature.as_point());
+    valid
+        & multi_miller_loop(&[
+            (pk.as_point(), &point),
+            (&-G1Affine::generator(), &signature),
+        ])
+        .final_exponentiation()
+        .is_identity()
+}
+
+/// Signs `message` with `sk`, hashing it to $\mathbb{G}_2$ under `dst`.
+///
+/// Implements `CoreSign` from [section 2.6 of `draft-irtf-cfrg-bls-signature-05`][core_sign].
+///
+/// [core_sign]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-2.6
+pub(crate) fn core_sign<X: ExpandMessage>(sk: &Scalar, message: &[u8], dst: &[u8]) -> G2Affine {
+    let q = <G2Projective as HashToCurve<X>>::hash_to_curve(message, dst);
+    G2Affine::from(q * sk)
+}
+
+/// Checks that `signature` is a signature of `message` under `pk`.
+///
+/// Implements `CoreVerify` from [section 2.7 of `draft-irtf-cfrg-bls-signature-05`][core_verify].
+///
+/// [core_verify]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-2.7
+pub(crate) fn core_verify<X: ExpandMessage>(
+    pk: &G1Affine,
+    message: &[u8],
+    signature: &G2Affine,
+    dst: &[u8],
+) -> Choice {
+    core_aggregate_verify::<X, _, _>(core::iter::once((pk, message)), signature, dst)
+}
+
+/// Checks that `signature` is an aggregate of signatures over each
+/// `(public key, message)` pair in `items`.
+///
+/// Implements `CoreAggregateVerify` from
+/// [section 2.9 of `draft-irtf-cfrg-bls-signature-05`][core_aggregate_verify].
+/// Returns false if `items` is empty.
+///
+/// [core_aggregate_verify]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-2.9
+pub(crate) fn core_aggregate_verify<'a, X, I, M>(
+    items: I,
+    signature: &G2Affine,
+    dst: &[u8],
+) -> Choice
+where
+    X: ExpandMessage,
+    I: IntoIterator<Item = (&'a G1Affine, M)>,
+    M: AsRef<[u8]>,
+{
+    let mut valid = signature.is_on_curve() & signature.is_torsion_free();
+
+    let mut g1 = Vec::new();
+    let mut hashes = Vec::new();
+    for (pk, message) in items {
+        valid &= key_validate(pk);
+        g1.push(*pk);
+        hashes.push(<G2Projective as HashToCurve<X>>::hash_to_curve(message, dst));
+    }
+    if g1.is_empty() {
+        return Choice::from(0);
+    }
+    g1.push(-G1Affine::generator());
+
+    // The hashes are converted to affine coordinates together.
+    let mut q = alloc::vec![G2Affine::identity(); hashes.len()];
+    G2Projective::batch_normalize(&hashes, &mut q);
+    let mut g2 = q.into_iter().map(G2Prepared::from).collect::<Vec<_>>();
+    g2.push(G2Prepared::from(*signature));
+
+    let terms = g1.iter().zip(g2.iter()).collect::<Vec<_>>();
+    valid
+        & multi_miller_loop(&terms)
+            .final_exponentiation()
+            .is_identity()
+}
+
+/// Checks every `(public key, message, signature)` triple in `items` at once.
+///
+/// Rather than checking $e(pk_i, H(m_i)) = e(g_1, \sigma_i)$ for each item, this
+/// checks $\prod_i e(r_i pk_i, H(m_i)) = e(g_1, \sum_i r_i \sigma_i)$ for random
+/// 128-bit $r_i$ drawn from `rng`, using a single multi-Miller loop and final
+/// exponentiation. If any item is invalid, the check fails except with negligible
+/// probability, provided that `rng` is cryptographically secure. Returns true if
+/// `items` is empty.
+pub(crate) fn core_batch_verify<X, M, R>(
+    items: &[(G1Affine, M, G2Affine)],
+    dst: &[u8],
+    mut rng: R,
+) -> Choice
+where
+    X: ExpandMessage,
+    M: AsRef<[u8]>,
+    R: RngCore,
+{
+    if items.is_empty() {
+        return Choice::from(1);
+    }
+
+    let mut valid = Choice::from(1);
+    let mut scaled_pks = Vec::with_capacity(items.len() + 1);
+    let mut g2_points = Vec::with_capacity(items.len() + 1);
+    let mut signature = G2Projective::identity();
+    for (pk, message, sig) in items {
+        valid &= key_validate(pk) & sig.is_on_curve() & sig.is_torsion_free();
+
+        let r = Scalar::from_raw([rng.next_u64(), rng.next_u64(), 0, 0]);
+        scaled_pks.push(pk * r);
+        g2_points.push(<G2Projective as HashToCurve<X>>::hash_to_curve(message, dst));
+        signature += sig * r;
+    }
+    scaled_pks.push(-G1Projective::generator());
+    g2_points.push(signature);
+
+    let mut g1 = alloc::vec![G1Affine::identity(); scaled_pks.len()];
+    G1Projective::batch_normalize(&scaled_pks, &mut g1);
+    let mut g2 = alloc::vec![G2Affine::identity(); g2_points.len()];
+    G2Projective::batch_normalize(&g2_points, &mut g2);
+    let g2 = g2.into_iter().map(G2Prepared::from).collect::<Vec<_>>();
+
+    let terms = g1.iter().zip(g2.iter()).collect::<Vec<_>>();
+    valid
+        & multi_miller_loop(&terms)
+            .final_exponentiation()
+            .is_identity()
+}
+
+/// Batch-verifies `items` with [`core_batch_verify`], and if that fails, verifies each
+/// item individually to find the invalid ones.
+///
+/// Returns the indices of the invalid items, in increasing order.
+pub(crate) fn batch_verify_or_find_invalid<X, M, R>(
+    items: &[(G1Affine, M, G2Affine)],
+    dst: &[u8],
+    rng: R,
+) -> Result<(), Vec<usize>>
+where
+    X: ExpandMessage,
+    M: AsRef<[u8]>,
+    R: RngCore,
+{
+    if bool::from(core_batch_verify::<X, M, R>(items, dst, rng)) {
+        return Ok(());
+    }
+
+    Err(items
+        .iter()
+        .enumerate()
+        .filter(|(_, (pk, message, sig))| {
+            !bool::from(core_verify::<X>(pk, message.as_ref(), sig, dst))
+        })
+        .map(|(i, _)| i)
+        .collect())
+}
+
+/// Sums a collection of public keys, returning `None` if it is empty.
+pub(crate) fn sum_public_keys<'a, I>(public_keys: I) -> Option<G1Affine>
+where
+    I: IntoIterator<Item = &'a G1Affine>,
+{
+    let mut public_keys = public_keys.into_iter();
+    let first = G1Projective::from(public_keys.next()?);
+    Some(G1Affine::from(public_keys.fold(first, |acc, pk| acc + pk)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_to_curve::ExpandMsgXmd;
+
+    const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+    type Xmd = ExpandMsgXmd<sha2::Sha256>;
+
+    #[test]
+    fn test_core_sign_verify() {
+        let sk = Scalar::from(0x1234_5678_9abc_def0);
+        let pk = sk_to_pk(&sk);
+        let sig = core_sign::<Xmd>(&sk, b"message", DST);
+
+        assert!(bool::from(core_verify::<Xmd>(&pk, b"message", &sig, DST)));
+        assert!(!bool::from(core_verify::<Xmd>(&pk, b"massage", &sig, DST)));
+        assert!(!bool::from(core_verify::<Xmd>(
+            &pk,
+            b"message",
+            &sig,
+            b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_"
+        )));
+        assert!(!bool::from(core_verify::<Xmd>(
+            &sk_to_pk(&Scalar::from(7)),
+            b"message",
+            &sig,
+            DST
+        )));
+    }
+
+    #[test]
+    fn test_sign_point() {
+        let sk = SecretKey::from_scalar(Scalar::from(0x5eed)).unwrap();
+        let pk = sk.public_key();
+        let point = G2Affine::from(<G2Projective as HashToCurve<Xmd>>::hash_to_curve(
+            b"message", DST,
+        ));
+
+        // Signing the hash of a message is signing the message.
+        let sig = sign_point(&sk, &point).unwrap();
+        assert_eq!(
+            *sig.as_point(),
+            core_sign::<Xmd>(sk.as_scalar(), b"message", DST)
+        );
+        assert!(bool::from(verify_point(&pk, &point, &sig)));
+        assert!(bool::from(core_verify::<Xmd>(
+            pk.as_point(),
+            b"message",
+            sig.as_point(),
+            DST
+        )));
+
+        assert!(!bool::from(verify_point(&pk, &G2Affine::generator(), &sig)));
+        let other = SecretKey::from_scalar(Scalar::from(7))
+            .unwrap()
+            .public_key();
+        assert!(!bool::from(verify_point(&other, &point, &sig)));
+
+        // Points outside of G2 are not signed.
+        let mut bytes = point.to_uncompressed();
+        bytes[191] ^= 1;
+        let off_curve = G2Affine::from_uncompressed_unchecked(&bytes).unwrap();
+        assert!(!bool::from(off_curve.is_on_curve()));
+        assert!(bool::from(sign_point(&sk, &off_curve).is_none()));
+
+        let mut x = [0u8; 96];
+        x[0] = 0x80;
+        let not_torsion_free = (1..=u8::MAX)
+            .find_map(|i| {
+                x[95] = i;
+                Option::<G2Affine>::from(G2Affine::from_compressed_unchecked(&x))
+            })
+            .unwrap();
+        assert!(!bool::from(not_torsion_free.is_torsion_free()));
+        assert!(bool::from(sign_point(&sk, &not_torsion_free).is_none()));
+    }
+
+    #[test]
+    fn test_key_validate() {
+        assert!(bool::from(key_validate(&G1Affine::generator())));
+        assert!(!bool::from(key_validate(&G1Affine::identity())));
+
+        // The identity public key must never verify, even against the
+        // identity signature that every message has under it.
+        let sig = G2Affine::identity();
+        assert!(!bool::from(core_verify::<Xmd>(
+            &G1Affine::identity(),
+            b"message",
+            &sig,
+            DST
+        )));
+    }
+
+    #[test]
+    fn test_core_aggregate_verify() {
+        let sks = [Scalar::from(3), Scalar::from(5), Scalar::from(7)];
+        let pks = sks.iter().map(sk_to_pk).collect::<Vec<_>>();
+        let messages: [&[u8]; 3] = [b"first", b"second", b"third"];
+        let sig = aggregate(
+            sks.iter()
+                .zip(messages.iter())
+                .map(|(sk, msg)| core_sign::<Xmd>(sk, msg, DST)),
+        );
+
+        assert!(bool::from(core_aggregate_verify::<Xmd, _, _>(
+            pks.iter().zip(messages.iter()),
+            &sig,
+            DST
+        )));
+        assert!(!bool::from(core_aggregate_verify::<Xmd, _, _>(
+            pks.iter().zip(messages.iter().rev()),
+            &sig,
+            DST
+        )));
+        assert!(!bool::from(core_aggregate_verify::<Xmd, _, &[u8]>(
+            core::iter::empty(),
+            &G2Affine::identity(),
+            DST
+        )));
+    }
+
+    #[test]
+    fn test_core_batch_verify() {
+        use rand_core::SeedableRng;
+
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        let mut items = (1..=4u64)
+            .map(|i| {
+                let sk = Scalar::from(i * 1000 + 7);
+                let message = [i as u8; 8];
+                (sk_to_pk(&sk), message, core_sign::<Xmd>(&sk, &message, DST))
+            })
+            .collect::<Vec<_>>();
+
+        assert!(bool::from(core_batch_verify::<Xmd, _, _>(
+            &items, DST, &mut rng
+        )));
+        assert_eq!(
+            batch_verify_or_find_invalid::<Xmd, _, _>(&items, DST, &mut rng),
+            Ok(())
+        );
+        assert!(bool::from(core_batch_verify::<Xmd, [u8; 8], _>(
+            &[],
+            DST,
+            &mut rng
+        )));
+
+        // Swapping two signatures keeps the sum of signatures the same, but the
+        // random coefficients still catch it.
+        let (a, b) = (items[1].2, items[3].2);
+        items[1].2 = b;
+        items[3].2 = a;
+        assert!(!bool::from(core_batch_verify::<Xmd, _, _>(
+            &items, DST, &mut rng
+        )));
+        assert_eq!(
+            batch_verify_or_find_invalid::<Xmd, _, _>(&items, DST, &mut rng),
+            Err(vec![1, 3])
+        );
+
+        items[1].2 = a;
+        items[3].2 = b;
+        items[2].0 = G1Affine::identity();
+        assert_eq!(
+            batch_verify_or_find_invalid::<Xmd, _, _>(&items, DST, &mut rng),
+            Err(vec![2])
+        );
+    }
+}
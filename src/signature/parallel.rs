@@ -0,0 +1,182 @@
+//! Multi-threaded aggregation and aggregate verification, for workloads with
+//! thousands of public keys or signatures, such as the attestations of a slot of the
+//! Ethereum beacon chain.
+//!
+//! Every function takes the number of threads to use, which is capped by the number
+//! of items. The items are split into that many contiguous chunks, each of which is
+//! copied to and processed by a thread of its own, and the partial results of the
+//! threads are then combined: sums of points for aggregation, and the products of
+//! the Miller loops of each chunk for verification, which share a single final
+//! exponentiation.
+//!
+//! The threads are those of the standard library rather than a `rayon` thread
+//! pool, which requires a newer compiler than this crate supports: every call
+//! spawns one thread per chunk and copies the chunk to it, so small inputs are
+//! faster with the serial functions. Each thread sums its chunk in order, and the
+//! partial sums, of which there are as many as threads, are added serially rather
+//! than in a tree.
+//!
+//! The aggregate verification of each scheme is computed with
+//! [`pop::aggregate_verify_parallel`](super::pop::aggregate_verify_parallel) and
+//! [`aug::aggregate_verify_parallel`](super::aug::aggregate_verify_parallel).
+
+use group::Group;
+use subtle::Choice;
+
+use crate::hash_to_curve::{ExpandMessage, HashToCurve};
+use crate::{
+    multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, MillerLoopResult,
+};
+
+use alloc::vec::Vec;
+
+/// Splits `items` into at most `threads` contiguous chunks, and returns the results
+/// of `f` on each chunk, in order, computed on a thread of its own.
+fn map_chunks<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Clone + Send + 'static,
+    R: Send + 'static,
+    F: Fn(Vec<T>) -> R + Clone + Send + 'static,
+{
+    let chunk_size = crate::threads::chunk_size(items.len(), threads);
+    crate::threads::map_chunks(items, chunk_size, move |_, chunk| f(chunk))
+}
+
+/// Sums `public_keys` on up to `threads` threads, returning `None` if there are
+/// none.
+pub fn aggregate_public_keys(public_keys: &[G1Affine], threads: usize) -> Option<G1Affine> {
+    let sums = map_chunks(public_keys, threads, |chunk| {
+        chunk
+            .iter()
+            .fold(G1Projective::identity(), |acc, pk| acc + pk)
+    });
+    if sums.is_empty() {
+        return None;
+    }
+
+    Some(G1Affine::from(sums.iter().sum::<G1Projective>()))
+}
+
+/// Aggregates `signatures` as [`super::aggregate`] does, on up to `threads` threads.
+pub fn aggregate(signatures: &[G2Affine], threads: usize) -> G2Affine {
+    let sums = map_chunks(signatures, threads, |chunk| super::aggregate(&chunk));
+    super::aggregate(&sums)
+}
+
+/// Computes `CoreAggregateVerify` as [`super::core_aggregate_verify`] does, with
+/// the `(public key, message)` pairs of `items` hashed and paired on up to `threads`
+/// threads. Returns false if `items` is empty.
+pub(crate) fn core_aggregate_verify<X: ExpandMessage + 'static>(
+    items: &[(G1Affine, Vec<u8>)],
+    signature: &G2Affine,
+    dst: &[u8],
+    threads: usize,
+) -> Choice {
+    if items.is_empty() {
+        return Choice::from(0);
+    }
+
+    let dst = dst.to_vec();
+    let partials = map_chunks(items, threads, move |chunk| {
+        miller_loop_of_hashes::<X>(&chunk, &dst)
+    });
+
+    let mut valid = signature.is_on_curve() & signature.is_torsion_free();
+    let mut f = multi_miller_loop(&[(&-G1Affine::generator(), &G2Prepared::from(*signature))]);
+    for (partial_valid, partial) in partials {
+        valid &= partial_valid;
+        f += partial;
+    }
+
+    valid & f.final_exponentiation().is_identity()
+}
+
+/// Validates the public keys of `items`, and computes the product of the Miller
+/// loops of each public key with the hash of its message.
+fn miller_loop_of_hashes<X: ExpandMessage>(
+    items: &[(G1Affine, Vec<u8>)],
+    dst: &[u8],
+) -> (Choice, MillerLoopResult) {
+    let mut valid = Choice::from(1);
+    let mut hashes = Vec::with_capacity(items.len());
+    for (pk, message) in items {
+        valid &= super::key_validate(pk);
+        hashes.push(<G2Projective as HashToCurve<X>>::hash_to_curve(message, dst));
+    }
+
+    let mut q = alloc::vec![G2Affine::identity(); hashes.len()];
+    G2Projective::batch_normalize(&hashes, &mut q);
+    let q = q.into_iter().map(G2Prepared::from).collect::<Vec<_>>();
+
+    let terms = items
+        .iter()
+        .map(|(pk, _)| pk)
+        .zip(q.iter())
+        .collect::<Vec<_>>();
+    (valid, multi_miller_loop(&terms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_to_curve::ExpandMsgXmd;
+    use crate::signature::{pop, sk_to_pk};
+    use crate::Scalar;
+
+    type Xmd = ExpandMsgXmd<sha2::Sha256>;
+
+    #[test]
+    fn test_aggregate() {
+        let sks = (1..=10u64).map(Scalar::from).collect::<Vec<_>>();
+        let pks = sks.iter().map(sk_to_pk).collect::<Vec<_>>();
+        let sigs = sks
+            .iter()
+            .map(|sk| pop::sign::<Xmd>(sk, b"message"))
+            .collect::<Vec<_>>();
+
+        for threads in [0, 1, 3, 10, 16] {
+            assert_eq!(
+                aggregate_public_keys(&pks, threads),
+                super::super::sum_public_keys(&pks)
+            );
+            assert_eq!(aggregate(&sigs, threads), super::super::aggregate(&sigs));
+        }
+        assert_eq!(aggregate_public_keys(&[], 4), None);
+        assert_eq!(aggregate(&[], 4), G2Affine::identity());
+    }
+
+    #[test]
+    fn test_core_aggregate_verify() {
+        let dst = pop::DST;
+        let items = (1..=5u64)
+            .map(|i| (sk_to_pk(&Scalar::from(i)), i.to_le_bytes().to_vec()))
+            .collect::<Vec<_>>();
+        let sig = super::super::aggregate(
+            (1..=5u64)
+                .map(|i| super::super::core_sign::<Xmd>(&Scalar::from(i), &i.to_le_bytes(), dst)),
+        );
+
+        for threads in [1, 2, 5, 8] {
+            assert!(bool::from(core_aggregate_verify::<Xmd>(
+                &items, &sig, dst, threads
+            )));
+        }
+
+        let mut invalid = items.clone();
+        invalid[3].1 = b"other".to_vec();
+        assert!(!bool::from(core_aggregate_verify::<Xmd>(
+            &invalid, &sig, dst, 2
+        )));
+        let mut invalid = items;
+        invalid[1].0 = G1Affine::identity();
+        assert!(!bool::from(core_aggregate_verify::<Xmd>(
+            &invalid, &sig, dst, 2
+        )));
+        assert!(!bool::from(core_aggregate_verify::<Xmd>(
+            &[],
+            &G2Affine::identity(),
+            dst,
+            2
+        )));
+    }
+}
@@ -0,0 +1,391 @@
+//! The proof-of-possession scheme from
+//! [section 3.3 of `draft-irtf-cfrg-bls-signature-05`][pop].
+//!
+//! Every signer publishes a proof that it knows the secret key for its public key.
+//! Once that proof has been checked, the public key is wrapped in a
+//! [`ProvenPublicKey`], and only such keys are accepted by
+//! [`fast_aggregate_verify`]. This makes rogue-key attacks against fast aggregate
+//! verification impossible to express with this API.
+//!
+//! [pop]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-3.3
+
+use rand_core::RngCore;
+use subtle::Choice;
+
+use super::{
+    batch_verify_or_find_invalid, core_aggregate_verify, core_sign, core_verify, sum_public_keys,
+};
+use crate::hash_to_curve::ExpandMessage;
+use crate::{G1Affine, G2Affine, Scalar};
+
+use alloc::vec::Vec;
+
+/// The domain separation tag used for signatures in the
+/// `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_` ciphersuite.
+pub const DST: &[u8] = crate::hash_to_curve::BLS_SIG_POP_DST;
+
+/// The domain separation tag used for proofs of possession in the
+/// `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_` ciphersuite.
+pub const POP_DST: &[u8] = crate::hash_to_curve::BLS_POP_PROOF_DST;
+
+/// Signs `message` with `sk`.
+pub fn sign<X: ExpandMessage>(sk: &Scalar, message: &[u8]) -> G2Affine {
+    core_sign::<X>(sk, message, DST)
+}
+
+/// Checks that `signature` is a signature of `message` under `pk`.
+pub fn verify<X: ExpandMessage>(pk: &G1Affine, message: &[u8], signature: &G2Affine) -> Choice {
+    core_verify::<X>(pk, message, signature, DST)
+}
+
+/// Checks that `signature` is an aggregate of signatures of `messages[i]` under
+/// `public_keys[i]` for every `i`.
+///
+/// Unlike the basic scheme, the messages do not need to be distinct. Returns false if
+/// the slices are empty or have different lengths.
+pub fn aggregate_verify<X: ExpandMessage, M: AsRef<[u8]>>(
+    public_keys: &[G1Affine],
+    messages: &[M],
+    signature: &G2Affine,
+) -> Choice {
+    if public_keys.len() != messages.len() {
+        return Choice::from(0);
+    }
+
+    core_aggregate_verify::<X, _, _>(public_keys.iter().zip(messages.iter()), signature, DST)
+}
+
+/// Checks many independent `(public key, message, signature)` triples at once, which
+/// is considerably faster than verifying each of them with [`verify`].
+///
+/// The triples are combined with random coefficients drawn from `rng`, which must be
+/// cryptographically secure, and checked with a single multi-pairing. If that check
+/// fails, each triple is verified individually and the indices of the invalid ones
+/// are returned, in increasing order.
+pub fn batch_verify<X: ExpandMessage, M: AsRef<[u8]>, R: RngCore>(
+    items: &[(G1Affine, M, G2Affine)],
+    rng: R,
+) -> Result<(), Vec<usize>> {
+    batch_verify_or_find_invalid::<X, M, R>(items, DST, rng)
+}
+
+/// Produces a proof that the holder of `sk` knows the secret key for its public key.
+///
+/// Implements `PopProve` from
+/// [section 3.3.2 of `draft-irtf-cfrg-bls-signature-05`][pop_prove].
+///
+/// [pop_prove]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-3.3.2
+pub fn pop_prove<X: ExpandMessage>(sk: &Scalar) -> G2Affine {
+    let pk = super::sk_to_pk(sk);
+    core_sign::<X>(sk, &pk.to_compressed(), POP_DST)
+}
+
+/// Checks that `proof` is a valid proof of possession for `pk`.
+///
+/// Implements `PopVerify` from
+/// [section 3.3.3 of `draft-irtf-cfrg-bls-signature-05`][pop_verify].
+///
+/// [pop_verify]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-3.3.3
+pub fn pop_verify<X: ExpandMessage>(pk: &G1Affine, proof: &G2Affine) -> Choice {
+    core_verify::<X>(pk, &pk.to_compressed(), proof, POP_DST)
+}
+
+/// A public key whose proof of possession has been verified.
+///
+/// This can only be constructed with [`ProvenPublicKey::from_proof`], and is the only
+/// kind of public key accepted by [`fast_aggregate_verify`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ProvenPublicKey(G1Affine);
+
+impl ProvenPublicKey {
+    /// Verifies `proof` against `pk`, returning the proven public key if it is valid.
+    ///
+    /// The key and its proof are public, so this returns an `Option` rather than a
+    /// `CtOption`, whose value could be used even when the proof is invalid.
+    pub fn from_proof<X: ExpandMessage>(pk: &G1Affine, proof: &G2Affine) -> Option<Self> {
+        if bool::from(pop_verify::<X>(pk, proof)) {
+            Some(ProvenPublicKey(*pk))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the underlying public key.
+    pub fn public_key(&self) -> &G1Affine {
+        &self.0
+    }
+}
+
+impl From<ProvenPublicKey> for G1Affine {
+    fn from(pk: ProvenPublicKey) -> G1Affine {
+        pk.0
+    }
+}
+
+impl<'a> From<&'a ProvenPublicKey> for G1Affine {
+    fn from(pk: &'a ProvenPublicKey) -> G1Affine {
+        pk.0
+    }
+}
+
+/// Checks that `signature` is an aggregate of signatures of the same `message`
+/// under every key in `public_keys`.
+///
+/// Implements `FastAggregateVerify` from
+/// [section 3.3.4 of `draft-irtf-cfrg-bls-signature-05`][fast_aggregate_verify].
+/// Returns false if `public_keys` is empty.
+///
+/// [fast_aggregate_verify]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-3.3.4
+pub fn fast_aggregate_verify<X: ExpandMessage>(
+    public_keys: &[ProvenPublicKey],
+    message: &[u8],
+    signature: &G2Affine,
+) -> Choice {
+    match sum_public_keys(public_keys.iter().map(|pk| &pk.0)) {
+        Some(pk) => core_verify::<X>(&pk, message, signature, DST),
+        None => Choice::from(0),
+    }
+}
+
+/// Computes [`aggregate_verify`] with the messages hashed and paired on up to
+/// `threads` threads.
+#[cfg(feature = "parallel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+pub fn aggregate_verify_parallel<X: ExpandMessage + 'static, M: AsRef<[u8]>>(
+    public_keys: &[G1Affine],
+    messages: &[M],
+    signature: &G2Affine,
+    threads: usize,
+) -> Choice {
+    if public_keys.len() != messages.len() {
+        return Choice::from(0);
+    }
+
+    let items = public_keys
+        .iter()
+        .zip(messages.iter())
+        .map(|(pk, message)| (*pk, message.as_ref().to_vec()))
+        .collect::<Vec<_>>();
+    super::parallel::core_aggregate_verify::<X>(&items, signature, DST, threads)
+}
+
+/// Computes [`fast_aggregate_verify`] with the public keys summed on up to `threads`
+/// threads.
+#[cfg(feature = "parallel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+pub fn fast_aggregate_verify_parallel<X: ExpandMessage>(
+    public_keys: &[ProvenPublicKey],
+    message: &[u8],
+    signature: &G2Affine,
+    threads: usize,
+) -> Choice {
+    let public_keys = public_keys.iter().map(G1Affine::from).collect::<Vec<_>>();
+    match super::parallel::aggregate_public_keys(&public_keys, threads) {
+        Some(pk) => core_verify::<X>(&pk, message, signature, DST),
+        None => Choice::from(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_to_curve::ExpandMsgXmd;
+    use crate::signature::{aggregate, sk_to_pk};
+    use crate::G1Projective;
+    use alloc::vec::Vec;
+
+    type Xmd = ExpandMsgXmd<sha2::Sha256>;
+
+    fn scalar_from_be_hex(s: &str) -> Scalar {
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(s, &mut bytes).unwrap();
+        Scalar::from_bytes_be(&bytes).unwrap()
+    }
+
+    #[test]
+    fn test_sign_vector() {
+        // From the Ethereum consensus-spec BLS test vectors, which use this ciphersuite.
+        let sk =
+            scalar_from_be_hex("263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3");
+        let pk = sk_to_pk(&sk);
+        assert_eq!(
+            hex::encode(pk.to_compressed()),
+            "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20f\
+             d6e10c1b77654d067c0618f6e5a7f79a"
+        );
+
+        let sig = sign::<Xmd>(&sk, &[0u8; 32]);
+        assert_eq!(
+            hex::encode(sig.to_compressed()),
+            "b6ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6\
+             076334f91e2366c96e9ab279fb5158090352ea1c5b0c9274504f4f0e7053af24\
+             802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55"
+        );
+        assert!(bool::from(verify::<Xmd>(&pk, &[0u8; 32], &sig)));
+        assert!(!bool::from(verify::<Xmd>(&pk, &[1u8; 32], &sig)));
+    }
+
+    #[test]
+    fn test_pop() {
+        let sk = Scalar::from(0xdead_beef);
+        let pk = sk_to_pk(&sk);
+        let proof = pop_prove::<Xmd>(&sk);
+        assert!(bool::from(pop_verify::<Xmd>(&pk, &proof)));
+
+        // A proof of possession is not a signature of the public key under
+        // the signing DST, and vice versa.
+        assert!(!bool::from(verify::<Xmd>(&pk, &pk.to_compressed(), &proof)));
+        let sig = sign::<Xmd>(&sk, &pk.to_compressed());
+        assert!(!bool::from(pop_verify::<Xmd>(&pk, &sig)));
+
+        // The proof is bound to the key it was produced for.
+        let other = sk_to_pk(&Scalar::from(0xcafe));
+        assert!(!bool::from(pop_verify::<Xmd>(&other, &proof)));
+        assert_eq!(
+            ProvenPublicKey::from_proof::<Xmd>(&pk, &proof),
+            Some(ProvenPublicKey(pk))
+        );
+        assert!(ProvenPublicKey::from_proof::<Xmd>(&other, &proof).is_none());
+    }
+
+    #[test]
+    fn test_fast_aggregate_verify() {
+        let message = b"attestation";
+        let sks = [Scalar::from(11), Scalar::from(13), Scalar::from(17)];
+        let pks = sks
+            .iter()
+            .map(|sk| {
+                ProvenPublicKey::from_proof::<Xmd>(&sk_to_pk(sk), &pop_prove::<Xmd>(sk)).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let sig = aggregate(sks.iter().map(|sk| sign::<Xmd>(sk, message)));
+
+        assert!(bool::from(fast_aggregate_verify::<Xmd>(
+            &pks, message, &sig
+        )));
+        assert!(!bool::from(fast_aggregate_verify::<Xmd>(
+            &pks[1..],
+            message,
+            &sig
+        )));
+        assert!(!bool::from(fast_aggregate_verify::<Xmd>(
+            &pks,
+            b"different",
+            &sig
+        )));
+        assert!(!bool::from(fast_aggregate_verify::<Xmd>(
+            &[],
+            message,
+            &G2Affine::identity()
+        )));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_verify_parallel() {
+        let message = b"attestation";
+        let sks = (1..=7u64).map(Scalar::from).collect::<Vec<_>>();
+        let pks = sks
+            .iter()
+            .map(|sk| {
+                ProvenPublicKey::from_proof::<Xmd>(&sk_to_pk(sk), &pop_prove::<Xmd>(sk)).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let sig = aggregate(sks.iter().map(|sk| sign::<Xmd>(sk, message)));
+        for threads in [1, 3, 8] {
+            assert!(bool::from(fast_aggregate_verify_parallel::<Xmd>(
+                &pks, message, &sig, threads
+            )));
+            assert!(!bool::from(fast_aggregate_verify_parallel::<Xmd>(
+                &pks[1..],
+                message,
+                &sig,
+                threads
+            )));
+        }
+
+        let public_keys = pks.iter().map(G1Affine::from).collect::<Vec<_>>();
+        let messages = (1..=7u64).map(u64::to_le_bytes).collect::<Vec<_>>();
+        let sig = aggregate(
+            sks.iter()
+                .zip(messages.iter())
+                .map(|(sk, msg)| sign::<Xmd>(sk, msg)),
+        );
+        for threads in [1, 3, 8] {
+            assert!(bool::from(aggregate_verify_parallel::<Xmd, _>(
+                &public_keys,
+                &messages,
+                &sig,
+                threads
+            )));
+        }
+        assert!(!bool::from(aggregate_verify_parallel::<Xmd, _>(
+            &public_keys,
+            &messages[1..],
+            &sig,
+            2
+        )));
+    }
+
+    #[test]
+    fn test_rogue_key_rejected() {
+        // A rogue key pk' = g1^a - pk lets an attacker forge an aggregate
+        // signature over {pk, pk'} without knowing the secret key of pk, but the
+        // attacker cannot produce a proof of possession for pk'.
+        let message = b"attestation";
+        let victim = sk_to_pk(&Scalar::from(23));
+        let a = Scalar::from(29);
+        let rogue = G1Affine::from(G1Projective::generator() * a - victim);
+        let forged = sign::<Xmd>(&a, message);
+
+        let aggregate_pk = G1Affine::from(G1Projective::from(victim) + rogue);
+        assert!(bool::from(verify::<Xmd>(&aggregate_pk, message, &forged)));
+
+        let rogue_proof = pop_prove::<Xmd>(&a);
+        assert!(ProvenPublicKey::from_proof::<Xmd>(&rogue, &rogue_proof).is_none());
+    }
+
+    #[test]
+    fn test_batch_verify() {
+        use rand_core::SeedableRng;
+
+        let mut rng = rand_xorshift::XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+        let mut items = [41u64, 43, 47]
+            .iter()
+            .map(|&i| {
+                let sk = Scalar::from(i);
+                let message = i.to_le_bytes();
+                (sk_to_pk(&sk), message, sign::<Xmd>(&sk, &message))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(batch_verify::<Xmd, _, _>(&items, &mut rng), Ok(()));
+
+        items[0].1 = [0; 8];
+        assert_eq!(batch_verify::<Xmd, _, _>(&items, &mut rng), Err(vec![0]));
+    }
+
+    #[test]
+    fn test_aggregate_verify() {
+        let sks = [Scalar::from(31), Scalar::from(37)];
+        let pks = sks.iter().map(sk_to_pk).collect::<Vec<_>>();
+        // Repeated messages are allowed in the proof-of-possession scheme.
+        let messages = [b"same", b"same"];
+        let sig = aggregate(
+            sks.iter()
+                .zip(messages.iter())
+                .map(|(sk, msg)| sign::<Xmd>(sk, &msg[..])),
+        );
+
+        assert!(bool::from(aggregate_verify::<Xmd, _>(
+            &pks, &messages, &sig
+        )));
+        assert!(!bool::from(aggregate_verify::<Xmd, _>(
+            &pks,
+            &messages[..1],
+            &sig
+        )));
+    }
+}
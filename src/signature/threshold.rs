@@ -0,0 +1,202 @@
+//! Threshold BLS signatures.
+//!
+//! A dealer splits a secret key into `n` shares with a degree `t - 1` sharing
+//! polynomial, and publishes Feldman commitments to its coefficients in
+//! $\mathbb{G}_1$. Each participant signs with its share, and any `t` valid partial
+//! signatures can be combined, by Lagrange interpolation at zero, into the signature
+//! that the original secret key would have produced. The shares and commitments
+//! are those of [`shamir`](crate::shamir) secret sharing.
+//!
+//! Partial and combined signatures use the [`pop`](super::pop) ciphersuite, so a
+//! combined signature verifies with [`pop::verify`](super::pop::verify) under
+//! [`Commitments::public_key`].
+
+use rand_core::RngCore;
+use subtle::Choice;
+
+use super::{core_sign, core_verify, pop};
+use crate::hash_to_curve::ExpandMessage;
+use crate::shamir;
+use crate::{G2Affine, G2Projective, Scalar};
+
+use alloc::vec::Vec;
+use group::Curve;
+
+pub use crate::shamir::{Commitments, Share as SecretKeyShare};
+
+/// Splits `secret` into `num_shares` shares, any `threshold` of which can produce
+/// signatures under the public key of `secret`.
+///
+/// Shares are assigned the indices `1..=num_shares`.
+///
+/// # Panics
+///
+/// Panics if `threshold` is zero or greater than `num_shares`, or if `num_shares`
+/// does not fit in a `u32`.
+pub fn deal(
+    secret: &Scalar,
+    threshold: usize,
+    num_shares: usize,
+    rng: impl RngCore,
+) -> (Commitments, Vec<SecretKeyShare>) {
+    shamir::split(secret, threshold, num_shares, rng)
+}
+
+/// A signature produced with a [`SecretKeyShare`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PartialSignature {
+    index: u32,
+    signature: G2Affine,
+}
+
+impl PartialSignature {
+    /// Constructs the partial signature of participant `index`, for example after
+    /// receiving it from that participant. Use [`verify_partial`] to check it.
+    pub fn new(index: u32, signature: G2Affine) -> Self {
+        PartialSignature { index, signature }
+    }
+
+    /// Returns the index of the participant that produced this partial signature.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns the signature of the message under the participant's key share.
+    pub fn signature(&self) -> &G2Affine {
+        &self.signature
+    }
+}
+
+/// Signs `message` with a share of a secret key.
+pub fn sign_partial<X: ExpandMessage>(share: &SecretKeyShare, message: &[u8]) -> PartialSignature {
+    PartialSignature {
+        index: share.index(),
+        signature: core_sign::<X>(share.value(), message, pop::DST),
+    }
+}
+
+/// Checks that `partial` is a signature of `message` under the key share of its
+/// participant, as determined by `commitments`.
+pub fn verify_partial<X: ExpandMessage>(
+    commitments: &Commitments,
+    partial: &PartialSignature,
+    message: &[u8],
+) -> Choice {
+    let pk = commitments.public_key_share(partial.index);
+    core_verify::<X>(&pk, message, &partial.signature, pop::DST)
+        & !Choice::from((partial.index == 0) as u8)
+}
+
+/// Combines at least `threshold` partial signatures of the same message into a
+/// signature under the shared public key.
+///
+/// Only the first `threshold` partial signatures are used. Returns `None` if fewer
+/// than `threshold` are provided, or if they do not have distinct, nonzero indices.
+/// The partial signatures are not checked, so callers should first use [`verify_partial`] to discard invalid ones.
+/// This function is variable time in the number and indices of the partial
+/// signatures.
+pub fn combine(threshold: usize, partials: &[PartialSignature]) -> Option<G2Affine> {
+    if threshold == 0 || partials.len() < threshold {
+        return None;
+    }
+    let partials = &partials[..threshold];
+
+    let indices = partials.iter().map(|p| p.index).collect::<Vec<_>>();
+    let signature = shamir::lagrange_coefficients(&indices, &Scalar::zero())?
+        .iter()
+        .zip(partials.iter())
+        .fold(G2Projective::identity(), |acc, (lambda, p)| {
+            acc + p.signature * lambda
+        });
+
+    Some(signature.to_affine())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_to_curve::ExpandMsgXmd;
+    use crate::signature::sk_to_pk;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    type Xmd = ExpandMsgXmd<sha2::Sha256>;
+
+    fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ])
+    }
+
+    #[test]
+    fn test_threshold_signing() {
+        let message = b"round 42";
+        let secret = Scalar::from(0x0123_4567_89ab_cdef);
+        let (commitments, shares) = deal(&secret, 3, 5, rng());
+
+        assert_eq!(commitments.threshold(), 3);
+        assert_eq!(commitments.public_key(), sk_to_pk(&secret));
+        for share in &shares {
+            assert!(bool::from(commitments.verify_share(share)));
+        }
+
+        let partials = shares
+            .iter()
+            .map(|share| sign_partial::<Xmd>(share, message))
+            .collect::<Vec<_>>();
+        for partial in &partials {
+            assert!(bool::from(verify_partial::<Xmd>(
+                &commitments,
+                partial,
+                message
+            )));
+        }
+
+        let expected = pop::sign::<Xmd>(&secret, message);
+        for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+            let subset = subset.iter().map(|&i| partials[i]).collect::<Vec<_>>();
+            let signature = combine(3, &subset).unwrap();
+            assert_eq!(signature, expected);
+            assert!(bool::from(pop::verify::<Xmd>(
+                &commitments.public_key(),
+                message,
+                &signature
+            )));
+        }
+
+        assert_eq!(combine(3, &partials[..2]), None);
+        assert_eq!(combine(3, &[partials[0], partials[0], partials[1]]), None);
+    }
+
+    #[test]
+    fn test_invalid_shares() {
+        let message = b"round 42";
+        let (commitments, shares) = deal(&Scalar::from(99), 2, 3, rng());
+
+        let tampered = SecretKeyShare::new(shares[0].index(), shares[0].value() + Scalar::one());
+        assert!(!bool::from(commitments.verify_share(&tampered)));
+
+        let swapped = SecretKeyShare::new(shares[1].index(), *shares[0].value());
+        assert!(!bool::from(commitments.verify_share(&swapped)));
+
+        let partial = sign_partial::<Xmd>(&tampered, message);
+        assert!(!bool::from(verify_partial::<Xmd>(
+            &commitments,
+            &partial,
+            message
+        )));
+        let partial = sign_partial::<Xmd>(&shares[0], message);
+        assert!(!bool::from(verify_partial::<Xmd>(
+            &commitments,
+            &partial,
+            b"round 43"
+        )));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_deal_threshold_too_large() {
+        deal(&Scalar::one(), 4, 3, rng());
+    }
+}
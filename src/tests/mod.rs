@@ -75,6 +75,333 @@ fn g2_compressed_valid_test_vectors() {
     );
 }
 
+macro_rules! test_from_bytes_any {
+    ($projective:ident, $affine:ident) => {
+        let p = $affine::from($projective::generator().double());
+        for q in [p, $affine::identity()] {
+            let compressed = q.to_compressed();
+            let uncompressed = q.to_uncompressed();
+            assert_eq!(
+                $affine::from_bytes_any(&compressed),
+                Ok((q, PointEncoding::Compressed))
+            );
+            assert_eq!(
+                $affine::from_bytes_any(&uncompressed),
+                Ok((q, PointEncoding::Uncompressed))
+            );
+
+            // The compression flag must agree with the length.
+            let mut flipped = compressed;
+            flipped[0] ^= 0x80;
+            assert_eq!($affine::from_bytes_any(&flipped), Err(Error::BadFlags));
+            let mut flipped = uncompressed;
+            flipped[0] ^= 0x80;
+            assert_eq!($affine::from_bytes_any(&flipped), Err(Error::BadFlags));
+
+            assert_eq!(
+                $affine::from_bytes_any(&uncompressed[1..]),
+                Err(Error::InvalidLength)
+            );
+        }
+        assert_eq!($affine::from_bytes_any(&[]), Err(Error::InvalidLength));
+    };
+}
+
+macro_rules! test_try_from {
+    ($projective:ident, $affine:ident, $compressed_len:expr) => {
+        let p = $affine::from($projective::generator().double());
+        assert_eq!($affine::try_from_compressed(&p.to_compressed()), Ok(p));
+        assert_eq!($affine::try_from_uncompressed(&p.to_uncompressed()), Ok(p));
+        assert_eq!(
+            $affine::try_from_compressed(&p.to_uncompressed()),
+            Err(Error::InvalidLength)
+        );
+
+        let mut bytes = [0xff; $compressed_len];
+        bytes[0] = 0x9f;
+        assert_eq!(
+            $affine::try_from_compressed(&bytes),
+            Err(Error::NonCanonicalField)
+        );
+
+        // Small x-coordinates are either not on the curve or, with overwhelming
+        // probability, outside of the prime-order subgroup.
+        let (mut not_on_curve, mut wrong_subgroup) = (false, false);
+        for x in 0..16 {
+            let mut bytes = [0; $compressed_len];
+            bytes[0] = 0x80;
+            bytes[$compressed_len - 1] = x;
+            match $affine::try_from_compressed(&bytes) {
+                Err(Error::NotOnCurve) => not_on_curve = true,
+                Err(Error::WrongSubgroup) => wrong_subgroup = true,
+                result => panic!("unexpected result {:?}", result),
+            }
+        }
+        assert!(not_on_curve && wrong_subgroup);
+
+        let mut bytes = p.to_uncompressed();
+        bytes[2 * $compressed_len - 1] ^= 1;
+        assert_eq!(
+            $affine::try_from_uncompressed(&bytes),
+            Err(Error::NotOnCurve)
+        );
+
+        let mut bytes = $affine::identity().to_compressed();
+        bytes[$compressed_len - 1] = 1;
+        assert_eq!($affine::try_from_compressed(&bytes), Err(Error::BadFlags));
+        let mut bytes = p.to_uncompressed();
+        bytes[0] |= 0x20;
+        assert_eq!($affine::try_from_uncompressed(&bytes), Err(Error::BadFlags));
+    };
+}
+
+#[test]
+fn test_try_from() {
+    test_try_from!(G1Projective, G1Affine, 48);
+    test_try_from!(G2Projective, G2Affine, 96);
+}
+
+#[test]
+fn test_try_from_slice() {
+    let s = Scalar::from_raw([1, 2, 3, 4]);
+    assert_eq!(Scalar::try_from(&s.to_bytes()[..]), Ok(s));
+    assert_eq!(
+        Scalar::try_from(&[0xff; 32][..]),
+        Err(Error::NonCanonicalField)
+    );
+    assert_eq!(Scalar::try_from(&[0; 31][..]), Err(Error::InvalidLength));
+
+    let x = G1Affine::generator().x;
+    assert_eq!(fp::Fp::try_from(&x.to_bytes()[..]), Ok(x));
+    assert_eq!(
+        fp::Fp::try_from(&[0xff; 48][..]),
+        Err(Error::NonCanonicalField)
+    );
+    assert_eq!(fp::Fp::try_from(&[0; 49][..]), Err(Error::InvalidLength));
+
+    let p = G1Affine::from(G1Affine::generator() * s);
+    assert_eq!(G1Affine::try_from(&p.to_compressed()[..]), Ok(p));
+    assert_eq!(
+        G1Affine::try_from(&p.to_uncompressed()[..]),
+        Err(Error::InvalidLength)
+    );
+    let q = G2Affine::from(G2Affine::generator() * s);
+    assert_eq!(G2Affine::try_from(&q.to_compressed()[..]), Ok(q));
+    assert_eq!(
+        G2Affine::try_from(&q.to_uncompressed()[..]),
+        Err(Error::InvalidLength)
+    );
+
+    let gt = pairing(&p, &G2Affine::generator());
+    let bytes = gt.to_compressed();
+    assert_eq!(Gt::try_from(&bytes[..]), Ok(gt));
+    assert_eq!(Gt::try_from(&bytes[1..]), Err(Error::InvalidLength));
+    let mut invalid = bytes;
+    invalid[0] &= 0x7f;
+    assert_eq!(Gt::try_from(&invalid[..]), Err(Error::BadFlags));
+    let mut invalid = bytes;
+    invalid[0] |= 0x3f;
+    invalid[1..48].copy_from_slice(&[0xff; 47]);
+    assert_eq!(Gt::try_from(&invalid[..]), Err(Error::NonCanonicalField));
+    // With c_1 = 0 and the sort flag set, this is -1, which has order 2.
+    let mut invalid = [0; 288];
+    invalid[0] = 0xc0;
+    assert_eq!(Gt::try_from(&invalid[..]), Err(Error::WrongSubgroup));
+}
+
+#[test]
+fn test_try_constructors() {
+    let s = Scalar::from_raw([1, 2, 3, 4]);
+    assert_eq!(Scalar::try_from_bytes_be(&s.to_bytes_be()), Ok(s));
+    assert_eq!(
+        Scalar::try_from_bytes_be(&[0xff; 32]),
+        Err(Error::NonCanonicalField)
+    );
+    assert_eq!(
+        Scalar::try_from_bytes_be(&[0; 33]),
+        Err(Error::InvalidLength)
+    );
+    assert_eq!(Scalar::try_from_raw([1, 2, 3, 4]), Ok(s));
+    assert_eq!(
+        Scalar::try_from_raw([u64::MAX; 4]),
+        Err(Error::NonCanonicalField)
+    );
+
+    assert_eq!(fp::Fp::try_from_raw([1, 0, 0, 0, 0, 0]), Ok(fp::Fp::one()));
+    assert_eq!(
+        fp::Fp::try_from_raw([u64::MAX; 6]),
+        Err(Error::NonCanonicalField)
+    );
+    assert_eq!(
+        fp2::Fp2::try_from_raw([[1, 0, 0, 0, 0, 0], [0; 6]]),
+        Ok(fp2::Fp2::one())
+    );
+    assert_eq!(
+        fp2::Fp2::try_from_raw([[0; 6], [u64::MAX; 6]]),
+        Err(Error::NonCanonicalField)
+    );
+
+    let gt = pairing(&G1Affine::generator(), &G2Affine::generator()) * s;
+    let bytes = gt.to_uncompressed();
+    assert_eq!(Gt::try_from_uncompressed(&bytes), Ok(gt));
+    assert_eq!(
+        Gt::try_from_uncompressed(&bytes[..575]),
+        Err(Error::InvalidLength)
+    );
+    let mut invalid = bytes;
+    invalid[..48].copy_from_slice(&[0xff; 48]);
+    assert_eq!(
+        Gt::try_from_uncompressed(&invalid),
+        Err(Error::NonCanonicalField)
+    );
+    assert_eq!(
+        Gt::try_from_uncompressed(&[0; 576]),
+        Err(Error::WrongSubgroup)
+    );
+}
+
+fn check_encodings<T>(element: T, compressed_len: usize)
+where
+    T: group::GroupEncoding + group::UncompressedEncoding + PartialEq + core::fmt::Debug,
+{
+    let bytes = element.to_bytes();
+    assert_eq!(bytes.as_ref().len(), compressed_len);
+    assert_eq!(T::from_bytes(&bytes).unwrap(), element);
+    assert_eq!(T::from_bytes_unchecked(&bytes).unwrap(), element);
+
+    let bytes = element.to_uncompressed();
+    assert_eq!(bytes.as_ref().len(), 2 * compressed_len);
+    assert_eq!(T::from_uncompressed(&bytes).unwrap(), element);
+    assert_eq!(T::from_uncompressed_unchecked(&bytes).unwrap(), element);
+
+    let mut bytes = T::Uncompressed::default();
+    bytes.as_mut()[0] = 0xff;
+    assert!(bool::from(T::from_uncompressed(&bytes).is_none()));
+}
+
+#[test]
+fn test_encoding_traits() {
+    let s = Scalar::from_raw([1, 2, 3, 4]);
+    check_encodings(G1Affine::from(G1Affine::generator() * s), 48);
+    check_encodings(G1Projective::generator() * s, 48);
+    check_encodings(G2Affine::from(G2Affine::generator() * s), 96);
+    check_encodings(G2Projective::generator() * s, 96);
+    check_encodings(
+        pairing(&G1Affine::generator(), &G2Affine::generator()) * s,
+        288,
+    );
+}
+
+#[test]
+fn test_from_bytes_any() {
+    test_from_bytes_any!(G1Projective, G1Affine);
+    test_from_bytes_any!(G2Projective, G2Affine);
+}
+
+macro_rules! test_display {
+    ($element:expr) => {
+        let element = $element;
+        assert_eq!(
+            format!("{}", element),
+            format!("0x{}", hex::encode(element.to_compressed()))
+        );
+        assert_eq!(
+            format!("{:#}", element),
+            format!("0x{}", hex::encode(element.to_uncompressed()))
+        );
+        assert_eq!(format!("{:x}", element), hex::encode(element.to_compressed()));
+        assert_eq!(
+            format!("{:#x}", element),
+            hex::encode(element.to_uncompressed())
+        );
+    };
+}
+
+#[test]
+fn test_display() {
+    test_display!(G1Affine::generator());
+    test_display!(G2Affine::generator());
+    test_display!(<Gt as group::Group>::generator());
+
+    assert_eq!(
+        format!("{}", G1Affine::identity()),
+        format!("0xc0{}", "00".repeat(47))
+    );
+}
+
+macro_rules! test_conditional_negate {
+    ($group:ident) => {
+        let generator = $group::generator();
+        for element in [generator, -generator, $group::identity()] {
+            let mut negated = element;
+            negated.neg_assign();
+            assert_eq!(negated, -element);
+
+            let mut unchanged = element;
+            unchanged.conditional_negate(subtle::Choice::from(0u8));
+            assert_eq!(unchanged, element);
+
+            let mut negated = element;
+            negated.conditional_negate(subtle::Choice::from(1u8));
+            assert_eq!(negated, -element);
+            assert!(bool::from(negated.is_on_curve()));
+        }
+    };
+}
+
+#[test]
+fn test_conditional_negate() {
+    test_conditional_negate!(G1Affine);
+    test_conditional_negate!(G1Projective);
+    test_conditional_negate!(G2Affine);
+    test_conditional_negate!(G2Projective);
+}
+
+/// Checks `conditional_swap` and the `ConditionallyNegatable` implementation of `T`
+/// on two distinct elements.
+fn check_swap_and_negate<T>(a: T, b: T)
+where
+    T: subtle::ConditionallySelectable
+        + subtle::ConditionallyNegatable
+        + core::ops::Neg<Output = T>
+        + PartialEq
+        + core::fmt::Debug,
+{
+    assert_ne!(a, b);
+    let (mut x, mut y) = (a, b);
+    T::conditional_swap(&mut x, &mut y, subtle::Choice::from(0u8));
+    assert_eq!((x, y), (a, b));
+    T::conditional_swap(&mut x, &mut y, subtle::Choice::from(1u8));
+    assert_eq!((x, y), (b, a));
+
+    let mut x = a;
+    subtle::ConditionallyNegatable::conditional_negate(&mut x, subtle::Choice::from(0u8));
+    assert_eq!(x, a);
+    subtle::ConditionallyNegatable::conditional_negate(&mut x, subtle::Choice::from(1u8));
+    assert_eq!(x, -a);
+}
+
+#[test]
+fn test_conditional_swap_and_negate() {
+    use crate::fp::Fp;
+    use crate::fp12::Fp12;
+    use crate::fp2::Fp2;
+    use crate::fp6::Fp6;
+
+    check_swap_and_negate(Fp::one(), Fp::one() + Fp::one());
+    check_swap_and_negate(Fp2::one(), Fp2::one() + Fp2::one());
+    check_swap_and_negate(Fp6::one(), Fp6::one() + Fp6::one());
+    check_swap_and_negate(Fp12::one(), Fp12::one() + Fp12::one());
+    check_swap_and_negate(Scalar::one(), Scalar::one() + Scalar::one());
+
+    check_swap_and_negate(G1Affine::generator(), G1Affine::identity());
+    check_swap_and_negate(G1Projective::generator(), G1Projective::identity());
+    check_swap_and_negate(G2Affine::generator(), G2Affine::identity());
+    check_swap_and_negate(G2Projective::generator(), G2Projective::identity());
+    #[cfg(feature = "pairings")]
+    check_swap_and_negate(Gt::generator(), Gt::identity());
+}
+
 #[test]
 fn test_pairing_result_against_relic() {
     /*
@@ -0,0 +1,179 @@
+//! Work split across threads of the standard library, for the `parallel` feature.
+//!
+//! The threads cannot borrow from their caller, so each of them is given a copy of
+//! its share of the input and returns its share of the output.
+
+use core::ops::Mul;
+use std::thread;
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use alloc::vec::Vec;
+
+use crate::{fp::Fp, fp2::Fp2, Scalar};
+
+/// Splits `items` into contiguous chunks of `chunk_size` items, the last of which
+/// may be shorter, and returns the results of `f` on each chunk, in order, computed
+/// on a thread of its own. `f` is also given the index in `items` of the first item
+/// of its chunk.
+///
+/// # Panics
+///
+/// Panics if `chunk_size` is zero while `items` is not empty, or if `f` panics.
+pub(crate) fn map_chunks<T, R, F>(items: &[T], chunk_size: usize, f: F) -> Vec<R>
+where
+    T: Clone + Send + 'static,
+    R: Send + 'static,
+    F: Fn(usize, Vec<T>) -> R + Clone + Send + 'static,
+{
+    if items.is_empty() {
+        return Vec::new();
+    }
+
+    // Every thread must be spawned before any of them is joined.
+    #[allow(clippy::needless_collect)]
+    let handles = items
+        .chunks(chunk_size)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let (chunk, f) = (chunk.to_vec(), f.clone());
+            thread::spawn(move || f(i * chunk_size, chunk))
+        })
+        .collect::<Vec<_>>();
+
+    handles
+        .into_iter()
+        .map(|handle| handle.join().expect("worker thread panicked"))
+        .collect()
+}
+
+/// Returns the size of the chunks that split `len` items between at most `threads`
+/// threads, or at least one if `threads` is zero.
+pub(crate) fn chunk_size(len: usize, threads: usize) -> usize {
+    let threads = threads.max(1);
+    (len + threads - 1) / threads
+}
+
+/// A field whose elements are inverted in batches by [`map_inverses`].
+pub(crate) trait BatchInvert:
+    Copy + ConditionallySelectable + Mul<Output = Self> + Send + 'static
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn is_zero(&self) -> Choice;
+    fn invert(&self) -> CtOption<Self>;
+}
+
+macro_rules! impl_batch_invert {
+    ($field:ident) => {
+        impl BatchInvert for $field {
+            fn zero() -> Self {
+                $field::zero()
+            }
+
+            fn one() -> Self {
+                $field::one()
+            }
+
+            fn is_zero(&self) -> Choice {
+                self.ct_eq(&$field::zero())
+            }
+
+            fn invert(&self) -> CtOption<Self> {
+                $field::invert(self)
+            }
+        }
+    };
+}
+
+impl_batch_invert!(Fp);
+impl_batch_invert!(Fp2);
+impl_batch_invert!(Scalar);
+
+/// Returns `finish(item, inverse)` for every item of `items`, in order, where
+/// `inverse` is the inverse of `denominator(item)`, or zero if that is zero, in
+/// constant time and on up to `threads` threads.
+///
+/// This is Montgomery's trick split between the threads: each of them multiplies
+/// the nonzero denominators of its chunk, the products of the chunks are then
+/// inverted together with a single inversion, and each thread finally recovers the
+/// inverses of the denominators of its chunk from the inverse of their product.
+pub(crate) fn map_inverses<T, F, R>(
+    items: &[T],
+    threads: usize,
+    denominator: fn(&T) -> F,
+    finish: fn(&T, F) -> R,
+) -> Vec<R>
+where
+    T: Clone + Send + 'static,
+    F: BatchInvert,
+    R: Send + 'static,
+{
+    let chunk_size = chunk_size(items.len(), threads);
+    let products = map_chunks(items, chunk_size, move |_, chunk| {
+        chunk.iter().fold(F::one(), |acc, item| {
+            let d = denominator(item);
+            F::conditional_select(&(acc * d), &acc, d.is_zero())
+        })
+    });
+
+    // The products are nonzero, since only nonzero denominators were multiplied.
+    let mut inverses = Vec::with_capacity(products.len());
+    let mut acc = F::one();
+    for product in products.iter() {
+        inverses.push(acc);
+        acc = acc * *product;
+    }
+    let mut inv = acc.invert().unwrap();
+    for (inverse, product) in inverses.iter_mut().zip(products.iter()).rev() {
+        *inverse = *inverse * inv;
+        inv = inv * *product;
+    }
+
+    map_chunks(items, chunk_size, move |offset, chunk| {
+        let mut products = Vec::with_capacity(chunk.len());
+        let mut acc = F::one();
+        for item in chunk.iter() {
+            products.push(acc);
+            let d = denominator(item);
+            acc = F::conditional_select(&(acc * d), &acc, d.is_zero());
+        }
+
+        let mut inv = inverses[offset / chunk_size];
+        let mut results = Vec::with_capacity(chunk.len());
+        for (item, product) in chunk.iter().zip(products.iter()).rev() {
+            let d = denominator(item);
+            let skip = d.is_zero();
+            results.push(finish(
+                item,
+                F::conditional_select(&(inv * *product), &F::zero(), skip),
+            ));
+            inv = F::conditional_select(&(inv * d), &inv, skip);
+        }
+        results.reverse();
+        results
+    })
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_chunks() {
+        let items = (0..10).collect::<Vec<u32>>();
+        let sums = map_chunks(&items, 4, |offset, chunk| {
+            assert_eq!(chunk[0] as usize, offset);
+            chunk.iter().sum::<u32>()
+        });
+        assert_eq!(sums, [6, 22, 17]);
+        assert!(map_chunks(&[] as &[u32], 0, |_, chunk| chunk).is_empty());
+
+        assert_eq!(chunk_size(10, 3), 4);
+        assert_eq!(chunk_size(10, 0), 10);
+        assert_eq!(chunk_size(2, 16), 1);
+    }
+}
@@ -0,0 +1,437 @@
+//! This module implements timelock encryption against a [drand]-style threshold BLS
+//! randomness beacon, following the construction of [tlock].
+//!
+//! A beacon with public key $P$ publishes, for every round $n$, the BLS signature
+//! of $H(n)$, where $H$ is SHA-256 and $n$ is encoded as a big-endian `u64`. That
+//! signature is the Boneh-Franklin private key of the identity $H(n)$ under the
+//! master public key $P$, so a message encrypted to round $n$ with the `FullIdent`
+//! variant of [IBE](crate::ibe) can be decrypted by anyone once the beacon reaches
+//! that round, and by no one before.
+//!
+//! Two kinds of beacon are supported:
+//!
+//! * [`encrypt_on_g1`] / [`decrypt_on_g1`]: beacons with public keys in
+//!   $\mathbb{G}_1$ and signatures in $\mathbb{G}_2$ (drand's
+//!   `pedersen-bls-unchained` scheme).
+//! * [`encrypt_on_g2`] / [`decrypt_on_g2`]: beacons with public keys in
+//!   $\mathbb{G}_2$ and signatures in $\mathbb{G}_1$ (drand's
+//!   `bls-unchained-g1-rfc9380` scheme, used by the "quicknet" network).
+//!
+//! Hashing follows drand's `kyber` library, with the hash function `H`, which must
+//! be SHA-256 to interoperate with drand: identities are hashed to the curve with the
+//! beacon's ciphersuite, and the `IBE-H2`, `IBE-H3` and `IBE-H4` tags domain-separate
+//! the derivation of masks and of the encryption randomness. Session keys in
+//! $\mathbb{G}_T$ are hashed in the encoding used by `kyber`, which lists the
+//! coefficients of the element in the reverse order of
+//! [`Gt::to_uncompressed`](crate::Gt::to_uncompressed).
+//!
+//! [`Ciphertext::to_bytes`] produces the body of the `tlock` recipient stanza; the
+//! surrounding [age] envelope is left to the caller.
+//!
+//! [drand]: https://drand.love
+//! [tlock]: https://eprint.iacr.org/2023/189
+//! [age]: https://age-encryption.org/v1
+
+use digest::{BlockInput, Digest};
+use group::GroupEncoding;
+use rand_core::RngCore;
+use subtle::ConstantTimeEq;
+
+use crate::generic_array::typenum::U32;
+use crate::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use crate::{pairing, G1Affine, G1Projective, G2Affine, G2Projective, Gt, Scalar};
+
+use alloc::vec::Vec;
+
+/// The domain separation tag with which beacons with signatures in $\mathbb{G}_2$
+/// hash round identities.
+pub const G2_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// The domain separation tag with which beacons with signatures in $\mathbb{G}_1$
+/// hash round identities.
+pub const G1_DST: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// The maximum length of a message, which is the output size of `H`. For the [age]
+/// envelope, messages are 16-byte file keys.
+///
+/// [age]: https://age-encryption.org/v1
+pub const MAX_MESSAGE_LEN: usize = 32;
+
+/// A timelock ciphertext $(U, V, W)$, where $U$ is in $\mathbb{G}_1$ or
+/// $\mathbb{G}_2$ depending on the beacon.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ciphertext<G> {
+    u: G,
+    v: Vec<u8>,
+    w: Vec<u8>,
+}
+
+impl<G: GroupEncoding> Ciphertext<G> {
+    /// Serializes this ciphertext as the compressed encoding of $U$ followed by $V$
+    /// and $W$, as in the body of a `tlock` stanza.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let u = self.u.to_bytes();
+        let mut bytes = Vec::with_capacity(u.as_ref().len() + self.v.len() + self.w.len());
+        bytes.extend_from_slice(u.as_ref());
+        bytes.extend_from_slice(&self.v);
+        bytes.extend_from_slice(&self.w);
+        bytes
+    }
+
+    /// Attempts to deserialize a ciphertext produced by [`Ciphertext::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut u = G::Repr::default();
+        let point_len = u.as_ref().len();
+        if bytes.len() < point_len || (bytes.len() - point_len) % 2 != 0 {
+            return None;
+        }
+        let message_len = (bytes.len() - point_len) / 2;
+        if message_len > MAX_MESSAGE_LEN {
+            return None;
+        }
+
+        u.as_mut().copy_from_slice(&bytes[..point_len]);
+        let u = Option::from(G::from_bytes(&u))?;
+        let (v, w) = bytes[point_len..].split_at(message_len);
+        Some(Ciphertext {
+            u,
+            v: v.to_vec(),
+            w: w.to_vec(),
+        })
+    }
+}
+
+/// Returns the identity of `round`, which is the message signed by the beacon.
+pub fn round_identity<H: Digest<OutputSize = U32>>(round: u64) -> [u8; 32] {
+    let mut identity = [0u8; 32];
+    identity.copy_from_slice(&H::new().chain(round.to_be_bytes()).finalize());
+    identity
+}
+
+/// Hashes `gt`, in the encoding used by `kyber`, to a mask of `len` bytes.
+fn h2<H: Digest<OutputSize = U32>>(gt: &Gt, len: usize) -> Vec<u8> {
+    let bytes = gt.to_uncompressed();
+    let mut h = H::new().chain(b"IBE-H2");
+    for coefficient in bytes.chunks(48).rev() {
+        h.update(coefficient);
+    }
+    h.finalize()[..len].to_vec()
+}
+
+/// Derives the encryption randomness from `sigma` and `message` by rejection
+/// sampling.
+fn h3<H: Digest<OutputSize = U32>>(sigma: &[u8], message: &[u8]) -> Scalar {
+    let buffer = H::new()
+        .chain(b"IBE-H3")
+        .chain(sigma)
+        .chain(message)
+        .finalize();
+
+    for i in 1..=u16::MAX {
+        let mut hashed = [0u8; 32];
+        hashed.copy_from_slice(&H::new().chain(i.to_le_bytes()).chain(&buffer).finalize());
+        // Clear the most significant bit, and interpret as a big-endian scalar.
+        hashed[0] >>= 1;
+        let r = Scalar::from_bytes_be(&hashed);
+        if bool::from(r.is_some()) {
+            return r.unwrap();
+        }
+    }
+
+    // Each candidate is a valid scalar with probability greater than 1/2.
+    unreachable!()
+}
+
+/// Hashes `sigma` to a mask of `len` bytes.
+fn h4<H: Digest<OutputSize = U32>>(sigma: &[u8], len: usize) -> Vec<u8> {
+    H::new().chain(b"IBE-H4").chain(sigma).finalize()[..len].to_vec()
+}
+
+fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
+    a.iter().zip(b.iter()).map(|(a, b)| a ^ b).collect()
+}
+
+/// Encrypts `message`, given the session key `gid` of the round identity, with
+/// randomness `sigma`. Returns $r$, $V$ and $W$.
+fn encrypt_with<H: Digest<OutputSize = U32>, F: FnOnce(&Scalar) -> Gt>(
+    message: &[u8],
+    sigma: &[u8],
+    gid: F,
+) -> (Scalar, Vec<u8>, Vec<u8>) {
+    let r = h3::<H>(sigma, message);
+    let v = xor(sigma, &h2::<H>(&gid(&r), message.len()));
+    let w = xor(message, &h4::<H>(sigma, message.len()));
+    (r, v, w)
+}
+
+/// Decrypts `ciphertext` given its session key, returning the message and the
+/// randomness $r$ it must have been encrypted with.
+fn decrypt_with<H: Digest<OutputSize = U32>, G>(
+    ciphertext: &Ciphertext<G>,
+    key: &Gt,
+) -> Option<(Vec<u8>, Scalar)> {
+    if ciphertext.v.len() != ciphertext.w.len() || ciphertext.w.len() > MAX_MESSAGE_LEN {
+        return None;
+    }
+
+    let sigma = xor(&ciphertext.v, &h2::<H>(key, ciphertext.w.len()));
+    let message = xor(&ciphertext.w, &h4::<H>(&sigma, ciphertext.w.len()));
+    let r = h3::<H>(&sigma, &message);
+    Some((message, r))
+}
+
+/// Encrypts `message` to `round` of the beacon with public key `public_key` in
+/// $\mathbb{G}_1$.
+///
+/// Returns `None` if `message` is longer than [`MAX_MESSAGE_LEN`].
+pub fn encrypt_on_g1<H, R>(
+    public_key: &G1Affine,
+    round: u64,
+    message: &[u8],
+    mut rng: R,
+) -> Option<Ciphertext<G1Affine>>
+where
+    H: Digest<OutputSize = U32> + BlockInput,
+    R: RngCore,
+{
+    if message.len() > MAX_MESSAGE_LEN {
+        return None;
+    }
+
+    let identity = round_identity::<H>(round);
+    let q = G2Affine::from(
+        <G2Projective as HashToCurve<ExpandMsgXmd<H>>>::hash_to_curve(identity, G2_DST),
+    );
+
+    let mut sigma = alloc::vec![0u8; message.len()];
+    rng.fill_bytes(&mut sigma);
+    let (r, v, w) = encrypt_with::<H, _>(message, &sigma, |r| {
+        pairing(&G1Affine::from(public_key * r), &q)
+    });
+
+    Some(Ciphertext {
+        u: G1Affine::from(G1Affine::generator() * r),
+        v,
+        w,
+    })
+}
+
+/// Decrypts `ciphertext` with the beacon's `signature` in $\mathbb{G}_2$ of the round
+/// it was encrypted to.
+///
+/// Returns `None` if the ciphertext was not encrypted to that round, or has been
+/// tampered with.
+pub fn decrypt_on_g1<H: Digest<OutputSize = U32>>(
+    signature: &G2Affine,
+    ciphertext: &Ciphertext<G1Affine>,
+) -> Option<Vec<u8>> {
+    let key = pairing(&ciphertext.u, signature);
+    let (message, r) = decrypt_with::<H, _>(ciphertext, &key)?;
+
+    let u = G1Affine::from(G1Affine::generator() * r);
+    if bool::from(u.ct_eq(&ciphertext.u)) {
+        Some(message)
+    } else {
+        None
+    }
+}
+
+/// Encrypts `message` to `round` of the beacon with public key `public_key` in
+/// $\mathbb{G}_2$.
+///
+/// Returns `None` if `message` is longer than [`MAX_MESSAGE_LEN`].
+pub fn encrypt_on_g2<H, R>(
+    public_key: &G2Affine,
+    round: u64,
+    message: &[u8],
+    mut rng: R,
+) -> Option<Ciphertext<G2Affine>>
+where
+    H: Digest<OutputSize = U32> + BlockInput,
+    R: RngCore,
+{
+    if message.len() > MAX_MESSAGE_LEN {
+        return None;
+    }
+
+    let identity = round_identity::<H>(round);
+    let q = G1Affine::from(
+        <G1Projective as HashToCurve<ExpandMsgXmd<H>>>::hash_to_curve(identity, G1_DST),
+    );
+
+    let mut sigma = alloc::vec![0u8; message.len()];
+    rng.fill_bytes(&mut sigma);
+    let (r, v, w) = encrypt_with::<H, _>(message, &sigma, |r| {
+        pairing(&q, &G2Affine::from(public_key * r))
+    });
+
+    Some(Ciphertext {
+        u: G2Affine::from(G2Affine::generator() * r),
+        v,
+        w,
+    })
+}
+
+/// Decrypts `ciphertext` with the beacon's `signature` in $\mathbb{G}_1$ of the round
+/// it was encrypted to.
+///
+/// Returns `None` if the ciphertext was not encrypted to that round, or has been
+/// tampered with.
+pub fn decrypt_on_g2<H: Digest<OutputSize = U32>>(
+    signature: &G1Affine,
+    ciphertext: &Ciphertext<G2Affine>,
+) -> Option<Vec<u8>> {
+    let key = pairing(signature, &ciphertext.u);
+    let (message, r) = decrypt_with::<H, _>(ciphertext, &key)?;
+
+    let u = G2Affine::from(G2Affine::generator() * r);
+    if bool::from(u.ct_eq(&ciphertext.u)) {
+        Some(message)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use sha2::Sha256;
+
+    fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ])
+    }
+
+    /// Signs a round as a beacon with public keys in G1 would.
+    fn sign_round_g2(sk: &Scalar, round: u64) -> G2Affine {
+        let q = <G2Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(
+            round_identity::<Sha256>(round),
+            G2_DST,
+        );
+        G2Affine::from(q * sk)
+    }
+
+    /// Signs a round as a beacon with public keys in G2 would.
+    fn sign_round_g1(sk: &Scalar, round: u64) -> G1Affine {
+        let q = <G1Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(
+            round_identity::<Sha256>(round),
+            G1_DST,
+        );
+        G1Affine::from(q * sk)
+    }
+
+    #[test]
+    fn test_round_identity() {
+        // SHA-256 of the big-endian encoding of round 1.
+        assert_eq!(
+            round_identity::<Sha256>(1),
+            [
+                0xcd, 0x26, 0x62, 0x15, 0x4e, 0x6d, 0x76, 0xb2, 0xb2, 0xb9, 0x2e, 0x70, 0xc0, 0xca,
+                0xc3, 0xcc, 0xf5, 0x34, 0xf9, 0xb7, 0x4e, 0xb5, 0xb8, 0x98, 0x19, 0xec, 0x50, 0x90,
+                0x83, 0xd0, 0x0a, 0x50
+            ]
+        );
+    }
+
+    fn from_hex<const N: usize>(hex: &str) -> [u8; N] {
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(&hex::decode(hex).unwrap());
+        bytes
+    }
+
+    #[test]
+    fn test_drand_fastnet() {
+        // The public key of drand's "fastnet" network, whose signatures are in G1,
+        // and its signature of round 1000, as published by the beacon.
+        let pk = G2Affine::from_compressed(&from_hex(
+            "a0b862a7527fee3a731bcb59280ab6abd62d5c0b6ea03dc4ddf6612fdfc9d01f01c31542541771903475eb1ec6615f8d0df0b8b6dce385811d6dcf8cbefb8759e5e616a3dfd054c928940766d9a5b9db91e3b697e5d70a975181e007f87fca5e",
+        ))
+        .unwrap();
+        let signature = G1Affine::from_compressed(&from_hex(
+            "b09eacd45767c4d58306b98901ad0d6086e2663766f3a4ec71d00cf26f0f49eaf248abc7151c60cf419c4e8b37e80412",
+        ))
+        .unwrap();
+
+        // fastnet predates the G1 ciphersuite and hashes rounds with the tag of G2.
+        let q = <G1Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(
+            round_identity::<Sha256>(1000),
+            G2_DST,
+        );
+        assert_eq!(
+            pairing(&signature, &G2Affine::generator()),
+            pairing(&G1Affine::from(q), &pk)
+        );
+
+        // The encryption of 16 zero bytes to round 1000, from the documentation of
+        // the `tlock` crate, which interoperates with the reference implementation.
+        let ciphertext = Ciphertext::<G2Affine>::from_bytes(&from_hex::<128>(
+            "9787b5ed1c3e36e84ce19064e975be835b81c0788d5aa2a49ab7edc98b2917f1d61ac21f196bdc693ed556194fb33da104ffafa3c036dbcfb55eb953aaf2d446871aad7a1266f531caac1d654247a2d8ee93b975a7a19f0286f44d3c646d76338f334f4450bddbb2db52daae55d9e20ec26503ea7855b165f713b4ea96e60376",
+        ))
+        .unwrap();
+        assert_eq!(
+            decrypt_on_g2::<Sha256>(&signature, &ciphertext).unwrap(),
+            [0u8; 16]
+        );
+    }
+
+    #[test]
+    fn test_timelock_g1() {
+        let mut rng = rng();
+        let sk = Scalar::from(0x1234_5678_9abc_def0);
+        let pk = G1Affine::from(G1Affine::generator() * sk);
+        let file_key = [0x42u8; 16];
+
+        let ciphertext = encrypt_on_g1::<Sha256, _>(&pk, 1000, &file_key, &mut rng).unwrap();
+        assert_eq!(ciphertext.to_bytes().len(), 48 + 16 + 16);
+        assert_eq!(
+            Ciphertext::from_bytes(&ciphertext.to_bytes()),
+            Some(ciphertext.clone())
+        );
+
+        let signature = sign_round_g2(&sk, 1000);
+        assert_eq!(
+            decrypt_on_g1::<Sha256>(&signature, &ciphertext).unwrap(),
+            file_key
+        );
+        assert_eq!(
+            decrypt_on_g1::<Sha256>(&sign_round_g2(&sk, 999), &ciphertext),
+            None
+        );
+
+        let mut tampered = ciphertext;
+        tampered.w[0] ^= 1;
+        assert_eq!(decrypt_on_g1::<Sha256>(&signature, &tampered), None);
+
+        assert!(encrypt_on_g1::<Sha256, _>(&pk, 1000, &[0; 33], &mut rng).is_none());
+    }
+
+    #[test]
+    fn test_timelock_g2() {
+        let mut rng = rng();
+        let sk = Scalar::from(0x0fed_cba9_8765_4321);
+        let pk = G2Affine::from(G2Affine::generator() * sk);
+        let message = b"revealed at round 5";
+
+        let ciphertext = encrypt_on_g2::<Sha256, _>(&pk, 5, message, &mut rng).unwrap();
+        let bytes = ciphertext.to_bytes();
+        assert_eq!(bytes.len(), 96 + 2 * message.len());
+        assert_eq!(
+            Ciphertext::<G2Affine>::from_bytes(&bytes),
+            Some(ciphertext.clone())
+        );
+        assert_eq!(Ciphertext::<G2Affine>::from_bytes(&bytes[1..]), None);
+
+        assert_eq!(
+            decrypt_on_g2::<Sha256>(&sign_round_g1(&sk, 5), &ciphertext).unwrap(),
+            message
+        );
+        assert_eq!(
+            decrypt_on_g2::<Sha256>(&sign_round_g1(&sk, 6), &ciphertext),
+            None
+        );
+    }
+}
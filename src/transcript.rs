@@ -0,0 +1,216 @@
+//! A transcript for the Fiat-Shamir transform, which turns interactive proofs
+//! into non-interactive ones by deriving the verifier's challenges from the
+//! messages exchanged so far.
+//!
+//! A [`Transcript`] is created with a domain separation tag that identifies the
+//! protocol. The prover and the verifier then absorb the same labeled messages,
+//! points and scalars in the same order, and squeeze challenges in
+//! $\mathbb{F}_q$ from the transcript at the points where an interactive verifier
+//! would have sent them. Each challenge depends on the tag and on everything
+//! absorbed before it, including the labels of the earlier challenges. The
+//! challenges of [Schnorr signatures](crate::schnorr) are derived this way.
+//!
+//! Messages are framed so that distinct sequences of absorptions cannot produce
+//! the same transcript: each one is encoded as
+//!
+//! ```text
+//! tag || I2OSP(len(label), 8) || label || I2OSP(len(data), 8) || data
+//! ```
+//!
+//! where `tag` is a zero byte for absorbed data and a one byte for challenges,
+//! whose data is empty. Points are absorbed as their compressed encodings and
+//! scalars as their little-endian encodings. A challenge is [`HashToField`]
+//! applied to the encoded transcript under the domain separation tag, so that the
+//! 48 bytes produced by the [`ExpandMessage`] variant `X` are reduced modulo $q$,
+//! with a bias of at most $2^{-128}$.
+//!
+//! ```
+//! use bls12_381::hash_to_curve::ExpandMsgXmd;
+//! use bls12_381::transcript::Transcript;
+//! use bls12_381::{G1Affine, Scalar};
+//!
+//! type Xmd = ExpandMsgXmd<sha2::Sha256>;
+//!
+//! let commitment = G1Affine::from(G1Affine::generator() * Scalar::from(42));
+//!
+//! let mut prover = Transcript::<Xmd>::new(b"EXAMPLE_PROTOCOL_V1");
+//! prover.append_g1(b"commitment", &commitment);
+//! let e = prover.challenge_scalar(b"challenge");
+//!
+//! // The verifier derives the same challenge from the same messages.
+//! let mut verifier = Transcript::<Xmd>::new(b"EXAMPLE_PROTOCOL_V1");
+//! verifier.append_g1(b"commitment", &commitment);
+//! assert_eq!(verifier.challenge_scalar(b"challenge"), e);
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use alloc::vec::Vec;
+
+use crate::hash_to_curve::{ExpandMessage, HashToField};
+use crate::{G1Affine, G2Affine, Scalar};
+
+const ABSORB: u8 = 0;
+const CHALLENGE: u8 = 1;
+
+/// A Fiat-Shamir transcript that absorbs labeled data and produces challenges in
+/// $\mathbb{F}_q$, hashed with the [`ExpandMessage`] variant `X`.
+pub struct Transcript<X> {
+    dst: Vec<u8>,
+    state: Vec<u8>,
+    _marker: PhantomData<X>,
+}
+
+impl<X: ExpandMessage> Transcript<X> {
+    /// Creates an empty transcript for the protocol identified by the domain
+    /// separation tag `dst`.
+    pub fn new(dst: &[u8]) -> Self {
+        Transcript {
+            dst: dst.to_vec(),
+            state: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn append(&mut self, tag: u8, label: &[u8], data: &[u8]) {
+        self.state.push(tag);
+        self.state
+            .extend_from_slice(&(label.len() as u64).to_be_bytes());
+        self.state.extend_from_slice(label);
+        self.state
+            .extend_from_slice(&(data.len() as u64).to_be_bytes());
+        self.state.extend_from_slice(data);
+    }
+
+    /// Absorbs `message` under `label`.
+    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        self.append(ABSORB, label, message);
+    }
+
+    /// Absorbs the compressed encoding of `point` under `label`.
+    pub fn append_g1(&mut self, label: &[u8], point: &G1Affine) {
+        self.append_message(label, &point.to_compressed());
+    }
+
+    /// Absorbs the compressed encoding of `point` under `label`.
+    pub fn append_g2(&mut self, label: &[u8], point: &G2Affine) {
+        self.append_message(label, &point.to_compressed());
+    }
+
+    /// Absorbs the little-endian encoding of `scalar` under `label`.
+    pub fn append_scalar(&mut self, label: &[u8], scalar: &Scalar) {
+        self.append_message(label, &scalar.to_bytes());
+    }
+
+    /// Returns the challenge labeled `label` for the data absorbed so far, and
+    /// absorbs the label so that the next challenge is independent of this one.
+    pub fn challenge_scalar(&mut self, label: &[u8]) -> Scalar {
+        self.append(CHALLENGE, label, &[]);
+        let mut output = [Scalar::zero()];
+        Scalar::hash_to_field::<X>(&self.state, &self.dst, &mut output);
+        output[0]
+    }
+}
+
+impl<X> Clone for Transcript<X> {
+    fn clone(&self) -> Self {
+        Transcript {
+            dst: self.dst.clone(),
+            state: self.state.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<X> fmt::Debug for Transcript<X> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transcript")
+            .field("dst", &self.dst)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_to_curve::{ExpandMsgXmd, ExpandMsgXof};
+
+    type Xmd = ExpandMsgXmd<sha2::Sha256>;
+
+    const DST: &[u8] = b"BLS12381_TRANSCRIPT_TEST";
+
+    #[test]
+    fn test_transcript_encoding() {
+        let mut transcript = Transcript::<Xmd>::new(DST);
+        transcript.append_message(b"msg", b"hello");
+        transcript.append_scalar(b"x", &Scalar::from(7));
+        let challenge = transcript.challenge_scalar(b"c");
+
+        let mut encoded = Vec::new();
+        encoded.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0, 3]);
+        encoded.extend_from_slice(b"msg");
+        encoded.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 5]);
+        encoded.extend_from_slice(b"hello");
+        encoded.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        encoded.extend_from_slice(b"x");
+        encoded.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 32]);
+        encoded.extend_from_slice(&Scalar::from(7).to_bytes());
+        encoded.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0, 1]);
+        encoded.extend_from_slice(b"c");
+        encoded.extend_from_slice(&[0; 8]);
+
+        let mut expected = [Scalar::zero()];
+        Scalar::hash_to_field::<Xmd>(&encoded, DST, &mut expected);
+        assert_eq!(challenge, expected[0]);
+    }
+
+    #[test]
+    fn test_transcript_challenges() {
+        let mut transcript = Transcript::<Xmd>::new(DST);
+        transcript.append_g1(b"a", &G1Affine::generator());
+        transcript.append_g2(b"b", &G2Affine::generator());
+
+        // Clones agree, and successive challenges are distinct.
+        let mut fork = transcript.clone();
+        let c0 = transcript.challenge_scalar(b"c");
+        assert_eq!(fork.challenge_scalar(b"c"), c0);
+        let c1 = transcript.challenge_scalar(b"c");
+        assert_ne!(c0, c1);
+        assert_eq!(fork.challenge_scalar(b"c"), c1);
+
+        // Points are absorbed as their compressed encodings.
+        let mut other = Transcript::<Xmd>::new(DST);
+        other.append_message(b"a", &G1Affine::generator().to_compressed());
+        other.append_message(b"b", &G2Affine::generator().to_compressed());
+        assert_eq!(other.challenge_scalar(b"c"), c0);
+
+        // The tag, the labels, the framing and the hash function all matter.
+        let challenge = |dst: &[u8], parts: &[(&[u8], &[u8])]| {
+            let mut transcript = Transcript::<Xmd>::new(dst);
+            for (label, message) in parts {
+                transcript.append_message(label, message);
+            }
+            transcript.challenge_scalar(b"c")
+        };
+        let base = challenge(DST, &[(b"ab", b"c")]);
+        assert_ne!(challenge(b"ANOTHER_DST", &[(b"ab", b"c")]), base);
+        assert_ne!(challenge(DST, &[(b"a", b"bc")]), base);
+        assert_ne!(challenge(DST, &[(b"ab", b""), (b"", b"c")]), base);
+        assert_ne!(challenge(DST, &[(b"ba", b"c")]), base);
+        let mut xof = Transcript::<ExpandMsgXof<sha3::Shake128>>::new(DST);
+        xof.append_message(b"ab", b"c");
+        assert_ne!(xof.challenge_scalar(b"c"), base);
+
+        // An absorbed message cannot stand in for the label of a challenge.
+        let mut absorbed = Transcript::<Xmd>::new(DST);
+        absorbed.append_message(b"c", b"");
+        let mut squeezed = Transcript::<Xmd>::new(DST);
+        squeezed.challenge_scalar(b"c");
+        assert_ne!(
+            absorbed.challenge_scalar(b"d"),
+            squeezed.challenge_scalar(b"d")
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! This module implements the isomorphism between the sextic twist
+//! $E'(\mathbb{F}_{p^2}): y^2 = x^3 + 4(u + 1)$, on which $\mathbb{G}_2$ is defined, and
+//! the curve $E(\mathbb{F}_{p^{12}}): y^2 = x^3 + 4$, on which the pairing is
+//! evaluated.
+//!
+//! With $w \in \mathbb{F}_{p^{12}}$ such that $w^6 = u + 1$, the untwisting map is
+//! $\psi(x, y) = (x / w^2, y / w^3)$, and the twisting map is its inverse
+//! $(x, y) \mapsto (x w^2, y w^3)$.
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use crate::fp::Fp;
+use crate::fp12::Fp12;
+use crate::fp6::Fp6;
+use crate::G2Affine;
+
+/// A point on the curve $E: y^2 = x^3 + 4$ over $\mathbb{F}_{p^{12}}$, in affine
+/// coordinates.
+#[derive(Copy, Clone, Debug)]
+pub struct Fp12Point {
+    x: Fp12,
+    y: Fp12,
+    infinity: Choice,
+}
+
+impl ConstantTimeEq for Fp12Point {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        (self.infinity & other.infinity)
+            | ((!self.infinity)
+                & (!other.infinity)
+                & self.x.ct_eq(&other.x)
+                & self.y.ct_eq(&other.y))
+    }
+}
+
+impl PartialEq for Fp12Point {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        bool::from(self.ct_eq(other))
+    }
+}
+
+impl Eq for Fp12Point {}
+
+impl Fp12Point {
+    /// Returns the point at infinity.
+    pub fn identity() -> Self {
+        Fp12Point {
+            x: Fp12::zero(),
+            y: Fp12::one(),
+            infinity: Choice::from(1u8),
+        }
+    }
+
+    /// Returns the point $(x, y)$, if it is on the curve.
+    pub fn from_coordinates(x: Fp12, y: Fp12) -> CtOption<Self> {
+        let p = Fp12Point {
+            x,
+            y,
+            infinity: Choice::from(0u8),
+        };
+        CtOption::new(p, p.is_on_curve())
+    }
+
+    /// Returns the $x$-coordinate of this point, which is zero for the point at
+    /// infinity.
+    pub fn x(&self) -> Fp12 {
+        Fp12::conditional_select(&self.x, &Fp12::zero(), self.infinity)
+    }
+
+    /// Returns the $y$-coordinate of this point, which is one for the point at
+    /// infinity.
+    pub fn y(&self) -> Fp12 {
+        Fp12::conditional_select(&self.y, &Fp12::one(), self.infinity)
+    }
+
+    /// Returns true if this point is the point at infinity.
+    pub fn is_identity(&self) -> Choice {
+        self.infinity
+    }
+
+    /// Returns true if this point is on the curve. This should always return
+    /// true unless an "unchecked" API was used.
+    pub fn is_on_curve(&self) -> Choice {
+        // y^2 - x^3 ?= 4
+        (self.y.square() - self.x.square() * self.x).ct_eq(&Fp12::from(b())) | self.infinity
+    }
+}
+
+fn b() -> Fp {
+    let two = Fp::one() + Fp::one();
+    two + two
+}
+
+/// Returns $w^2$ and $w^3$, where $w$ generates $\mathbb{F}_{p^{12}}$ over
+/// $\mathbb{F}_{p^6}$.
+fn w2_w3() -> (Fp12, Fp12) {
+    let w = Fp12 {
+        c0: Fp6::zero(),
+        c1: Fp6::one(),
+    };
+    let w2 = w.square();
+    (w2, w2 * w)
+}
+
+/// Maps a point of $\mathbb{G}_2$ on the twist to the curve over
+/// $\mathbb{F}_{p^{12}}$.
+pub fn untwist(p: &G2Affine) -> Fp12Point {
+    let (w2, w3) = w2_w3();
+    Fp12Point {
+        x: Fp12::from(p.x) * w2.invert().unwrap(),
+        y: Fp12::from(p.y) * w3.invert().unwrap(),
+        infinity: p.infinity,
+    }
+}
+
+/// Maps a point on the curve over $\mathbb{F}_{p^{12}}$ to the twist, if its image
+/// is an element of $\mathbb{G}_2$.
+pub fn twist(p: &Fp12Point) -> CtOption<G2Affine> {
+    let (w2, w3) = w2_w3();
+    let x = p.x * w2;
+    let y = p.y * w3;
+
+    // The image is defined over Fp2 only when every other coefficient is zero.
+    let in_fp2 = |f: &Fp12| f.c1.is_zero() & f.c0.c1.is_zero() & f.c0.c2.is_zero();
+    let q = G2Affine::conditional_select(
+        &G2Affine {
+            x: x.c0.c0,
+            y: y.c0.c0,
+            infinity: Choice::from(0u8),
+        },
+        &G2Affine::identity(),
+        p.infinity,
+    );
+    CtOption::new(
+        q,
+        p.infinity | (in_fp2(&x) & in_fp2(&y) & q.is_on_curve() & q.is_torsion_free()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{G1Affine, G2Projective, Scalar};
+
+    #[test]
+    fn test_untwist() {
+        let points = [
+            G2Affine::generator(),
+            G2Affine::from(G2Projective::generator() * Scalar::from_raw([1, 2, 3, 4])),
+            G2Affine::identity(),
+        ];
+        for p in points.iter() {
+            let q = untwist(p);
+            assert!(bool::from(q.is_on_curve()));
+            assert_eq!(bool::from(q.is_identity()), bool::from(p.is_identity()));
+            assert_eq!(twist(&q).unwrap(), *p);
+        }
+    }
+
+    #[test]
+    fn test_twist_rejects_other_points() {
+        // A point of G1 is on the curve over Fp12, but does not map to the twist.
+        let g = G1Affine::generator();
+        let p = Fp12Point::from_coordinates(Fp12::from(g.x), Fp12::from(g.y)).unwrap();
+        assert!(bool::from(twist(&p).is_none()));
+
+        assert!(bool::from(
+            Fp12Point::from_coordinates(Fp12::one(), Fp12::one()).is_none()
+        ));
+        assert!(bool::from(
+            twist(&Fp12Point::identity()).unwrap().is_identity()
+        ));
+    }
+}
@@ -19,6 +19,102 @@ pub const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
     (ret as u64, (ret >> 64) as u64)
 }
 
+/// Computes the inverse of `a` modulo the odd `modulus`, both given as little-endian
+/// limbs, with the binary extended Euclidean algorithm. Returns `None` if `a` is
+/// zero. `a` must be smaller than `modulus`, whose most significant bit must be
+/// clear so that sums of two residues do not overflow.
+///
+/// **This is not constant time.**
+pub fn invert_vartime<const N: usize>(a: &[u64; N], modulus: &[u64; N]) -> Option<[u64; N]> {
+    fn is_zero<const N: usize>(x: &[u64; N]) -> bool {
+        x.iter().all(|&limb| limb == 0)
+    }
+
+    fn is_one<const N: usize>(x: &[u64; N]) -> bool {
+        x[0] == 1 && x[1..].iter().all(|&limb| limb == 0)
+    }
+
+    fn shr1<const N: usize>(x: &mut [u64; N]) {
+        for i in 0..N - 1 {
+            x[i] = (x[i] >> 1) | (x[i + 1] << 63);
+        }
+        x[N - 1] >>= 1;
+    }
+
+    fn add_assign<const N: usize>(x: &mut [u64; N], y: &[u64; N]) {
+        let mut carry = 0;
+        for i in 0..N {
+            let (limb, c) = adc(x[i], y[i], carry);
+            x[i] = limb;
+            carry = c;
+        }
+    }
+
+    /// Subtracts `y` from `x`, returning whether it underflowed.
+    fn sub_assign<const N: usize>(x: &mut [u64; N], y: &[u64; N]) -> bool {
+        let mut borrow = 0;
+        for i in 0..N {
+            let (limb, b) = sbb(x[i], y[i], borrow);
+            x[i] = limb;
+            borrow = b;
+        }
+        borrow != 0
+    }
+
+    fn less_than<const N: usize>(x: &[u64; N], y: &[u64; N]) -> bool {
+        x.iter().rev().cmp(y.iter().rev()) == core::cmp::Ordering::Less
+    }
+
+    /// Halves `x` modulo `modulus`, given that `x` is a residue.
+    fn half_mod<const N: usize>(x: &mut [u64; N], modulus: &[u64; N]) {
+        if x[0] & 1 == 1 {
+            add_assign(x, modulus);
+        }
+        shr1(x);
+    }
+
+    if is_zero(a) {
+        return None;
+    }
+
+    // Invariants: x1 a = u and x2 a = v modulo the modulus.
+    let (mut u, mut v) = (*a, *modulus);
+    let (mut x1, mut x2) = ([0; N], [0; N]);
+    x1[0] = 1;
+    while !is_one(&u) && !is_one(&v) {
+        while u[0] & 1 == 0 {
+            shr1(&mut u);
+            half_mod(&mut x1, modulus);
+        }
+        while v[0] & 1 == 0 {
+            shr1(&mut v);
+            half_mod(&mut x2, modulus);
+        }
+        if less_than(&u, &v) {
+            sub_assign(&mut v, &u);
+            if sub_assign(&mut x2, &x1) {
+                add_assign(&mut x2, modulus);
+            }
+        } else {
+            sub_assign(&mut u, &v);
+            if sub_assign(&mut x1, &x2) {
+                add_assign(&mut x1, modulus);
+            }
+        }
+    }
+
+    Some(if is_one(&u) { x1 } else { x2 })
+}
+
+/// Writes `bytes` to `f` in lowercase hexadecimal.
+#[cfg(feature = "groups")]
+pub fn write_hex(f: &mut core::fmt::Formatter, bytes: &[u8]) -> core::fmt::Result {
+    for b in bytes {
+        write!(f, "{:02x}", b)?;
+    }
+    Ok(())
+}
+
 macro_rules! impl_add_binop_specify_output {
     ($lhs:ident, $rhs:ident, $output:ident) => {
         impl<'b> Add<&'b $rhs> for $lhs {